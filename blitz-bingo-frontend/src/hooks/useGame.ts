@@ -268,7 +268,7 @@ export function useGame() {
 
     // Deposit funds
     const deposit = useCallback(async (amountLinera: number = 10) => {
-        await executeMutation(`mutation { deposit(amountLinera: ${amountLinera}) }`, `Deposit ${amountLinera}`);
+        await executeMutation(`mutation { deposit(amount: "${amountLinera}") }`, `Deposit ${amountLinera}`);
     }, [executeMutation]);
 
     // Mint tokens
@@ -279,7 +279,7 @@ export function useGame() {
         setTxStatus(`Minting ${amountLinera} Blitz Tokens...`);
 
         try {
-            await executeMutation(`mutation { deposit(amountLinera: ${amountLinera}) }`, `Mint ${amountLinera} $BLITZ`);
+            await executeMutation(`mutation { deposit(amount: "${amountLinera}") }`, `Mint ${amountLinera} $BLITZ`);
             setTxStatus(`Successfully minted ${amountLinera} $BLITZ tokens!`);
             setTimeout(() => setTxStatus(null), 3000);
         } catch (e: any) {
@@ -342,7 +342,7 @@ export function useGame() {
         setLastRoll(null);
         setDrawnNumbers([]);
         setPotentialPayout(null);
-        await executeMutation(`mutation { newGame(betAmountLinera: ${bet}) }`, 'New Game');
+        await executeMutation(`mutation { newGame(betAmount: "${bet}") }`, 'New Game');
     }, [session, betAmount, playerBalance, executeMutation]);
 
     // Roll dice
@@ -355,7 +355,7 @@ export function useGame() {
 
     // Deposit funds (generic)
     const deposit = useCallback(async (amountLinera: number = 10) => {
-        await executeMutation(`mutation { deposit(amountLinera: ${amountLinera}) }`, `Deposit ${amountLinera}`);
+        await executeMutation(`mutation { deposit(amount: "${amountLinera}") }`, `Deposit ${amountLinera}`);
         await refreshChainBalance();
     }, [executeMutation, refreshChainBalance]);
 
@@ -368,7 +368,7 @@ export function useGame() {
 
         try {
             // Call deposit mutation with the specific amount
-            await executeMutation(`mutation { deposit(amountLinera: ${amountLinera}) }`, `Mint ${amountLinera} FP`);
+            await executeMutation(`mutation { deposit(amount: "${amountLinera}") }`, `Mint ${amountLinera} FP`);
 
             // Refresh both balances
             await refreshChainBalance();
@@ -434,7 +434,7 @@ export function useGame() {
         setLastRoll(null);
         setDrawnNumbers([]);
         setPotentialPayout(null);
-        await executeMutation(`mutation { newGame(betAmountLinera: ${bet}) }`, 'New Game');
+        await executeMutation(`mutation { newGame(betAmount: "${bet}") }`, 'New Game');
     }, [session, betAmount, playerBalance, executeMutation]);
 
     // Roll dice
@@ -0,0 +1,86 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Native benchmarks of the per-roll path. Run with `cargo bench --features bench`.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::hint::black_box;
+
+use blitz_bingo::{rules, BingoCard, CardConstraints, PayoutTier, MIN_BET};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// A freshly dealt card with only the FREE center marked
+fn fresh_card(seed: u64) -> BingoCard {
+    let numbers = rules::deal_numbers(seed);
+    let mut marked = [false; 25];
+    marked[12] = true;
+    BingoCard {
+        numbers,
+        marked,
+        ..BingoCard::default()
+    }
+}
+
+fn bench_generate_card(c: &mut Criterion) {
+    c.bench_function("deal_numbers", |b| {
+        b.iter(|| rules::deal_numbers(black_box(0x5eed)))
+    });
+    let constraints = CardConstraints::default();
+    c.bench_function("deal_hedge", |b| {
+        b.iter(|| rules::deal_hedge(black_box(0xf00d), 1, 1, &constraints))
+    });
+}
+
+/// Dice, marking and bingo check: the pure part of one RollAndMatch
+fn bench_roll(c: &mut Criterion) {
+    let card = fresh_card(0x5eed);
+    c.bench_function("roll_and_match", |b| {
+        b.iter_batched_ref(
+            || (card.clone(), 0u64),
+            |(card, nonce)| {
+                *nonce += 1;
+                let rng_state = black_box(*nonce).wrapping_mul(0x2545f4914f6cdd1d);
+                let dice = rules::dice_from_state(rng_state);
+                let sum = dice.iter().sum();
+                let (matched, _, _) = rules::mark_number_on_card(card, sum);
+                (matched, rules::bingo_lines(&card.marked))
+            },
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_bingo_check(c: &mut Criterion) {
+    let mut half = [false; 25];
+    half.iter_mut().step_by(2).for_each(|cell| *cell = true);
+    for (name, marked) in [("empty", [false; 25]), ("half", half), ("full", [true; 25])] {
+        c.bench_function(&format!("bingo_lines_{}", name), |b| {
+            b.iter(|| rules::bingo_lines(black_box(&marked)))
+        });
+        c.bench_function(&format!("count_completed_lines_{}", name), |b| {
+            b.iter(|| rules::count_completed_lines(black_box(&marked)))
+        });
+    }
+}
+
+fn bench_payout(c: &mut Criterion) {
+    let curve = PayoutTier::default_curve();
+    c.bench_function("payout", |b| {
+        b.iter(|| {
+            let bet = black_box(MIN_BET);
+            let tier = rules::tier_payout(&curve, bet, black_box(12))?;
+            let lines = rules::stacked_bonus(bet, 5_000, black_box(2))?;
+            tier.checked_add(lines)
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_generate_card,
+    bench_roll,
+    bench_bingo_check,
+    bench_payout
+);
+criterion_main!(benches);
@@ -0,0 +1,105 @@
+// FlashPort: Amount Parsing
+// Decimal LINERA inputs converted to atto exactly, without going through floats
+
+use linera_sdk::linera_base_types::Amount;
+
+/// Parse a decimal LINERA amount such as "1.5" into atto.
+/// Accepts at most 18 decimals; rejects empty, negative and malformed input.
+pub fn parse_linera(input: &str) -> Result<u128, String> {
+    let input = input.trim();
+    if !input.chars().any(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid LINERA amount {:?}: expected a decimal like \"1.5\"", input));
+    }
+    input
+        .parse::<Amount>()
+        .map(u128::from)
+        .map_err(|error| format!("Invalid LINERA amount {:?}: {}", input, error))
+}
+
+/// Convert a legacy float LINERA amount into atto through its shortest decimal form,
+/// so 0.1 becomes exactly 10^17 atto. Rejects NaN, infinities and negatives.
+pub fn linera_from_f64(value: f64) -> Result<u128, String> {
+    if !value.is_finite() || value < 0.0 {
+        return Err(format!("Invalid LINERA amount {}: must be a non-negative number", value));
+    }
+    parse_linera(&value.to_string())
+}
+
+/// `a * b / divisor` with its remainder, exact even where `a * b` overflows u128.
+/// None if `divisor` is zero or the quotient does not fit in u128.
+pub fn mul_div(a: u128, b: u128, divisor: u128) -> Option<(u128, u128)> {
+    if divisor == 0 {
+        return None;
+    }
+    // 256-bit product from 64-bit halves, as (high, low)
+    let (a_high, a_low) = (a >> 64, a & u64::MAX as u128);
+    let (b_high, b_low) = (b >> 64, b & u64::MAX as u128);
+    let (cross_a, cross_b) = (a_high * b_low, a_low * b_high);
+    let (low, carry_a) = (a_low * b_low).overflowing_add(cross_a << 64);
+    let (low, carry_b) = low.overflowing_add(cross_b << 64);
+    let high =
+        a_high * b_high + (cross_a >> 64) + (cross_b >> 64) + carry_a as u128 + carry_b as u128;
+    if high >= divisor {
+        return None;
+    }
+
+    // Long division, one bit of `low` at a time; `remainder` stays below `divisor`
+    let mut remainder = high;
+    let mut quotient = 0u128;
+    for bit in (0..128).rev() {
+        let overflowed = remainder >> 127 == 1;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+        if overflowed || remainder >= divisor {
+            remainder = remainder.wrapping_sub(divisor);
+            quotient |= 1;
+        }
+    }
+    Some((quotient, remainder))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{linera_from_f64, mul_div, parse_linera};
+
+    const ONE: u128 = 1_000_000_000_000_000_000;
+
+    #[test]
+    fn test_parse_linera() {
+        assert_eq!(parse_linera("1"), Ok(ONE));
+        assert_eq!(parse_linera(" 2.5 "), Ok(5 * ONE / 2));
+        assert_eq!(parse_linera("0.000000000000000001"), Ok(1));
+        assert!(parse_linera("").is_err());
+        assert!(parse_linera(".").is_err());
+        assert!(parse_linera("-1").is_err());
+        assert!(parse_linera("1.2.3").is_err());
+        assert!(parse_linera("1e18").is_err());
+        assert!(parse_linera("0.0000000000000000001").is_err());
+    }
+
+    #[test]
+    fn test_linera_from_f64() {
+        assert_eq!(linera_from_f64(0.1), Ok(ONE / 10));
+        assert_eq!(linera_from_f64(100.0), Ok(100 * ONE));
+        assert!(linera_from_f64(f64::NAN).is_err());
+        assert!(linera_from_f64(f64::INFINITY).is_err());
+        assert!(linera_from_f64(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(mul_div(10, 3, 4), Some((7, 2)));
+        assert_eq!(
+            mul_div(u128::MAX, u128::MAX, u128::MAX),
+            Some((u128::MAX, 0))
+        );
+        // Half of 1000 LINERA for a 450 of 900 LINERA stake overflows a plain u128 product
+        assert_eq!(
+            mul_div(1_000 * ONE, 450 * ONE, 900 * ONE),
+            Some((500 * ONE, 0))
+        );
+        assert_eq!(mul_div(1_000 * ONE, 1, 3 * ONE), Some((333, ONE)));
+        assert_eq!(mul_div(u128::MAX, 3, 2), None);
+        assert_eq!(mul_div(1, 1, 0), None);
+    }
+}
@@ -3,88 +3,525 @@
 
 #![cfg_attr(target_arch = "wasm32", no_main)]
 
+mod escrow;
 mod state;
 
 
+use std::collections::BTreeMap;
+
 use blitz_bingo::{
-    BingoCard, BingoType, FlashportAbi, GameSession, Operation, OperationResponse, RollRecord,
-    MIN_BET, MAX_BET, ROLL_COST,
+    archive_bet_bucket, chain_roll_results, dice_skin, multiplier_tier, probability, rules,
+    ActiveGameSummary, AdminAction, AdminActionKind, AirdropBatch, AirdropCredit, ApiUsage,
+    AttestedOutcome, BatchOutcome, BingoCard, BingoType, BridgeRequest, BucketStats, BudgetAlert,
+    BudgetAlertKind, BudgetTracker, CascadeRoll, ConfigSummary, ConsumedNonce, Currency,
+    DataPurgeRequest, DeltaField, DemoRoll, DiagnosticEvent, DiagnosticKind, Dispute,
+    DisputeOutcome, DisputeResolution, Duel, DuelSeat, DuelStatus, EntropyLink, EntropyRecord,
+    EpochPool, EpochVolume, ErrorCode, Experiment, ExperimentBucket, FlashportAbi, FlashportEvent,
+    FlashportParameters, GameAttestation, GameConfig, GameMode, GamePhase, GameSession,
+    GameTemplate, GovernanceProposal, HappyHour, HealthCounters, HealthReport, HighRollerTable,
+    HoldReason, InstantiationArgument, LeaderboardEntry, Message, MultiplierLock, OnboardingStep,
+    Operation, OperationAuth, OperationMetrics, OperationResponse, OutboxEntry, OutboxKind,
+    PartnerNotice, PayoutBridgeAbi, PayoutRoute, PayoutTier, PendingAutoRoll, PlayerAccount,
+    PlayerPreferences, PointsRoll, PointsSettlement, ProposalKind, ProposalStatus, PruneReport,
+    RaffleDraw, Receipt, ReceiptKind, RegisteredInstance, RelayEnvelope, RelayPayload,
+    RewardHookAbi, RollRecord, RoutedPayout, RuntimeContext, ScheduledWithdrawal, SeasonMode,
+    SessionCadence, SessionEndPolicy, SessionSettlement, SessionSummary, SettledGame,
+    SettlementOutcome, SkinInventory, StatsSnapshot, Syndicate, SyndicateGameResult,
+    SyndicateMember, SyndicateShare, Tournament, TournamentEntry, TranscriptLink, VestingSchedule,
+    WinNotification, WinPattern, WinRecord, WithdrawalHold, Withheld, WithholdReason, ABI_VERSION,
+    ALERTS_STREAM_NAME, ALL_SESSION_PERMISSIONS, BLACKOUT_LINE_BONUS_BPS, BLACKOUT_SKIN_ID,
+    CADENCE_MIN_SAMPLES, CONGESTION_GAP_FACTOR, DATA_PURGE_GRACE_SECS, DEMO_GAME_ROLLS,
+    DIAGNOSTICS_CAPACITY, DUEL_TURN_SECS, ENTROPY_HISTORY_LEN, EPOCH_PERIOD_SECS,
+    INSURANCE_ROLL_COST, LEADERBOARD_SIZE, LIGHTNING_SKIN_ID, MAX_AIRDROP_RECIPIENTS,
+    MAX_AUTO_ROLLS, MAX_BET, MAX_CARD_DEALS, MAX_CLAIM_EXTENSION_PAGE, MAX_DISPUTE_REASON_LEN,
+    MAX_MARK_PURCHASES, MAX_RELAY_TTL_SECS, MAX_SEASON_DICE, MAX_SYNDICATE_MEMBERS,
+    MAX_SYNDICATE_NAME_LEN, MAX_TARGET_ASSET_LEN, MIN_BET, MIN_GOVERNANCE_TIMELOCK_SECS,
+    MULTIPLIER_LOCK_FEE_BPS, MULTIPLIER_LOCK_ROLLS, MULTIPLIER_TIER_MAX_ROLLS,
+    MULTIPLIER_TIER_NAMES, MULTI_LINE_BONUS_BPS, PLAY_POINTS_GRANT, POINTS_GAME_ROLLS,
+    RAFFLE_PERIOD_SECS, RAFFLE_POOL_BPS, ROLLING_RTP_EPOCHS, ROLL_COST, SESSION_CAN_NEW_GAME,
+    SESSION_CAN_ROLL, SESSION_STREAM_NAME, STATS_PUBLISH_INTERVAL_SECS, STATS_SCHEMA_VERSION,
+    STATS_STREAM_NAME, SYNDICATE_GAME_MAX_ROLLS, TIER_WIN_CHANCE_BPS, TOURNAMENT_PAYOUT_BPS,
 };
 use linera_sdk::{
-    linera_base_types::{Amount, ChainId, WithContractAbi},
-    views::{RootView, View},
+    bcs,
+    linera_base_types::{
+        AccountOwner, AccountSignature, Amount, ChainId, CryptoHash, StreamName, WithContractAbi,
+    },
+    views::{MapView, RootView, View},
     Contract, ContractRuntime,
 };
+use serde::Serialize;
 
-use self::state::FlashportState;
+use self::{
+    escrow::{Escrow, LedgerError},
+    state::FlashportState,
+};
 
 /// The FlashPort contract handler
 pub struct FlashportContract {
     state: FlashportState,
     runtime: ContractRuntime<Self>,
+    /// Owner the current operation runs as when it was relayed (see `signer`)
+    relayed_owner: Option<AccountOwner>,
+    /// Owner of the account in `player`, if one is loaded (see `load_player`)
+    player_owner: Option<AccountOwner>,
+    /// Account the current operation acts on, written back to `players` when the
+    /// operation ends
+    player: PlayerAccount,
+    /// `total_spent` and `total_won` of `player` as last written to `players`, so the
+    /// deployment-wide totals can follow what the operation changed
+    stored_totals: (Amount, Amount),
+    /// Whether the current operation runs in quick play, without a session (see
+    /// `is_quick_play`)
+    quick_play: bool,
 }
 
 linera_sdk::contract!(FlashportContract);
 
+/// Values behind each `DeltaField`, compared across an operation to find what changed
+struct DeltaFields {
+    balance: Amount,
+    points: Amount,
+    game_phase: GamePhase,
+    current_card: Option<BingoCard>,
+    hedge_card: Option<BingoCard>,
+    session: Option<GameSession>,
+    budget_alerts: Vec<BudgetAlert>,
+}
+
+/// A check that refused an operation, carried back to become its `Error` response
+struct Refusal {
+    code: ErrorCode,
+    message: String,
+}
+
+impl Refusal {
+    fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Refusal {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Refusal> for OperationResponse {
+    fn from(refusal: Refusal) -> Self {
+        OperationResponse::Error {
+            code: refusal.code,
+            message: refusal.message,
+        }
+    }
+}
+
+/// Published rules, beyond its mode, that a new game is dealt under
+enum GameVariant {
+    Standard,
+    Season(SeasonMode),
+    Template(GameTemplate),
+}
+
 impl WithContractAbi for FlashportContract {
     type Abi = FlashportAbi;
 }
 
 impl Contract for FlashportContract {
-    type Message = ();
-    type Parameters = ();
-    type InstantiationArgument = ();
-    type EventValue = ();
+    type Message = Message;
+    type Parameters = FlashportParameters;
+    type InstantiationArgument = InstantiationArgument;
+    type EventValue = FlashportEvent;
 
     async fn load(runtime: ContractRuntime<Self>) -> Self {
         let state = FlashportState::load(runtime.root_view_storage_context())
             .await
             .expect("Failed to load state");
-        FlashportContract { state, runtime }
+        FlashportContract {
+            state,
+            runtime,
+            relayed_owner: None,
+            player_owner: None,
+            player: PlayerAccount::default(),
+            stored_totals: (Amount::ZERO, Amount::ZERO),
+            quick_play: false,
+        }
     }
 
-    async fn instantiate(&mut self, _argument: Self::InstantiationArgument) {
-        // Initialize with zero balances
-        self.state.player_balance.set(Amount::ZERO);
-        self.state.total_deposited.set(Amount::ZERO);
-        self.state.total_won.set(Amount::ZERO);
-        self.state.total_spent.set(Amount::ZERO);
-        self.state.current_prize_pool.set(Amount::ZERO);
+    async fn instantiate(&mut self, argument: Self::InstantiationArgument) {
+        // Whoever creates the application administers it
+        self.state.admin.set(self.runtime.authenticated_signer());
+        if let Err(reason) = argument.card_constraints.validate() {
+            panic!("Invalid card constraints: {}", reason);
+        }
+        self.state.config.set(GameConfig {
+            card_constraints: argument.card_constraints,
+            governance_timelock_secs: argument.governance_timelock_secs,
+            ..GameConfig::default()
+        });
+        self.state.reward_partners.set(argument.reward_partners);
+        self.state
+            .reward_notify_threshold
+            .set(Amount::from_attos(argument.reward_notify_threshold_atto));
+        self.state.lobby_chain.set(argument.lobby_chain);
+        self.state.payout_bridge.set(argument.payout_bridge);
+
+        // Open the admin's account with zero balances
+        let admin = self
+            .runtime
+            .authenticated_signer()
+            .unwrap_or(AccountOwner::CHAIN);
+        self.player_owner = Some(admin);
+        self.player = PlayerAccount::open();
+        self.store_player();
+
+        // First raffle round closes one period after creation
+        let first_draw = self.runtime.system_time().micros() + RAFFLE_PERIOD_SECS * 1_000_000;
+        self.state.raffle_draw_at_micros.set(first_draw);
+
+        // Prize pool accounting starts with the first epoch
+        let now = self.runtime.system_time().micros();
+        self.state.epoch.set(EpochPool {
+            epoch_id: 1,
+            started_at_micros: now,
+            ends_at_micros: now + EPOCH_PERIOD_SECS * 1_000_000,
+            ..EpochPool::default()
+        });
     }
 
     async fn execute_operation(&mut self, operation: Operation) -> OperationResponse {
-        match operation {
+        // Unwrap version tags, rejecting anything built for another ABI before it runs,
+        // sequencing tags, reverting the block if the required session is not active,
+        // and relay envelopes, refusing any that are forged, expired or replayed
+        self.relayed_owner = None;
+        let mut required_sessions = Vec::new();
+        let mut operation = operation;
+        let operation = loop {
+            match operation {
+                Operation::Versioned {
+                    abi_version,
+                    operation: inner,
+                } => {
+                    if abi_version != ABI_VERSION {
+                        return OperationResponse::IncompatibleAbiVersion {
+                            supported: ABI_VERSION,
+                            requested: abi_version,
+                        };
+                    }
+                    operation = *inner;
+                }
+                Operation::Sequenced {
+                    requires_session_id,
+                    operation: inner,
+                } => {
+                    required_sessions.push(requires_session_id);
+                    operation = *inner;
+                }
+                Operation::Relayed {
+                    envelope,
+                    signature,
+                    operation: inner,
+                } => {
+                    if let Err(refusal) = self.accept_relayed(&envelope, &signature, &inner).await {
+                        return refusal.into();
+                    }
+                    self.relayed_owner = Some(envelope.owner);
+                    operation = *inner;
+                }
+                operation => break operation,
+            }
+        };
+
+        // Every operation acts on the account of the owner it runs as, except for the
+        // play of a sponsored game and the release of someone's scheduled withdrawal;
+        // admin operations update the accounts they touch through `update_account`.
+        // Each counts toward the usage of the account it is signed from.
+        let owner = self.signer().unwrap_or(AccountOwner::CHAIN);
+        if operation.required_auth() != OperationAuth::Admin {
+            self.load_player(owner).await;
+            self.meter_api_usage();
+            match operation {
+                Operation::ReleaseWithdrawal { withdrawal_id } => {
+                    self.load_withdrawal_owner(withdrawal_id).await
+                }
+                _ if operation.required_auth() == OperationAuth::Delegate => {
+                    self.load_sponsored_game().await
+                }
+                _ => {}
+            }
+        }
+        for session_id in required_sessions {
+            self.require_session_id(session_id);
+        }
+        self.quick_play = self.is_quick_play(&operation);
+
+        self.record_in_transcript(&operation);
+        self.bump_state_version();
+        if self.roll_epoch() {
+            self.prune_on_rollover().await;
+        }
+        self.pause_for_congestion();
+        let spent_before = self.player.total_spent;
+        let delta_before = self.delta_fields();
+
+        let name = operation.name();
+
+        // Check the operation's entry in the authorization matrix (after the preamble,
+        // so a congestion pause can extend the session first)
+        let authorized = self
+            .authorize(operation.required_auth())
+            .and_then(|()| self.check_session_permission(&operation))
+            .and_then(|()| self.check_paused(&operation));
+        if let Err(refusal) = authorized {
+            self.record_operation_metrics(name, spent_before, true);
+            return refusal.into();
+        }
+
+        let response = match operation {
             // === Dice-Bingo Operations ===
-            Operation::StartSession { expires_in_secs } => self.start_session(expires_in_secs).await,
+            Operation::StartSession {
+                expires_in_secs,
+                permissions,
+            } => self.start_session(expires_in_secs, permissions).await,
             Operation::EndSession => self.end_session().await,
-            Operation::NewGame { bet_amount_atto } => {
-                if let Err(msg) = self.validate_session() {
-                    return OperationResponse::Error { message: msg };
-                }
-                self.new_game(bet_amount_atto).await
+            Operation::NewGame {
+                bet_amount_atto,
+                currency,
+                hedge,
+                template_id: Some(template_id),
+                ..
+            } => {
+                self.new_template_game(template_id, bet_amount_atto, currency, hedge)
+                    .await
+            }
+            Operation::NewGame {
+                bet_amount_atto,
+                currency,
+                mode,
+                hedge,
+                template_id: None,
+            } => {
+                let variant = GameVariant::Standard;
+                self.new_game(bet_amount_atto, currency, mode, hedge, None, variant)
+                    .await
+            }
+            Operation::NewSeasonGame {
+                mode_id,
+                bet_amount_atto,
+            } => self.new_season_game(mode_id, bet_amount_atto).await,
+            Operation::SponsorGame {
+                for_owner,
+                bet_amount_atto,
+            } => {
+                let (currency, mode) = (Currency::Real, GameMode::Classic);
+                let (sponsored_player, variant) = (Some(for_owner), GameVariant::Standard);
+                self.new_game(bet_amount_atto, currency, mode, false, sponsored_player, variant)
+                    .await
             }
             Operation::RollAndMatch => {
-                if let Err(msg) = self.validate_session() {
-                    return OperationResponse::Error { message: msg };
-                }
+                self.record_roll_cadence();
                 self.roll_and_match().await
             }
-            Operation::ClaimPrize => {
-                if let Err(msg) = self.validate_session() {
-                    return OperationResponse::Error { message: msg };
-                }
-                self.claim_prize().await
+            Operation::BuyMark { row, col } => self.buy_mark(row, col).await,
+            Operation::LockMultiplier => self.lock_multiplier().await,
+            Operation::AutoRoll {
+                count,
+                stop_on_bingo,
+                aggregate,
+            } => {
+                self.record_roll_cadence();
+                self.auto_roll(count, stop_on_bingo, aggregate).await
             }
-            Operation::Deposit { amount_atto } => self.handle_deposit(amount_atto).await,
+            Operation::ContinueAutoRoll => {
+                self.record_roll_cadence();
+                self.continue_auto_roll().await
+            }
+            Operation::ClaimPrize => self.claim_prize().await,
+            Operation::ClaimVestedTranche => self.claim_vested_tranche().await,
+            Operation::Deposit {
+                amount_atto,
+                credit_to,
+            } => self.handle_deposit(amount_atto, credit_to).await,
             Operation::Withdraw { amount } => self.handle_withdraw(amount).await,
-        }
+            Operation::WithdrawAll => self.handle_withdraw_all().await,
+            Operation::ScheduleWithdrawal {
+                amount,
+                release_at_micros,
+            } => self.schedule_withdrawal(amount, release_at_micros).await,
+            Operation::ReleaseWithdrawal { withdrawal_id } => {
+                self.release_withdrawal(withdrawal_id).await
+            }
+            Operation::RefillPoints => self.refill_points().await,
+            Operation::SetPreferences { preferences } => self.set_preferences(preferences).await,
+            Operation::SetPayoutRoute { target_asset } => self.set_payout_route(target_asset).await,
+            Operation::PurgeMyData => self.purge_my_data().await,
+            Operation::ConfirmDataPurge => self.confirm_data_purge().await,
+            Operation::CancelDataPurge => self.cancel_data_purge().await,
+            Operation::BuySkin { skin_id } => self.buy_skin(skin_id).await,
+            Operation::EquipSkin { skin_id } => self.equip_skin(skin_id).await,
+            Operation::UpsertGameTemplate { template } => self.upsert_game_template(template).await,
+
+            // === Admin Operations ===
+            Operation::UpdateConfig { config } => self.update_config(config).await,
+            Operation::SetExperiment { experiment } => self.set_experiment(experiment).await,
+            Operation::SetHighRollerTable { table } => self.set_high_roller_table(table),
+            Operation::UpsertSeasonMode { mode } => self.upsert_season_mode(mode).await,
+            Operation::ExtendClaimDeadlines {
+                window_secs,
+                reason,
+                after,
+                limit,
+            } => {
+                self.extend_claim_deadlines(window_secs, reason, after, limit)
+                    .await
+            }
+
+            Operation::SetPaused { paused, reason } => self.set_paused(paused, reason).await,
+
+            Operation::WithdrawTreasury { amount, reason } => {
+                self.withdraw_treasury(amount, reason).await
+            }
+
+            Operation::ExecuteProposal { proposal_id } => self.execute_proposal(proposal_id).await,
+
+            Operation::CancelProposal {
+                proposal_id,
+                reason,
+            } => self.cancel_proposal(proposal_id, reason).await,
+
+            Operation::PlaceWithdrawalHold {
+                owner,
+                reason,
+                expires_in_secs,
+                note,
+            } => {
+                self.place_withdrawal_hold(owner, reason, expires_in_secs, note)
+                    .await
+            }
+            Operation::ReleaseWithdrawalHold { owner, note } => {
+                self.release_withdrawal_hold(owner, note).await
+            }
+
+            Operation::ScheduleHappyHour {
+                starts_at_micros,
+                ends_at_micros,
+                discount_bps,
+            } => {
+                self.schedule_happy_hour(starts_at_micros, ends_at_micros, discount_bps)
+                    .await
+            }
+            Operation::CancelHappyHour { happy_hour_id } => {
+                self.cancel_happy_hour(happy_hour_id).await
+            }
+            Operation::AirdropBonus { recipients } => self.airdrop_bonus(recipients).await,
+
+            // === Raffle ===
+            Operation::DrawRaffle => self.draw_raffle().await,
+
+            // === Tournaments ===
+            Operation::CreateTournament {
+                entry_fee_atto,
+                duration_secs,
+            } => self.create_tournament(entry_fee_atto, duration_secs).await,
+            Operation::EnterTournament { tournament_id } => {
+                self.enter_tournament(tournament_id).await
+            }
+            Operation::TournamentRoll { tournament_id } => {
+                self.tournament_roll(tournament_id).await
+            }
+            Operation::SettleTournament { tournament_id } => {
+                self.settle_tournament(tournament_id).await
+            }
+
+            // === Duels ===
+            Operation::OpenDuel { roll_fee_atto } => self.open_duel(roll_fee_atto).await,
+
+            Operation::JoinDuel { duel_id } => self.join_duel(duel_id).await,
+
+            Operation::DuelRoll { duel_id } => self.duel_roll(duel_id).await,
+            Operation::ClaimDuelPot { duel_id } => self.claim_duel_pot(duel_id).await,
+
+            // === Syndicates ===
+            Operation::CreateSyndicate { name } => self.create_syndicate(name),
+
+            Operation::SetSyndicateMember {
+                syndicate_id,
+                member,
+                max_bet_atto,
+            } => {
+                self.set_syndicate_member(syndicate_id, member, max_bet_atto)
+                    .await
+            }
+
+            Operation::ContributeToSyndicate {
+                syndicate_id,
+                amount_atto,
+            } => self.contribute_to_syndicate(syndicate_id, amount_atto).await,
+
+            Operation::LeaveSyndicate { syndicate_id } => self.leave_syndicate(syndicate_id).await,
+
+            Operation::SyndicateGame {
+                syndicate_id,
+                bet_amount_atto,
+            } => self.syndicate_game(syndicate_id, bet_amount_atto).await,
+
+            // === Lobby Registry ===
+            Operation::RegisterWithLobby => self.register_with_lobby().await,
+
+            // === Public Statistics ===
+            Operation::PublishStats => self.publish_stats().await,
+
+            // === Operator Outbox ===
+            Operation::AckOutbox { up_to_id } => self.ack_outbox(up_to_id).await,
+            Operation::DeliverPartnerNotification { entry_id } => {
+                self.deliver_partner_notification(entry_id).await
+            }
+
+            // === Disputes ===
+            Operation::DisputeGame { game_id, reason } => self.dispute_game(game_id, reason).await,
+            Operation::AttestGame { game_id } => self.attest_game(game_id).await,
+
+            // === Health Checks ===
+            Operation::HealthCheck => self.health_check().await,
+            Operation::PruneHistory { keep_last } => self.prune_history(keep_last).await,
+            Operation::ResolveDispute {
+                dispute_id,
+                outcome,
+                award_atto,
+                note,
+            } => {
+                self.resolve_dispute(dispute_id, outcome, award_atto, note)
+                    .await
+            }
+
+            // === Demo ===
+            Operation::DemoGame => self.demo_game(),
+
+            // === Versioning ===
+            Operation::GetVersion => OperationResponse::Version {
+                abi_version: ABI_VERSION,
+            },
+            Operation::Versioned { .. } => unreachable!("version tags are unwrapped above"),
+            Operation::Sequenced { .. } => unreachable!("sequencing tags are unwrapped above"),
+            Operation::Relayed { .. } => unreachable!("relay envelopes are unwrapped above"),
+        };
+        let failed = matches!(response, OperationResponse::Error { .. });
+        self.record_operation_metrics(name, spent_before, failed);
+        self.check_budget_alerts(spent_before);
+        self.record_delta_versions(delta_before);
+        self.refresh_active_game();
+        response
     }
 
-    async fn execute_message(&mut self, _message: Self::Message) {
-        // No cross-chain messages for Dice-Bingo
+    async fn execute_message(&mut self, message: Self::Message) {
+        self.bump_state_version();
+        match message {
+            Message::RegisterInstance {
+                chain,
+                config_summary,
+            } => self.register_instance(chain, config_summary).await,
+        }
     }
 
     async fn store(mut self) {
+        self.store_player();
         self.state.save().await.expect("Failed to save state");
     }
 }
@@ -98,12 +535,313 @@ impl FlashportContract {
         format!("{}", atto)
     }
 
+    // =========================================================================
+    // HELPER: Ledger failures
+    // =========================================================================
+
+    /// Turn a failed balance update into a response: insufficient funds are an
+    /// ordinary error, while an overflow or underflow reverts the operation
+    fn ledger_failure(error: LedgerError) -> OperationResponse {
+        match error {
+            LedgerError::Insufficient(message) => OperationResponse::Error {
+                code: ErrorCode::InsufficientFunds,
+                message,
+            },
+            error => Self::revert(error),
+        }
+    }
+
+    /// Unwrap a ledger update that can only fail by over- or underflowing
+    fn or_revert<T>(result: Result<T, LedgerError>) -> T {
+        result.unwrap_or_else(|error| Self::revert(error))
+    }
+
+    /// Abort the operation so that none of its state changes are committed
+    fn revert(error: LedgerError) -> ! {
+        panic!("Operation reverted: {}", error)
+    }
+
+    /// Abort unless the session `session_id` is the active one. Panicking rejects the
+    /// whole block, so operations sequenced after a failed StartSession never run
+    /// against another session (or none).
+    fn require_session_id(&self, session_id: u64) {
+        let active = self.player.active_session.as_ref().map(|s| s.session_id);
+        if active != Some(session_id) {
+            panic!(
+                "Operation reverted: requires session {} but the active session is {:?}",
+                session_id, active
+            );
+        }
+    }
+
+    /// Make `owner`'s account the one operations act on, writing back the account
+    /// loaded before it. An owner seen for the first time gets a new account.
+    async fn load_player(&mut self, owner: AccountOwner) {
+        if self.player_owner == Some(owner) {
+            return;
+        }
+        self.store_player();
+        self.player = self
+            .state
+            .players
+            .get(&owner)
+            .await
+            .expect("Failed to load player account")
+            .unwrap_or_else(PlayerAccount::open);
+        self.player_owner = Some(owner);
+        self.stored_totals = Self::totals_of(&self.player);
+    }
+
+    /// Write the loaded account back to `players`
+    fn store_player(&mut self) {
+        if let Some(owner) = self.player_owner {
+            let totals = Self::totals_of(&self.player);
+            self.shift_totals(self.stored_totals, totals);
+            self.stored_totals = totals;
+            self.state
+                .players
+                .insert(&owner, self.player.clone())
+                .expect("Failed to store player account");
+        }
+    }
+
+    /// An account's real money spent and won
+    fn totals_of(account: &PlayerAccount) -> (Amount, Amount) {
+        (account.total_spent, account.total_won)
+    }
+
+    /// Move the deployment-wide totals by how much one account's went from `before`
+    /// to `after`
+    fn shift_totals(&mut self, before: (Amount, Amount), after: (Amount, Amount)) {
+        let shift = |ledger, total: Amount, before: Amount, after: Amount| {
+            if after >= before {
+                escrow::add(ledger, total, escrow::sub(ledger, after, before)?)
+            } else {
+                escrow::sub(ledger, total, escrow::sub(ledger, before, after)?)
+            }
+        };
+        if before.0 != after.0 {
+            let spent = shift(
+                "total_spent",
+                *self.state.total_spent.get(),
+                before.0,
+                after.0,
+            );
+            self.state.total_spent.set(Self::or_revert(spent));
+        }
+        if before.1 != after.1 {
+            let won = shift("total_won", *self.state.total_won.get(), before.1, after.1);
+            self.state.total_won.set(Self::or_revert(won));
+        }
+    }
+
+    /// While the loaded owner has an open game sponsored for them, switch to the
+    /// sponsor's account, which holds that game; the sponsor is forgotten once the
+    /// game is over
+    async fn load_sponsored_game(&mut self) {
+        let (Some(player), Some(sponsor)) = (self.player_owner, self.player.sponsored_by) else {
+            return;
+        };
+        self.load_player(sponsor).await;
+        let sponsored = self.player.game_phase.is_open()
+            && self
+                .player
+                .current_card
+                .as_ref()
+                .is_some_and(|card| card.sponsored_player == Some(player));
+        if !sponsored {
+            self.load_player(player).await;
+            self.player.sponsored_by = None;
+        }
+    }
+
+    /// Switch to the account scheduled withdrawal `withdrawal_id` was taken from
+    async fn load_withdrawal_owner(&mut self, withdrawal_id: u64) {
+        let withdrawal = self
+            .state
+            .scheduled_withdrawals
+            .get(&withdrawal_id)
+            .await
+            .expect("Failed to read scheduled withdrawals");
+        if let Some(withdrawal) = withdrawal {
+            self.load_player(withdrawal.owner).await;
+        }
+    }
+
+    /// Run `update` on `owner`'s account, which need not be the loaded one
+    async fn update_account<R>(
+        &mut self,
+        owner: AccountOwner,
+        update: impl FnOnce(&mut PlayerAccount, &mut EpochPool) -> R,
+    ) -> R {
+        if self.player_owner == Some(owner) {
+            return update(&mut self.player, self.state.epoch.get_mut());
+        }
+        let mut account = self
+            .state
+            .players
+            .get(&owner)
+            .await
+            .expect("Failed to load player account")
+            .unwrap_or_else(PlayerAccount::open);
+        let before = Self::totals_of(&account);
+        let result = update(&mut account, self.state.epoch.get_mut());
+        self.shift_totals(before, Self::totals_of(&account));
+        self.state
+            .players
+            .insert(&owner, account)
+            .expect("Failed to store player account");
+        result
+    }
+
+    /// Count the operation against its signer's usage, in the account just loaded
+    fn meter_api_usage(&mut self) {
+        if self.signer().is_none() {
+            return;
+        }
+        let now = self.runtime.system_time().micros();
+        let usage = &mut self.player.api_usage;
+        usage.operations += 1;
+        usage.last_operation_micros = now;
+    }
+
+    /// Fund handling over the loaded account and the epoch in progress
+    fn escrow(&mut self) -> Escrow<'_> {
+        Escrow::new(&mut self.player, self.state.epoch.get_mut())
+    }
+
+    /// Set the loaded account's paused AutoRoll batch, keeping `paused_batches` in step
+    fn set_pending_auto_roll(&mut self, pending: Option<PendingAutoRoll>) {
+        let paused = self.state.paused_batches.get_mut();
+        match (self.player.pending_auto_roll.is_some(), pending.is_some()) {
+            (false, true) => *paused += 1,
+            (true, false) => *paused = paused.saturating_sub(1),
+            _ => {}
+        }
+        self.player.pending_auto_roll = pending;
+    }
+
+    /// Deployment and block the current operation runs in, stamped on key results
+    fn runtime_context(&mut self) -> RuntimeContext {
+        RuntimeContext {
+            chain_id: self.runtime.chain_id(),
+            application_id: self.runtime.application_id().forget_abi(),
+            block_height: self.runtime.block_height().0,
+        }
+    }
+
+    /// The owner the current operation acts for: the signer of its relay envelope if
+    /// it was relayed, otherwise the block's authenticated signer
+    fn signer(&mut self) -> Option<AccountOwner> {
+        self.relayed_owner
+            .or_else(|| self.runtime.authenticated_signer())
+    }
+
+    /// Check a relay envelope and consume its nonce. The signature must be the
+    /// owner's over this exact operation and envelope, for this chain and application.
+    async fn accept_relayed(
+        &mut self,
+        envelope: &RelayEnvelope,
+        signature: &AccountSignature,
+        operation: &Operation,
+    ) -> Result<(), Refusal> {
+        if self.relayed_owner.is_some() || matches!(operation, Operation::Relayed { .. }) {
+            return Err(Refusal::new(
+                ErrorCode::InvalidInput,
+                "Relayed operations cannot be nested",
+            ));
+        }
+        let chain_id = self.runtime.chain_id();
+        let application_id = self.runtime.application_id().forget_abi();
+        if envelope.chain_id != chain_id || envelope.application_id != application_id {
+            return Err(Refusal::new(
+                ErrorCode::NotPermitted,
+                "Relayed operation was signed for another chain or application",
+            ));
+        }
+        let now = self.runtime.system_time().micros();
+        if now >= envelope.expires_at_micros {
+            return Err(Refusal::new(
+                ErrorCode::TooLate,
+                format!("Relay envelope with nonce {} has expired", envelope.nonce),
+            ));
+        }
+        if envelope.expires_at_micros - now > MAX_RELAY_TTL_SECS * 1_000_000 {
+            return Err(Refusal::new(
+                ErrorCode::InvalidInput,
+                format!(
+                    "Relay envelopes may be valid for at most {}s",
+                    MAX_RELAY_TTL_SECS
+                ),
+            ));
+        }
+        let payload = RelayPayload {
+            envelope: envelope.clone(),
+            operation: operation.clone(),
+        };
+        if signature.owner() != envelope.owner || signature.verify(&payload).is_err() {
+            return Err(Refusal::new(
+                ErrorCode::NotPermitted,
+                format!("Invalid relay signature for {}", envelope.owner),
+            ));
+        }
+
+        // Expired nonces can go: their envelopes are refused above
+        let mut nonces = self
+            .state
+            .relay_nonces
+            .get(&envelope.owner)
+            .await
+            .expect("Failed to read relay nonces")
+            .unwrap_or_default();
+        nonces.retain(|consumed| consumed.expires_at_micros > now);
+        if nonces
+            .iter()
+            .any(|consumed| consumed.nonce == envelope.nonce)
+        {
+            return Err(Refusal::new(
+                ErrorCode::AlreadyDone,
+                format!(
+                    "Relay nonce {} was already used by {}",
+                    envelope.nonce, envelope.owner
+                ),
+            ));
+        }
+        nonces.push(ConsumedNonce {
+            nonce: envelope.nonce,
+            expires_at_micros: envelope.expires_at_micros,
+        });
+        self.state
+            .relay_nonces
+            .insert(&envelope.owner, nonces)
+            .expect("Failed to record relay nonce");
+        Ok(())
+    }
+
     // =========================================================================
     // SESSION MANAGEMENT
     // =========================================================================
 
-    async fn start_session(&mut self, expires_in_secs: u64) -> OperationResponse {
+    async fn start_session(
+        &mut self,
+        expires_in_secs: u64,
+        permissions: Option<u32>,
+    ) -> OperationResponse {
         let now = self.runtime.system_time();
+        let permissions = permissions.unwrap_or(ALL_SESSION_PERMISSIONS);
+        // A restricted session cannot replace itself with a wider one
+        if let Some(active) = &self.player.active_session {
+            let widened = permissions & !active.permissions;
+            if now.micros() < active.expires_at_micros && widened != 0 {
+                return OperationResponse::Error {
+                    code: ErrorCode::SessionPermission,
+                    message: format!(
+                        "Session {} cannot grant permissions {:#b} it lacks; end it first",
+                        active.session_id, widened
+                    ),
+                };
+            }
+        }
         let session_id = *self.state.session_counter.get() + 1;
         let expires_at_micros = now.micros() + expires_in_secs * 1_000_000;
 
@@ -112,685 +850,11381 @@ impl FlashportContract {
             created_at_micros: now.micros(),
             expires_at_micros,
             operations_count: 0,
+            transcript_hash: None,
+            won_at_start: self.player.total_won,
+            spent_at_start: self.player.total_spent,
+            last_operation_micros: now.micros(),
+            paused_micros: 0,
+            permissions,
+            cadence: SessionCadence {
+                session_id,
+                ..SessionCadence::default()
+            },
         };
 
-        self.state.active_session.set(Some(session));
+        self.player.active_session = Some(session);
         self.state.session_counter.set(session_id);
+        self.reach_onboarding_step(OnboardingStep::StartedSession);
 
         OperationResponse::SessionStarted {
             session_id,
             expires_at_micros,
+            permissions,
         }
     }
 
     async fn end_session(&mut self) -> OperationResponse {
         // Clear session
-        self.state.active_session.set(None);
-        
-        // Clear game state so new session starts fresh
-        self.state.current_card.set(None);
-        self.state.drawn_numbers.set(Vec::new());
-        self.state.has_unclaimed_prize.set(false);
+        let session = self.player.active_session.take();
+
+        // Settle the open game, then clear game state so the new session starts fresh
+        let settlement = self.settle_open_game().await;
+        self.player.current_card = None;
+        self.player.hedge_card = None;
+        self.set_pending_auto_roll(None);
+        self.player.drawn_numbers = Vec::new();
+        let phase = if self.player.game_phase.is_open() {
+            GamePhase::Cancelled
+        } else {
+            GamePhase::Idle
+        };
+        self.player.game_phase = phase;
         
         // Clear roll history for new session
-        while self.state.roll_history.count() > 0 {
-            self.state.roll_history.delete_front();
+        self.player.roll_history.clear();
+
+        // Publish the session's results, after any forfeit above, for outside auditors
+        let Some(session) = session else {
+            return OperationResponse::SessionEnded {
+                summary: None,
+                event_index: None,
+                settlement,
+            };
+        };
+        let won = self.player.total_won.saturating_sub(session.won_at_start);
+        let spent = self
+            .player
+            .total_spent
+            .saturating_sub(session.spent_at_start);
+        let net_pnl_atto = if won >= spent {
+            u128::from(won.saturating_sub(spent)).to_string()
+        } else {
+            format!("-{}", u128::from(spent.saturating_sub(won)))
+        };
+        let summary = SessionSummary {
+            session_id: session.session_id,
+            player: self.signer(),
+            operations_count: session.operations_count,
+            won,
+            spent,
+            net_pnl_atto,
+            transcript_hash: session.transcript_hash,
+            ended_at_micros: self.runtime.system_time().micros(),
+        };
+        let event_index = self.runtime.emit(
+            StreamName(SESSION_STREAM_NAME.to_vec()),
+            &FlashportEvent::SessionSummary(summary.clone()),
+        );
+        OperationResponse::SessionEnded {
+            summary: Some(summary),
+            event_index: Some(event_index),
+            settlement,
         }
-        
-        OperationResponse::SessionEnded
     }
 
-    fn validate_session(&mut self) -> Result<(), String> {
-        let session = self
-            .state
+    /// Settle the game a session leaves open: claim a pending prize on the player's
+    /// behalf, and refund or forfeit an unfinished game per `session_end_policy`
+    async fn settle_open_game(&mut self) -> Option<SessionSettlement> {
+        let card = self.player.current_card.clone()?;
+        let stake = self.player.current_prize_pool;
+        let (outcome, credited) = match self.player.game_phase {
+            GamePhase::BingoPending => match self.claim_prize().await {
+                OperationResponse::PrizeClaimed { payout_amount, .. } => {
+                    (SettlementOutcome::PrizeClaimed, payout_amount)
+                }
+                _ => (SettlementOutcome::ClaimExpired, "0".to_string()),
+            },
+            GamePhase::Active => {
+                let refund = match self.state.config.get().session_end_policy {
+                    SessionEndPolicy::RefundUnplayed => card.rolls_count == 0,
+                    SessionEndPolicy::Refund => true,
+                    SessionEndPolicy::Forfeit => false,
+                };
+                if refund {
+                    let escrow = &mut self.escrow();
+                    let refunded = Self::or_revert(escrow.refund_bet(card.currency));
+                    (SettlementOutcome::Refunded, Self::format_amount(refunded))
+                } else {
+                    (SettlementOutcome::Forfeited, "0".to_string())
+                }
+            }
+            _ => return None,
+        };
+        let forfeited = Self::or_revert(self.escrow().forfeit());
+        if outcome != SettlementOutcome::PrizeClaimed {
+            let payout = stake.saturating_sub(forfeited);
+            self.archive_settlement(&card, outcome, stake, payout);
+        }
+        Some(SessionSettlement {
+            game_id: card.id,
+            outcome,
+            credited_atto: credited,
+            forfeited_atto: Self::format_amount(forfeited),
+        })
+    }
+
+    /// Chain the operation onto the active session's transcript hash
+    fn record_in_transcript(&mut self, operation: &Operation) {
+        if let Some(session) = &mut self.player.active_session {
+            session.transcript_hash = Some(CryptoHash::new(&TranscriptLink {
+                previous: session.transcript_hash,
+                operation: operation.clone(),
+            }));
+        }
+    }
+
+    /// Whether `operation` runs in quick play: one of the operations that allow it,
+    /// with no live session, signed directly (not relayed) by an owner of the chain.
+    /// With a live session, the session's rules apply instead.
+    fn is_quick_play(&mut self, operation: &Operation) -> bool {
+        if !operation.allows_quick_play() || self.relayed_owner.is_some() {
+            return false;
+        }
+        let now = self.runtime.system_time().micros();
+        let session_live = self
+            .player
             .active_session
-            .get()
             .as_ref()
-            .ok_or_else(|| "No active session - call StartSession first".to_string())?;
+            .is_some_and(|session| now < session.expires_at_micros);
+        if session_live {
+            return false;
+        }
+        match self.runtime.authenticated_signer() {
+            Some(signer) => self.runtime.chain_ownership().verify_owner(&signer),
+            None => false,
+        }
+    }
+
+    fn validate_session(&mut self) -> Result<(), Refusal> {
+        let session = self.player.active_session.as_ref().ok_or_else(|| {
+            Refusal::new(
+                ErrorCode::SessionRequired,
+                "No active session - call StartSession first",
+            )
+        })?;
 
         let now = self.runtime.system_time();
         if now.micros() >= session.expires_at_micros {
-            return Err("Session expired - start a new session".to_string());
+            return Err(Refusal::new(
+                ErrorCode::SessionExpired,
+                "Session expired - start a new session",
+            ));
         }
 
         Ok(())
     }
 
-    // =========================================================================
-    // TOKEN OPERATIONS
-    // =========================================================================
-
-    async fn handle_deposit(&mut self, amount_atto: u128) -> OperationResponse {
-        // Use the amount passed by the user
-        let deposit_amount = Amount::from_attos(amount_atto);
-        
-        // Validate minimum deposit
-        if amount_atto == 0 {
-            return OperationResponse::Error {
-                message: "Deposit amount must be greater than 0".to_string(),
-            };
+    /// Credit a congestion gap back to the active session. A gap since its last
+    /// operation longer than CONGESTION_GAP_FACTOR mean roll intervals is blamed on
+    /// the chain, not the player: everything past one interval is added to the
+    /// session expiry and the claim deadline, up to `congestion_pause_max_secs` per
+    /// session.
+    fn pause_for_congestion(&mut self) {
+        let Some(mut session) = self.player.active_session.clone() else {
+            return;
+        };
+        let now = self.runtime.system_time().micros();
+        let last = std::mem::replace(&mut session.last_operation_micros, now);
+        let max_pause = self.state.config.get().congestion_pause_max_secs as u64 * 1_000_000;
+        let expected = session.cadence.mean_interval_micros();
+        let gap = now.saturating_sub(last);
+        let congested = max_pause > 0
+            && last > 0
+            && expected > 0
+            && session.cadence.intervals >= CADENCE_MIN_SAMPLES
+            && gap > expected.saturating_mul(CONGESTION_GAP_FACTOR);
+        if congested {
+            let pause = (gap - expected).min(max_pause.saturating_sub(session.paused_micros));
+            session.paused_micros += pause;
+            session.expires_at_micros = session.expires_at_micros.saturating_add(pause);
+            Self::push_claim_deadline(&mut self.player, pause, now);
         }
+        self.player.active_session = Some(session);
+    }
 
-        // Add to player balance
-        let current = *self.state.player_balance.get();
-        let new_balance = current.saturating_add(deposit_amount);
-        self.state.player_balance.set(new_balance);
-
-        // Track total deposited
-        let total_dep = *self.state.total_deposited.get();
-        self.state.total_deposited.set(total_dep.saturating_add(deposit_amount));
+    /// Fold this roll request into the active session's cadence stats, and copy them
+    /// to `session_cadence` for the admin's review
+    fn record_roll_cadence(&mut self) {
+        let Some(mut session) = self.player.active_session.clone() else {
+            return;
+        };
+        let config = self.state.config.get().clone();
+        let (was_fast, was_regular) = session.cadence.flags(&config);
+        session.cadence.record(self.runtime.system_time().micros());
 
-        OperationResponse::DepositReceived {
-            amount: Self::format_amount(deposit_amount),
-            new_balance: Self::format_amount(new_balance),
+        // Report a session to the operator the first time it gets flagged
+        let (too_fast, too_regular) = session.cadence.flags(&config);
+        if !(was_fast || was_regular) && (too_fast || too_regular) {
+            let owner = self.signer();
+            let detail = format!(
+                "Session {} rolling every {} ms with {} bps jitter",
+                session.session_id,
+                session.cadence.mean_interval_micros() / 1_000,
+                session.cadence.jitter_bps()
+            );
+            self.push_outbox(
+                OutboxKind::SuspiciousActivity,
+                owner,
+                session.session_id,
+                Amount::ZERO,
+                detail,
+            );
         }
-    }
 
-    async fn handle_withdraw(&mut self, amount: Amount) -> OperationResponse {
-        let current = *self.state.player_balance.get();
-        
-        if amount > current {
-            return OperationResponse::Error {
-                message: format!(
-                    "Insufficient balance. Available: {} atto, Requested: {} atto",
-                    u128::from(current),
-                    u128::from(amount)
-                ),
-            };
+        if let Some(owner) = self.player_owner {
+            self.state
+                .session_cadence
+                .insert(&owner, session.cadence.clone())
+                .expect("Failed to record session cadence");
         }
+        self.player.active_session = Some(session);
+    }
 
-        // Deduct from balance
-        let remaining = current.saturating_sub(amount);
-        self.state.player_balance.set(remaining);
-
-        // In production: Transfer back to the authenticated signer
-        // self.runtime.transfer(owner, amount);
+    // =========================================================================
+    // ADMINISTRATION
+    // =========================================================================
 
-        OperationResponse::WithdrawalProcessed {
-            amount: Self::format_amount(amount),
-            remaining_balance: Self::format_amount(remaining),
+    /// Check the caller against an operation's `OperationAuth`
+    fn authorize(&mut self, auth: OperationAuth) -> Result<(), Refusal> {
+        match auth {
+            OperationAuth::Public => Ok(()),
+            OperationAuth::Signer => match self.signer() {
+                Some(_) => Ok(()),
+                None => Err(Refusal::new(
+                    ErrorCode::SignerRequired,
+                    "This operation requires an authenticated signer",
+                )),
+            },
+            OperationAuth::Session if self.quick_play => Ok(()),
+            OperationAuth::Session => self.validate_session(),
+            OperationAuth::Delegate => {
+                if !self.quick_play {
+                    self.validate_session()?;
+                }
+                let sponsored_player = self
+                    .player
+                    .current_card
+                    .as_ref()
+                    .and_then(|card| card.sponsored_player);
+                let open = self.player.game_phase.is_open();
+                match sponsored_player {
+                    Some(player) if open && self.signer() != Some(player) => Err(Refusal::new(
+                        ErrorCode::NotPermitted,
+                        "Only the sponsored player can play this game",
+                    )),
+                    _ => Ok(()),
+                }
+            }
+            OperationAuth::Admin => self.require_admin(),
         }
     }
 
-    fn charge_fee(&mut self, fee: u128) -> Result<(), String> {
-        let fee_amount = Amount::from_attos(fee);
-        let current = *self.state.player_balance.get();
+    /// Refuse operations outside the permissions of the active, unexpired session, so
+    /// a key handed to automation cannot move funds out unless its session allows it
+    fn check_session_permission(&mut self, operation: &Operation) -> Result<(), Refusal> {
+        let Some(permission) = operation.session_permission() else {
+            return Ok(());
+        };
+        let now = self.runtime.system_time().micros();
+        match &self.player.active_session {
+            Some(session)
+                if now < session.expires_at_micros && session.permissions & permission == 0 =>
+            {
+                Err(Refusal::new(
+                    ErrorCode::SessionPermission,
+                    format!(
+                        "Session {} lacks permission {:#b} for this operation",
+                        session.session_id, permission
+                    ),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
 
-        if fee_amount > current {
-            return Err(format!(
-                "Insufficient balance. Need {} atto, have {} atto. Deposit more LINERA.",
-                fee,
-                u128::from(current)
+    /// Refuse new games and rolls while the deployment is paused; claims and
+    /// withdrawals go through so players can always get their money out
+    fn check_paused(&self, operation: &Operation) -> Result<(), Refusal> {
+        let blocked = matches!(
+            operation.session_permission(),
+            Some(SESSION_CAN_ROLL | SESSION_CAN_NEW_GAME)
+        );
+        if blocked && *self.state.paused.get() {
+            return Err(Refusal::new(
+                ErrorCode::Paused,
+                "The deployment is paused: no new games or rolls until it resumes",
             ));
         }
-
-        // Deduct fee
-        let new_balance = current.saturating_sub(fee_amount);
-        self.state.player_balance.set(new_balance);
-
-        // Track total spent
-        let total_spent = *self.state.total_spent.get();
-        self.state.total_spent.set(total_spent.saturating_add(fee_amount));
-
         Ok(())
     }
 
-    // =========================================================================
-    // GAME LOGIC
-    // =========================================================================
+    fn require_admin(&mut self) -> Result<(), Refusal> {
+        let signer = self.signer();
+        match self.state.admin.get() {
+            Some(admin) if signer.as_ref() == Some(admin) => Ok(()),
+            Some(_) => Err(Refusal::new(
+                ErrorCode::NotPermitted,
+                "Only the admin can perform this operation",
+            )),
+            None => Err(Refusal::new(
+                ErrorCode::NotConfigured,
+                "No admin configured for this deployment",
+            )),
+        }
+    }
 
-    async fn new_game(&mut self, bet_amount_atto: u128) -> OperationResponse {
-        // Validate bet amount is within allowed range
-        if bet_amount_atto < MIN_BET {
+    async fn update_config(&mut self, config: GameConfig) -> OperationResponse {
+        if let Err(reason) = config.card_constraints.validate() {
             return OperationResponse::Error {
-                message: format!(
-                    "Bet too low. Minimum is 1 LINERA ({} atto)",
-                    MIN_BET
-                ),
+                code: ErrorCode::InvalidInput,
+                message: format!("Invalid card constraints: {}", reason),
             };
         }
-        if bet_amount_atto > MAX_BET {
+        if let Err(reason) = PayoutTier::validate_curve(&config.payout_tiers()) {
             return OperationResponse::Error {
-                message: format!(
-                    "Bet too high. Maximum is 100 LINERA ({} atto)",
-                    MAX_BET
-                ),
+                code: ErrorCode::InvalidInput,
+                message: format!("Invalid payout curve: {}", reason),
             };
         }
+        self.propose_change(Operation::UpdateConfig { config })
+    }
 
-        // Charge bet amount as escrow
-        if let Err(msg) = self.charge_fee(bet_amount_atto) {
-            return OperationResponse::Error { message: msg };
+    fn apply_config(&mut self, config: GameConfig) -> OperationResponse {
+        self.state.config.set(config.clone());
+        OperationResponse::ConfigUpdated { config }
+    }
+
+    async fn set_experiment(&mut self, experiment: Option<Experiment>) -> OperationResponse {
+        if let Some(experiment) = &experiment {
+            if experiment.split_a_bps > 10_000 {
+                return OperationResponse::Error {
+                    code: ErrorCode::InvalidInput,
+                    message: "Experiment split must be between 0 and 10000 bps".to_string(),
+                };
+            }
+            if experiment.arm_a.payout_scale_bps == 0 || experiment.arm_b.payout_scale_bps == 0 {
+                return OperationResponse::Error {
+                    code: ErrorCode::InvalidInput,
+                    message: "Experiment payout scale must be greater than 0".to_string(),
+                };
+            }
         }
+        self.propose_change(Operation::SetExperiment { experiment })
+    }
 
-        let game_id = *self.state.game_counter.get() + 1;
-        self.state.game_counter.set(game_id);
+    fn apply_experiment(&mut self, experiment: Option<Experiment>) -> OperationResponse {
+        self.state.experiment.set(experiment.clone());
+        OperationResponse::ExperimentUpdated { experiment }
+    }
 
-        // Generate a new bingo card with verifiable randomness
-        let mut card = self.generate_card(game_id);
-        // Store the bet amount in the card
-        card.bet_amount_atto = bet_amount_atto.to_string();
-        
-        self.state.current_card.set(Some(card.clone()));
-        self.state.drawn_numbers.set(Vec::new());
-        self.state.has_unclaimed_prize.set(false);
+    fn set_high_roller_table(&mut self, table: Option<HighRollerTable>) -> OperationResponse {
+        if let Some(table) = &table {
+            if u128::from(table.max_bet) <= MAX_BET {
+                return OperationResponse::Error {
+                    code: ErrorCode::InvalidInput,
+                    message: format!("High-roller max bet must exceed {} atto", MAX_BET),
+                };
+            }
+            if dice_skin(table.required_skin_id).is_none_or(|skin| skin.price_atto != 0) {
+                return OperationResponse::Error {
+                    code: ErrorCode::InvalidInput,
+                    message: format!("Skin {} is not an achievement skin", table.required_skin_id),
+                };
+            }
+        }
+        self.propose_change(Operation::SetHighRollerTable { table })
+    }
 
-        // Set up prize pool (bet amount goes to pool)
-        let bet_amount = Amount::from_attos(bet_amount_atto);
-        self.state.current_prize_pool.set(bet_amount);
+    fn apply_high_roller_table(&mut self, table: Option<HighRollerTable>) -> OperationResponse {
+        self.state.high_roller_table.set(table.clone());
+        OperationResponse::HighRollerTableUpdated { table }
+    }
 
-        // Increment total games
-        let total = *self.state.total_games.get() + 1;
-        self.state.total_games.set(total);
+    /// Bets above MAX_BET need the open table, a signer holding its achievement, a
+    /// real-money bet within the table limit, and room under the exposure cap should
+    /// the game win the top tier
+    fn check_high_roller_bet(
+        &mut self,
+        bet_amount_atto: u128,
+        currency: Currency,
+    ) -> Result<(), Refusal> {
+        let too_high = || {
+            let message = format!("Bet too high. Maximum is 100 LINERA ({} atto)", MAX_BET);
+            Refusal::new(ErrorCode::BetTooHigh, message)
+        };
+        let signed = self.signer().is_some();
+        let Some(table) = self.state.high_roller_table.get() else {
+            return Err(too_high());
+        };
+        if !signed || !self.player.skins.owned.contains(&table.required_skin_id) {
+            return Err(too_high());
+        }
+        if currency != Currency::Real {
+            return Err(Refusal::new(
+                ErrorCode::Unsupported,
+                "The high-roller table only takes real-money bets",
+            ));
+        }
+        if bet_amount_atto > u128::from(table.max_bet) {
+            return Err(Refusal::new(
+                ErrorCode::BetTooHigh,
+                format!(
+                    "Bet too high. High-roller maximum is {} atto",
+                    u128::from(table.max_bet)
+                ),
+            ));
+        }
 
-        // Update session operations count
-        if let Some(session) = self.state.active_session.get_mut() {
-            session.operations_count += 1;
+        let curve = self.state.config.get().payout_tiers();
+        let stats = self.state.high_roller_stats.get();
+        let bet = Amount::from_attos(bet_amount_atto);
+        let worst_loss = Self::high_roller_exposure(&curve, stats, bet).map_err(|error| {
+            Refusal::new(
+                ErrorCode::BetTooHigh,
+                format!("High-roller bet rejected: {}", error),
+            )
+        })?;
+        if worst_loss > table.exposure_cap {
+            return Err(Refusal::new(
+                ErrorCode::LimitReached,
+                "The high-roller table is at its exposure cap",
+            ));
         }
+        Ok(())
+    }
 
-        OperationResponse::GameStarted {
-            game_id,
-            card,
-            entry_fee_paid: Self::format_amount(bet_amount),
-            prize_pool: Self::format_amount(bet_amount),
+    /// House loss on the high-roller bucket should a new `bet` win the top tier: what
+    /// the bucket has paid out plus that payout, less everything staked in it
+    fn high_roller_exposure(
+        curve: &[PayoutTier],
+        stats: &BucketStats,
+        bet: Amount,
+    ) -> Result<Amount, LedgerError> {
+        let (numerator, denominator, _) = rules::mode_multiplier(curve, GameMode::Classic, 0);
+        let scaled = escrow::mul("high_roller_exposure", bet, numerator as u128)?;
+        let top_payout = Amount::from_attos(u128::from(scaled) / denominator as u128);
+        let owed = escrow::add("high_roller_exposure", stats.paid_out, top_payout)?;
+        let staked = escrow::add("high_roller_stats.wagered", stats.wagered, stats.fees)?;
+        let staked = escrow::add("high_roller_stats.wagered", staked, bet)?;
+        // A bucket that has taken in more than it could owe is not exposed at all
+        Ok(owed.try_sub(staked).unwrap_or(Amount::ZERO))
+    }
+
+    fn update_high_roller_stats(&mut self, update: impl FnOnce(&mut BucketStats)) {
+        let mut stats = self.state.high_roller_stats.get().clone();
+        update(&mut stats);
+        self.state.high_roller_stats.set(stats);
+    }
+
+    fn update_insurance_stats(&mut self, update: impl FnOnce(&mut BucketStats)) {
+        let mut stats = self.state.insurance_stats.get().clone();
+        update(&mut stats);
+        self.state.insurance_stats.set(stats);
+    }
+
+    /// Sticky bucket for the signer, assigning one by hashing the owner on first sight.
+    /// Unsigned operations always land in bucket A without being recorded.
+    async fn experiment_bucket_for_signer(&mut self, split_a_bps: u32) -> ExperimentBucket {
+        let Some(owner) = self.signer() else {
+            return ExperimentBucket::A;
+        };
+        if let Some(bucket) = self
+            .state
+            .experiment_buckets
+            .get(&owner)
+            .await
+            .expect("Failed to read experiment bucket")
+        {
+            return bucket;
         }
+
+        let bucket = if Self::owner_hash_point(&owner) < split_a_bps {
+            ExperimentBucket::A
+        } else {
+            ExperimentBucket::B
+        };
+        self.state
+            .experiment_buckets
+            .insert(&owner, bucket)
+            .expect("Failed to record experiment bucket");
+        self.update_bucket_stats(bucket, |stats| stats.players += 1).await;
+        bucket
     }
 
-    /// THE CORE ATOMIC OPERATION: Roll 4 dice, calculate sum, mark card, check win
-    async fn roll_and_match(&mut self) -> OperationResponse {
-        // Check if there's an active game
-        let card = match self.state.current_card.get().clone() {
-            Some(c) => c,
-            None => {
-                return OperationResponse::Error {
-                    message: "No active game - call NewGame first".to_string(),
-                };
-            }
+    /// FNV-1a over the owner's serialized form, reduced to 0..10_000
+    fn owner_hash_point(owner: &AccountOwner) -> u32 {
+        let bytes = serde_json::to_vec(owner).expect("Failed to serialize owner");
+        let hash = bytes.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+        });
+        (hash % 10_000) as u32
+    }
+
+    async fn update_bucket_stats(
+        &mut self,
+        bucket: ExperimentBucket,
+        update: impl FnOnce(&mut BucketStats),
+    ) {
+        let mut stats = self
+            .state
+            .experiment_stats
+            .get(&bucket)
+            .await
+            .expect("Failed to read experiment stats")
+            .unwrap_or_default();
+        update(&mut stats);
+        self.state
+            .experiment_stats
+            .insert(&bucket, stats)
+            .expect("Failed to record experiment stats");
+    }
+
+    /// Roll fee for a card: the high-roller table's, the Insurance table's or its
+    /// experiment arm's fee, or ROLL_COST, less the largest discount of any happy hour
+    /// running at `now_micros`
+    fn roll_fee_for(&self, card: &BingoCard, now_micros: u64) -> u128 {
+        let fee = match (self.state.high_roller_table.get(), card.template_roll_cost) {
+            (Some(table), _) if card.high_roller => u128::from(table.roll_cost),
+            (_, Some(roll_cost)) => u128::from(roll_cost),
+            _ if card.mode == GameMode::Insurance => INSURANCE_ROLL_COST,
+            _ => card
+                .experiment_arm
+                .as_ref()
+                .map(|arm| u128::from(arm.roll_cost))
+                .unwrap_or(ROLL_COST),
         };
+        let discount_bps = self
+            .state
+            .happy_hours
+            .get()
+            .iter()
+            .filter(|happy_hour| happy_hour.is_active(now_micros))
+            .map(|happy_hour| happy_hour.discount_bps)
+            .max()
+            .unwrap_or(0);
+        let discount = Self::or_revert(
+            fee.checked_mul(discount_bps as u128)
+                .ok_or(LedgerError::Overflow("roll_fee")),
+        ) / 10_000;
+        fee - discount
+    }
 
-        // Check if game already won
-        if card.prize_claimed {
+    async fn extend_claim_deadlines(
+        &mut self,
+        window_secs: u64,
+        reason: String,
+        after: Option<AccountOwner>,
+        limit: u32,
+    ) -> OperationResponse {
+        if limit == 0 || limit > MAX_CLAIM_EXTENSION_PAGE {
             return OperationResponse::Error {
-                message: "Game already completed. Start a new game.".to_string(),
+                code: ErrorCode::InvalidInput,
+                message: format!(
+                    "A claim extension page visits between 1 and {} accounts, got {}",
+                    MAX_CLAIM_EXTENSION_PAGE, limit
+                ),
             };
         }
+        let window_micros = Self::or_revert(
+            window_secs
+                .checked_mul(1_000_000)
+                .ok_or(LedgerError::Overflow("claim_deadline_micros")),
+        );
+        let now = self.runtime.system_time().micros();
+        self.store_player();
 
-        // Check if bingo was achieved but prize not yet claimed
-        if *self.state.has_unclaimed_prize.get() {
+        // Walk owners in key order past the cursor, stopping once the page is full
+        let mut found_cursor = after.is_none();
+        let mut page = Vec::new();
+        let mut has_more = false;
+        self.state
+            .players
+            .for_each_index_while(|owner| {
+                if !found_cursor {
+                    found_cursor = after == Some(owner);
+                    return Ok(true);
+                }
+                if page.len() == limit as usize {
+                    has_more = true;
+                    return Ok(false);
+                }
+                page.push(owner);
+                Ok(true)
+            })
+            .await
+            .expect("Failed to list player accounts");
+        if !found_cursor {
             return OperationResponse::Error {
-                message: "BINGO! Claim your prize or start a new game.".to_string(),
+                code: ErrorCode::NotFound,
+                message: "The `after` cursor is not a player account".to_string(),
             };
         }
-
-        // Charge roll fee (0.1 LINERA)
-        if let Err(msg) = self.charge_fee(ROLL_COST) {
-            return OperationResponse::Error { message: msg };
+        for owner in &page {
+            self.update_account(*owner, |account, _| {
+                Self::push_claim_deadline(account, window_micros, now)
+            })
+            .await;
         }
 
-        let roll_fee_amount = Amount::from_attos(ROLL_COST);
-
-        // Get the current roll count for RNG
-        let current_rolls = card.rolls_count as u64;
-
-        // 1. Generate 4 dice with verifiable randomness
-        let dice = self.generate_dice_roll(current_rolls);
-        let sum: u8 = dice.iter().sum();
-
-        // 2. Track drawn numbers
-        let mut drawn = self.state.drawn_numbers.get().clone();
-        if !drawn.contains(&sum) {
-            drawn.push(sum);
+        let action = self.log_admin_action(
+            AdminActionKind::ClaimDeadlinesExtended,
+            format!(
+                "Extended claim deadlines by {}s for {} accounts: {}",
+                window_secs,
+                page.len(),
+                reason
+            ),
+        );
+        OperationResponse::ClaimDeadlinesExtended {
+            action,
+            visited: page.len() as u32,
+            next_after: page.last().copied().filter(|_| has_more),
         }
-        self.state.drawn_numbers.set(drawn);
-
-        // 3. Clone card for mutation
-        let mut updated_card = card;
+    }
 
-        // 4. Find and mark the number on the card
-        let (matched, match_pos, match_count) = Self::mark_number_on_card(&mut updated_card, sum);
-        let is_lucky = match_count > 1;
+    async fn set_paused(&mut self, paused: bool, reason: String) -> OperationResponse {
+        if *self.state.paused.get() == paused {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: format!(
+                    "The deployment is already {}",
+                    if paused { "paused" } else { "running" }
+                ),
+            };
+        }
+        self.state.paused.set(paused);
 
-        // 5. Check for bingo
-        let bingo_type = Self::check_bingo_on_card(&updated_card);
-        let game_over = bingo_type.is_some();
+        let (kind, verb) = if paused {
+            (AdminActionKind::Paused, "Paused")
+        } else {
+            (AdminActionKind::Resumed, "Resumed")
+        };
+        let action = self.log_admin_action(kind, format!("{}: {}", verb, reason));
+        OperationResponse::PauseChanged { paused, action }
+    }
 
-        if game_over {
-            let wins = *self.state.total_wins.get() + 1;
-            self.state.total_wins.set(wins);
-            self.state.has_unclaimed_prize.set(true);
+    async fn withdraw_treasury(&mut self, amount: Amount, reason: String) -> OperationResponse {
+        if amount == Amount::ZERO {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Treasury withdrawal must be greater than 0".to_string(),
+            };
         }
+        self.propose_change(Operation::WithdrawTreasury { amount, reason })
+    }
 
-        // 6. Update roll count and fees
-        updated_card.rolls_count += 1;
-        let rolls_count = updated_card.rolls_count;
-        
-        // Parse and update total roll fees
-        let prev_fees: u128 = updated_card.total_roll_fees_atto.parse().unwrap_or(0);
-        let new_total_fees = prev_fees + ROLL_COST;
-        updated_card.total_roll_fees_atto = new_total_fees.to_string();
-
-        // Save updated card back
-        self.state.current_card.set(Some(updated_card));
+    fn pay_treasury(&mut self, amount: Amount, reason: String) -> OperationResponse {
+        let bonus_pool = match self.escrow().withdraw_treasury(amount) {
+            Ok(bonus_pool) => bonus_pool,
+            Err(error) => return Self::ledger_failure(error),
+        };
+        // In production: Transfer `amount` from the application account to the admin
 
-        // Update session operations count
-        if let Some(session) = self.state.active_session.get_mut() {
-            session.operations_count += 1;
+        let action = self.log_admin_action(
+            AdminActionKind::TreasuryWithdrawn,
+            format!(
+                "Withdrew {} atto from the treasury: {}",
+                u128::from(amount),
+                reason
+            ),
+        );
+        OperationResponse::TreasuryWithdrawn {
+            amount,
+            bonus_pool,
+            action,
         }
+    }
 
-        // 7. Record in history (keep last 50)
-        let record = RollRecord {
-            dice,
-            sum,
-            matched,
-            timestamp_micros: self.runtime.system_time().micros(),
-            fee_paid_atto: ROLL_COST.to_string(),
-            is_lucky,
+    /// Queue a validated governed `change` behind the governance timelock;
+    /// ExecuteProposal applies it once `governance_timelock_secs` (never less than
+    /// MIN_GOVERNANCE_TIMELOCK_SECS) have passed
+    fn propose_change(&mut self, change: Operation) -> OperationResponse {
+        let proposal_id = *self.state.proposal_counter.get() + 1;
+        self.state.proposal_counter.set(proposal_id);
+        let now = self.runtime.system_time().micros();
+        let timelock_secs = self.state.config.get().governance_timelock_secs;
+        let delay_secs = u64::from(timelock_secs.max(MIN_GOVERNANCE_TIMELOCK_SECS));
+        let executable_at_micros = now.saturating_add(delay_secs * 1_000_000);
+        let config = match &change {
+            Operation::UpdateConfig { config } => Some(config.clone()),
+            _ => None,
+        };
+        let (amount, reason) = match &change {
+            Operation::WithdrawTreasury { amount, reason } => (Some(*amount), reason.clone()),
+            _ => (None, String::new()),
+        };
+        let (kind, detail) = match &change {
+            Operation::UpdateConfig { .. } => {
+                (ProposalKind::ConfigUpdate, "config change".to_string())
+            }
+            Operation::WithdrawTreasury { amount, .. } => {
+                let detail = format!("treasury withdrawal of {} atto", u128::from(*amount));
+                (ProposalKind::TreasuryWithdrawal, detail)
+            }
+            Operation::SetExperiment { experiment } => {
+                let detail = format!("experiment {:?}", experiment);
+                (ProposalKind::Experiment, detail)
+            }
+            Operation::SetHighRollerTable { table } => {
+                let detail = format!("high-roller table {:?}", table);
+                (ProposalKind::HighRollerTable, detail)
+            }
+            Operation::UpsertSeasonMode { mode } => {
+                let detail = format!("season mode {:?}", mode);
+                (ProposalKind::SeasonMode, detail)
+            }
+            Operation::ScheduleHappyHour {
+                starts_at_micros,
+                ends_at_micros,
+                discount_bps,
+            } => {
+                let detail = format!(
+                    "happy hour of {} bps from {} to {} micros",
+                    discount_bps, starts_at_micros, ends_at_micros
+                );
+                (ProposalKind::HappyHour, detail)
+            }
+            Operation::CancelHappyHour { happy_hour_id } => {
+                let detail = format!("cancellation of happy hour {}", happy_hour_id);
+                (ProposalKind::HappyHourCancellation, detail)
+            }
+            other => panic!("{} is not a governed operation", other.name()),
         };
-        self.state.roll_history.push_back(record);
-        while self.state.roll_history.count() > 50 {
-            self.state.roll_history.delete_front();
+        let mut detail = format!(
+            "Proposed {} (proposal {}), executable at {} micros",
+            detail, proposal_id, executable_at_micros
+        );
+        if !reason.is_empty() {
+            detail = format!("{}: {}", detail, reason);
         }
+        let action = self.log_admin_action(AdminActionKind::ChangeProposed, detail.clone());
+        let proposal = GovernanceProposal {
+            proposal_id,
+            kind,
+            change,
+            detail,
+            config,
+            amount,
+            reason,
+            status: ProposalStatus::Pending,
+            proposed_at_micros: now,
+            executable_at_micros,
+            closed_at_micros: None,
+            action_id: action.action_id,
+        };
+        self.store_proposal(&proposal);
+        OperationResponse::ChangeProposed { proposal, action }
+    }
 
-        OperationResponse::RollResult {
-            dice,
-            sum,
-            matched,
-            match_row: match_pos.map(|(r, _)| r),
-            match_col: match_pos.map(|(_, c)| c),
-            bingo_type,
-            game_over,
-            rolls_count,
-            roll_fee_paid: Self::format_amount(roll_fee_amount),
-            total_roll_fees: new_total_fees.to_string(),
-            is_lucky,
+    fn store_proposal(&mut self, proposal: &GovernanceProposal) {
+        self.state
+            .proposals
+            .insert(&proposal.proposal_id, proposal.clone())
+            .expect("Failed to store proposal");
+    }
+
+    /// Load a proposal that is still pending, or refuse
+    async fn pending_proposal(&mut self, proposal_id: u64) -> Result<GovernanceProposal, Refusal> {
+        let proposal = self
+            .state
+            .proposals
+            .get(&proposal_id)
+            .await
+            .expect("Failed to read proposal")
+            .ok_or_else(|| {
+                Refusal::new(
+                    ErrorCode::NotFound,
+                    format!("Proposal {} not found", proposal_id),
+                )
+            })?;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(Refusal::new(
+                ErrorCode::AlreadyDone,
+                format!("Proposal {} is already {:?}", proposal_id, proposal.status),
+            ));
         }
+        Ok(proposal)
     }
 
-    async fn claim_prize(&mut self) -> OperationResponse {
-        // Check if there's an unclaimed prize
-        if !*self.state.has_unclaimed_prize.get() {
+    /// Apply a pending proposal once its timelock has passed. A change that fails to
+    /// apply (e.g. a treasury the pool no longer covers) stays pending.
+    async fn execute_proposal(&mut self, proposal_id: u64) -> OperationResponse {
+        let mut proposal = match self.pending_proposal(proposal_id).await {
+            Ok(proposal) => proposal,
+            Err(refusal) => return refusal.into(),
+        };
+        let now = self.runtime.system_time().micros();
+        if now < proposal.executable_at_micros {
             return OperationResponse::Error {
-                message: "No unclaimed prize. Win a bingo first!".to_string(),
+                code: ErrorCode::TooEarly,
+                message: format!(
+                    "Proposal {} is timelocked until {} micros",
+                    proposal_id, proposal.executable_at_micros
+                ),
             };
         }
 
-        let card = match self.state.current_card.get().clone() {
-            Some(c) => c,
-            None => {
+        let outcome = match proposal.change.clone() {
+            Operation::UpdateConfig { config } => self.apply_config(config),
+            Operation::WithdrawTreasury { amount, reason } => self.pay_treasury(amount, reason),
+            Operation::SetExperiment { experiment } => self.apply_experiment(experiment),
+            Operation::SetHighRollerTable { table } => self.apply_high_roller_table(table),
+            Operation::UpsertSeasonMode { mode } => self.apply_season_mode(mode),
+            Operation::ScheduleHappyHour {
+                starts_at_micros,
+                ends_at_micros,
+                discount_bps,
+            } => self.apply_happy_hour(starts_at_micros, ends_at_micros, discount_bps),
+            Operation::CancelHappyHour { happy_hour_id } => {
+                self.apply_happy_hour_cancellation(happy_hour_id)
+            }
+            other => {
                 return OperationResponse::Error {
-                    message: "No game data found.".to_string(),
+                    code: ErrorCode::InconsistentState,
+                    message: format!("Proposal {} cannot run {}", proposal_id, other.name()),
                 };
             }
         };
-
-        if card.prize_claimed {
-            return OperationResponse::Error {
-                message: "Prize already claimed.".to_string(),
-            };
+        if matches!(outcome, OperationResponse::Error { .. }) {
+            return outcome;
         }
+        proposal.status = ProposalStatus::Executed;
+        proposal.closed_at_micros = Some(now);
+        self.store_proposal(&proposal);
+        OperationResponse::ProposalExecuted {
+            proposal,
+            outcome: Box::new(outcome),
+        }
+    }
 
-        // Parse bet amount from card
-        let bet_amount_atto: u128 = card.bet_amount_atto.parse().unwrap_or(0);
-        if bet_amount_atto == 0 {
+    async fn cancel_proposal(&mut self, proposal_id: u64, reason: String) -> OperationResponse {
+        let mut proposal = match self.pending_proposal(proposal_id).await {
+            Ok(proposal) => proposal,
+            Err(refusal) => return refusal.into(),
+        };
+        let action = self.log_admin_action(
+            AdminActionKind::ProposalCancelled,
+            format!("Cancelled proposal {}: {}", proposal_id, reason),
+        );
+        proposal.status = ProposalStatus::Cancelled;
+        proposal.closed_at_micros = Some(action.timestamp_micros);
+        self.store_proposal(&proposal);
+        OperationResponse::ProposalCancelled { proposal, action }
+    }
+
+    async fn place_withdrawal_hold(
+        &mut self,
+        owner: AccountOwner,
+        reason: HoldReason,
+        expires_in_secs: u64,
+        note: String,
+    ) -> OperationResponse {
+        let now = self.runtime.system_time().micros();
+        let expires_at_micros = match expires_in_secs {
+            0 => 0,
+            secs => now.saturating_add(secs.saturating_mul(1_000_000)),
+        };
+        let expiry = match expires_at_micros {
+            0 => "until released".to_string(),
+            at => format!("until {} micros", at),
+        };
+        let action = self.log_admin_action(
+            AdminActionKind::WithdrawalHoldPlaced,
+            format!("Held withdrawals of {} ({:?}, {}): {}", owner, reason, expiry, note),
+        );
+        let hold = WithdrawalHold {
+            owner,
+            reason,
+            note,
+            placed_at_micros: now,
+            expires_at_micros,
+            action_id: action.action_id,
+        };
+        self.state
+            .withdrawal_holds
+            .insert(&owner, hold.clone())
+            .expect("Failed to record withdrawal hold");
+        OperationResponse::WithdrawalHoldPlaced { hold, action }
+    }
+
+    async fn release_withdrawal_hold(
+        &mut self,
+        owner: AccountOwner,
+        note: String,
+    ) -> OperationResponse {
+        if self.active_withdrawal_hold(owner).await.is_none() {
             return OperationResponse::Error {
-                message: "Invalid bet amount stored in game.".to_string(),
+                code: ErrorCode::NotFound,
+                message: format!("Withdrawals of {} are not on hold", owner),
             };
         }
+        self.state
+            .withdrawal_holds
+            .remove(&owner)
+            .expect("Failed to remove withdrawal hold");
+        let action = self.log_admin_action(
+            AdminActionKind::WithdrawalHoldReleased,
+            format!("Released withdrawals of {}: {}", owner, note),
+        );
+        OperationResponse::WithdrawalHoldReleased { owner, action }
+    }
 
-        // Get multiplier based on rolls count
-        let (multiplier_num, multiplier_denom, multiplier_display) = 
-            Self::get_multiplier(card.rolls_count);
-        
-        // Calculate payout: bet_amount * multiplier_num / multiplier_denom
-        let payout_atto = bet_amount_atto
-            .saturating_mul(multiplier_num as u128)
-            / (multiplier_denom as u128);
-        
-        // Cap payout at player's deposited pool (never pay more than available)
-        // In production, this would check the contract's total balance
-        let capped_payout_atto = payout_atto;
-        let payout_amount = Amount::from_attos(capped_payout_atto);
-
-        // Add payout to player balance
-        let current = *self.state.player_balance.get();
-        let new_balance = current.saturating_add(payout_amount);
-        self.state.player_balance.set(new_balance);
+    /// `owner`'s compliance hold if it still applies; a lapsed one is cleared
+    async fn active_withdrawal_hold(&mut self, owner: AccountOwner) -> Option<WithdrawalHold> {
+        let hold = self
+            .state
+            .withdrawal_holds
+            .get(&owner)
+            .await
+            .expect("Failed to read withdrawal holds")?;
+        if hold.is_active(self.runtime.system_time().micros()) {
+            return Some(hold);
+        }
+        self.state
+            .withdrawal_holds
+            .remove(&owner)
+            .expect("Failed to remove withdrawal hold");
+        None
+    }
 
-        // Track total won
-        let total_won = *self.state.total_won.get();
-        self.state.total_won.set(total_won.saturating_add(payout_amount));
+    /// Refuse to move funds out while the withdrawals of the loaded account are on hold
+    async fn check_withdrawal_hold(&mut self) -> Result<(), Refusal> {
+        let Some(owner) = self.player_owner else {
+            return Ok(());
+        };
+        match self.active_withdrawal_hold(owner).await {
+            Some(hold) => Err(Refusal::new(
+                ErrorCode::WithdrawalHeld,
+                format!("Withdrawals are on hold for review ({:?})", hold.reason),
+            )),
+            None => Ok(()),
+        }
+    }
 
-        // Mark prize as claimed
-        let mut updated_card = card.clone();
-        updated_card.prize_claimed = true;
-        self.state.current_card.set(Some(updated_card));
-        self.state.has_unclaimed_prize.set(false);
-        self.state.current_prize_pool.set(Amount::ZERO);
+    /// Credit every recipient's bonus balance out of the epoch bonus pool. The batch
+    /// is checked as a whole first, so it is either fully credited or not at all.
+    async fn airdrop_bonus(&mut self, recipients: Vec<(AccountOwner, u128)>) -> OperationResponse {
+        if recipients.is_empty() || recipients.len() > MAX_AIRDROP_RECIPIENTS {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!(
+                    "An airdrop credits between 1 and {} recipients, got {}",
+                    MAX_AIRDROP_RECIPIENTS,
+                    recipients.len()
+                ),
+            };
+        }
+        let mut credits: Vec<AirdropCredit> = Vec::with_capacity(recipients.len());
+        let mut total = Amount::ZERO;
+        for (owner, amount_atto) in recipients {
+            if amount_atto == 0 {
+                return OperationResponse::Error {
+                    code: ErrorCode::InvalidInput,
+                    message: format!("Airdrop credit for {} must be greater than 0", owner),
+                };
+            }
+            if credits.iter().any(|credit| credit.owner == owner) {
+                return OperationResponse::Error {
+                    code: ErrorCode::InvalidInput,
+                    message: format!("{} is listed more than once", owner),
+                };
+            }
+            let amount = Amount::from_attos(amount_atto);
+            total = Self::or_revert(escrow::add("airdrop total", total, amount));
+            credits.push(AirdropCredit { owner, amount });
+        }
+        if let Err(error) = self.escrow().fund_promotion(total) {
+            return Self::ledger_failure(error);
+        }
 
-        OperationResponse::PrizeClaimed {
-            bet_amount: bet_amount_atto.to_string(),
-            rolls_count: card.rolls_count,
-            multiplier_display,
-            payout_amount: Self::format_amount(payout_amount),
-            new_balance: Self::format_amount(new_balance),
+        for credit in &credits {
+            let balance = self
+                .state
+                .bonus_balances
+                .get(&credit.owner)
+                .await
+                .expect("Failed to read bonus balance")
+                .unwrap_or_default();
+            let balance = Self::or_revert(escrow::add("bonus_balance", balance, credit.amount));
+            self.state
+                .bonus_balances
+                .insert(&credit.owner, balance)
+                .expect("Failed to credit bonus balance");
         }
+        let batch = AirdropBatch {
+            batch_id: self.state.airdrops.count() as u64 + 1,
+            credits,
+            total,
+            executed_at_micros: self.runtime.system_time().micros(),
+        };
+        self.state.airdrops.push(batch.clone());
+        OperationResponse::BonusAirdropped { batch }
     }
 
-    /// Get the multiplier based on number of rolls
-    /// Returns (numerator, denominator, display_string)
-    /// Using integer math to avoid floating point issues
-    fn get_multiplier(rolls: u32) -> (u32, u32, String) {
-        match rolls {
-            0..=9 => (10, 1, "10x".to_string()),        // 10x
-            10..=14 => (5, 1, "5x".to_string()),       // 5x
-            15..=19 => (3, 1, "3x".to_string()),       // 3x
-            20..=24 => (2, 1, "2x".to_string()),       // 2x
-            25..=34 => (12, 10, "1.2x".to_string()),   // 1.2x
-            35..=44 => (8, 10, "0.8x".to_string()),    // 0.8x
-            _ => (2, 10, "0.2x".to_string()),          // 0.2x (45+)
+    /// Push the pending prize's claim deadline on `account` out by `window_micros`; a
+    /// prize that expired in the meantime becomes claimable again
+    fn push_claim_deadline(account: &mut PlayerAccount, window_micros: u64, now: u64) {
+        let phase = account.game_phase;
+        if !matches!(phase, GamePhase::BingoPending | GamePhase::Expired) {
+            return;
+        }
+        let Some(card) = &mut account.current_card else {
+            return;
+        };
+        if card.claim_deadline_micros != 0 {
+            card.claim_deadline_micros = Self::or_revert(
+                card.claim_deadline_micros
+                    .checked_add(window_micros)
+                    .ok_or(LedgerError::Overflow("claim_deadline_micros")),
+            );
         }
+        if phase == GamePhase::Expired && card.claim_deadline_micros > now {
+            account.game_phase = GamePhase::BingoPending;
+        }
+    }
+
+    /// Append an admin intervention to the public log
+    fn log_admin_action(&mut self, kind: AdminActionKind, detail: String) -> AdminAction {
+        let action = AdminAction {
+            action_id: self.state.admin_log.count() as u64 + 1,
+            admin: self.state.admin.get().expect("Admin checked by require_admin"),
+            kind,
+            detail,
+            timestamp_micros: self.runtime.system_time().micros(),
+        };
+        self.state.admin_log.push(action.clone());
+        action
     }
 
     // =========================================================================
-    // HELPERS
+    // TOKEN OPERATIONS
     // =========================================================================
 
-    /// Generate a new bingo card with numbers 4-24
-    fn generate_card(&mut self, game_id: u64) -> BingoCard {
-        // Create deterministic seed from block + game_id
-        let seed = self.create_seed(game_id);
+    async fn handle_deposit(
+        &mut self,
+        amount_atto: u128,
+        credit_to: Option<AccountOwner>,
+    ) -> OperationResponse {
+        // Use the amount passed by the user
+        let deposit_amount = Amount::from_attos(amount_atto);
+        
+        // Validate minimum deposit
+        if amount_atto == 0 {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Deposit amount must be greater than 0".to_string(),
+            };
+        }
+
+        // A custodial deposit credits the named player's own account, not the sender's
+        let signer = self.signer();
+        let (new_balance, funded_by) = match credit_to {
+            Some(player) if signer.is_some() && signer != Some(player) => {
+                let now = self.runtime.system_time().micros();
+                let new_balance = self
+                    .update_account(player, |account, epoch| {
+                        account.onboarding.reach(OnboardingStep::Deposited, now);
+                        Escrow::new(account, epoch).deposit(deposit_amount)
+                    })
+                    .await;
+                (Self::or_revert(new_balance), signer)
+            }
+            Some(_) if signer.is_none() => {
+                return OperationResponse::Error {
+                    code: ErrorCode::NotPermitted,
+                    message: "A deposit for another player must be signed by its sender"
+                        .to_string(),
+                };
+            }
+            _ => {
+                let new_balance = Self::or_revert(self.escrow().deposit(deposit_amount));
+                self.reach_onboarding_step(OnboardingStep::Deposited);
+                (new_balance, None)
+            }
+        };
 
-        // Generate pool of numbers 4-24 (21 unique numbers)
-        let mut pool: Vec<u8> = (4..=24).collect();
+        // In production: Transfer the deposit from the signer (or custodian)
+        let receipt_id =
+            self.issue_receipt(ReceiptKind::Deposit, deposit_amount, new_balance, funded_by);
 
-        // Simple shuffle using LCG-style randomness
-        let mut rng_state = seed;
-        for i in (1..pool.len()).rev() {
-            rng_state = Self::next_random(rng_state);
-            let j = (rng_state % (i as u64 + 1)) as usize;
-            pool.swap(i, j);
+        OperationResponse::DepositReceived {
+            receipt_id,
+            amount: Self::format_amount(deposit_amount),
+            new_balance: Self::format_amount(new_balance),
+            credited_to: credit_to,
         }
+    }
 
-        // Fill 5x5 grid (25 cells, center is FREE)
-        let mut numbers = [0u8; 25];
-        let mut marked = [false; 25];
-        let mut pool_idx = 0;
-
-        for i in 0..25 {
-            if i == 12 {
-                // Center cell (row 2, col 2) is FREE
-                numbers[i] = 0;
-                marked[i] = true;
-            } else {
-                numbers[i] = pool[pool_idx % pool.len()];
-                pool_idx += 1;
-            }
+    async fn handle_withdraw(&mut self, amount: Amount) -> OperationResponse {
+        if let Err(refusal) = self.check_withdrawal_hold().await {
+            return refusal.into();
         }
+        // Deduct from balance
+        let remaining = match self.escrow().withdraw(amount) {
+            Ok(remaining) => remaining,
+            Err(error) => return Self::ledger_failure(error),
+        };
 
-        BingoCard {
-            id: game_id,
-            numbers,
-            marked,
-            rolls_count: 0,
-            bet_amount_atto: "0".to_string(), // Will be set by new_game
-            total_roll_fees_atto: "0".to_string(),
-            prize_claimed: false,
+        // In production: Transfer back to the authenticated signer
+        // self.runtime.transfer(owner, amount);
+
+        let receipt_id = self.issue_receipt(ReceiptKind::Withdrawal, amount, remaining, None);
+        self.reach_onboarding_step(OnboardingStep::Withdrew);
+
+        OperationResponse::WithdrawalProcessed {
+            receipt_id,
+            amount: Self::format_amount(amount),
+            remaining_balance: Self::format_amount(remaining),
         }
     }
 
-    /// Generate 4 dice (1-6 each) with verifiable randomness
-    fn generate_dice_roll(&mut self, nonce: u64) -> [u8; 4] {
-        // Use multiple entropy sources for better randomness
-        let block_height = self.runtime.block_height().0;
-        let timestamp = self.runtime.system_time().micros();
-        
-        // Increment a running counter for additional entropy within same block
-        let counter = *self.state.game_counter.get();
-        let roll_count = *self.state.total_games.get();
+    async fn schedule_withdrawal(
+        &mut self,
+        amount: Amount,
+        release_at_micros: u64,
+    ) -> OperationResponse {
+        if let Err(refusal) = self.check_withdrawal_hold().await {
+            return refusal.into();
+        }
+        let now = self.runtime.system_time().micros();
+        if amount == Amount::ZERO {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Withdrawal amount must be greater than 0".to_string(),
+            };
+        }
+        if release_at_micros <= now {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Release time must be in the future; use Withdraw instead".to_string(),
+            };
+        }
+        let remaining = match self.escrow().schedule_withdrawal(amount) {
+            Ok(remaining) => remaining,
+            Err(error) => return Self::ledger_failure(error),
+        };
+
+        let withdrawal_id = *self.state.scheduled_withdrawal_counter.get() + 1;
+        self.state.scheduled_withdrawal_counter.set(withdrawal_id);
+        let withdrawal = ScheduledWithdrawal {
+            withdrawal_id,
+            amount,
+            scheduled_at_micros: now,
+            release_at_micros,
+            receipt_id: None,
+            owner: self.player_owner.unwrap_or(AccountOwner::CHAIN),
+        };
+        self.state
+            .scheduled_withdrawals
+            .insert(&withdrawal_id, withdrawal.clone())
+            .expect("Failed to record scheduled withdrawal");
+        OperationResponse::WithdrawalScheduled {
+            withdrawal,
+            remaining_balance: Self::format_amount(remaining),
+        }
+    }
+
+    async fn release_withdrawal(&mut self, withdrawal_id: u64) -> OperationResponse {
+        let Some(mut withdrawal) = self
+            .state
+            .scheduled_withdrawals
+            .get(&withdrawal_id)
+            .await
+            .expect("Failed to read scheduled withdrawals")
+        else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: format!("Unknown scheduled withdrawal {}", withdrawal_id),
+            };
+        };
+        if withdrawal.receipt_id.is_some() {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: format!("Withdrawal {} was already released", withdrawal_id),
+            };
+        }
+        if let Err(refusal) = self.check_withdrawal_hold().await {
+            return refusal.into();
+        }
+        if self.runtime.system_time().micros() < withdrawal.release_at_micros {
+            return OperationResponse::Error {
+                code: ErrorCode::TooEarly,
+                message: format!(
+                    "Withdrawal {} is locked until {} micros",
+                    withdrawal_id, withdrawal.release_at_micros
+                ),
+            };
+        }
+        Self::or_revert(self.escrow().release_withdrawal(withdrawal.amount));
+
+        // In production: Transfer the amount to the player's account
+        let balance = self.player.player_balance;
+        let receipt_id =
+            self.issue_receipt(ReceiptKind::Withdrawal, withdrawal.amount, balance, None);
+        self.reach_onboarding_step(OnboardingStep::Withdrew);
+        withdrawal.receipt_id = Some(receipt_id);
+        self.state
+            .scheduled_withdrawals
+            .insert(&withdrawal_id, withdrawal.clone())
+            .expect("Failed to record scheduled withdrawal");
+        OperationResponse::WithdrawalReleased {
+            withdrawal,
+            receipt_id,
+        }
+    }
+
+    /// Withdraw the whole real balance except a reserve for an open game, reporting
+    /// everything that stays behind. Amounts are exact, so no dust is left over.
+    async fn handle_withdraw_all(&mut self) -> OperationResponse {
+        if let Err(refusal) = self.check_withdrawal_hold().await {
+            return refusal.into();
+        }
+        let mut withheld = Vec::new();
+        let mut withhold = |reason, amount: Amount| {
+            if amount > Amount::ZERO {
+                withheld.push(Withheld {
+                    reason,
+                    amount_atto: Self::format_amount(amount),
+                });
+            }
+        };
+
+        let balance = self.player.player_balance;
+        let real_game = self
+            .player
+            .current_card
+            .as_ref()
+            .is_some_and(|card| card.currency == Currency::Real);
+        if real_game {
+            withhold(WithholdReason::LockedBet, self.player.current_prize_pool);
+        }
+        let reserve = match self.validate_roll() {
+            Ok((card, roll_fee)) if card.currency == Currency::Real => {
+                Amount::from_attos(roll_fee).min(balance)
+            }
+            _ => Amount::ZERO,
+        };
+        withhold(WithholdReason::OpenGameReserve, reserve);
+        withhold(WithholdReason::PlayPoints, self.player.points_balance);
+        withhold(
+            WithholdReason::ScheduledWithdrawals,
+            self.player.pending_withdrawals,
+        );
+
+        let amount = Self::or_revert(escrow::sub("player_balance", balance, reserve));
+        if amount == Amount::ZERO {
+            return OperationResponse::Error {
+                code: ErrorCode::InsufficientFunds,
+                message: "Nothing to withdraw".to_string(),
+            };
+        }
+        let remaining = match self.escrow().withdraw(amount) {
+            Ok(remaining) => remaining,
+            Err(error) => return Self::ledger_failure(error),
+        };
+
+        let receipt_id = self.issue_receipt(ReceiptKind::Withdrawal, amount, remaining, None);
+        self.reach_onboarding_step(OnboardingStep::Withdrew);
+
+        OperationResponse::WithdrawAllProcessed {
+            receipt_id,
+            amount: Self::format_amount(amount),
+            remaining_balance: Self::format_amount(remaining),
+            withheld,
+        }
+    }
+
+    /// Record a balance movement in the ledger and return its receipt id.
+    /// `counterparty` is the custodian that funded a deposit or the player a
+    /// withdrawal was paid to, when that is not the chain's own player.
+    fn issue_receipt(
+        &mut self,
+        kind: ReceiptKind,
+        amount: Amount,
+        balance_after: Amount,
+        counterparty: Option<AccountOwner>,
+    ) -> u64 {
+        let id = *self.state.receipt_counter.get() + 1;
+        self.state.receipt_counter.set(id);
+
+        let receipt = Receipt {
+            id,
+            kind,
+            amount_atto: Self::format_amount(amount),
+            balance_after_atto: Self::format_amount(balance_after),
+            block_height: self.runtime.block_height().0,
+            funded_by: counterparty.filter(|_| kind == ReceiptKind::Deposit),
+            paid_to: counterparty.filter(|_| kind == ReceiptKind::Withdrawal),
+            timestamp_micros: self.runtime.system_time().micros(),
+        };
+        self.state
+            .receipts
+            .insert(&id, receipt)
+            .expect("Failed to record receipt");
+        id
+    }
+
+    async fn refill_points(&mut self) -> OperationResponse {
+        let current = self.player.points_balance;
+        if current >= Amount::from_attos(MIN_BET) {
+            return OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: format!(
+                    "Play points can only be refilled below {} atto. Current: {} atto",
+                    MIN_BET,
+                    u128::from(current)
+                ),
+            };
+        }
+
+        let refilled = Amount::from_attos(PLAY_POINTS_GRANT);
+        self.escrow().refill_points(refilled);
+
+        OperationResponse::PointsRefilled {
+            new_points_balance: Self::format_amount(refilled),
+        }
+    }
+
+    async fn set_preferences(&mut self, preferences: PlayerPreferences) -> OperationResponse {
+        let thresholds = [
+            ("auto-withdraw threshold", &preferences.auto_withdraw_above_atto),
+            ("daily spend alert", &preferences.daily_spend_alert_atto),
+            ("game loss alert", &preferences.game_loss_alert_atto),
+        ];
+        for (name, threshold) in thresholds {
+            if !threshold.is_empty() && threshold.parse::<u128>().is_err() {
+                return OperationResponse::Error {
+                    code: ErrorCode::InvalidInput,
+                    message: format!("Invalid {}: {}", name, threshold),
+                };
+            }
+        }
+        if preferences.auto_withdraw_threshold() > 0 && preferences.payout_address.is_none() {
+            return OperationResponse::Error {
+                code: ErrorCode::NotConfigured,
+                message: "Auto-withdraw requires a payout address".to_string(),
+            };
+        }
+        self.player.preferences = preferences.clone();
+        OperationResponse::PreferencesUpdated { preferences }
+    }
+
+    /// Set or clear the asset the signer's real-money winnings are bridged into
+    async fn set_payout_route(&mut self, target_asset: Option<String>) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Setting a payout route requires a signed operation".to_string(),
+            };
+        };
+        let Some(target_asset) = target_asset else {
+            self.state
+                .payout_routes
+                .remove(&owner)
+                .expect("Failed to clear payout route");
+            return OperationResponse::PayoutRouteSet { owner, route: None };
+        };
+        let target_asset = target_asset.trim().to_string();
+        if target_asset.is_empty() || target_asset.len() > MAX_TARGET_ASSET_LEN {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!("Target asset must be 1-{} bytes long", MAX_TARGET_ASSET_LEN),
+            };
+        }
+        if self.state.payout_bridge.get().is_none() {
+            return OperationResponse::Error {
+                code: ErrorCode::NotConfigured,
+                message: "No payout bridge is configured".to_string(),
+            };
+        }
+        let route = PayoutRoute {
+            target_asset,
+            set_at_micros: self.runtime.system_time().micros(),
+        };
+        self.state
+            .payout_routes
+            .insert(&owner, route.clone())
+            .expect("Failed to store payout route");
+        OperationResponse::PayoutRouteSet {
+            owner,
+            route: Some(route),
+        }
+    }
+
+    /// Open a purge request for the signer, confirmable after DATA_PURGE_GRACE_SECS
+    async fn purge_my_data(&mut self) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Purging data requires a signed operation".to_string(),
+            };
+        };
+        if let Some(request) = &self.player.data_purge {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: format!(
+                    "A data purge is already pending, confirmable from {} micros",
+                    request.confirmable_at_micros
+                ),
+            };
+        }
+        let now = self.runtime.system_time().micros();
+        let request = DataPurgeRequest {
+            owner,
+            requested_at_micros: now,
+            confirmable_at_micros: now + DATA_PURGE_GRACE_SECS * 1_000_000,
+        };
+        self.player.data_purge = Some(request.clone());
+        OperationResponse::DataPurgeRequested { request }
+    }
+
+    /// Purge the requester's personal data: preferences, budget tracking, experiment
+    /// assignment, API usage, cell heatmap, roll cadence, roll history and batch
+    /// outcomes. Balances, receipts and the win archive stay, as solvency accounting,
+    /// disputes and attestations depend on them.
+    async fn confirm_data_purge(&mut self) -> OperationResponse {
+        let Some(request) = self.player.data_purge.clone() else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: "No data purge requested. Send PurgeMyData first".to_string(),
+            };
+        };
+        if self.signer() != Some(request.owner) {
+            return OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: "Only the owner who requested the purge can confirm it".to_string(),
+            };
+        }
+        let now = self.runtime.system_time().micros();
+        if now < request.confirmable_at_micros {
+            return OperationResponse::Error {
+                code: ErrorCode::TooEarly,
+                message: format!(
+                    "The purge can be confirmed from {} micros",
+                    request.confirmable_at_micros
+                ),
+            };
+        }
+        if self.player.active_session.is_some() {
+            return OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: "End your session before purging your data".to_string(),
+            };
+        }
+
+        let owner = request.owner;
+        self.player.preferences = PlayerPreferences::default();
+        self.player.budget_tracker = BudgetTracker::default();
+        self.player.budget_alerts = Vec::new();
+        self.state
+            .experiment_buckets
+            .remove(&owner)
+            .expect("Failed to purge experiment bucket");
+        self.player.api_usage = ApiUsage::default();
+        self.player.cell_hits = [0; 25];
+        self.state
+            .payout_routes
+            .remove(&owner)
+            .expect("Failed to purge payout route");
+        self.state
+            .session_cadence
+            .remove(&owner)
+            .expect("Failed to purge session cadence");
+        self.player.roll_history.clear();
+        self.player.batch_outcomes.clear();
+        self.player.data_purge = None;
+        OperationResponse::DataPurged {
+            owner,
+            purged_at_micros: now,
+        }
+    }
+
+    /// Drop the pending purge request (the requester only)
+    async fn cancel_data_purge(&mut self) -> OperationResponse {
+        let signer = self.signer();
+        match &self.player.data_purge {
+            Some(request) if signer == Some(request.owner) => {
+                self.player.data_purge = None;
+                OperationResponse::DataPurgeCancelled
+            }
+            Some(_) => OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: "Only the owner who requested the purge can cancel it".to_string(),
+            },
+            None => OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: "No data purge is pending".to_string(),
+            },
+        }
+    }
+
+    /// The owner's skin inventory (empty if they never collected one)
+    async fn skin_inventory(&self, owner: &AccountOwner) -> SkinInventory {
+        if self.player_owner == Some(*owner) {
+            return self.player.skins.clone();
+        }
+        self.state
+            .players
+            .get(owner)
+            .await
+            .expect("Failed to read player account")
+            .map(|account| account.skins)
+            .unwrap_or_default()
+    }
+
+    /// Add a skin to the owner's inventory; false if they already own it
+    async fn grant_skin(&mut self, owner: AccountOwner, skin_id: u32) -> bool {
+        self.update_account(owner, |account, _| {
+            if account.skins.owned.contains(&skin_id) {
+                return false;
+            }
+            account.skins.owned.push(skin_id);
+            true
+        })
+        .await
+    }
+
+    /// Buy a skin from the catalog with the signer's real balance
+    async fn buy_skin(&mut self, skin_id: u32) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Buying a skin requires an authenticated signer".to_string(),
+            };
+        };
+        let Some(skin) = dice_skin(skin_id) else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: format!("Unknown skin {}", skin_id),
+            };
+        };
+        if skin.price_atto == 0 {
+            return OperationResponse::Error {
+                code: ErrorCode::Unsupported,
+                message: format!("The {} skin is earned, not sold", skin.name),
+            };
+        }
+        if self.skin_inventory(&owner).await.owned.contains(&skin_id) {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: format!("You already own the {} skin", skin.name),
+            };
+        }
+
+        let price = Amount::from_attos(skin.price_atto);
+        if let Err(error) = self.escrow().charge(Currency::Real, price) {
+            return Self::ledger_failure(error);
+        }
+        self.grant_skin(owner, skin_id).await;
+        OperationResponse::SkinPurchased {
+            skin_id,
+            price_paid: Self::format_amount(price),
+            inventory: self.skin_inventory(&owner).await,
+        }
+    }
+
+    /// Equip an owned skin (or plain dice with None) for the signer's next games
+    async fn equip_skin(&mut self, skin_id: Option<u32>) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Equipping a skin requires an authenticated signer".to_string(),
+            };
+        };
+        let mut inventory = self.skin_inventory(&owner).await;
+        if let Some(skin_id) = skin_id {
+            if !inventory.owned.contains(&skin_id) {
+                return OperationResponse::Error {
+                    code: ErrorCode::NotPermitted,
+                    message: format!("You do not own skin {}", skin_id),
+                };
+            }
+        }
+        inventory.equipped = skin_id;
+        self.update_account(owner, |account, _| account.skins = inventory.clone())
+            .await;
+        OperationResponse::SkinEquipped { inventory }
+    }
+
+    // =========================================================================
+    // GAME LOGIC
+    // =========================================================================
+
+    async fn new_game(
+        &mut self,
+        bet_amount_atto: u128,
+        currency: Currency,
+        mode: GameMode,
+        hedge: bool,
+        sponsored_player: Option<AccountOwner>,
+        variant: GameVariant,
+    ) -> OperationResponse {
+        // Validate bet amount is within allowed range
+        if bet_amount_atto < MIN_BET {
+            return OperationResponse::Error {
+                code: ErrorCode::BetTooLow,
+                message: format!(
+                    "Bet too low. Minimum is 1 LINERA ({} atto)",
+                    MIN_BET
+                ),
+            };
+        }
+        let high_roller = bet_amount_atto > MAX_BET;
+        if high_roller {
+            if let Err(refusal) = self.check_high_roller_bet(bet_amount_atto, currency) {
+                return refusal.into();
+            }
+        }
+
+        if hedge && mode != GameMode::Classic {
+            return OperationResponse::Error {
+                code: ErrorCode::Unsupported,
+                message: "Hedged games can only be played in Classic mode".to_string(),
+            };
+        }
+        if high_roller && mode == GameMode::Insurance {
+            return OperationResponse::Error {
+                code: ErrorCode::Unsupported,
+                message: "The Insurance table does not take high-roller bets".to_string(),
+            };
+        }
+        if high_roller && mode == GameMode::PointsPerMark {
+            return OperationResponse::Error {
+                code: ErrorCode::Unsupported,
+                message: "PointsPerMark games do not take high-roller bets".to_string(),
+            };
+        }
+
+        if let Err(refusal) = self.check_loss_guard(bet_amount_atto, currency) {
+            return refusal.into();
+        }
+
+        // Enforce the open-game limit before taking any funds
+        let limit = self.state.config.get().max_active_games;
+        if limit > 0 {
+            let open_game_ids = self.open_game_ids();
+            if open_game_ids.len() >= limit as usize {
+                return OperationResponse::TooManyActiveGames {
+                    limit,
+                    open_game_ids,
+                };
+            }
+        }
+
+        // Charge bet amount as escrow (bet goes to the prize pool); a previous game
+        // still unsettled is forfeited by the lock
+        let unsettled = match self.player.game_phase {
+            GamePhase::Active | GamePhase::BingoPending => Some(SettlementOutcome::Forfeited),
+            GamePhase::Expired => Some(SettlementOutcome::ClaimExpired),
+            _ => None,
+        };
+        let previous = self.player.current_card.clone().zip(unsettled);
+        let previous_stake = self.player.current_prize_pool;
+        let bet_amount = Amount::from_attos(bet_amount_atto);
+        if let Err(error) = self.escrow().lock(currency, bet_amount) {
+            return Self::ledger_failure(error);
+        }
+        if let Some((previous_card, outcome)) = previous {
+            self.archive_settlement(&previous_card, outcome, previous_stake, Amount::ZERO);
+        }
+
+        let game_id = *self.state.game_counter.get() + 1;
+        self.state.game_counter.set(game_id);
+
+        // Generate a new bingo card with verifiable randomness
+        let max_difficulty = match &variant {
+            GameVariant::Template(template) if template.max_card_difficulty > 0 => {
+                template.max_card_difficulty
+            }
+            _ => self.state.config.get().max_card_difficulty,
+        };
+        let mut card = self.generate_card(game_id, hedge.then_some(0), max_difficulty);
+        // Store the bet amount, currency and mode in the card
+        card.bet_amount_atto = bet_amount_atto.to_string();
+        card.currency = currency;
+        card.mode = mode;
+        card.high_roller = high_roller;
+        if sponsored_player.is_some() {
+            card.sponsor = self.signer();
+            card.sponsored_player = sponsored_player;
+        }
+        if let Some(player) = sponsored_player.or(self.signer()) {
+            card.skin_id = self.skin_inventory(&player).await.equipped;
+        }
+        match &variant {
+            GameVariant::Standard => {}
+            GameVariant::Season(season_mode) => {
+                let seed = self.create_seed(game_id);
+                card.numbers =
+                    rules::deal_range(seed, season_mode.min_number, season_mode.max_number);
+                card.commit_deal_seed(seed);
+                card.art_seed = BingoCard::layout_seed(&card.numbers);
+                card.difficulty_score = 0;
+                card.difficulty_bonus_bps = 0;
+                card.season_mode_id = Some(season_mode.mode_id);
+                card.extra_dice = season_mode.dice_count - 4;
+            }
+            GameVariant::Template(template) => {
+                card.template_id = Some(template.template_id);
+                card.pattern = template.pattern;
+                card.template_roll_cost =
+                    (template.roll_cost > Amount::ZERO).then_some(template.roll_cost);
+            }
+        }
+
+        // A hedged game splits the bet evenly across a second card holding the
+        // numbers the first one does not
+        let mut hedge_card = hedge.then(|| {
+            let hedge_share = bet_amount_atto / 2;
+            card.bet_amount_atto = (bet_amount_atto - hedge_share).to_string();
+            let mut hedge_card = self.generate_card(game_id, Some(1), max_difficulty);
+            hedge_card.bet_amount_atto = hedge_share.to_string();
+            hedge_card.currency = currency;
+            hedge_card.mode = mode;
+            hedge_card.high_roller = high_roller;
+            hedge_card.template_id = card.template_id;
+            hedge_card.pattern = card.pattern;
+            hedge_card
+        });
+
+        // High-roller and Insurance games settle in their own buckets; other real-money
+        // games started during an experiment settle under the owner's bucket, except
+        // PointsPerMark games, whose payouts no experiment arm scales
+        let experiment = self
+            .state
+            .experiment
+            .get()
+            .clone()
+            .filter(|_| mode != GameMode::PointsPerMark);
+        if high_roller {
+            self.update_high_roller_stats(|stats| {
+                stats.games += 1;
+                stats.wagered = Self::or_revert(escrow::add(
+                    "high_roller_stats.wagered",
+                    stats.wagered,
+                    Amount::from_attos(bet_amount_atto),
+                ));
+            });
+        } else if mode == GameMode::Insurance {
+            self.update_insurance_stats(|stats| {
+                stats.games += 1;
+                stats.wagered = Self::or_revert(escrow::add(
+                    "insurance_stats.wagered",
+                    stats.wagered,
+                    Amount::from_attos(bet_amount_atto),
+                ));
+            });
+        } else if let (Some(experiment), Currency::Real) = (experiment, currency) {
+            let bucket = self
+                .experiment_bucket_for_signer(experiment.split_a_bps)
+                .await;
+            for dealt in std::iter::once(&mut card).chain(hedge_card.as_mut()) {
+                dealt.experiment_bucket = Some(bucket);
+                dealt.experiment_arm = Some(experiment.arm(bucket).clone());
+            }
+            self.update_bucket_stats(bucket, |stats| {
+                stats.games += 1;
+                let wagered = Amount::from_attos(bet_amount_atto);
+                stats.wagered = Self::or_revert(escrow::add(
+                    "experiment_stats.wagered",
+                    stats.wagered,
+                    wagered,
+                ));
+            })
+            .await;
+        }
         
-        // Combine multiple entropy sources
-        let mut rng_state: u64 = block_height
-            .wrapping_mul(0xc6a4a7935bd1e995) // Large prime multiplier
-            .wrapping_add(timestamp)
-            .wrapping_mul(0x5851f42d4c957f2d)
-            .wrapping_add(nonce.wrapping_mul(0x2545f4914f6cdd1d))
-            .wrapping_add(counter.wrapping_mul(0x1b873593))
-            .wrapping_add(roll_count.wrapping_mul(0xcc9e2d51));
+        if currency == Currency::Real {
+            self.add_raffle_entry(bet_amount_atto);
+        }
+        // The game stays in the sponsor's account; the sponsored player is pointed to it
+        if let (Some(player), Some(sponsor)) = (sponsored_player, card.sponsor) {
+            self.update_account(player, |account, _| account.sponsored_by = Some(sponsor))
+                .await;
+        }
+
+        self.player.current_card = Some(card.clone());
+        self.player.hedge_card = hedge_card.clone();
+        self.set_pending_auto_roll(None);
+        self.player.drawn_numbers = Vec::new();
+        self.player.game_phase = GamePhase::Active;
+
+        // Increment total games
+        let total = *self.state.total_games.get() + 1;
+        self.state.total_games.set(total);
+        self.player.games_played += 1;
+        self.reach_onboarding_step(OnboardingStep::PlayedGame);
+
+        // Update session operations count
+        if let Some(session) = &mut self.player.active_session {
+            session.operations_count += 1;
+        }
+
+        OperationResponse::GameStarted {
+            game_id,
+            difficulty_score: card.difficulty_score,
+            difficulty_bonus_bps: card.difficulty_bonus_bps,
+            card: Box::new(card),
+            hedge_card: hedge_card.map(Box::new),
+            currency,
+            entry_fee_paid: Self::format_amount(bet_amount),
+            prize_pool: Self::format_amount(bet_amount),
+            context: self.runtime_context(),
+        }
+    }
+
+    // =========================================================================
+    // SEASON MODES
+    // =========================================================================
+
+    /// Validate and store a season mode, issuing an id to new ones
+    async fn upsert_season_mode(&mut self, mode: SeasonMode) -> OperationResponse {
+        if mode.name.trim().is_empty() {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Season modes need a name".to_string(),
+            };
+        }
+        if !(4..=MAX_SEASON_DICE).contains(&mode.dice_count) {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!("Season modes roll 4 to {} dice", MAX_SEASON_DICE),
+            };
+        }
+        // Numbers must be reachable sums, with enough of them for a card
+        let (lowest, highest) = (mode.dice_count, mode.dice_count * 6);
+        if mode.min_number < lowest
+            || mode.max_number > highest
+            || mode.max_number < mode.min_number + 20
+        {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!(
+                    "Card numbers must span at least 21 sums within {}-{}",
+                    lowest, highest
+                ),
+            };
+        }
+        if mode.ends_at_micros <= mode.starts_at_micros {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Season mode must end after it starts".to_string(),
+            };
+        }
+        if mode.mode_id != 0
+            && !self
+                .state
+                .season_modes
+                .contains_key(&mode.mode_id)
+                .await
+                .expect("Failed to read season modes")
+        {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: format!("No season mode {}", mode.mode_id),
+            };
+        }
+        self.propose_change(Operation::UpsertSeasonMode { mode })
+    }
+
+    /// Store a season mode, numbering a new one (`mode_id` 0)
+    fn apply_season_mode(&mut self, mut mode: SeasonMode) -> OperationResponse {
+        if mode.mode_id == 0 {
+            mode.mode_id = *self.state.season_mode_counter.get() + 1;
+            self.state.season_mode_counter.set(mode.mode_id);
+        }
+        self.state
+            .season_modes
+            .insert(&mode.mode_id, mode.clone())
+            .expect("Failed to store season mode");
+        OperationResponse::SeasonModeUpdated { mode }
+    }
+
+    /// Start a Classic real-money game under a live season mode
+    async fn new_season_game(&mut self, mode_id: u64, bet_amount_atto: u128) -> OperationResponse {
+        let season_mode = self
+            .state
+            .season_modes
+            .get(&mode_id)
+            .await
+            .expect("Failed to read season modes");
+        let now = self.runtime.system_time().micros();
+        let Some(season_mode) = season_mode.filter(|mode| mode.is_live(now)) else {
+            return OperationResponse::Error {
+                code: ErrorCode::Closed,
+                message: format!("Season mode {} is not running", mode_id),
+            };
+        };
+        let (currency, mode) = (Currency::Real, GameMode::Classic);
+        let variant = GameVariant::Season(season_mode);
+        self.new_game(bet_amount_atto, currency, mode, false, None, variant)
+            .await
+    }
+
+    // =========================================================================
+    // GAME TEMPLATES
+    // =========================================================================
+
+    /// Validate and store a game template under the signer, issuing an id to new ones
+    async fn upsert_game_template(&mut self, mut template: GameTemplate) -> OperationResponse {
+        if template.name.trim().is_empty() {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Game templates need a name".to_string(),
+            };
+        }
+        let entry_fee = u128::from(template.entry_fee);
+        if entry_fee != 0 && !(MIN_BET..=MAX_BET).contains(&entry_fee) {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!("Template entry fees must be {}-{} atto", MIN_BET, MAX_BET),
+            };
+        }
+        // Templates may raise the roll fee but never undercut the house's own
+        let standard_roll_cost = match template.mode {
+            GameMode::Insurance => INSURANCE_ROLL_COST,
+            _ => ROLL_COST,
+        };
+        let roll_cost = u128::from(template.roll_cost);
+        if roll_cost != 0 && roll_cost < standard_roll_cost {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!(
+                    "Template roll fees must be at least {} atto",
+                    standard_roll_cost
+                ),
+            };
+        }
+
+        let organizer = self.signer();
+        if template.template_id == 0 {
+            template.template_id = *self.state.game_template_counter.get() + 1;
+            self.state.game_template_counter.set(template.template_id);
+        } else {
+            let existing = self
+                .state
+                .game_templates
+                .get(&template.template_id)
+                .await
+                .expect("Failed to read game templates");
+            match existing {
+                None => {
+                    return OperationResponse::Error {
+                        code: ErrorCode::NotFound,
+                        message: format!("No game template {}", template.template_id),
+                    }
+                }
+                Some(existing) if existing.organizer != organizer => {
+                    return OperationResponse::Error {
+                        code: ErrorCode::NotPermitted,
+                        message: "Only the template's organizer may replace it".to_string(),
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        template.organizer = organizer;
+        self.state
+            .game_templates
+            .insert(&template.template_id, template.clone())
+            .expect("Failed to store game template");
+        OperationResponse::GameTemplateUpdated { template }
+    }
+
+    /// Start a game under a published template, which sets the mode and may fix the bet
+    async fn new_template_game(
+        &mut self,
+        template_id: u64,
+        bet_amount_atto: u128,
+        currency: Currency,
+        hedge: bool,
+    ) -> OperationResponse {
+        let template = self
+            .state
+            .game_templates
+            .get(&template_id)
+            .await
+            .expect("Failed to read game templates");
+        let Some(template) = template else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: format!("No game template {}", template_id),
+            };
+        };
+        let entry_fee = u128::from(template.entry_fee);
+        if entry_fee != 0 && bet_amount_atto != entry_fee {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!(
+                    "Games under template {} stake exactly {} atto",
+                    template_id, entry_fee
+                ),
+            };
+        }
+        let mode = template.mode;
+        let variant = GameVariant::Template(template);
+        self.new_game(bet_amount_atto, currency, mode, hedge, None, variant)
+            .await
+    }
+
+    // =========================================================================
+    // HAPPY HOURS
+    // =========================================================================
+
+    /// Add a discount window to the schedule, dropping windows that have ended
+    async fn schedule_happy_hour(
+        &mut self,
+        starts_at_micros: u64,
+        ends_at_micros: u64,
+        discount_bps: u32,
+    ) -> OperationResponse {
+        let now = self.runtime.system_time().micros();
+        if ends_at_micros <= starts_at_micros || ends_at_micros <= now {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Happy hour must end after it starts and in the future".to_string(),
+            };
+        }
+        if discount_bps == 0 || discount_bps > 10_000 {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Happy hour discount must be between 1 and 10000 bps".to_string(),
+            };
+        }
+        self.propose_change(Operation::ScheduleHappyHour {
+            starts_at_micros,
+            ends_at_micros,
+            discount_bps,
+        })
+    }
+
+    fn apply_happy_hour(
+        &mut self,
+        starts_at_micros: u64,
+        ends_at_micros: u64,
+        discount_bps: u32,
+    ) -> OperationResponse {
+        let now = self.runtime.system_time().micros();
+        let happy_hour_id = *self.state.happy_hour_counter.get() + 1;
+        self.state.happy_hour_counter.set(happy_hour_id);
+        let happy_hour = HappyHour {
+            happy_hour_id,
+            starts_at_micros,
+            ends_at_micros,
+            discount_bps,
+        };
+        let schedule = self.state.happy_hours.get_mut();
+        schedule.retain(|scheduled| scheduled.ends_at_micros > now);
+        schedule.push(happy_hour.clone());
+        schedule.sort_by_key(|scheduled| (scheduled.starts_at_micros, scheduled.happy_hour_id));
+        OperationResponse::HappyHourScheduled { happy_hour }
+    }
+
+    async fn cancel_happy_hour(&mut self, happy_hour_id: u64) -> OperationResponse {
+        let schedule = self.state.happy_hours.get();
+        if !schedule
+            .iter()
+            .any(|scheduled| scheduled.happy_hour_id == happy_hour_id)
+        {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: format!("Happy hour {} is not scheduled", happy_hour_id),
+            };
+        }
+        self.propose_change(Operation::CancelHappyHour { happy_hour_id })
+    }
+
+    fn apply_happy_hour_cancellation(&mut self, happy_hour_id: u64) -> OperationResponse {
+        let schedule = self.state.happy_hours.get_mut();
+        let before = schedule.len();
+        schedule.retain(|scheduled| scheduled.happy_hour_id != happy_hour_id);
+        if schedule.len() == before {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: format!("Happy hour {} is not scheduled", happy_hour_id),
+            };
+        }
+        OperationResponse::HappyHourCancelled { happy_hour_id }
+    }
+
+    // =========================================================================
+    // RAFFLE
+    // =========================================================================
+
+    /// Feed the raffle pool from the house take on a real-money bet and give the
+    /// account that placed it its tickets
+    fn add_raffle_entry(&mut self, bet_amount_atto: u128) {
+        let share = Amount::from_attos(bet_amount_atto * RAFFLE_POOL_BPS as u128 / 10_000);
+        let contribution = Self::or_revert(self.escrow().fund_raffle(share));
+        let pool = *self.state.raffle_pool.get();
+        let pool = Self::or_revert(escrow::add("raffle_pool", pool, contribution));
+        self.state.raffle_pool.set(pool);
+
+        let Some(owner) = self.player_owner else {
+            return;
+        };
+        let tickets = (bet_amount_atto / MIN_BET) as u64;
+        let round = self.state.raffle_history.count() as u64 + 1;
+        if self.player.raffle_round != round {
+            self.player.raffle_round = round;
+            self.player.raffle_tickets = 0;
+        }
+        self.player.raffle_tickets += tickets;
+        self.state
+            .raffle_tickets
+            .insert(&owner, self.player.raffle_tickets)
+            .expect("Failed to record raffle tickets");
+        let total = *self.state.raffle_total_tickets.get();
+        self.state.raffle_total_tickets.set(total + tickets);
+    }
+
+    /// Pick a winner weighted by tickets, credit them the pool and open the next round
+    async fn draw_raffle(&mut self) -> OperationResponse {
+        let now = self.runtime.system_time().micros();
+        let draw_at = *self.state.raffle_draw_at_micros.get();
+        if now < draw_at {
+            return OperationResponse::Error {
+                code: ErrorCode::TooEarly,
+                message: format!("Raffle cannot be drawn before {} micros", draw_at),
+            };
+        }
+        let total_tickets = *self.state.raffle_total_tickets.get();
+        if total_tickets == 0 {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: "No raffle tickets issued this round".to_string(),
+            };
+        }
+
+        let draw_id = self.state.raffle_history.count() as u64 + 1;
+        let seed = self.create_seed(draw_id.wrapping_mul(0x52af_f1e5));
+        let target = rules::next_random(seed) % total_tickets;
+
+        // Walk owners in key order until the cumulative ticket count passes the target
+        let mut winner = None;
+        let mut cumulative = 0u64;
+        self.state
+            .raffle_tickets
+            .for_each_index_value_while(|owner, tickets| {
+                cumulative += *tickets;
+                if cumulative > target {
+                    winner = Some((owner, *tickets));
+                    return Ok(false);
+                }
+                Ok(true)
+            })
+            .await
+            .expect("Failed to read raffle tickets");
+        let Some((winner, winner_tickets)) = winner else {
+            return OperationResponse::Error {
+                code: ErrorCode::InconsistentState,
+                message: "Raffle ticket ledger is inconsistent".to_string(),
+            };
+        };
+
+        let prize = *self.state.raffle_pool.get();
+        let award = |account: &mut PlayerAccount, epoch: &mut EpochPool| {
+            Escrow::new(account, epoch).award(prize)
+        };
+        Self::or_revert(self.update_account(winner, award).await);
+
+        let draw = RaffleDraw {
+            draw_id,
+            winner,
+            winner_tickets,
+            total_tickets,
+            prize,
+            drawn_at_micros: now,
+        };
+        self.state.raffle_history.push(draw.clone());
+        self.push_outbox(
+            OutboxKind::Jackpot,
+            Some(winner),
+            draw_id,
+            prize,
+            format!(
+                "Raffle draw {} won with {} of {} tickets",
+                draw_id, winner_tickets, total_tickets
+            ),
+        );
+        self.state.raffle_tickets.clear();
+        self.state.raffle_total_tickets.set(0);
+        self.state.raffle_pool.set(Amount::ZERO);
+        self.state
+            .raffle_draw_at_micros
+            .set(now + RAFFLE_PERIOD_SECS * 1_000_000);
+
+        OperationResponse::RaffleDrawn { draw }
+    }
+
+    // =========================================================================
+    // OPERATOR OUTBOX
+    // =========================================================================
+
+    /// Queue a notification for the operator daemon, returning its entry id
+    fn push_outbox(
+        &mut self,
+        kind: OutboxKind,
+        owner: Option<AccountOwner>,
+        reference_id: u64,
+        amount: Amount,
+        detail: String,
+    ) -> u64 {
+        let id = *self.state.outbox_counter.get() + 1;
+        self.state.outbox_counter.set(id);
+        self.state.outbox.push_back(OutboxEntry {
+            id,
+            kind,
+            created_at_micros: self.runtime.system_time().micros(),
+            owner,
+            reference_id,
+            amount,
+            detail,
+        });
+        id
+    }
+
+    /// Drop delivered entries from the front of the outbox
+    async fn ack_outbox(&mut self, up_to_id: u64) -> OperationResponse {
+        let mut removed = 0;
+        while let Some(entry) = self.state.outbox.front().await.expect("Failed to read outbox") {
+            if entry.id > up_to_id {
+                break;
+            }
+            self.state.outbox.delete_front();
+            removed += 1;
+        }
+        OperationResponse::OutboxAcked {
+            up_to_id,
+            removed,
+            pending: self.state.outbox.count() as u32,
+        }
+    }
+
+    /// Hand a queued win notification to its partner. Runs in its own operation so a
+    /// failing partner cannot fail the claim that queued it.
+    async fn deliver_partner_notification(&mut self, entry_id: u64) -> OperationResponse {
+        let notice = self
+            .state
+            .partner_notices
+            .get(&entry_id)
+            .await
+            .expect("Failed to read partner notices");
+        let Some(PartnerNotice {
+            partner,
+            notification,
+        }) = notice
+        else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: format!("No partner notification is queued as entry {}", entry_id),
+            };
+        };
+        self.state
+            .partner_notices
+            .remove(&entry_id)
+            .expect("Failed to remove partner notice");
+        self.runtime
+            .call_application(true, partner.with_abi::<RewardHookAbi>(), &notification);
+        OperationResponse::PartnerNotified {
+            entry_id,
+            partner,
+            game_id: notification.game_id,
+        }
+    }
+
+    // =========================================================================
+    // DISPUTES
+    // =========================================================================
+
+    /// Record how a game was settled, so it can be disputed
+    fn archive_settlement(
+        &mut self,
+        card: &BingoCard,
+        outcome: SettlementOutcome,
+        stake: Amount,
+        payout: Amount,
+    ) {
+        let game = SettledGame {
+            game_id: card.id,
+            owner: self.signer(),
+            sponsor: card.sponsor,
+            currency: card.currency,
+            outcome,
+            stake,
+            payout,
+            settled_at_micros: self.runtime.system_time().micros(),
+        };
+        if game.currency == Currency::Real && outcome != SettlementOutcome::Refunded {
+            self.player.last_real_settlement = Some(game.clone());
+        }
+        self.state
+            .settled_games
+            .insert(&card.id, game)
+            .expect("Failed to archive settled game");
+    }
+
+    /// Freeze a recently settled game's funds, the player's winnings and the house's
+    /// take alike, pending an admin ruling
+    async fn dispute_game(&mut self, game_id: u64, reason: String) -> OperationResponse {
+        let window_secs = self.state.config.get().dispute_window_secs;
+        if window_secs == 0 {
+            return OperationResponse::Error {
+                code: ErrorCode::NotConfigured,
+                message: "Disputes are not enabled on this deployment".to_string(),
+            };
+        }
+        if reason.trim().is_empty() || reason.len() > MAX_DISPUTE_REASON_LEN {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!(
+                    "A dispute needs a reason of at most {} bytes",
+                    MAX_DISPUTE_REASON_LEN
+                ),
+            };
+        }
+        if let Some(dispute_id) = self
+            .state
+            .disputed_games
+            .get(&game_id)
+            .await
+            .expect("Failed to read disputed games")
+        {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: format!("Game {} is already under dispute {}", game_id, dispute_id),
+            };
+        }
+
+        let now = self.runtime.system_time().micros();
+        let window_start = now.saturating_sub(window_secs as u64 * 1_000_000);
+        let Some(game) = self
+            .state
+            .settled_games
+            .get(&game_id)
+            .await
+            .expect("Failed to read settled games")
+            .filter(|game| game.currency == Currency::Real)
+            .filter(|game| game.settled_at_micros >= window_start)
+        else {
+            return OperationResponse::Error {
+                code: ErrorCode::TooLate,
+                message: format!(
+                    "Game {} has no real-money settlement in the last {}s to dispute",
+                    game_id, window_secs
+                ),
+            };
+        };
+        let owner = self.signer();
+        if game.owner != owner {
+            return OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: "Only the player who played the game can dispute it".to_string(),
+            };
+        }
+        if game.sponsor.is_some() {
+            return OperationResponse::Error {
+                code: ErrorCode::Unsupported,
+                message: "Sponsored winnings were paid out and cannot be frozen".to_string(),
+            };
+        }
+
+        let winnings = match game.outcome {
+            SettlementOutcome::PrizeClaimed => game.payout,
+            _ => Amount::ZERO,
+        };
+        let frozen = Self::or_revert(self.escrow().freeze(winnings));
+        let house_held = Self::or_revert(self.escrow().hold_house_take(game.house_take()));
+        let dispute_id = *self.state.dispute_counter.get() + 1;
+        self.state.dispute_counter.set(dispute_id);
+        let dispute = Dispute {
+            dispute_id,
+            game_id,
+            owner,
+            reason,
+            frozen,
+            house_held,
+            filed_at_micros: now,
+            resolution: None,
+        };
+        self.state
+            .disputes
+            .insert(&dispute_id, dispute.clone())
+            .expect("Failed to record dispute");
+        self.state
+            .disputed_games
+            .insert(&game_id, dispute_id)
+            .expect("Failed to record disputed game");
+        OperationResponse::DisputeFiled { dispute }
+    }
+
+    /// Hash a claimed game's archived outcome into an attestation other chains can
+    /// verify; later calls return the stored attestation unchanged
+    async fn attest_game(&mut self, game_id: u64) -> OperationResponse {
+        if let Some(attestation) = self
+            .state
+            .attestations
+            .get(&game_id)
+            .await
+            .expect("Failed to read attestations")
+        {
+            return OperationResponse::GameAttested { attestation };
+        }
+        let Some(win) = self.archived_win(game_id).await else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: format!("Game {} has no claimed prize to attest", game_id),
+            };
+        };
+        let outcome = AttestedOutcome {
+            chain_id: self.runtime.chain_id(),
+            game_id,
+            owner: win.owner,
+            currency: win.currency,
+            payout: Amount::from_attos(win.payout_atto.parse().unwrap_or(0)),
+            bingo_type: win.bingo_type,
+            rolls_count: win.rolls_count,
+            claimed_at_micros: win.claimed_at_micros,
+        };
+        let attestation = GameAttestation {
+            hash: CryptoHash::new(&outcome),
+            outcome,
+            attested_at_height: self.runtime.block_height().0,
+        };
+        self.state
+            .attestations
+            .insert(&game_id, attestation.clone())
+            .expect("Failed to record attestation");
+        OperationResponse::GameAttested { attestation }
+    }
+
+    /// Check that id counters never moved backwards since the last check, that the
+    /// prize pool holds exactly the open game's bet (and nothing once it settled) and
+    /// that scheduled withdrawals add up, then record the result
+    async fn health_check(&mut self) -> OperationResponse {
+        let counters = HealthCounters {
+            state_version: *self.state.state_version.get(),
+            game_counter: *self.state.game_counter.get(),
+            session_counter: *self.state.session_counter.get(),
+            roll_counter: *self.state.roll_counter.get(),
+            receipt_counter: *self.state.receipt_counter.get(),
+            outbox_counter: *self.state.outbox_counter.get(),
+        };
+        let mut violations = Vec::new();
+
+        if let Some(previous) = self.state.last_health_check.get() {
+            let before = &previous.counters;
+            let pairs = [
+                ("state_version", before.state_version, counters.state_version),
+                ("game_counter", before.game_counter, counters.game_counter),
+                ("session_counter", before.session_counter, counters.session_counter),
+                ("roll_counter", before.roll_counter, counters.roll_counter),
+                ("receipt_counter", before.receipt_counter, counters.receipt_counter),
+                ("outbox_counter", before.outbox_counter, counters.outbox_counter),
+            ];
+            for (name, before, now) in pairs {
+                if now < before {
+                    violations.push(format!("{} went back from {} to {}", name, before, now));
+                }
+            }
+        }
+
+        let card = self.player.current_card.as_ref();
+        if let Some(card) = card.filter(|card| card.id > counters.game_counter) {
+            violations.push(format!(
+                "Current game {} is past game_counter {}",
+                card.id, counters.game_counter
+            ));
+        }
+
+        let pool = u128::from(self.player.current_prize_pool);
+        let phase = self.player.game_phase;
+        let bet = |card: Option<&BingoCard>| {
+            card.map_or(0, |card| card.bet_amount_atto.parse::<u128>().unwrap_or(0))
+        };
+        if phase.is_open() {
+            let locked = bet(card) + bet(self.player.hedge_card.as_ref());
+            if card.is_none() {
+                violations.push(format!("Game phase is {:?} without a card", phase));
+            } else if pool != locked {
+                violations.push(format!(
+                    "Prize pool holds {} atto but the open game's bet is {} atto",
+                    pool, locked
+                ));
+            }
+        } else if pool > 0 && phase != GamePhase::Expired {
+            violations.push(format!(
+                "Orphaned prize: {} atto locked after the game settled as {:?}",
+                pool, phase
+            ));
+        }
+
+        // Each owner's schedules must add up to what their own account holds back:
+        // (unreleased withdrawals, vesting still owed) by owner
+        let mut held_back: BTreeMap<AccountOwner, (Amount, Amount)> = BTreeMap::new();
+        if let Some(owner) = self.player_owner {
+            held_back.entry(owner).or_default();
+        }
+        let withdrawals = self
+            .state
+            .scheduled_withdrawals
+            .index_values()
+            .await
+            .expect("Failed to read scheduled withdrawals");
+        for (_, withdrawal) in withdrawals {
+            if withdrawal.receipt_id.is_none() {
+                let (unreleased, _) = held_back.entry(withdrawal.owner).or_default();
+                *unreleased = unreleased.saturating_add(withdrawal.amount);
+            }
+        }
+        let vesting_schedules = self
+            .state
+            .vesting_schedules
+            .index_values()
+            .await
+            .expect("Failed to read vesting schedules");
+        for (owner, schedules) in vesting_schedules {
+            let (_, unvested) = held_back.entry(owner).or_default();
+            for schedule in schedules {
+                *unvested = unvested.saturating_add(schedule.remaining());
+            }
+        }
+        for (owner, (unreleased, unvested)) in held_back {
+            let account = if self.player_owner == Some(owner) {
+                self.player.clone()
+            } else {
+                self.state
+                    .players
+                    .get(&owner)
+                    .await
+                    .expect("Failed to read player account")
+                    .unwrap_or_default()
+            };
+            if unreleased != account.pending_withdrawals {
+                violations.push(format!(
+                    "{}'s pending_withdrawals is {} atto but their unreleased withdrawals \
+                     total {} atto",
+                    owner,
+                    u128::from(account.pending_withdrawals),
+                    u128::from(unreleased)
+                ));
+            }
+            if unvested != account.vesting_balance {
+                violations.push(format!(
+                    "{}'s vesting_balance is {} atto but their vesting schedules still owe \
+                     {} atto",
+                    owner,
+                    u128::from(account.vesting_balance),
+                    u128::from(unvested)
+                ));
+            }
+        }
+
+        let report = HealthReport {
+            checked_at_micros: self.runtime.system_time().micros(),
+            block_height: self.runtime.block_height().0,
+            healthy: violations.is_empty(),
+            violations,
+            counters,
+        };
+        self.state.last_health_check.set(Some(report.clone()));
+        OperationResponse::HealthChecked { report }
+    }
+
+    /// The archived win of `game_id`, if it was claimed
+    async fn archived_win(&self, game_id: u64) -> Option<WinRecord> {
+        for index in (0..self.state.win_archive.count()).rev() {
+            let win = self
+                .state
+                .win_archive
+                .get(index)
+                .await
+                .expect("Failed to read win archive")?;
+            if win.game_id == game_id {
+                return Some(win);
+            }
+        }
+        None
+    }
+
+    async fn resolve_dispute(
+        &mut self,
+        dispute_id: u64,
+        outcome: DisputeOutcome,
+        award_atto: u128,
+        note: String,
+    ) -> OperationResponse {
+        let Some(mut dispute) = self
+            .state
+            .disputes
+            .get(&dispute_id)
+            .await
+            .expect("Failed to read disputes")
+        else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: format!("Unknown dispute {}", dispute_id),
+            };
+        };
+        if dispute.resolution.is_some() {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: format!("Dispute {} was already resolved", dispute_id),
+            };
+        }
+        if award_atto > 0 && outcome != DisputeOutcome::Upheld {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Only an upheld dispute can carry an award".to_string(),
+            };
+        }
+
+        let award = Amount::from_attos(award_atto);
+        let treasury = self.state.epoch.get().bonus_pool;
+        if award > treasury {
+            return OperationResponse::Error {
+                code: ErrorCode::InsufficientFunds,
+                message: format!(
+                    "Award of {} atto exceeds the treasury's {} atto",
+                    award_atto,
+                    u128::from(treasury)
+                ),
+            };
+        }
+
+        // The ruling applies to the account of the owner who filed the dispute
+        let frozen = dispute.frozen;
+        let held = dispute.house_held;
+        let rule = |account: &mut PlayerAccount, epoch: &mut EpochPool| {
+            let mut escrow = Escrow::new(account, epoch);
+            match outcome {
+                DisputeOutcome::Dismissed => {
+                    escrow.unfreeze(frozen)?;
+                    escrow.return_house_take(held)
+                }
+                DisputeOutcome::Upheld => {
+                    escrow.unfreeze(frozen)?;
+                    escrow.refund(Currency::Real, held)
+                }
+                DisputeOutcome::Voided => {
+                    escrow.void_frozen(frozen)?;
+                    escrow.refund(Currency::Real, held)
+                }
+            }?;
+            if award > Amount::ZERO {
+                escrow.award_from_treasury(award)?;
+            }
+            Ok(())
+        };
+        let owner = dispute.owner.unwrap_or(AccountOwner::CHAIN);
+        Self::or_revert(self.update_account(owner, rule).await);
+
+        let action = self.log_admin_action(
+            AdminActionKind::DisputeResolved,
+            format!(
+                "Dispute {} on game {} {:?} (award {} atto): {}",
+                dispute_id, dispute.game_id, outcome, award_atto, note
+            ),
+        );
+        dispute.resolution = Some(DisputeResolution {
+            outcome,
+            award,
+            note,
+            action_id: action.action_id,
+            resolved_at_micros: action.timestamp_micros,
+        });
+        self.state
+            .disputes
+            .insert(&dispute_id, dispute.clone())
+            .expect("Failed to record dispute");
+        OperationResponse::DisputeResolved { dispute, action }
+    }
+
+    // =========================================================================
+    // TOURNAMENTS
+    // =========================================================================
+
+    /// Deal the shared card of a new tournament
+    async fn create_tournament(
+        &mut self,
+        entry_fee_atto: u128,
+        duration_secs: u64,
+    ) -> OperationResponse {
+        if !(MIN_BET..=MAX_BET).contains(&entry_fee_atto) {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!("Entry fee must be between {} and {} atto", MIN_BET, MAX_BET),
+            };
+        }
+        if duration_secs == 0 {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Tournament duration must be greater than 0".to_string(),
+            };
+        }
+
+        let tournament_id = *self.state.tournament_counter.get() + 1;
+        self.state.tournament_counter.set(tournament_id);
+        let max_difficulty = self.state.config.get().max_card_difficulty;
+        let deal_id = tournament_id.wrapping_mul(0x7f4a_7c15);
+        let numbers = self.generate_card(deal_id, None, max_difficulty).numbers;
+        let tournament = Tournament {
+            tournament_id,
+            numbers,
+            entry_fee: Amount::from_attos(entry_fee_atto),
+            pot: Amount::ZERO,
+            closes_at_micros: self.runtime.system_time().micros() + duration_secs * 1_000_000,
+            settled: false,
+            entries: Vec::new(),
+            interest_bps_per_day: self.state.config.get().tournament_interest_bps_per_day,
+            interest_paid: Amount::ZERO,
+        };
+        self.state
+            .tournaments
+            .insert(&tournament_id, tournament.clone())
+            .expect("Failed to store tournament");
+        OperationResponse::TournamentCreated { tournament }
+    }
+
+    /// Load a tournament that is still taking entries and rolls
+    async fn open_tournament(&mut self, tournament_id: u64) -> Result<Tournament, Refusal> {
+        let tournament = self
+            .state
+            .tournaments
+            .get(&tournament_id)
+            .await
+            .expect("Failed to read tournament")
+            .ok_or_else(|| {
+                let message = format!("Tournament {} not found", tournament_id);
+                Refusal::new(ErrorCode::NotFound, message)
+            })?;
+        if self.runtime.system_time().micros() >= tournament.closes_at_micros {
+            let message = format!("Tournament {} has closed", tournament_id);
+            return Err(Refusal::new(ErrorCode::Closed, message));
+        }
+        Ok(tournament)
+    }
+
+    async fn enter_tournament(&mut self, tournament_id: u64) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Entering a tournament requires a signed operation".to_string(),
+            };
+        };
+        let mut tournament = match self.open_tournament(tournament_id).await {
+            Ok(tournament) => tournament,
+            Err(refusal) => return refusal.into(),
+        };
+        if tournament.entry_mut(&owner).is_some() {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: "Already entered this tournament".to_string(),
+            };
+        }
+        let fee = tournament.entry_fee;
+        if let Err(error) = self.escrow().charge(Currency::Real, fee) {
+            return Self::ledger_failure(error);
+        }
+
+        let mut marked = [false; 25];
+        marked[12] = true;
+        // Every entrant rolls their own stream, so finishing times differ
+        let nonce = tournament_id.wrapping_mul(0x2545_f491) ^ tournament.entries.len() as u64;
+        let dice_seed = self.create_seed(nonce);
+        tournament.pot = Self::or_revert(escrow::add("tournament.pot", tournament.pot, fee));
+        tournament.entries.push(TournamentEntry {
+            owner,
+            marked,
+            rolls: 0,
+            rolls_to_bingo: None,
+            finished_at_micros: 0,
+            rank: None,
+            payout: Amount::ZERO,
+            entered_at_micros: self.runtime.system_time().micros(),
+            interest: Amount::ZERO,
+            dice_seed,
+        });
+        let response = OperationResponse::TournamentEntered {
+            tournament_id,
+            entrants: tournament.entries.len() as u32,
+            pot: tournament.pot,
+        };
+        self.state
+            .tournaments
+            .insert(&tournament_id, tournament)
+            .expect("Failed to store tournament");
+        response
+    }
+
+    /// Play the signer's next roll of their own stream against the shared card
+    async fn tournament_roll(&mut self, tournament_id: u64) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Tournament rolls require a signed operation".to_string(),
+            };
+        };
+        let mut tournament = match self.open_tournament(tournament_id).await {
+            Ok(tournament) => tournament,
+            Err(refusal) => return refusal.into(),
+        };
+        let now = self.runtime.system_time().micros();
+        let numbers = tournament.numbers;
+        let Some(entry) = tournament.entry_mut(&owner) else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: "Not entered in this tournament".to_string(),
+            };
+        };
+        if entry.rolls_to_bingo.is_some() {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: "Already finished this tournament".to_string(),
+            };
+        }
+        let dice = entry.stream_dice(entry.rolls);
+        let sum: u8 = dice.iter().sum();
+        let mut matched = false;
+        for (cell, number) in entry.marked.iter_mut().zip(numbers) {
+            if number == sum && !*cell {
+                *cell = true;
+                matched = true;
+            }
+        }
+        entry.rolls += 1;
+        let rolls = entry.rolls;
+        let finished = rules::count_completed_lines(&entry.marked) > 0;
+        if finished {
+            entry.rolls_to_bingo = Some(rolls);
+            entry.finished_at_micros = now;
+        }
+
+        self.state
+            .tournaments
+            .insert(&tournament_id, tournament)
+            .expect("Failed to store tournament");
+        OperationResponse::TournamentRolled {
+            tournament_id,
+            dice,
+            sum,
+            matched,
+            rolls,
+            finished,
+        }
+    }
+
+    /// Rank finishers by rolls-to-bingo (earlier finish breaks ties) and split the pot
+    /// by TOURNAMENT_PAYOUT_BPS. With no finishers every entry fee is refunded. Every
+    /// entrant is also credited the interest their fee earned in escrow, out of the
+    /// treasury and in entry order for as long as it lasts.
+    async fn settle_tournament(&mut self, tournament_id: u64) -> OperationResponse {
+        let Some(mut tournament) = self
+            .state
+            .tournaments
+            .get(&tournament_id)
+            .await
+            .expect("Failed to read tournament")
+        else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: format!("Tournament {} not found", tournament_id),
+            };
+        };
+        if tournament.settled {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: format!("Tournament {} is already settled", tournament_id),
+            };
+        }
+        if self.runtime.system_time().micros() < tournament.closes_at_micros {
+            return OperationResponse::Error {
+                code: ErrorCode::TooEarly,
+                message: format!(
+                    "Tournament cannot be settled before {} micros",
+                    tournament.closes_at_micros
+                ),
+            };
+        }
+
+        let mut finishers: Vec<usize> = (0..tournament.entries.len())
+            .filter(|index| tournament.entries[*index].rolls_to_bingo.is_some())
+            .collect();
+        finishers.sort_by_key(|index| {
+            let entry = &tournament.entries[*index];
+            (entry.rolls_to_bingo, entry.finished_at_micros)
+        });
+        finishers.truncate(TOURNAMENT_PAYOUT_BPS.len());
+
+        // Each entrant is settled in their own account
+        let fee = tournament.entry_fee;
+        if finishers.is_empty() {
+            for entry in &mut tournament.entries {
+                let refund = |account: &mut PlayerAccount, epoch: &mut EpochPool| {
+                    Escrow::new(account, epoch).refund(Currency::Real, fee)
+                };
+                Self::or_revert(self.update_account(entry.owner, refund).await);
+                entry.payout = fee;
+            }
+        } else {
+            let pot = u128::from(tournament.pot);
+            let shares_bps: u128 = TOURNAMENT_PAYOUT_BPS[..finishers.len()]
+                .iter()
+                .map(|bps| *bps as u128)
+                .sum();
+            let mut remaining = pot;
+            for (rank, index) in finishers.iter().enumerate().rev() {
+                // Rank 1 is settled last and takes the rounding dust
+                let payout = if rank == 0 {
+                    remaining
+                } else {
+                    pot * TOURNAMENT_PAYOUT_BPS[rank] as u128 / shares_bps
+                };
+                remaining -= payout;
+                let payout = Amount::from_attos(payout);
+                let award = |account: &mut PlayerAccount, epoch: &mut EpochPool| {
+                    Escrow::new(account, epoch).award(payout)
+                };
+                let owner = tournament.entries[*index].owner;
+                Self::or_revert(self.update_account(owner, award).await);
+                let entry = &mut tournament.entries[*index];
+                entry.rank = Some(rank as u32 + 1);
+                entry.payout = payout;
+            }
+        }
+
+        // Interest comes out of the treasury, pro-rata to each fee's time in escrow; once
+        // the treasury is empty the remaining entrants earn none
+        let now = self.runtime.system_time().micros();
+        let mut interest_paid = Amount::ZERO;
+        for index in 0..tournament.entries.len() {
+            let accrued = tournament.accrued_interest(&tournament.entries[index], now);
+            let accrued = Amount::from_attos(Self::or_revert(
+                accrued.ok_or(LedgerError::Overflow("tournament.interest")),
+            ));
+            let interest = accrued.min(self.state.epoch.get().bonus_pool);
+            if interest > Amount::ZERO {
+                let award = |account: &mut PlayerAccount, epoch: &mut EpochPool| {
+                    Escrow::new(account, epoch).award_from_treasury(interest)
+                };
+                let owner = tournament.entries[index].owner;
+                Self::or_revert(self.update_account(owner, award).await);
+            }
+            interest_paid = Self::or_revert(escrow::add(
+                "tournament.interest_paid",
+                interest_paid,
+                interest,
+            ));
+            tournament.entries[index].interest = interest;
+        }
+        tournament.interest_paid = interest_paid;
+        tournament.settled = true;
+
+        self.state
+            .tournaments
+            .insert(&tournament_id, tournament.clone())
+            .expect("Failed to store tournament");
+        OperationResponse::TournamentSettled { tournament }
+    }
+
+    // =========================================================================
+    // DUELS
+    // =========================================================================
+
+    /// Deal a card for one duel seat
+    fn deal_duel_seat(&mut self, duel_id: u64, owner: AccountOwner, seat: u64) -> DuelSeat {
+        let max_difficulty = self.state.config.get().max_card_difficulty;
+        let deal_id = duel_id.wrapping_mul(0x6a09_e667).wrapping_add(seat);
+        let card = self.generate_card(deal_id, None, max_difficulty);
+        DuelSeat {
+            owner,
+            numbers: card.numbers,
+            marked: card.marked,
+            rolls: 0,
+        }
+    }
+
+    async fn open_duel(&mut self, roll_fee_atto: u128) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Opening a duel requires a signed operation".to_string(),
+            };
+        };
+        if !(ROLL_COST..=MAX_BET).contains(&roll_fee_atto) {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!(
+                    "Duel roll fee must be between {} and {} atto",
+                    ROLL_COST, MAX_BET
+                ),
+            };
+        }
+
+        let duel_id = *self.state.duel_counter.get() + 1;
+        self.state.duel_counter.set(duel_id);
+        let seat = self.deal_duel_seat(duel_id, owner, 0);
+        let duel = Duel {
+            duel_id,
+            roll_fee: Amount::from_attos(roll_fee_atto),
+            pot: Amount::ZERO,
+            seats: vec![seat],
+            turn: 0,
+            turn_deadline_micros: 0,
+            status: DuelStatus::Waiting,
+            winner: None,
+            payout: Amount::ZERO,
+            rake: Amount::ZERO,
+            opened_at_micros: self.runtime.system_time().micros(),
+        };
+        self.state
+            .duels
+            .insert(&duel_id, duel.clone())
+            .expect("Failed to store duel");
+        OperationResponse::DuelOpened { duel }
+    }
+
+    /// Load a duel, or refuse with NotFound
+    async fn load_duel(&mut self, duel_id: u64) -> Result<Duel, Refusal> {
+        self.state
+            .duels
+            .get(&duel_id)
+            .await
+            .expect("Failed to read duel")
+            .ok_or_else(|| Refusal::new(ErrorCode::NotFound, format!("Duel {} not found", duel_id)))
+    }
+
+    async fn join_duel(&mut self, duel_id: u64) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Joining a duel requires a signed operation".to_string(),
+            };
+        };
+        let mut duel = match self.load_duel(duel_id).await {
+            Ok(duel) => duel,
+            Err(refusal) => return refusal.into(),
+        };
+        if duel.status != DuelStatus::Waiting {
+            return OperationResponse::Error {
+                code: ErrorCode::Closed,
+                message: format!("Duel {} already has two players", duel_id),
+            };
+        }
+        if duel.seat_of(&owner).is_some() {
+            return OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: "You cannot join your own duel".to_string(),
+            };
+        }
+
+        let seat = self.deal_duel_seat(duel_id, owner, 1);
+        duel.seats.push(seat);
+        duel.status = DuelStatus::Playing;
+        duel.turn_deadline_micros = self.duel_turn_deadline();
+        self.state
+            .duels
+            .insert(&duel_id, duel.clone())
+            .expect("Failed to store duel");
+        OperationResponse::DuelJoined { duel }
+    }
+
+    /// When a duel turn starting now runs out
+    fn duel_turn_deadline(&mut self) -> u64 {
+        let now = self.runtime.system_time().micros();
+        now.saturating_add(DUEL_TURN_SECS * 1_000_000)
+    }
+
+    /// Pay the duel's pot out of escrow to `winner`, the signer, less the house rake
+    fn settle_duel(&mut self, duel: &mut Duel, winner: AccountOwner) {
+        let (payout, rake) = duel.split_pot();
+        Self::or_revert(self.escrow().release_duel_pot(duel.pot, payout));
+        duel.winner = Some(winner);
+        duel.payout = payout;
+        duel.rake = rake;
+        duel.status = DuelStatus::Settled;
+    }
+
+    /// Play the signer's turn: the fee goes into the pot, the roll marks their own card,
+    /// and a completed line settles the duel. Otherwise the turn passes. A turn taken
+    /// after its deadline is refused; the other player may claim the pot instead.
+    async fn duel_roll(&mut self, duel_id: u64) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Duel rolls require a signed operation".to_string(),
+            };
+        };
+        let mut duel = match self.load_duel(duel_id).await {
+            Ok(duel) => duel,
+            Err(refusal) => return refusal.into(),
+        };
+        match duel.status {
+            DuelStatus::Waiting => {
+                return OperationResponse::Error {
+                    code: ErrorCode::TooEarly,
+                    message: format!("Duel {} is waiting for a challenger", duel_id),
+                };
+            }
+            DuelStatus::Settled => {
+                return OperationResponse::Error {
+                    code: ErrorCode::GameOver,
+                    message: format!("Duel {} is over", duel_id),
+                };
+            }
+            DuelStatus::Playing => {}
+        }
+        let Some(seat) = duel.seat_of(&owner) else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: format!("Not seated in duel {}", duel_id),
+            };
+        };
+        if seat != duel.turn as usize {
+            return OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: format!("It is {}'s turn", duel.seats[duel.turn as usize].owner),
+            };
+        }
+        if self.runtime.system_time().micros() > duel.turn_deadline_micros {
+            return OperationResponse::Error {
+                code: ErrorCode::TooLate,
+                message: format!("Your turn ran out at {} micros", duel.turn_deadline_micros),
+            };
+        }
+        if let Err(error) = self.escrow().stake_duel_fee(duel.roll_fee) {
+            return Self::ledger_failure(error);
+        }
+        duel.pot = Self::or_revert(escrow::add("duel.pot", duel.pot, duel.roll_fee));
+
+        // Number the roll across both seats so each one draws fresh dice
+        let rolls: u64 = duel.seats.iter().map(|seat| seat.rolls as u64).sum();
+        let dice = self.generate_dice_roll(duel_id.wrapping_mul(0x3c6e_f372).wrapping_add(rolls));
+        let sum: u8 = dice.iter().sum();
+        let player = &mut duel.seats[seat];
+        let mut matched = false;
+        for (cell, number) in player.marked.iter_mut().zip(player.numbers) {
+            if number == sum && !*cell {
+                *cell = true;
+                matched = true;
+            }
+        }
+        player.rolls += 1;
+
+        if rules::count_completed_lines(&player.marked) > 0 {
+            self.settle_duel(&mut duel, owner);
+        } else {
+            duel.turn = 1 - duel.turn;
+            duel.turn_deadline_micros = self.duel_turn_deadline();
+        }
+
+        self.state
+            .duels
+            .insert(&duel_id, duel.clone())
+            .expect("Failed to store duel");
+        OperationResponse::DuelRolled {
+            dice,
+            sum,
+            matched,
+            duel,
+        }
+    }
+
+    /// Settle a duel for the seated player who is not on turn, once the other player's
+    /// turn has run out
+    async fn claim_duel_pot(&mut self, duel_id: u64) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Claiming a duel pot requires a signed operation".to_string(),
+            };
+        };
+        let mut duel = match self.load_duel(duel_id).await {
+            Ok(duel) => duel,
+            Err(refusal) => return refusal.into(),
+        };
+        match duel.status {
+            DuelStatus::Waiting => {
+                return OperationResponse::Error {
+                    code: ErrorCode::TooEarly,
+                    message: format!("Duel {} is waiting for a challenger", duel_id),
+                };
+            }
+            DuelStatus::Settled => {
+                return OperationResponse::Error {
+                    code: ErrorCode::GameOver,
+                    message: format!("Duel {} is over", duel_id),
+                };
+            }
+            DuelStatus::Playing => {}
+        }
+        match duel.seat_of(&owner) {
+            None => {
+                return OperationResponse::Error {
+                    code: ErrorCode::NotPermitted,
+                    message: format!("Not seated in duel {}", duel_id),
+                };
+            }
+            Some(seat) if seat == duel.turn as usize => {
+                return OperationResponse::Error {
+                    code: ErrorCode::NotPermitted,
+                    message: "It is your turn".to_string(),
+                };
+            }
+            Some(_) => {}
+        }
+        if self.runtime.system_time().micros() <= duel.turn_deadline_micros {
+            return OperationResponse::Error {
+                code: ErrorCode::TooEarly,
+                message: format!(
+                    "The other player has until {} micros to roll",
+                    duel.turn_deadline_micros
+                ),
+            };
+        }
+
+        self.settle_duel(&mut duel, owner);
+        self.state
+            .duels
+            .insert(&duel_id, duel.clone())
+            .expect("Failed to store duel");
+        OperationResponse::DuelPotClaimed { duel }
+    }
+
+    // =========================================================================
+    // SYNDICATES
+    // =========================================================================
+
+    fn create_syndicate(&mut self, name: String) -> OperationResponse {
+        let Some(manager) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Creating a syndicate requires a signed operation".to_string(),
+            };
+        };
+        let name = name.trim().to_string();
+        if name.is_empty() || name.len() > MAX_SYNDICATE_NAME_LEN {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!(
+                    "Syndicate name must be 1 to {} bytes",
+                    MAX_SYNDICATE_NAME_LEN
+                ),
+            };
+        }
+
+        let syndicate_id = *self.state.syndicate_counter.get() + 1;
+        self.state.syndicate_counter.set(syndicate_id);
+        let now = self.runtime.system_time().micros();
+        let syndicate = Syndicate {
+            syndicate_id,
+            name,
+            manager,
+            members: vec![SyndicateMember {
+                owner: manager,
+                contributed: Amount::ZERO,
+                stake: Amount::ZERO,
+                max_bet: Amount::from_attos(MAX_BET),
+                joined_at_micros: now,
+            }],
+            bankroll: Amount::ZERO,
+            games_played: 0,
+            wagered: Amount::ZERO,
+            paid_out: Amount::ZERO,
+            created_at_micros: now,
+        };
+        self.state
+            .syndicates
+            .insert(&syndicate_id, syndicate.clone())
+            .expect("Failed to store syndicate");
+        OperationResponse::SyndicateCreated { syndicate }
+    }
+
+    /// Load a syndicate and the signer's member index in it, or refuse
+    async fn load_syndicate_membership(
+        &mut self,
+        syndicate_id: u64,
+    ) -> Result<(Syndicate, usize), Refusal> {
+        let owner = self.signer().ok_or_else(|| {
+            Refusal::new(
+                ErrorCode::SignerRequired,
+                "Syndicate operations require a signed operation",
+            )
+        })?;
+        let syndicate = self
+            .state
+            .syndicates
+            .get(&syndicate_id)
+            .await
+            .expect("Failed to read syndicate")
+            .ok_or_else(|| {
+                Refusal::new(
+                    ErrorCode::NotFound,
+                    format!("Syndicate {} not found", syndicate_id),
+                )
+            })?;
+        let index = syndicate.member_index(&owner).ok_or_else(|| {
+            Refusal::new(
+                ErrorCode::NotPermitted,
+                format!("Not a member of syndicate {}", syndicate_id),
+            )
+        })?;
+        Ok((syndicate, index))
+    }
+
+    fn store_syndicate(&mut self, syndicate: &Syndicate) {
+        self.state
+            .syndicates
+            .insert(&syndicate.syndicate_id, syndicate.clone())
+            .expect("Failed to store syndicate");
+    }
+
+    async fn set_syndicate_member(
+        &mut self,
+        syndicate_id: u64,
+        member: AccountOwner,
+        max_bet_atto: u128,
+    ) -> OperationResponse {
+        let (mut syndicate, index) = match self.load_syndicate_membership(syndicate_id).await {
+            Ok(membership) => membership,
+            Err(refusal) => return refusal.into(),
+        };
+        if syndicate.members[index].owner != syndicate.manager {
+            return OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: "Only the syndicate manager can set members".to_string(),
+            };
+        }
+        if max_bet_atto != 0 && !(MIN_BET..=MAX_BET).contains(&max_bet_atto) {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!(
+                    "A member bet limit is 0 (backer only) or between {} and {} atto",
+                    MIN_BET, MAX_BET
+                ),
+            };
+        }
+
+        let max_bet = Amount::from_attos(max_bet_atto);
+        match syndicate.member_index(&member) {
+            Some(index) => syndicate.members[index].max_bet = max_bet,
+            None if syndicate.members.len() >= MAX_SYNDICATE_MEMBERS => {
+                return OperationResponse::Error {
+                    code: ErrorCode::LimitReached,
+                    message: format!(
+                        "A syndicate holds at most {} members",
+                        MAX_SYNDICATE_MEMBERS
+                    ),
+                };
+            }
+            None => syndicate.members.push(SyndicateMember {
+                owner: member,
+                contributed: Amount::ZERO,
+                stake: Amount::ZERO,
+                max_bet,
+                joined_at_micros: self.runtime.system_time().micros(),
+            }),
+        }
+        self.store_syndicate(&syndicate);
+        OperationResponse::SyndicateMemberSet { syndicate }
+    }
+
+    async fn contribute_to_syndicate(
+        &mut self,
+        syndicate_id: u64,
+        amount_atto: u128,
+    ) -> OperationResponse {
+        if amount_atto == 0 {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "Contribution must be greater than 0".to_string(),
+            };
+        }
+        let (mut syndicate, index) = match self.load_syndicate_membership(syndicate_id).await {
+            Ok(membership) => membership,
+            Err(refusal) => return refusal.into(),
+        };
+
+        // In production: Transfer the contribution from the member to the application
+        let amount = Amount::from_attos(amount_atto);
+        let member = &mut syndicate.members[index];
+        member.contributed = Self::or_revert(escrow::add(
+            "syndicate.contributed",
+            member.contributed,
+            amount,
+        ));
+        member.stake = Self::or_revert(escrow::add("syndicate.stake", member.stake, amount));
+        syndicate.bankroll = Self::or_revert(escrow::add(
+            "syndicate.bankroll",
+            syndicate.bankroll,
+            amount,
+        ));
+        self.store_syndicate(&syndicate);
+        OperationResponse::SyndicateFunded { syndicate }
+    }
+
+    async fn leave_syndicate(&mut self, syndicate_id: u64) -> OperationResponse {
+        let (mut syndicate, index) = match self.load_syndicate_membership(syndicate_id).await {
+            Ok(membership) => membership,
+            Err(refusal) => return refusal.into(),
+        };
+        let member = syndicate.members[index].owner;
+        if member == syndicate.manager && syndicate.members.len() > 1 {
+            return OperationResponse::Error {
+                code: ErrorCode::NotPermitted,
+                message: "The manager can only leave once every other member has".to_string(),
+            };
+        }
+        if let Err(refusal) = self.check_withdrawal_hold().await {
+            return refusal.into();
+        }
+
+        // In production: Transfer the stake to the member
+        let paid_out = syndicate.members.remove(index).stake;
+        syndicate.bankroll = Self::or_revert(escrow::sub(
+            "syndicate.bankroll",
+            syndicate.bankroll,
+            paid_out,
+        ));
+        let syndicate = if syndicate.members.is_empty() {
+            self.state
+                .syndicates
+                .remove(&syndicate_id)
+                .expect("Failed to close syndicate");
+            None
+        } else {
+            self.store_syndicate(&syndicate);
+            Some(syndicate)
+        };
+        OperationResponse::SyndicateLeft {
+            syndicate_id,
+            member,
+            paid_out,
+            syndicate,
+        }
+    }
+
+    /// Play a Classic game for the syndicate until its first line or
+    /// SYNDICATE_GAME_MAX_ROLLS rolls, then share the net result out over the members
+    async fn syndicate_game(
+        &mut self,
+        syndicate_id: u64,
+        bet_amount_atto: u128,
+    ) -> OperationResponse {
+        let (mut syndicate, index) = match self.load_syndicate_membership(syndicate_id).await {
+            Ok(membership) => membership,
+            Err(refusal) => return refusal.into(),
+        };
+        if bet_amount_atto < MIN_BET {
+            return OperationResponse::Error {
+                code: ErrorCode::BetTooLow,
+                message: format!("Bet too low. Minimum is {} atto", MIN_BET),
+            };
+        }
+        let max_bet = u128::from(syndicate.members[index].max_bet);
+        if bet_amount_atto > max_bet {
+            return OperationResponse::Error {
+                code: ErrorCode::BetTooHigh,
+                message: format!("Your syndicate bet limit is {} atto", max_bet),
+            };
+        }
+        // The bankroll must cover the bet and every roll the game may take
+        let max_cost = bet_amount_atto + SYNDICATE_GAME_MAX_ROLLS as u128 * ROLL_COST;
+        if u128::from(syndicate.bankroll) < max_cost {
+            return OperationResponse::Error {
+                code: ErrorCode::InsufficientFunds,
+                message: format!(
+                    "The syndicate bankroll must cover {} atto for this bet, it holds {} atto",
+                    max_cost,
+                    u128::from(syndicate.bankroll)
+                ),
+            };
+        }
+
+        // The house pool, with this bet added, must cover the best payout the game can win
+        let curve = self.state.config.get().payout_tiers();
+        let best_payout = Self::or_revert(
+            rules::tier_payout(&curve, bet_amount_atto, 1)
+                .map(Amount::from_attos)
+                .ok_or(LedgerError::Overflow("syndicate.payout")),
+        );
+        let house_pool = Self::or_revert(escrow::house_pool(self.state.epoch.get()));
+        let bet = Amount::from_attos(bet_amount_atto);
+        if Self::or_revert(escrow::add("house pool", house_pool, bet)) < best_payout {
+            return OperationResponse::Error {
+                code: ErrorCode::InsufficientFunds,
+                message: format!(
+                    "The house pool cannot cover the {} atto this bet can win",
+                    u128::from(best_payout)
+                ),
+            };
+        }
+
+        // Tagged with the top bit so syndicate seeds never share a nonce with a game id
+        let nonce = 1 << 63 | syndicate_id << 32 | syndicate.games_played;
+        let seed = self.create_seed(nonce);
+        let numbers = rules::deal_numbers(seed);
+        let mut marked = [false; 25];
+        marked[12] = true;
+        let mut rolls = 0;
+        let mut bingo_type = None;
+        while bingo_type.is_none() && rolls < SYNDICATE_GAME_MAX_ROLLS {
+            rolls += 1;
+            let step = u64::from(rolls).wrapping_mul(0x9e3779b97f4a7c15);
+            let dice = rules::dice_from_state(seed ^ step);
+            let sum = dice.iter().sum::<u8>();
+            for (cell, number) in marked.iter_mut().zip(numbers) {
+                if number == sum {
+                    *cell = true;
+                }
+            }
+            bingo_type = rules::bingo_lines(&marked).first().copied();
+        }
+
+        let roll_fees = Amount::from_attos(rolls as u128 * ROLL_COST);
+        let cost = Self::or_revert(escrow::add("syndicate.cost", bet, roll_fees));
+        let payout = match bingo_type {
+            Some(_) => Self::or_revert(
+                rules::tier_payout(&curve, bet_amount_atto, rolls)
+                    .map(Amount::from_attos)
+                    .ok_or(LedgerError::Overflow("syndicate.payout")),
+            ),
+            None => Amount::ZERO,
+        };
+        let won = payout >= cost;
+        let net = if won {
+            Self::or_revert(escrow::sub("syndicate.net", payout, cost))
+        } else {
+            Self::or_revert(escrow::sub("syndicate.net", cost, payout))
+        };
+        let signed = |amount: Amount| match (won, u128::from(amount)) {
+            (false, atto) if atto > 0 => format!("-{}", atto),
+            (_, atto) => atto.to_string(),
+        };
+
+        // Share the result out before the bankroll moves, so the weights are the stakes
+        // the game was played with
+        let member_shares = syndicate.pro_rata_shares(net);
+        let mut shares = Vec::new();
+        for (member, share) in syndicate.members.iter_mut().zip(member_shares) {
+            member.stake = Self::or_revert(if won {
+                escrow::add("syndicate.stake", member.stake, share)
+            } else {
+                escrow::sub("syndicate.stake", member.stake, share)
+            });
+            shares.push(SyndicateShare {
+                owner: member.owner,
+                change_atto: signed(share),
+                stake: member.stake,
+            });
+        }
+        syndicate.bankroll = Self::or_revert(if won {
+            escrow::add("syndicate.bankroll", syndicate.bankroll, net)
+        } else {
+            escrow::sub("syndicate.bankroll", syndicate.bankroll, net)
+        });
+        syndicate.games_played += 1;
+        syndicate.wagered =
+            Self::or_revert(escrow::add("syndicate.wagered", syndicate.wagered, cost));
+        syndicate.paid_out = Self::or_revert(escrow::add(
+            "syndicate.paid_out",
+            syndicate.paid_out,
+            payout,
+        ));
+        self.store_syndicate(&syndicate);
+        let epoch = self.state.epoch.get_mut();
+        Self::or_revert(escrow::settle_house_game(epoch, bet, cost, payout));
+
+        let player = syndicate.members[index].owner;
+        OperationResponse::SyndicateGamePlayed {
+            result: SyndicateGameResult {
+                syndicate_id,
+                player,
+                seed,
+                bet,
+                rolls,
+                roll_fees,
+                bingo_type,
+                payout,
+                net_result_atto: signed(net),
+                shares,
+            },
+            syndicate,
+        }
+    }
+
+    // =========================================================================
+    // LOBBY REGISTRY
+    // =========================================================================
+
+    /// Parameters advertised to the lobby registry
+    fn config_summary(&self) -> ConfigSummary {
+        let config = self.state.config.get();
+        ConfigSummary {
+            abi_version: ABI_VERSION,
+            min_bet: Amount::from_attos(MIN_BET),
+            max_bet: Amount::from_attos(MAX_BET),
+            roll_cost: Amount::from_attos(ROLL_COST),
+            max_active_games: config.max_active_games,
+            claim_window_secs: config.claim_window_secs,
+        }
+    }
+
+    async fn register_with_lobby(&mut self) -> OperationResponse {
+        let Some(lobby_chain) = *self.state.lobby_chain.get() else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotConfigured,
+                message: "No lobby chain configured for this deployment".to_string(),
+            };
+        };
+        let config_summary = self.config_summary();
+        let chain = self.runtime.chain_id();
+        self.runtime
+            .prepare_message(Message::RegisterInstance {
+                chain,
+                config_summary: config_summary.clone(),
+            })
+            .send_to(lobby_chain);
+        OperationResponse::LobbyRegistrationSent {
+            lobby_chain,
+            config_summary,
+        }
+    }
+
+    /// Record a deployment in the registry. Only the lobby chain keeps one, and a
+    /// chain may only register itself.
+    async fn register_instance(&mut self, chain: ChainId, config_summary: ConfigSummary) {
+        if *self.state.lobby_chain.get() != Some(self.runtime.chain_id()) {
+            return;
+        }
+        if self.runtime.message_origin_chain_id() != Some(chain) {
+            return;
+        }
+        let now = self.runtime.system_time().micros();
+        let first_registered_micros = self
+            .state
+            .instances
+            .get(&chain)
+            .await
+            .expect("Failed to read registered instance")
+            .map_or(now, |instance| instance.first_registered_micros);
+        self.state
+            .instances
+            .insert(
+                &chain,
+                RegisteredInstance {
+                    chain_id: chain,
+                    config_summary,
+                    first_registered_micros,
+                    updated_at_micros: now,
+                },
+            )
+            .expect("Failed to register instance");
+    }
+
+    // =========================================================================
+    // PUBLIC STATISTICS
+    // =========================================================================
+
+    /// Emit an aggregate statistics snapshot for external indexers, at most once
+    /// per STATS_PUBLISH_INTERVAL_SECS
+    async fn publish_stats(&mut self) -> OperationResponse {
+        let now = self.runtime.system_time().micros();
+        if let Some(last) = self.state.last_published_stats.get() {
+            let next_at = last.published_at_micros + STATS_PUBLISH_INTERVAL_SECS * 1_000_000;
+            if now < next_at {
+                return OperationResponse::Error {
+                    code: ErrorCode::TooEarly,
+                    message: format!("Stats can next be published at {} micros", next_at),
+                };
+            }
+        }
+
+        // Bring the deployment-wide totals up to date with the loaded account
+        self.store_player();
+        let volume = *self.state.total_spent.get();
+        let paid_out = *self.state.total_won.get();
+        let rtp_bps = if volume == Amount::ZERO {
+            0
+        } else {
+            let rtp = Self::or_revert(
+                u128::from(paid_out)
+                    .checked_mul(10_000)
+                    .ok_or(LedgerError::Overflow("rtp_bps")),
+            ) / u128::from(volume);
+            u64::try_from(rtp).unwrap_or(u64::MAX)
+        };
+        let snapshot = StatsSnapshot {
+            schema_version: STATS_SCHEMA_VERSION,
+            published_at_micros: now,
+            total_games: *self.state.total_games.get(),
+            total_wins: *self.state.total_wins.get(),
+            volume,
+            paid_out,
+            rtp_bps,
+            jackpot: *self.state.raffle_pool.get(),
+        };
+        let event_index = self.runtime.emit(
+            StreamName(STATS_STREAM_NAME.to_vec()),
+            &FlashportEvent::Stats(snapshot.clone()),
+        );
+        self.state.last_published_stats.set(Some(snapshot.clone()));
+        OperationResponse::StatsPublished {
+            snapshot,
+            event_index,
+        }
+    }
+
+    // =========================================================================
+    // READ MODELS
+    // =========================================================================
+
+    /// Add a real-money win to the owner's lifetime winnings and re-rank them.
+    /// Totals only grow, so this is the only point an owner can enter the top ranks.
+    async fn record_leaderboard_win(&mut self, owner: AccountOwner, payout: Amount) {
+        let mut entry = self
+            .state
+            .owner_winnings
+            .get(&owner)
+            .await
+            .expect("Failed to read owner winnings")
+            .unwrap_or(LeaderboardEntry {
+                owner,
+                total_won: Amount::ZERO,
+                wins: 0,
+                best_payout: Amount::ZERO,
+            });
+        entry.total_won = Self::or_revert(escrow::add(
+            "owner_winnings.total_won",
+            entry.total_won,
+            payout,
+        ));
+        entry.wins += 1;
+        entry.best_payout = entry.best_payout.max(payout);
+        self.state
+            .owner_winnings
+            .insert(&owner, entry.clone())
+            .expect("Failed to store owner winnings");
+
+        // Ties keep the owner who got there first ahead
+        let leaderboard = self.state.leaderboard.get_mut();
+        leaderboard.retain(|ranked| ranked.owner != owner);
+        let rank = leaderboard.partition_point(|ranked| ranked.total_won >= entry.total_won);
+        if rank < LEADERBOARD_SIZE {
+            leaderboard.insert(rank, entry);
+            leaderboard.truncate(LEADERBOARD_SIZE);
+        }
+    }
+
+    /// Tick `step` off the account's first-run checklist; reaching it again keeps the
+    /// first time
+    fn reach_onboarding_step(&mut self, step: OnboardingStep) {
+        if self.signer().is_none() {
+            return;
+        }
+        let now = self.runtime.system_time().micros();
+        self.player.onboarding.reach(step, now);
+    }
+
+    /// Slide the rolling RTP window on by one closed epoch
+    fn record_closed_epoch(&mut self, epoch: &EpochPool) {
+        let mut rtp = self.state.rolling_rtp.get().clone();
+        rtp.wagered = Self::or_revert(escrow::add(
+            "rolling_rtp.wagered",
+            rtp.wagered,
+            epoch.wagered,
+        ));
+        rtp.paid_out = Self::or_revert(escrow::add(
+            "rolling_rtp.paid_out",
+            rtp.paid_out,
+            epoch.paid_out,
+        ));
+        rtp.epochs.push(EpochVolume {
+            epoch_id: epoch.epoch_id,
+            wagered: epoch.wagered,
+            paid_out: epoch.paid_out,
+        });
+        if rtp.epochs.len() > ROLLING_RTP_EPOCHS {
+            let oldest = rtp.epochs.remove(0);
+            rtp.wagered = Self::or_revert(escrow::sub(
+                "rolling_rtp.wagered",
+                rtp.wagered,
+                oldest.wagered,
+            ));
+            rtp.paid_out = Self::or_revert(escrow::sub(
+                "rolling_rtp.paid_out",
+                rtp.paid_out,
+                oldest.paid_out,
+            ));
+        }
+        self.state.rolling_rtp.set(rtp);
+    }
+
+    /// Bring the active-game summary in line with the current card and phase
+    fn refresh_active_game(&mut self) {
+        let phase = self.player.game_phase;
+        let summary = match &self.player.current_card {
+            Some(card) if phase.is_open() => Some(ActiveGameSummary {
+                game_id: card.id,
+                phase,
+                mode: card.mode,
+                currency: card.currency,
+                bet: Amount::from_attos(card.bet_amount_atto.parse().unwrap_or(0)),
+                rolls_count: card.rolls_count,
+                marked_cells: card.marked.iter().filter(|&&marked| marked).count() as u32,
+                completed_lines: rules::count_completed_lines(&card.marked),
+                tier: MULTIPLIER_TIER_NAMES[multiplier_tier(card.payout_rolls())].to_string(),
+                claim_deadline_micros: card.claim_deadline_micros,
+            }),
+            _ => None,
+        };
+        if self.player.active_game != summary {
+            self.player.active_game = summary;
+        }
+    }
+
+    // =========================================================================
+    // PRIZE POOL EPOCHS
+    // =========================================================================
+
+    /// Advance the state version and height that query responses report
+    fn bump_state_version(&mut self) {
+        let version = *self.state.state_version.get() + 1;
+        self.state.state_version.set(version);
+        self.state.state_height.set(self.runtime.block_height().0);
+    }
+
+    fn delta_fields(&self) -> DeltaFields {
+        DeltaFields {
+            balance: self.player.player_balance,
+            points: self.player.points_balance,
+            game_phase: self.player.game_phase,
+            current_card: self.player.current_card.clone(),
+            hedge_card: self.player.hedge_card.clone(),
+            session: self.player.active_session.clone(),
+            budget_alerts: self.player.budget_alerts.clone(),
+        }
+    }
+
+    /// Stamp every `DeltaField` the operation changed with the current state version
+    fn record_delta_versions(&mut self, before: DeltaFields) {
+        let after = self.delta_fields();
+        let version = *self.state.state_version.get();
+        let changes = [
+            (DeltaField::Balance, before.balance != after.balance),
+            (DeltaField::Points, before.points != after.points),
+            (DeltaField::GamePhase, before.game_phase != after.game_phase),
+            (DeltaField::CurrentCard, before.current_card != after.current_card),
+            (DeltaField::HedgeCard, before.hedge_card != after.hedge_card),
+            (DeltaField::Session, before.session != after.session),
+            (DeltaField::BudgetAlerts, before.budget_alerts != after.budget_alerts),
+        ];
+        for (field, _) in changes.into_iter().filter(|(_, changed)| *changed) {
+            self.state
+                .delta_versions
+                .insert(&field, version)
+                .expect("Failed to record delta version");
+        }
+    }
+
+    /// Close the current epoch once it has ended and open the one containing now.
+    /// Unwon house contributions roll into the new epoch's bonus pool; epochs in
+    /// which nothing happened are skipped rather than recorded.
+    fn roll_epoch(&mut self) -> bool {
+        let now = self.runtime.system_time().micros();
+        let current = self.state.epoch.get().clone();
+        if now < current.ends_at_micros {
+            return false;
+        }
+
+        let period = EPOCH_PERIOD_SECS * 1_000_000;
+        let skipped = (now - current.ends_at_micros) / period;
+        let started_at_micros = current.ends_at_micros + skipped * period;
+        let next = EpochPool {
+            epoch_id: current.epoch_id + 1 + skipped,
+            started_at_micros,
+            ends_at_micros: started_at_micros + period,
+            prize_pool: current.prize_pool,
+            bonus_pool: Self::or_revert(escrow::add(
+                "epoch.bonus_pool",
+                current.bonus_pool,
+                current.house_contributions,
+            )),
+            ..EpochPool::default()
+        };
+        self.record_closed_epoch(&current);
+        self.state.epoch_history.push(current);
+        self.state.epoch.set(next);
+        true
+    }
+
+    /// Count one execution of `name`, a failure if `failed`, and the real money it
+    /// moved out of the player's balance
+    fn record_operation_metrics(&mut self, name: &str, spent_before: Amount, failed: bool) {
+        let spent = self.player.total_spent.saturating_sub(spent_before);
+        let all_metrics = self.state.operation_metrics.get_mut();
+        let index =
+            match all_metrics.binary_search_by(|metrics| metrics.operation.as_str().cmp(name)) {
+                Ok(index) => index,
+                Err(index) => {
+                    let metrics = OperationMetrics {
+                        operation: name.to_string(),
+                        ..OperationMetrics::default()
+                    };
+                    all_metrics.insert(index, metrics);
+                    index
+                }
+            };
+        let metrics = &mut all_metrics[index];
+        metrics.count += 1;
+        if failed {
+            metrics.failures += 1;
+        }
+        metrics.fees_charged = Self::or_revert(escrow::add(
+            "operation_metrics.fees_charged",
+            metrics.fees_charged,
+            spent,
+        ));
+    }
+
+    /// Move the budget baselines on to a new day or game, dropping the alerts raised
+    /// for the old one, then raise and emit an alert for each threshold in the player's
+    /// preferences that the real money spent since its baseline has reached
+    fn check_budget_alerts(&mut self, spent_before: Amount) {
+        let now = self.runtime.system_time().micros();
+        let day = now / (EPOCH_PERIOD_SECS * 1_000_000);
+        let game_id = self.player.current_card.as_ref().map_or(0, |card| card.id);
+        let mut tracker = self.player.budget_tracker.clone();
+        let mut alerts = self.player.budget_alerts.clone();
+        if tracker.day != day {
+            tracker.day = day;
+            tracker.spent_at_day_start = spent_before;
+            alerts.retain(|alert| alert.kind != BudgetAlertKind::DailySpend);
+        }
+        if tracker.game_id != game_id {
+            tracker.game_id = game_id;
+            tracker.spent_at_game_start = spent_before;
+            alerts.retain(|alert| alert.kind != BudgetAlertKind::GameLoss);
+        }
+
+        let spent = self.player.total_spent;
+        let preferences = &self.player.preferences;
+        let budgets = [
+            (
+                BudgetAlertKind::DailySpend,
+                preferences.daily_spend_alert(),
+                tracker.spent_at_day_start,
+                None,
+            ),
+            (
+                BudgetAlertKind::GameLoss,
+                preferences.game_loss_alert(),
+                tracker.spent_at_game_start,
+                Some(game_id),
+            ),
+        ];
+        for (kind, threshold, baseline, alert_game_id) in budgets {
+            let spent_since = spent.saturating_sub(baseline);
+            let raised = alerts.iter().any(|alert| alert.kind == kind);
+            if threshold == 0 || u128::from(spent_since) < threshold || raised {
+                continue;
+            }
+            let alert = BudgetAlert {
+                kind,
+                threshold: Amount::from_attos(threshold),
+                spent: spent_since,
+                game_id: alert_game_id,
+                raised_at_micros: now,
+            };
+            self.runtime.emit(
+                StreamName(ALERTS_STREAM_NAME.to_vec()),
+                &FlashportEvent::BudgetAlert(alert.clone()),
+            );
+            alerts.push(alert);
+        }
+        self.player.budget_tracker = tracker;
+        self.player.budget_alerts = alerts;
+    }
+
+    /// Enforce the player's anti-martingale preference: after a real-money loss
+    /// (payout below the bet) cap the next bet at K x the previous one. The previous
+    /// game is the unsettled one this bet would forfeit, a loss, or else the account's
+    /// last settlement, which outlives sessions.
+    fn check_loss_guard(&self, bet_amount_atto: u128, currency: Currency) -> Result<(), Refusal> {
+        let max_multiple = self.player.preferences.max_bet_multiple_after_loss;
+        if max_multiple == 0 || currency != Currency::Real {
+            return Ok(());
+        }
+        let unsettled = matches!(
+            self.player.game_phase,
+            GamePhase::Active | GamePhase::BingoPending | GamePhase::Expired
+        );
+        let (previous_bet, lost) =
+            match (&self.player.current_card, &self.player.last_real_settlement) {
+                (Some(card), _) if unsettled => {
+                    if card.currency != Currency::Real {
+                        return Ok(());
+                    }
+                    (card.bet_amount_atto.parse().unwrap_or(0), true)
+                }
+                (_, Some(settled)) => (u128::from(settled.stake), settled.payout < settled.stake),
+                _ => return Ok(()),
+            };
+        // A cap too large to represent cannot be exceeded
+        let Some(cap) = previous_bet.checked_mul(max_multiple as u128) else {
+            return Ok(());
+        };
+
+        if lost && bet_amount_atto > cap {
+            return Err(Refusal::new(
+                ErrorCode::LossGuard,
+                format!(
+                    "Bet blocked by your loss guard: after a loss the bet may be at most {}x the previous bet ({} atto)",
+                    max_multiple, cap
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Ids of the owner's games still in play: unsettled, played in the session that
+    /// is running now, and (once won) still claimable. A game left behind by a lapsed
+    /// session or an expired claim no longer counts, though it stays unsettled until
+    /// the next EndSession or NewGame.
+    fn open_game_ids(&mut self) -> Vec<u64> {
+        let now = self.runtime.system_time().micros();
+        let Some(session) = self
+            .player
+            .active_session
+            .as_ref()
+            .filter(|session| now < session.expires_at_micros)
+        else {
+            return Vec::new();
+        };
+        let phase = self.player.game_phase;
+        self.player
+            .current_card
+            .iter()
+            .filter(|_| phase.is_open())
+            .filter(|card| card.last_active_micros >= session.created_at_micros)
+            .filter(|card| {
+                phase != GamePhase::BingoPending
+                    || card.claim_deadline_micros == 0
+                    || now < card.claim_deadline_micros
+            })
+            .map(|card| card.id)
+            .collect()
+    }
+
+    /// THE CORE ATOMIC OPERATION: Roll 4 dice, calculate sum, mark card, check win
+    /// Validate-then-commit: every check runs before the fee is taken, and the fee is
+    /// refunded if the commit step still rejects the roll
+    async fn roll_and_match(&mut self) -> OperationResponse {
+        let (card, roll_fee) = match self.validate_roll() {
+            Ok(validated) => validated,
+            Err(refusal) => return refusal.into(),
+        };
+
+        // Charge roll fee (0.05 LINERA, or the experiment arm's fee) in the game's currency
+        let currency = card.currency;
+        let fee_amount = Amount::from_attos(roll_fee);
+        if let Err(error) = self.escrow().charge(currency, fee_amount) {
+            return Self::ledger_failure(error);
+        }
+        let game_id = card.id;
+
+        match self.commit_roll(card, roll_fee).await {
+            Ok(response) => {
+                self.trace(DiagnosticKind::FeeCharged, Some(game_id), || {
+                    format!("fee=roll amount_atto={} currency={:?}", roll_fee, currency)
+                });
+                response
+            }
+            Err(refusal) => {
+                Self::or_revert(self.escrow().refund(currency, fee_amount));
+                refusal.into()
+            }
+        }
+    }
+
+    /// Run every check a roll must pass before any funds move
+    /// Returns the current card and the fee the roll will cost
+    fn validate_roll(&mut self) -> Result<(BingoCard, u128), Refusal> {
+        let phase = self.player.game_phase;
+        match phase {
+            GamePhase::Idle | GamePhase::Cancelled => {
+                return Err(Refusal::new(
+                    ErrorCode::NoActiveGame,
+                    "No active game - call NewGame first",
+                ));
+            }
+            GamePhase::Claimed => {
+                return Err(Refusal::new(
+                    ErrorCode::GameOver,
+                    "Game already completed. Start a new game.",
+                ));
+            }
+            GamePhase::Expired => {
+                return Err(Refusal::new(
+                    ErrorCode::ClaimExpired,
+                    "Claim window expired. Start a new game.",
+                ));
+            }
+            GamePhase::Active | GamePhase::BingoPending => {}
+        }
+        let mut card = self
+            .player
+            .current_card
+            .clone()
+            .ok_or_else(|| Refusal::new(ErrorCode::NoActiveGame, "No game data found."))?;
+        self.reconcile_marks(&mut card)?;
+
+        // Check if bingo was achieved but prize not yet claimed
+        // (blackout-continuation games keep rolling until the card is full)
+        let continuing = card.mode == GameMode::BlackoutContinuation;
+        if phase == GamePhase::BingoPending && !continuing {
+            return Err(Refusal::new(
+                ErrorCode::BingoPending,
+                "BINGO! Claim your prize or start a new game.",
+            ));
+        }
+        if continuing && card.marked.iter().all(|m| *m) {
+            return Err(Refusal::new(
+                ErrorCode::CardFull,
+                "Card is full. Claim your prize or start a new game.",
+            ));
+        }
+        let now = self.runtime.system_time().micros();
+        let roll_fee = self.roll_fee_for(&card, now);
+        Ok((card, roll_fee))
+    }
+
+    /// Check the card's marks against the ones its layout, the drawn numbers and its
+    /// bought cells imply, so corrupted state is caught before it pays a prize.
+    /// Missing marks are restored; marks nothing explains suspend rolling. Either
+    /// divergence is reported to the operator outbox.
+    fn reconcile_marks(&mut self, card: &mut BingoCard) -> Result<(), Refusal> {
+        let expected = rules::expected_marks(
+            &card.numbers,
+            &self.player.drawn_numbers,
+            &card.purchased_marks,
+        );
+        if expected == card.marked {
+            return Ok(());
+        }
+        let differing = |stored: bool| {
+            (0..25)
+                .filter(|i| card.marked[*i] == stored && expected[*i] != stored)
+                .count()
+        };
+        let (unexplained, missing) = (differing(true), differing(false));
+        let owner = self.signer();
+        self.push_outbox(
+            OutboxKind::MarkDivergence,
+            owner,
+            card.id,
+            Amount::ZERO,
+            format!(
+                "Game {} had {} unexplained and {} missing marks",
+                card.id, unexplained, missing
+            ),
+        );
+        if unexplained > 0 {
+            return Err(Refusal::new(
+                ErrorCode::RollingSuspended,
+                format!(
+                    "Game {} has marks its drawn numbers don't explain; rolling is \
+                     suspended until an operator reviews it",
+                    card.id
+                ),
+            ));
+        }
+        card.marked = expected;
+        self.player.current_card = Some(card.clone());
+        Ok(())
+    }
+
+    /// Apply a validated, paid roll to the card.
+    /// Anything that can still reject the roll must fail before state is touched,
+    /// so the caller's refund leaves no trace.
+    async fn commit_roll(
+        &mut self,
+        card: BingoCard,
+        roll_fee: u128,
+    ) -> Result<OperationResponse, Refusal> {
+        // Parse and update total roll fees
+        let prev_fees: u128 = card.total_roll_fees_atto.parse().unwrap_or(0);
+        let new_total_fees = prev_fees.checked_add(roll_fee).ok_or_else(|| {
+            Refusal::new(
+                ErrorCode::InconsistentState,
+                "Roll fee total overflow - roll rejected",
+            )
+        })?;
+        let continuing = card.mode == GameMode::BlackoutContinuation;
+        let points_game = card.mode == GameMode::PointsPerMark;
+
+        let roll_fee_amount = Amount::from_attos(roll_fee);
+        if let Some(bucket) = card.experiment_bucket {
+            self.update_bucket_stats(bucket, |stats| {
+                stats.rolls += 1;
+                stats.fees = Self::or_revert(escrow::add(
+                    "experiment_stats.fees",
+                    stats.fees,
+                    roll_fee_amount,
+                ));
+            })
+            .await;
+        }
+        if card.high_roller {
+            self.update_high_roller_stats(|stats| {
+                stats.rolls += 1;
+                stats.fees = Self::or_revert(escrow::add(
+                    "high_roller_stats.fees",
+                    stats.fees,
+                    roll_fee_amount,
+                ));
+            });
+        }
+        if card.mode == GameMode::Insurance {
+            self.update_insurance_stats(|stats| {
+                stats.rolls += 1;
+                stats.fees = Self::or_revert(escrow::add(
+                    "insurance_stats.fees",
+                    stats.fees,
+                    roll_fee_amount,
+                ));
+            });
+        }
+
+        // Get the current roll count for RNG
+        let current_rolls = card.rolls_count as u64;
+
+        // Clone card for mutation
+        let mut updated_card = card;
+        let lines_before = rules::count_completed_lines(&updated_card.marked);
+        let marks_before = updated_card.marked_bitmask();
+
+        // 1-4. Roll, track drawn numbers, mark the card and record history
+        let (roll, match_pos) = self.roll_once(&mut updated_card, current_rolls, roll_fee);
+        let RollRecord {
+            dice,
+            sum,
+            matched,
+            is_lucky,
+            extra_dice,
+            skin_id,
+            ..
+        } = roll;
+
+        // 5. Check for bingo (a single roll can complete several lines at once);
+        // PointsPerMark games score their marks instead
+        let mut bingo_lines = if points_game {
+            Vec::new()
+        } else {
+            rules::pattern_lines(updated_card.pattern, &updated_card.marked)
+        };
+        if bingo_lines.is_empty() {
+            bingo_lines = self.promote_hedge_card(&mut updated_card);
+        }
+        let bingo_type = bingo_lines.first().copied();
+
+        // 6. Update roll count and fees (bonus rolls below are free and don't count)
+        updated_card.rolls_count += 1;
+        let rolls_count = updated_card.rolls_count;
+        updated_card.total_roll_fees_atto = new_total_fees.to_string();
+
+        // 7. In blackout-continuation mode every newly completed line grants a free
+        // bonus roll, chaining until a roll completes no new line or the card is full
+        let mut cascade = Vec::new();
+        let mut lines = rules::count_completed_lines(&updated_card.marked);
+        if continuing && lines > lines_before {
+            cascade.push(CascadeRoll {
+                dice,
+                sum,
+                matched,
+                new_lines: lines - lines_before,
+                bonus: false,
+                is_lucky,
+            });
+            let mut bonus_nonce = current_rolls;
+            while cascade.last().is_some_and(|roll| roll.new_lines > 0)
+                && !updated_card.marked.iter().all(|m| *m)
+            {
+                bonus_nonce = bonus_nonce.wrapping_add(1 << 32);
+                let (roll, _) = self.roll_once(&mut updated_card, bonus_nonce, 0);
+                let lines_after = rules::count_completed_lines(&updated_card.marked);
+                cascade.push(CascadeRoll {
+                    dice: roll.dice,
+                    sum: roll.sum,
+                    matched: roll.matched,
+                    new_lines: lines_after - lines,
+                    bonus: true,
+                    is_lucky: roll.is_lucky,
+                });
+                lines = lines_after;
+            }
+        }
+
+        let new_marks = updated_card.marked_bitmask() & !marks_before;
+        self.record_cell_hits(new_marks);
+
+        let game_over = if points_game {
+            rolls_count >= POINTS_GAME_ROLLS
+        } else if continuing {
+            updated_card.marked.iter().all(|m| *m)
+        } else {
+            bingo_type.is_some()
+        };
+
+        if bingo_type.is_some() {
+            self.open_claim(&mut updated_card);
+        }
+
+        // Near-miss analytics for the card as it now stands
+        let (pattern, marked) = (updated_card.pattern, updated_card.marked);
+        let cells_to_bingo = rules::pattern_cells_to_bingo(pattern, &marked);
+        let dice_count = 4 + updated_card.extra_dice;
+        let completing_sums =
+            rules::completing_sums(pattern, &updated_card.numbers, &marked, dice_count);
+
+        // Save updated card back
+        self.player.current_card = Some(updated_card.clone());
+
+        // Update session operations count
+        if let Some(session) = &mut self.player.active_session {
+            session.operations_count += 1;
+        }
+
+        let points = if points_game {
+            Some(self.score_points_roll(&updated_card, new_marks).await)
+        } else {
+            None
+        };
+
+        if !cascade.is_empty() {
+            return Ok(OperationResponse::CascadeResult {
+                rolls: cascade,
+                lines_completed: lines,
+                bingo_type,
+                game_over,
+                rolls_count,
+                roll_fee_paid: Self::format_amount(roll_fee_amount),
+                total_roll_fees: new_total_fees.to_string(),
+            });
+        }
+
+        Ok(OperationResponse::RollResult {
+            dice,
+            sum,
+            matched,
+            match_row: match_pos.map(|(r, _)| r),
+            match_col: match_pos.map(|(_, c)| c),
+            bingo_type,
+            bingo_lines,
+            game_over,
+            rolls_count,
+            roll_fee_paid: Self::format_amount(roll_fee_amount),
+            total_roll_fees: new_total_fees.to_string(),
+            is_lucky,
+            extra_dice,
+            skin_id,
+            cells_to_bingo,
+            completing_sums,
+            points,
+            context: self.runtime_context(),
+        })
+    }
+
+    /// Score the cells a PointsPerMark roll marked (`new_marks`, a row-major bitmask);
+    /// the game's final roll settles it on the card's total
+    async fn score_points_roll(&mut self, card: &BingoCard, new_marks: u32) -> PointsRoll {
+        let total_points = rules::cells_points(&card.numbers, card.marked_bitmask());
+        let rolls_left = POINTS_GAME_ROLLS.saturating_sub(card.rolls_count);
+        let settlement = match rolls_left {
+            0 => Some(self.settle_points_game(card, total_points).await),
+            _ => None,
+        };
+        PointsRoll {
+            points_scored: rules::cells_points(&card.numbers, new_marks),
+            total_points,
+            rolls_left,
+            settlement,
+        }
+    }
+
+    /// Settle a finished PointsPerMark game: its points pick the payout tier, and a
+    /// game short of every tier leaves the whole bet with the house
+    async fn settle_points_game(&mut self, card: &BingoCard, points: u32) -> PointsSettlement {
+        let bet_amount_atto: u128 = card.bet_amount_atto.parse().unwrap_or(0);
+        let (_, _, multiplier_display) = rules::points_multiplier(points);
+        let payout_atto = Self::or_revert(
+            rules::points_payout(bet_amount_atto, points).ok_or(LedgerError::Overflow("payout")),
+        );
+        self.trace(DiagnosticKind::TierChosen, Some(card.id), || {
+            format!(
+                "points={} tier={} multiplier={} payout_atto={}",
+                points,
+                rules::points_tier(points),
+                multiplier_display,
+                payout_atto
+            )
+        });
+        let payout = Amount::from_attos(payout_atto);
+        let new_balance = Self::or_revert(self.escrow().release(card.currency, payout));
+        if let (Some(winner), Currency::Real) = (self.signer(), card.currency) {
+            if payout > Amount::ZERO {
+                self.record_leaderboard_win(winner, payout).await;
+            }
+        }
+
+        let mut settled = card.clone();
+        settled.payout_atto = payout_atto.to_string();
+        settled.prize_claimed = true;
+        self.player.current_card = Some(settled);
+        self.player.game_phase = GamePhase::Claimed;
+        PointsSettlement {
+            game_id: card.id,
+            total_points: points,
+            multiplier_display,
+            payout_atto: payout_atto.to_string(),
+            new_balance: Self::format_amount(new_balance),
+        }
+    }
+
+    /// The first completed line makes the prize claimable (counted as one win)
+    fn open_claim(&mut self, card: &mut BingoCard) {
+        if self.player.game_phase != GamePhase::Active {
+            return;
+        }
+        let wins = *self.state.total_wins.get() + 1;
+        self.state.total_wins.set(wins);
+        self.player.games_won += 1;
+        self.player.game_phase = GamePhase::BingoPending;
+        let claim_window = self.state.config.get().claim_window_secs as u64;
+        if claim_window > 0 {
+            card.claim_deadline_micros =
+                self.runtime.system_time().micros() + claim_window * 1_000_000;
+        }
+    }
+
+    /// Mark one chosen cell for a fee priced off the card's tier payout and how close
+    /// it is to bingo. Every purchase stays on the card and in its win record.
+    async fn buy_mark(&mut self, row: u8, col: u8) -> OperationResponse {
+        let (mut card, _) = match self.validate_roll() {
+            Ok(validated) => validated,
+            Err(refusal) => return refusal.into(),
+        };
+        if row >= 5 || col >= 5 {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: format!("Cell ({}, {}) is off the 5x5 card", row, col),
+            };
+        }
+        if self.player.hedge_card.is_some() {
+            return OperationResponse::Error {
+                code: ErrorCode::Unsupported,
+                message: "Marks cannot be bought in hedged games".to_string(),
+            };
+        }
+        if card.mode == GameMode::PointsPerMark {
+            return OperationResponse::Error {
+                code: ErrorCode::Unsupported,
+                message: "Marks cannot be bought in PointsPerMark games".to_string(),
+            };
+        }
+        if card.purchased_marks.len() >= MAX_MARK_PURCHASES as usize {
+            return OperationResponse::Error {
+                code: ErrorCode::LimitReached,
+                message: format!("At most {} marks can be bought per game", MAX_MARK_PURCHASES),
+            };
+        }
+        let cell = row * 5 + col;
+        if card.marked[cell as usize] {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: format!("Cell ({}, {}) is already marked", row, col),
+            };
+        }
+
+        let bet_amount_atto: u128 = card.bet_amount_atto.parse().unwrap_or(0);
+        let cells_to_bingo = rules::cells_to_bingo(&card.marked);
+        let curve = self.state.config.get().payout_tiers();
+        let overflow = || LedgerError::Overflow("mark_fee");
+        let fee_atto = Self::or_revert(
+            rules::mode_tier_payout(&curve, card.mode, bet_amount_atto, card.payout_rolls())
+                .and_then(|payout| rules::mark_purchase_fee(payout, cells_to_bingo))
+                .ok_or_else(overflow),
+        );
+        let prev_fees: u128 = card.mark_fees_atto.parse().unwrap_or(0);
+        let total_fees = Self::or_revert(prev_fees.checked_add(fee_atto).ok_or_else(overflow));
+        let fee = Amount::from_attos(fee_atto);
+        if let Err(error) = self.escrow().charge(card.currency, fee) {
+            return Self::ledger_failure(error);
+        }
+        self.trace(DiagnosticKind::FeeCharged, Some(card.id), || {
+            format!(
+                "fee=mark amount_atto={} currency={:?} cells_to_bingo={}",
+                fee_atto, card.currency, cells_to_bingo
+            )
+        });
+
+        card.marked[cell as usize] = true;
+        card.purchased_marks.push(cell);
+        card.mark_fees_atto = total_fees.to_string();
+        card.last_active_micros = self.runtime.system_time().micros();
+        let bingo_lines = rules::pattern_lines(card.pattern, &card.marked);
+        if !bingo_lines.is_empty() {
+            self.open_claim(&mut card);
+        }
+        let purchases_left = MAX_MARK_PURCHASES - card.purchased_marks.len() as u32;
+        self.player.current_card = Some(card);
+
+        if let Some(session) = &mut self.player.active_session {
+            session.operations_count += 1;
+        }
+
+        OperationResponse::MarkPurchased {
+            row,
+            col,
+            fee_paid: Self::format_amount(fee),
+            cells_to_bingo,
+            bingo_lines,
+            purchases_left,
+        }
+    }
+
+    /// Freeze the card's current multiplier tier for the next MULTIPLIER_LOCK_ROLLS rolls
+    /// for a share of its tier payout (once per game)
+    async fn lock_multiplier(&mut self) -> OperationResponse {
+        let (mut card, _) = match self.validate_roll() {
+            Ok(validated) => validated,
+            Err(refusal) => return refusal.into(),
+        };
+        if card.multiplier_lock.is_some() {
+            return OperationResponse::Error {
+                code: ErrorCode::AlreadyDone,
+                message: "The multiplier can only be locked once per game".to_string(),
+            };
+        }
+        if card.mode == GameMode::PointsPerMark {
+            return OperationResponse::Error {
+                code: ErrorCode::Unsupported,
+                message: "PointsPerMark games settle on points, not a multiplier".to_string(),
+            };
+        }
+
+        let bet_amount_atto: u128 = card.bet_amount_atto.parse().unwrap_or(0);
+        let curve = self.state.config.get().payout_tiers();
+        let fee_atto = Self::or_revert(
+            rules::mode_tier_payout(&curve, card.mode, bet_amount_atto, card.rolls_count)
+                .and_then(|payout| rules::stacked_bonus(payout, MULTIPLIER_LOCK_FEE_BPS, 1))
+                .ok_or(LedgerError::Overflow("multiplier_lock_fee")),
+        );
+        let fee = Amount::from_attos(fee_atto);
+        if let Err(error) = self.escrow().charge(card.currency, fee) {
+            return Self::ledger_failure(error);
+        }
+        self.trace(DiagnosticKind::FeeCharged, Some(card.id), || {
+            format!(
+                "fee=multiplier_lock amount_atto={} currency={:?} rolls={}",
+                fee_atto, card.currency, card.rolls_count
+            )
+        });
+
+        let lock = MultiplierLock {
+            locked_at_rolls: card.rolls_count,
+            expires_after_rolls: card.rolls_count + MULTIPLIER_LOCK_ROLLS,
+            fee_atto: fee_atto.to_string(),
+            applied: false,
+        };
+        let (_, _, multiplier_display) =
+            rules::mode_multiplier(&curve, card.mode, card.rolls_count);
+        card.multiplier_lock = Some(lock.clone());
+        card.last_active_micros = self.runtime.system_time().micros();
+        self.player.current_card = Some(card);
+
+        if let Some(session) = &mut self.player.active_session {
+            session.operations_count += 1;
+        }
+
+        OperationResponse::MultiplierLocked {
+            lock,
+            multiplier_display,
+        }
+    }
+
+    /// Run up to `count` rolls in one operation, stopping on error or at game over.
+    /// Every batch is recorded as a `BatchOutcome` under the next handle.
+    async fn auto_roll(
+        &mut self,
+        count: u32,
+        stop_on_bingo: bool,
+        aggregate: bool,
+    ) -> OperationResponse {
+        let count = count.min(MAX_AUTO_ROLLS);
+        let mut results = Vec::new();
+        let mut stop = None;
+        let fees_before = self.current_total_roll_fees();
+        self.set_pending_auto_roll(None);
+
+        for rolled in 0..count {
+            // Past the per-block cap, park the rest of the batch for the next operation
+            if !self.take_block_roll() {
+                let remaining = count - rolled;
+                let pending = PendingAutoRoll {
+                    game_id: self.current_game_id(),
+                    remaining,
+                    stop_on_bingo,
+                    aggregate,
+                    paused_at_block: self.runtime.block_height().0,
+                };
+                self.set_pending_auto_roll(Some(pending));
+                let message = format!(
+                    "Block roll limit reached; {} rolls pending until the next block",
+                    remaining
+                );
+                stop = Some((ErrorCode::LimitReached, message));
+                break;
+            }
+            let before = self.current_marked_bitmask();
+            let response = self.roll_and_match().await;
+            let done = match &response {
+                OperationResponse::Error { code, message } => {
+                    stop = Some((*code, message.clone()));
+                    break;
+                }
+                OperationResponse::RollResult {
+                    bingo_type,
+                    game_over,
+                    ..
+                }
+                | OperationResponse::CascadeResult {
+                    bingo_type,
+                    game_over,
+                    ..
+                } => *game_over || (stop_on_bingo && bingo_type.is_some()),
+                _ => false,
+            };
+            let delta = self.current_marked_bitmask() & !before;
+            results.push((response, delta));
+            if done {
+                break;
+            }
+        }
+
+        let marks_delta: Vec<u32> = results.iter().map(|(_, delta)| *delta).collect();
+        let marks_gained = marks_delta.iter().map(|delta| delta.count_ones()).sum();
+        let rolls_made = results.len() as u32;
+        let fees_paid = (self.current_total_roll_fees() - fees_before).to_string();
+        let (bingo_type, game_over, rolls_count, total_roll_fees) = match results.last() {
+            Some((
+                OperationResponse::RollResult {
+                    bingo_type,
+                    game_over,
+                    rolls_count,
+                    total_roll_fees,
+                    ..
+                }
+                | OperationResponse::CascadeResult {
+                    bingo_type,
+                    game_over,
+                    rolls_count,
+                    total_roll_fees,
+                    ..
+                },
+                _,
+            )) => (*bingo_type, *game_over, *rolls_count, total_roll_fees.clone()),
+            _ => (None, false, 0, "0".to_string()),
+        };
+        let handle = self.record_batch_outcome(BatchOutcome {
+            rolls_made,
+            marks_gained,
+            fees_paid_atto: fees_paid.clone(),
+            bingo_type,
+            game_over,
+            stop_reason: stop.clone().map(|(_, message)| message),
+            stop_code: stop.as_ref().map(|(code, _)| *code),
+            ..BatchOutcome::default()
+        });
+
+        // Nothing rolled at all: surface the error as-is
+        let (stop_code, stop_reason) = stop.unzip();
+        if let (true, Some(code), Some(message)) = (results.is_empty(), stop_code, &stop_reason) {
+            return OperationResponse::Error {
+                code,
+                message: message.clone(),
+            };
+        }
+
+        if !aggregate {
+            return OperationResponse::AutoRollResult {
+                results: results.into_iter().map(|(response, _)| response).collect(),
+            };
+        }
+
+        let mut dice = Vec::new();
+        let mut lucky_count = 0;
+        for (response, _) in &results {
+            match response {
+                OperationResponse::RollResult {
+                    dice: roll, is_lucky, ..
+                } => {
+                    dice.push(*roll);
+                    lucky_count += *is_lucky as u32;
+                }
+                OperationResponse::CascadeResult { rolls, .. } => {
+                    dice.extend(rolls.iter().map(|roll| roll.dice));
+                    lucky_count += rolls.iter().filter(|roll| roll.is_lucky).count() as u32;
+                }
+                _ => {}
+            }
+        }
+
+        OperationResponse::AggregatedRolls {
+            handle,
+            dice,
+            marks_delta,
+            rolls_made,
+            marks_gained,
+            fees_paid,
+            lucky_count,
+            bingo_type,
+            game_over,
+            rolls_count,
+            total_roll_fees,
+            stop_reason,
+            stop_code,
+        }
+    }
+
+    /// Resume the batch paused by the per-block cap, dropping it if its game has ended
+    async fn continue_auto_roll(&mut self) -> OperationResponse {
+        let Some(pending) = self.player.pending_auto_roll.clone() else {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: "No paused AutoRoll batch to continue".to_string(),
+            };
+        };
+        if pending.game_id != self.current_game_id() {
+            self.set_pending_auto_roll(None);
+            return OperationResponse::Error {
+                code: ErrorCode::GameOver,
+                message: format!("Paused AutoRoll batch for game {} was dropped", pending.game_id),
+            };
+        }
+        self.auto_roll(pending.remaining, pending.stop_on_bingo, pending.aggregate).await
+    }
+
+    /// Count one AutoRoll roll against the current block, or return false once the
+    /// configured per-block cap is used up
+    fn take_block_roll(&mut self) -> bool {
+        let height = self.runtime.block_height().0;
+        if *self.state.block_rolls_height.get() != height {
+            self.state.block_rolls_height.set(height);
+            self.state.block_rolls.set(0);
+        }
+        let cap = self.state.config.get().max_rolls_per_block;
+        let rolls = *self.state.block_rolls.get();
+        if cap > 0 && rolls >= cap {
+            return false;
+        }
+        self.state.block_rolls.set(rolls + 1);
+        true
+    }
+
+    /// Id of the current card's game (0 with no card)
+    fn current_game_id(&self) -> u64 {
+        self.player
+            .current_card
+            .as_ref()
+            .map(|card| card.id)
+            .unwrap_or(0)
+    }
+
+    /// Store a batch outcome under the next handle (keeps last 20), returning the handle
+    fn record_batch_outcome(&mut self, mut outcome: BatchOutcome) -> u64 {
+        let handle = *self.state.batch_counter.get() + 1;
+        self.state.batch_counter.set(handle);
+        outcome.handle = handle;
+        outcome.game_id = self.current_game_id();
+        let outcomes = &mut self.player.batch_outcomes;
+        outcomes.push(outcome);
+        if outcomes.len() > 20 {
+            outcomes.remove(0);
+        }
+        handle
+    }
+
+    /// Roll fees charged so far on the current card (0 with no card)
+    fn current_total_roll_fees(&self) -> u128 {
+        self.player
+            .current_card
+            .as_ref()
+            .and_then(|card| card.total_roll_fees_atto.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Add newly marked cells to the global and account heatmaps
+    fn record_cell_hits(&mut self, new_marks: u32) {
+        if new_marks == 0 {
+            return;
+        }
+        let add_hits = |hits: &mut [u64; 25]| {
+            for (i, cell) in hits.iter_mut().enumerate() {
+                *cell += (new_marks >> i & 1) as u64;
+            }
+        };
+        add_hits(self.state.cell_hits.get_mut());
+        add_hits(&mut self.player.cell_hits);
+    }
+
+    /// Bitmap of marked cells on the current card (0 with no card)
+    fn current_marked_bitmask(&self) -> u32 {
+        self.player
+            .current_card
+            .as_ref()
+            .map(|card| card.marked_bitmask())
+            .unwrap_or(0)
+    }
+
+    /// If the hedge card has completed a line, swap its layout and bet share into
+    /// `card` (keeping the shared roll count and fees) and return its bingo lines
+    fn promote_hedge_card(&mut self, card: &mut BingoCard) -> Vec<BingoType> {
+        let Some(hedge_card) = &mut self.player.hedge_card else {
+            return Vec::new();
+        };
+        let bingo_lines = rules::pattern_lines(hedge_card.pattern, &hedge_card.marked);
+        if !bingo_lines.is_empty() {
+            std::mem::swap(&mut card.numbers, &mut hedge_card.numbers);
+            std::mem::swap(&mut card.marked, &mut hedge_card.marked);
+            std::mem::swap(&mut card.art_seed, &mut hedge_card.art_seed);
+            std::mem::swap(&mut card.fresh_sum_marks, &mut hedge_card.fresh_sum_marks);
+            std::mem::swap(&mut card.difficulty_score, &mut hedge_card.difficulty_score);
+            std::mem::swap(&mut card.difficulty_bonus_bps, &mut hedge_card.difficulty_bonus_bps);
+            std::mem::swap(&mut card.bet_amount_atto, &mut hedge_card.bet_amount_atto);
+        }
+        bingo_lines
+    }
+
+    /// Roll the dice once against `card`: track the drawn sum, mark the card and
+    /// record the roll in history (keeps last 50)
+    /// Returns the recorded roll and the position of its last mark
+    fn roll_once(
+        &mut self,
+        card: &mut BingoCard,
+        nonce: u64,
+        fee_paid: u128,
+    ) -> (RollRecord, Option<(u8, u8)>) {
+        // Generate 4 dice with verifiable randomness, plus a season mode's extra dice
+        let dice = self.generate_dice_roll(nonce);
+        let extra_dice = match card.extra_dice {
+            0 => Vec::new(),
+            count => self.generate_dice_roll(nonce ^ (1 << 56))[..count as usize].to_vec(),
+        };
+        let sum: u8 = dice.iter().chain(&extra_dice).sum();
+
+        // Track drawn numbers
+        let mut drawn = self.player.drawn_numbers.clone();
+        let fresh = !drawn.contains(&sum);
+        if fresh {
+            drawn.push(sum);
+        }
+        self.player.drawn_numbers = drawn;
+
+        // Find and mark the number on the card
+        let now = self.runtime.system_time().micros();
+        card.last_active_micros = now;
+        let (matched, match_pos, match_count) = rules::mark_number_on_card(card, sum);
+        if matched && fresh {
+            card.fresh_sum_marks += 1;
+        }
+        if let Some(hedge_card) = &mut self.player.hedge_card {
+            if rules::mark_number_on_card(hedge_card, sum).0 && fresh {
+                hedge_card.fresh_sum_marks += 1;
+            }
+        }
+
+        let roll_id = *self.state.roll_counter.get() + 1;
+        self.state.roll_counter.set(roll_id);
+        let record = RollRecord {
+            roll_id,
+            dice,
+            sum,
+            matched,
+            timestamp_micros: now,
+            fee_paid_atto: fee_paid.to_string(),
+            is_lucky: match_count > 1,
+            extra_dice,
+            skin_id: card.skin_id,
+        };
+        let head = *self.state.roll_results_hash.get();
+        self.state
+            .roll_results_hash
+            .set(chain_roll_results(head, [&record]));
+        let history = &mut self.player.roll_history;
+        history.push(record.clone());
+        if history.len() > 50 {
+            history.remove(0);
+        }
+
+        (record, match_pos)
+    }
+
+    async fn claim_prize(&mut self) -> OperationResponse {
+        match self.player.game_phase {
+            GamePhase::BingoPending => {}
+            GamePhase::Claimed => {
+                return OperationResponse::Error {
+                    code: ErrorCode::AlreadyDone,
+                    message: "Prize already claimed.".to_string(),
+                };
+            }
+            GamePhase::Expired => {
+                return OperationResponse::Error {
+                    code: ErrorCode::ClaimExpired,
+                    message: "Claim window expired. Start a new game.".to_string(),
+                };
+            }
+            _ => {
+                return OperationResponse::Error {
+                    code: ErrorCode::NoPrize,
+                    message: "No unclaimed prize. Win a bingo first!".to_string(),
+                };
+            }
+        }
+
+        let card = match self.player.current_card.clone() {
+            Some(c) => c,
+            None => {
+                return OperationResponse::Error {
+                    code: ErrorCode::NoActiveGame,
+                    message: "No game data found.".to_string(),
+                };
+            }
+        };
+
+        let deadline = card.claim_deadline_micros;
+        if deadline != 0 && self.runtime.system_time().micros() > deadline {
+            self.player.game_phase = GamePhase::Expired;
+            return OperationResponse::Error {
+                code: ErrorCode::ClaimExpired,
+                message: format!("Claim deadline passed at {} micros", deadline),
+            };
+        }
+
+        // Parse bet amount from card
+        let bet_amount_atto: u128 = card.bet_amount_atto.parse().unwrap_or(0);
+        if bet_amount_atto == 0 {
+            return OperationResponse::Error {
+                code: ErrorCode::InconsistentState,
+                message: "Invalid bet amount stored in game.".to_string(),
+            };
+        }
+
+        // Calculate payout: bet_amount * multiplier for the rolls count (or the
+        // locked one, while a multiplier lock holds)
+        let payout_rolls = card.payout_rolls();
+        let curve = self.state.config.get().payout_tiers();
+        let (_, _, multiplier_display) = rules::mode_multiplier(&curve, card.mode, payout_rolls);
+        let overflow = || LedgerError::Overflow("payout");
+        let mut payout_atto = Self::or_revert(
+            rules::mode_tier_payout(&curve, card.mode, bet_amount_atto, payout_rolls)
+                .ok_or_else(overflow),
+        );
+
+        // Experiment arms scale the tier payout
+        if let Some(arm) = &card.experiment_arm {
+            payout_atto = Self::or_revert(
+                payout_atto
+                    .checked_mul(arm.payout_scale_bps as u128)
+                    .ok_or_else(overflow),
+            ) / 10_000;
+        }
+
+        // Hard cards earn a bonus fixed when they were dealt
+        let difficulty_bonus = Self::or_revert(
+            rules::stacked_bonus(payout_atto, card.difficulty_bonus_bps, 1).ok_or_else(overflow),
+        );
+        payout_atto =
+            Self::or_revert(payout_atto.checked_add(difficulty_bonus).ok_or_else(overflow));
+
+        // Every line beyond the first earns a stacked bonus. Classic games stop at the
+        // first bingo, so their extra lines were all completed by the winning roll.
+        let line_bonus_bps = match card.mode {
+            GameMode::Classic | GameMode::Insurance | GameMode::PointsPerMark => {
+                MULTI_LINE_BONUS_BPS
+            }
+            GameMode::BlackoutContinuation => BLACKOUT_LINE_BONUS_BPS,
+        };
+        let extra_lines = rules::count_completed_lines(&card.marked).saturating_sub(1) as u128;
+        let line_bonus = Self::or_revert(
+            rules::stacked_bonus(bet_amount_atto, line_bonus_bps, extra_lines).ok_or_else(overflow),
+        );
+        payout_atto =
+            Self::or_revert(payout_atto.checked_add(line_bonus).ok_or_else(overflow));
+
+        // No-repeat rule: every mark made by a first-drawn sum adds to the payout
+        let fresh_sum_bonus_bps = self.state.config.get().fresh_sum_bonus_bps;
+        let fresh_marks = card.fresh_sum_marks as u128;
+        let fresh_sum_bonus = Self::or_revert(
+            rules::stacked_bonus(bet_amount_atto, fresh_sum_bonus_bps, fresh_marks)
+                .ok_or_else(overflow),
+        );
+        payout_atto =
+            Self::or_revert(payout_atto.checked_add(fresh_sum_bonus).ok_or_else(overflow));
+        
+        let payout_amount = Amount::from_attos(payout_atto);
+
+        // Settle payout into the balance the game was played with; winnings above the
+        // stake come out of the house pool, and the claim waits while it is short
+        let stake = self.player.current_prize_pool;
+        let mut new_balance = match self.escrow().release(card.currency, payout_amount) {
+            Ok(balance) => balance,
+            Err(error) => return Self::ledger_failure(error),
+        };
+        // A sponsored game's winnings leave the sponsor's balance for the sponsored player
+        let mut sponsored_payout_receipt_id = None;
+        if let Some(player) = card.sponsored_player {
+            new_balance = Self::or_revert(self.escrow().withdraw(payout_amount));
+
+            // In production: Transfer the payout to the sponsored player
+            // self.runtime.transfer(owner, player, payout_amount);
+
+            sponsored_payout_receipt_id = Some(self.issue_receipt(
+                ReceiptKind::Withdrawal,
+                payout_amount,
+                new_balance,
+                Some(player),
+            ));
+        }
+        // A payout at or above the vesting threshold is credited one installment now
+        // and the rest as the installments unlock
+        let mut vesting = None;
+        let mut credited_payout = payout_amount;
+        if card.currency == Currency::Real && card.sponsored_player.is_none() {
+            if let Some((schedule, remaining)) = self.vest_payout(card.id, payout_amount).await {
+                credited_payout = schedule.installment_amount(0);
+                new_balance = remaining;
+                vesting = Some(schedule);
+            }
+        }
+        // A winner with a payout route has the payout forwarded through the bridge;
+        // if the bridge refuses, it stays in the balance. Neither it nor the
+        // auto-withdrawal moves funds out while the winner's withdrawals are on hold.
+        let withdrawals_held = self.check_withdrawal_hold().await.is_err();
+        let mut routed_payout = None;
+        if card.currency == Currency::Real && card.sponsored_player.is_none() && !withdrawals_held {
+            if let Some((routed, remaining)) = self
+                .route_payout(card.id, credited_payout, new_balance)
+                .await
+            {
+                routed_payout = Some(routed);
+                new_balance = remaining;
+            }
+        }
+        let mut auto_withdrawal_receipt_id = None;
+        if card.currency == Currency::Real && !withdrawals_held {
+            if let Some((receipt_id, remaining)) = self.auto_withdraw_excess(new_balance) {
+                auto_withdrawal_receipt_id = Some(receipt_id);
+                new_balance = remaining;
+            }
+        }
+
+        // Place the win in its tier and against every earlier win
+        let tier = multiplier_tier(card.rolls_count);
+        let tier_win_chance_bps = TIER_WIN_CHANCE_BPS[tier];
+        self.trace(DiagnosticKind::TierChosen, Some(card.id), || {
+            format!(
+                "rolls={} payout_rolls={} multiplier={} tier={} payout_atto={}",
+                card.rolls_count,
+                payout_rolls,
+                multiplier_display,
+                MULTIPLIER_TIER_NAMES[tier],
+                payout_atto
+            )
+        });
+        let multiplier_lock = card.multiplier_lock.clone().map(|lock| MultiplierLock {
+            applied: multiplier_tier(payout_rolls) != tier,
+            ..lock
+        });
+        let rolls_percentile_bps = self.record_rolls_to_bingo(card.rolls_count);
+        let mut skins_unlocked = Vec::new();
+        if let Some(winner) = self.signer() {
+            let lightning = card.rolls_count <= MULTIPLIER_TIER_MAX_ROLLS[0];
+            let blackout = card.marked.iter().all(|&marked| marked);
+            let achievements = [(lightning, LIGHTNING_SKIN_ID), (blackout, BLACKOUT_SKIN_ID)];
+            for (earned, skin_id) in achievements {
+                if earned && self.grant_skin(winner, skin_id).await {
+                    skins_unlocked.push(skin_id);
+                }
+            }
+        }
+        let owner = self.signer();
+        let claimed_at_micros = self.runtime.system_time().micros();
+        let context = self.runtime_context();
+        self.archive_win(WinRecord {
+            game_id: card.id,
+            owner,
+            sponsor: card.sponsor,
+            currency: card.currency,
+            mode: card.mode,
+            bet_amount_atto: bet_amount_atto.to_string(),
+            payout_atto: payout_atto.to_string(),
+            rolls_count: card.rolls_count,
+            bingo_type: rules::pattern_lines(card.pattern, &card.marked).first().copied(),
+            purchased_marks: card.purchased_marks.clone(),
+            mark_fees_atto: card.mark_fees_atto.clone(),
+            multiplier_lock: multiplier_lock.clone(),
+            tier: MULTIPLIER_TIER_NAMES[tier].to_string(),
+            tier_win_chance_bps,
+            rolls_percentile_bps,
+            claimed_at_micros,
+            context,
+        })
+        .await;
+        self.archive_settlement(&card, SettlementOutcome::PrizeClaimed, stake, payout_amount);
+
+        // Mark prize as claimed
+        let mut updated_card = card.clone();
+        updated_card.payout_atto = payout_atto.to_string();
+        updated_card.multiplier_lock = multiplier_lock;
+        updated_card.prize_claimed = true;
+        self.player.current_card = Some(updated_card);
+        self.player.game_phase = GamePhase::Claimed;
+
+        if let Some(bucket) = card.experiment_bucket {
+            self.update_bucket_stats(bucket, |stats| {
+                stats.paid_out = Self::or_revert(escrow::add(
+                    "experiment_stats.paid_out",
+                    stats.paid_out,
+                    payout_amount,
+                ));
+            })
+            .await;
+        }
+        if card.high_roller {
+            self.update_high_roller_stats(|stats| {
+                stats.paid_out = Self::or_revert(escrow::add(
+                    "high_roller_stats.paid_out",
+                    stats.paid_out,
+                    payout_amount,
+                ));
+            });
+        }
+        if card.mode == GameMode::Insurance {
+            self.update_insurance_stats(|stats| {
+                stats.paid_out = Self::or_revert(escrow::add(
+                    "insurance_stats.paid_out",
+                    stats.paid_out,
+                    payout_amount,
+                ));
+            });
+        }
+        if let Some(winner) = self.signer() {
+            if card.currency == Currency::Real {
+                self.record_leaderboard_win(winner, payout_amount).await;
+            }
+        }
+        self.reach_onboarding_step(OnboardingStep::Won);
+
+        let notify_threshold = *self.state.reward_notify_threshold.get();
+        if card.currency == Currency::Real && payout_amount >= notify_threshold {
+            let owner = self.signer();
+            self.push_outbox(
+                OutboxKind::BigWin,
+                owner,
+                card.id,
+                payout_amount,
+                format!(
+                    "Game {} paid {} in {} rolls",
+                    card.id, multiplier_display, card.rolls_count
+                ),
+            );
+            self.notify_reward_partners(WinNotification {
+                game_id: card.id,
+                owner,
+                bet_amount_atto,
+                payout_atto,
+                rolls_count: card.rolls_count,
+            });
+        }
+
+        OperationResponse::PrizeClaimed {
+            bet_amount: bet_amount_atto.to_string(),
+            rolls_count: card.rolls_count,
+            multiplier_display,
+            payout_amount: Self::format_amount(payout_amount),
+            currency: card.currency,
+            new_balance: Self::format_amount(new_balance),
+            auto_withdrawal_receipt_id,
+            sponsored_payout_receipt_id,
+            tier: MULTIPLIER_TIER_NAMES[tier].to_string(),
+            tier_win_chance_bps,
+            rolls_percentile_bps,
+            skins_unlocked,
+            routed_payout,
+            vesting,
+        }
+    }
+
+    /// Put all but the first installment of a payout at or above the vesting threshold
+    /// into a schedule for the signer, returning it and the balance left afterwards
+    async fn vest_payout(
+        &mut self,
+        game_id: u64,
+        payout: Amount,
+    ) -> Option<(VestingSchedule, Amount)> {
+        let config = self.state.config.get();
+        let threshold = config.vesting_threshold;
+        let (installments, interval_secs) = config.vesting_terms();
+        if threshold == Amount::ZERO || payout < threshold || installments < 2 {
+            return None;
+        }
+        let owner = self.signer()?;
+        let schedule = VestingSchedule {
+            game_id,
+            total: payout,
+            installments,
+            installments_released: 1,
+            vested_at_micros: self.runtime.system_time().micros(),
+            interval_secs,
+        };
+        let remaining = Self::or_revert(self.escrow().vest(schedule.remaining()));
+        let mut schedules = self
+            .state
+            .vesting_schedules
+            .get(&owner)
+            .await
+            .expect("Failed to read vesting schedules")
+            .unwrap_or_default();
+        schedules.push(schedule.clone());
+        self.state
+            .vesting_schedules
+            .insert(&owner, schedules)
+            .expect("Failed to store vesting schedules");
+        Some((schedule, remaining))
+    }
+
+    /// Credit every installment of the signer's vesting payouts that has unlocked,
+    /// dropping the schedules that are paid in full
+    async fn claim_vested_tranche(&mut self) -> OperationResponse {
+        let Some(owner) = self.signer() else {
+            return OperationResponse::Error {
+                code: ErrorCode::SignerRequired,
+                message: "Claiming vested installments requires an authenticated signer"
+                    .to_string(),
+            };
+        };
+        let mut schedules = self
+            .state
+            .vesting_schedules
+            .get(&owner)
+            .await
+            .expect("Failed to read vesting schedules")
+            .unwrap_or_default();
+        if schedules.is_empty() {
+            return OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                message: "No vesting prizes to claim".to_string(),
+            };
+        }
+
+        let now = self.runtime.system_time().micros();
+        let mut released = Amount::ZERO;
+        for schedule in &mut schedules {
+            let unlocked = schedule.unlocked_installments(now);
+            while schedule.installments_released < unlocked {
+                let installment = schedule.installment_amount(schedule.installments_released);
+                released = Self::or_revert(escrow::add("vesting_schedules", released, installment));
+                schedule.installments_released += 1;
+            }
+        }
+        if released == Amount::ZERO {
+            let next_unlock = schedules
+                .iter()
+                .map(|schedule| {
+                    let wait_secs = u64::from(schedule.installments_released)
+                        .saturating_mul(schedule.interval_secs);
+                    schedule
+                        .vested_at_micros
+                        .saturating_add(wait_secs.saturating_mul(1_000_000))
+                })
+                .min()
+                .unwrap_or_default();
+            return OperationResponse::Error {
+                code: ErrorCode::TooEarly,
+                message: format!(
+                    "No vested installment has unlocked yet; the next unlocks at {} micros",
+                    next_unlock
+                ),
+            };
+        }
+
+        schedules.retain(|schedule| schedule.installments_released < schedule.installments);
+        if schedules.is_empty() {
+            self.state
+                .vesting_schedules
+                .remove(&owner)
+                .expect("Failed to remove vesting schedules");
+        } else {
+            self.state
+                .vesting_schedules
+                .insert(&owner, schedules.clone())
+                .expect("Failed to store vesting schedules");
+        }
+        let new_balance = Self::or_revert(self.escrow().release_vested(released));
+        OperationResponse::VestedTrancheClaimed {
+            released_atto: Self::format_amount(released),
+            new_balance: Self::format_amount(new_balance),
+            schedules,
+        }
+    }
+
+    /// Withdraw whatever the real balance holds above the player's auto-withdraw
+    /// threshold, returning the receipt id and remaining balance
+    fn auto_withdraw_excess(&mut self, balance: Amount) -> Option<(u64, Amount)> {
+        let preferences = &self.player.preferences;
+        let threshold = preferences.auto_withdraw_threshold();
+        if threshold == 0 || preferences.payout_address.is_none() {
+            return None;
+        }
+        if u128::from(balance) <= threshold {
+            return None;
+        }
+        let excess = Amount::from_attos(u128::from(balance) - threshold);
+        let remaining = Self::or_revert(self.escrow().withdraw(excess));
+
+        // In production: Transfer the excess to the bound payout address
+        // self.runtime.transfer(owner, payout_address, excess);
+
+        let receipt_id = self.issue_receipt(ReceiptKind::Withdrawal, excess, remaining, None);
+        Some((receipt_id, remaining))
+    }
+
+    /// Offer `payout` to the payout bridge in the winner's chosen asset. Returns the
+    /// outcome and the balance left afterwards, or None if the winner has no route.
+    /// A bridge that panics fails the claim; refusals must come back as `Err`.
+    async fn route_payout(
+        &mut self,
+        game_id: u64,
+        payout: Amount,
+        balance: Amount,
+    ) -> Option<(RoutedPayout, Amount)> {
+        let owner = self.signer()?;
+        let bridge = (*self.state.payout_bridge.get())?;
+        let route = self
+            .state
+            .payout_routes
+            .get(&owner)
+            .await
+            .expect("Failed to read payout route")?;
+        let request = BridgeRequest {
+            game_id,
+            owner,
+            amount_atto: u128::from(payout),
+            target_asset: route.target_asset.clone(),
+        };
+        let bridge = bridge.with_abi::<PayoutBridgeAbi>();
+        let response = self.runtime.call_application(true, bridge, &request);
+        match response {
+            Ok(bridge_reference) => {
+                let remaining = Self::or_revert(self.escrow().withdraw(payout));
+
+                // In production: Transfer the payout to the bridge application
+                // self.runtime.transfer(owner, bridge, payout);
+
+                let receipt_id =
+                    self.issue_receipt(ReceiptKind::Withdrawal, payout, remaining, None);
+                let routed = RoutedPayout {
+                    target_asset: route.target_asset,
+                    receipt_id: Some(receipt_id),
+                    bridge_reference: Some(bridge_reference),
+                    error: None,
+                };
+                Some((routed, remaining))
+            }
+            Err(error) => {
+                let routed = RoutedPayout {
+                    target_asset: route.target_asset,
+                    receipt_id: None,
+                    bridge_reference: None,
+                    error: Some(error),
+                };
+                Some((routed, balance))
+            }
+        }
+    }
+
+    /// Queue a qualifying win for every configured partner application. Each
+    /// notice waits in the outbox for DeliverPartnerNotification, so the claim never
+    /// depends on a partner's call succeeding.
+    fn notify_reward_partners(&mut self, notification: WinNotification) {
+        let partners = self.state.reward_partners.get().clone();
+        for partner in partners {
+            let entry_id = self.push_outbox(
+                OutboxKind::PartnerNotification,
+                notification.owner,
+                notification.game_id,
+                Amount::from_attos(notification.payout_atto),
+                format!(
+                    "Notify partner {} of game {}",
+                    partner, notification.game_id
+                ),
+            );
+            let notice = PartnerNotice {
+                partner,
+                notification: notification.clone(),
+            };
+            self.state
+                .partner_notices
+                .insert(&entry_id, notice)
+                .expect("Failed to queue partner notice");
+        }
+    }
+
+    /// Rank `rolls` against earlier claimed wins, then add it to the distribution.
+    /// Returns the share of earlier wins that took longer (ties count half), in bps.
+    fn record_rolls_to_bingo(&mut self, rolls: u32) -> Option<u32> {
+        let counts = self.state.rolls_to_bingo_counts.get_mut();
+        let rolls = rolls as usize;
+        let earlier: u64 = counts.iter().sum();
+        let slower: u64 = counts.iter().skip(rolls + 1).sum();
+        let ties = counts.get(rolls).copied().unwrap_or(0);
+        if counts.len() <= rolls {
+            counts.resize(rolls + 1, 0);
+        }
+        counts[rolls] += 1;
+        (earlier > 0).then(|| ((2 * slower + ties) * 5_000 / earlier) as u32)
+    }
+
+    /// Append `win` to the win archive and file its index under every search index
+    async fn archive_win(&mut self, win: WinRecord) {
+        let index = self.state.win_archive.count() as u32;
+        if win.net_win() {
+            self.state.archive_net_wins.push(index);
+        }
+        if let Some(bingo_type) = win.bingo_type {
+            Self::add_to_index(&mut self.state.archive_by_bingo_type, &bingo_type, index).await;
+        }
+        Self::add_to_index(&mut self.state.archive_by_mode, &win.mode, index).await;
+        let bet_amount_atto: u128 = win.bet_amount_atto.parse().unwrap_or(0);
+        let bucket = archive_bet_bucket(bet_amount_atto);
+        Self::add_to_index(&mut self.state.archive_by_bet_bucket, &bucket, index).await;
+        Self::add_to_index(&mut self.state.archive_by_day, &win.archive_day(), index).await;
+        self.state.win_archive.push(win);
+    }
+
+    async fn add_to_index<K: Serialize + Send + Sync>(
+        map: &mut MapView<K, Vec<u32>>,
+        key: &K,
+        index: u32,
+    ) {
+        map.get_mut_or_default(key)
+            .await
+            .expect("Failed to update archive index")
+            .push(index);
+    }
+
+    // =========================================================================
+    // HISTORY PRUNING
+    // =========================================================================
+
+    async fn prune_history(&mut self, keep_last: u32) -> OperationResponse {
+        if keep_last == 0 {
+            return OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                message: "PruneHistory must keep at least 1 entry of each history".to_string(),
+            };
+        }
+        let report = self.compact_history(keep_last, false).await;
+        OperationResponse::HistoryPruned { report }
+    }
+
+    /// Apply the configured `history_retention` after an epoch closes
+    async fn prune_on_rollover(&mut self) {
+        let retention = self.state.config.get().history_retention;
+        if retention > 0 {
+            self.compact_history(retention, true).await;
+        }
+    }
+
+    /// Delete everything but the newest `keep_last` roll records of the loaded account,
+    /// receipts, and settled tournaments and duels, folding them into `history_summary`.
+    /// Open tournaments and duels are kept whatever their age.
+    async fn compact_history(&mut self, keep_last: u32, automatic: bool) -> PruneReport {
+        let keep = u64::from(keep_last);
+        let mut summary = self.state.history_summary.get().clone();
+        let mut report = PruneReport {
+            keep_last,
+            automatic,
+            ..PruneReport::default()
+        };
+
+        let excess = (self.player.roll_history.len() as u64).saturating_sub(keep) as usize;
+        let records: Vec<RollRecord> = self.player.roll_history.drain(..excess).collect();
+        for record in records {
+            let fee = Amount::from_attos(record.fee_paid_atto.parse().unwrap_or(0));
+            summary.rolls += 1;
+            summary.matched_rolls += u64::from(record.matched);
+            summary.roll_fees =
+                Self::or_revert(escrow::add("history.roll_fees", summary.roll_fees, fee));
+            report.rolls_pruned += 1;
+            report.bytes_reclaimed += Self::stored_size(&record);
+        }
+
+        let cutoff = self.state.receipt_counter.get().saturating_sub(keep);
+        let receipt_ids = self
+            .state
+            .receipts
+            .indices()
+            .await
+            .expect("Failed to list receipts");
+        for receipt_id in receipt_ids.into_iter().filter(|id| *id <= cutoff) {
+            let receipt = self
+                .state
+                .receipts
+                .get(&receipt_id)
+                .await
+                .expect("Failed to read receipt")
+                .expect("Listed receipt exists");
+            let amount = Amount::from_attos(receipt.amount_atto.parse().unwrap_or(0));
+            match receipt.kind {
+                ReceiptKind::Deposit => {
+                    summary.deposits += 1;
+                    summary.deposited = Self::or_revert(escrow::add(
+                        "history.deposited",
+                        summary.deposited,
+                        amount,
+                    ));
+                }
+                ReceiptKind::Withdrawal => {
+                    summary.withdrawals += 1;
+                    summary.withdrawn = Self::or_revert(escrow::add(
+                        "history.withdrawn",
+                        summary.withdrawn,
+                        amount,
+                    ));
+                }
+            }
+            report.receipts_pruned += 1;
+            report.bytes_reclaimed += 8 + Self::stored_size(&receipt);
+            self.state
+                .receipts
+                .remove(&receipt_id)
+                .expect("Failed to prune receipt");
+        }
+
+        let cutoff = self.state.tournament_counter.get().saturating_sub(keep);
+        let tournament_ids = self
+            .state
+            .tournaments
+            .indices()
+            .await
+            .expect("Failed to list tournaments");
+        for tournament_id in tournament_ids.into_iter().filter(|id| *id <= cutoff) {
+            let tournament = self
+                .state
+                .tournaments
+                .get(&tournament_id)
+                .await
+                .expect("Failed to read tournament")
+                .expect("Listed tournament exists");
+            if !tournament.settled {
+                continue;
+            }
+            summary.tournaments += 1;
+            summary.tournament_pots = Self::or_revert(escrow::add(
+                "history.tournament_pots",
+                summary.tournament_pots,
+                tournament.pot,
+            ));
+            report.tournaments_pruned += 1;
+            report.bytes_reclaimed += 8 + Self::stored_size(&tournament);
+            self.state
+                .tournaments
+                .remove(&tournament_id)
+                .expect("Failed to prune tournament");
+        }
+
+        let cutoff = self.state.duel_counter.get().saturating_sub(keep);
+        let duel_ids = self
+            .state
+            .duels
+            .indices()
+            .await
+            .expect("Failed to list duels");
+        for duel_id in duel_ids.into_iter().filter(|id| *id <= cutoff) {
+            let duel = self
+                .state
+                .duels
+                .get(&duel_id)
+                .await
+                .expect("Failed to read duel")
+                .expect("Listed duel exists");
+            if duel.status != DuelStatus::Settled {
+                continue;
+            }
+            summary.duels += 1;
+            summary.duel_pots = Self::or_revert(escrow::add(
+                "history.duel_pots",
+                summary.duel_pots,
+                duel.pot,
+            ));
+            report.duels_pruned += 1;
+            report.bytes_reclaimed += 8 + Self::stored_size(&duel);
+            self.state
+                .duels
+                .remove(&duel_id)
+                .expect("Failed to prune duel");
+        }
+
+        report.entries_pruned = report.rolls_pruned
+            + report.receipts_pruned
+            + report.tournaments_pruned
+            + report.duels_pruned;
+        summary.bytes_reclaimed += report.bytes_reclaimed;
+        summary.last_pruned_micros = self.runtime.system_time().micros();
+        self.state.history_summary.set(summary);
+        self.state.last_prune.set(Some(report.clone()));
+        report
+    }
+
+    /// Serialized size of a stored value, in bytes
+    fn stored_size<T: Serialize>(value: &T) -> u64 {
+        bcs::serialized_size(value).map_or(0, |size| size as u64)
+    }
+
+    // =========================================================================
+    // HELPERS
+    // =========================================================================
+
+    /// Generate a new bingo card with numbers 4-24, under any configured card
+    /// constraints. With `hedge_side` set, the card is that side of a hedged game and
+    /// holds only its share of the numbers (see `rules::deal_hedge`).
+    fn generate_card(
+        &mut self,
+        game_id: u64,
+        hedge_side: Option<usize>,
+        max_difficulty: u32,
+    ) -> BingoCard {
+        let constraints = self.state.config.get().card_constraints.clone();
+        let deal = |seed| match hedge_side {
+            Some(side) => rules::deal_hedge(seed, game_id, side, &constraints),
+            None if constraints.is_active() => rules::deal_constrained(seed, &constraints),
+            None => rules::deal_numbers(seed),
+        };
+
+        // Create deterministic seed from block + game_id (the second hedge card's
+        // from its own nonce)
+        let nonce = match hedge_side {
+            Some(1) => game_id.rotate_left(32),
+            _ => game_id,
+        };
+        let mut seed = self.create_seed(nonce);
+        let mut dealt_seed = seed;
+        let mut numbers = deal(seed);
+        let mut difficulty_score = probability::difficulty_score(&numbers);
+
+        // Redeal degenerate layouts, keeping the easiest one if none is good enough
+        for _ in 1..MAX_CARD_DEALS {
+            if max_difficulty == 0 || difficulty_score <= max_difficulty {
+                break;
+            }
+            seed = seed.wrapping_add(0x9e3779b97f4a7c15);
+            let redealt = deal(seed);
+            let redealt_score = probability::difficulty_score(&redealt);
+            if redealt_score < difficulty_score {
+                (numbers, difficulty_score, dealt_seed) = (redealt, redealt_score, seed);
+            }
+        }
+
+        // Center cell (row 2, col 2) is FREE
+        let mut marked = [false; 25];
+        marked[12] = true;
+
+        let mut card = BingoCard {
+            id: game_id,
+            numbers,
+            marked,
+            rolls_count: 0,
+            bet_amount_atto: "0".to_string(), // Will be set by new_game
+            total_roll_fees_atto: "0".to_string(),
+            prize_claimed: false,
+            payout_atto: "0".to_string(),
+            experiment_bucket: None,
+            experiment_arm: None,
+            art_seed: BingoCard::layout_seed(&numbers),
+            claim_deadline_micros: 0,
+            fresh_sum_marks: 0,
+            high_roller: false,
+            last_active_micros: self.runtime.system_time().micros(),
+            difficulty_score,
+            difficulty_bonus_bps: rules::difficulty_bonus_bps(
+                difficulty_score,
+                self.state.config.get().difficulty_bonus_bps,
+            ),
+            sponsor: None,
+            sponsored_player: None,
+            purchased_marks: Vec::new(),
+            mark_fees_atto: "0".to_string(),
+            multiplier_lock: None,
+            season_mode_id: None,
+            template_id: None,
+            pattern: WinPattern::AnyLine,
+            template_roll_cost: None,
+            extra_dice: 0,
+            skin_id: None,
+            deal_seed: 0,
+            deal_commitment: None,
+            currency: Currency::Real, // Will be set by new_game
+            mode: GameMode::Classic,  // Will be set by new_game
+        };
+        card.commit_deal_seed(dealt_seed);
+        card
+    }
+
+    // ============================================
+    // DEMO GAME
+    // ============================================
+
+    /// Play a Classic game on a throwaway card until its first line or DEMO_GAME_ROLLS
+    /// rolls. The dice come from one seed so the demo can be replayed; only the demo
+    /// counter and the block's entropy record change.
+    fn demo_game(&mut self) -> OperationResponse {
+        let demo_id = *self.state.demo_games.get() + 1;
+        self.state.demo_games.set(demo_id);
+        // Counted down from the top so demo seeds never share a nonce with a game id
+        let seed = self.create_seed(u64::MAX - demo_id);
+        let numbers = rules::deal_numbers(seed);
+        let mut marked = [false; 25];
+        marked[12] = true;
+
+        let mut rolls = Vec::new();
+        let mut bingo_type = None;
+        while bingo_type.is_none() && rolls.len() < DEMO_GAME_ROLLS as usize {
+            let step = rolls.len() as u64 + 1;
+            let dice = rules::dice_from_state(seed ^ step.wrapping_mul(0x9e3779b97f4a7c15));
+            let sum = dice.iter().sum::<u8>();
+            let mut matched = false;
+            for (cell, number) in marked.iter_mut().zip(numbers) {
+                if number == sum && !*cell {
+                    *cell = true;
+                    matched = true;
+                }
+            }
+            rolls.push(DemoRoll { dice, sum, matched });
+            bingo_type = rules::bingo_lines(&marked).first().copied();
+        }
+
+        let curve = self.state.config.get().payout_tiers();
+        let multiplier_display =
+            bingo_type.map(|_| rules::multiplier(&curve, rolls.len() as u32).2);
+        OperationResponse::DemoResult {
+            seed,
+            numbers,
+            marked,
+            rolls,
+            bingo_type,
+            multiplier_display,
+        }
+    }
+
+    /// Generate 4 dice (1-6 each) with verifiable randomness
+    fn generate_dice_roll(&mut self, nonce: u64) -> [u8; 4] {
+        // Use multiple entropy sources for better randomness
+        let block_height = self.runtime.block_height().0;
+        let timestamp = self.runtime.system_time().micros();
+        
+        // Increment a running counter for additional entropy within same block
+        let counter = *self.state.game_counter.get();
+        let roll_count = *self.state.total_games.get();
+        
+        // Combine multiple entropy sources
+        let rng_state: u64 = block_height
+            .wrapping_mul(0xc6a4a7935bd1e995) // Large prime multiplier
+            .wrapping_add(timestamp)
+            .wrapping_mul(0x5851f42d4c957f2d)
+            .wrapping_add(nonce.wrapping_mul(0x2545f4914f6cdd1d))
+            .wrapping_add(counter.wrapping_mul(0x1b873593))
+            .wrapping_add(roll_count.wrapping_mul(0xcc9e2d51));
+
+        self.record_entropy(rng_state);
+        let dice = rules::dice_from_state(rng_state);
+        self.trace(DiagnosticKind::EntropyInputs, None, || {
+            format!(
+                "block_height={} timestamp={} nonce={} counter={} roll_count={} \
+                 rng_state={:#x} dice={:?}",
+                block_height, timestamp, nonce, counter, roll_count, rng_state, dice
+            )
+        });
+        dice
+    }
+
+    /// Create a seed from block data for verifiable randomness
+    fn create_seed(&mut self, nonce: u64) -> u64 {
+        let block_height = self.runtime.block_height().0;
+        let timestamp = self.runtime.system_time().micros();
+        let counter = *self.state.game_counter.get();
+
+        // Use xorshift-style mixing
+        let mut seed = block_height
+            .wrapping_mul(0xc6a4a7935bd1e995)
+            .wrapping_add(timestamp)
+            .wrapping_add(nonce.wrapping_mul(0x5851f42d4c957f2d))
+            .wrapping_add(counter.wrapping_mul(0x9e3779b97f4a7c15));
+        
+        seed ^= seed >> 33;
+        seed = seed.wrapping_mul(0xff51afd7ed558ccd);
+        seed ^= seed >> 33;
+        self.record_entropy(seed);
+        seed
+    }
+
+    /// Fold a draw's mixed state into the current block's entropy record, archiving
+    /// the previous block's record when a new block draws
+    fn record_entropy(&mut self, mixed_state: u64) {
+        let block_height = self.runtime.block_height().0;
+        let timestamp_micros = self.runtime.system_time().micros();
+        let current = self.state.entropy_block.get_mut();
+        if let Some(record) = current.as_mut().filter(|r| r.block_height == block_height) {
+            record.draws += 1;
+            record.digest = CryptoHash::new(&EntropyLink {
+                previous: Some(record.digest),
+                mixed_state,
+            });
+            return;
+        }
+        let record = EntropyRecord {
+            block_height,
+            timestamp_micros,
+            draws: 1,
+            digest: CryptoHash::new(&EntropyLink {
+                previous: None,
+                mixed_state,
+            }),
+        };
+        if let Some(finished) = current.replace(record) {
+            self.state.entropy_history.push_back(finished);
+            while self.state.entropy_history.count() > ENTROPY_HISTORY_LEN {
+                self.state.entropy_history.delete_front();
+            }
+        }
+    }
+
+    /// Record a decision in the diagnostics ring buffer while `diagnostics` is on;
+    /// `detail` is only built then
+    fn trace(
+        &mut self,
+        kind: DiagnosticKind,
+        game_id: Option<u64>,
+        detail: impl FnOnce() -> String,
+    ) {
+        if !self.runtime.application_parameters().diagnostics {
+            return;
+        }
+        let event_id = *self.state.diagnostic_counter.get() + 1;
+        self.state.diagnostic_counter.set(event_id);
+        let event = DiagnosticEvent {
+            event_id,
+            kind,
+            game_id,
+            block_height: self.runtime.block_height().0,
+            at_micros: self.runtime.system_time().micros(),
+            detail: detail(),
+        };
+        self.state.diagnostics.push_back(event);
+        while self.state.diagnostics.count() > DIAGNOSTICS_CAPACITY {
+            self.state.diagnostics.delete_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use futures::FutureExt as _;
+    use linera_sdk::{
+        bcs,
+        linera_base_types::{
+            AccountOwner, AccountSecretKey, Amount, ApplicationId, BlockHeight, ChainId,
+            ChainOwnership, CryptoHash, Timestamp,
+        },
+        util::BlockingWait,
+        views::View,
+        Contract, ContractRuntime,
+    };
+
+    use blitz_bingo::{
+        archive_bet_bucket, chain_roll_results, probability, rules, AdminActionKind, BingoCard,
+        BingoType, BridgeRequest, BudgetAlertKind, CardConstraints, Currency, DealCommitment,
+        DeltaField, DiagnosticKind, DisputeOutcome, DuelStatus, ErrorCode, Experiment,
+        ExperimentArm, ExperimentBucket, FlashportAbi, FlashportParameters, GameConfig, GameMode,
+        GamePhase, GameTemplate, HighRollerTable, HoldReason, InstantiationArgument,
+        LeaderboardEntry, Message, OnboardingStep, Operation, OperationAuth, OperationResponse,
+        OutboxKind, PayoutTier, PlayerAccount, PlayerPreferences, ProposalKind, ProposalStatus,
+        RelayEnvelope, RelayPayload, SeasonMode, SessionCadence, SessionEndPolicy,
+        SettlementOutcome, SyndicateShare, TranscriptLink, WinNotification, WinPattern,
+        WithholdReason, ABI_VERSION, ARCHIVE_DAY_MICROS, BLACKOUT_SKIN_ID, CADENCE_MIN_SAMPLES,
+        DEMO_GAME_ROLLS, DIAGNOSTICS_CAPACITY, DUEL_RAKE_BPS, EPOCH_PERIOD_SECS,
+        INSURANCE_ROLL_COST, LEADERBOARD_SIZE, LIGHTNING_SKIN_ID, MAX_AIRDROP_RECIPIENTS, MAX_BET,
+        MAX_CLAIM_EXTENSION_PAGE, MAX_RELAY_TTL_SECS, MIN_BET, MIN_GOVERNANCE_TIMELOCK_SECS,
+        MULTI_LINE_BONUS_BPS, POINTS_GAME_ROLLS, RAFFLE_POOL_BPS, ROLLING_RTP_EPOCHS, ROLL_COST,
+        SESSION_CAN_NEW_GAME, SESSION_CAN_ROLL, STATS_PUBLISH_INTERVAL_SECS, STATS_SCHEMA_VERSION,
+        TIER_WIN_CHANCE_BPS, TOURNAMENT_PAYOUT_BPS,
+    };
+
+    use super::{FlashportContract, FlashportState};
+
+    #[test]
+    fn test_start_session() {
+        let mut app = create_app();
+
+        let response = app
+            .execute_operation(Operation::StartSession {
+                expires_in_secs: 3600,
+                permissions: None,
+            })
+            .now_or_never()
+            .expect("Should not await");
+
+        match response {
+            blitz_bingo::OperationResponse::SessionStarted { session_id, .. } => {
+                assert_eq!(session_id, 1);
+            }
+            _ => panic!("Expected SessionStarted response"),
+        }
+    }
+
+    #[test]
+    fn test_end_session_emits_summary() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+
+        let operations = [
+            Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            },
+            Operation::RollAndMatch,
+        ];
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        for operation in operations.clone() {
+            app.execute_operation(operation).blocking_wait();
+        }
+        let response = app.execute_operation(Operation::EndSession).blocking_wait();
+
+        // The transcript chains every operation after StartSession, EndSession included
+        let transcript_hash = operations
+            .into_iter()
+            .chain([Operation::EndSession])
+            .fold(None, |previous, operation| {
+                Some(CryptoHash::new(&TranscriptLink {
+                    previous,
+                    operation,
+                }))
+            });
+        let OperationResponse::SessionEnded {
+            summary: Some(summary),
+            event_index: Some(0),
+            settlement: Some(settlement),
+        } = response
+        else {
+            panic!("Expected SessionEnded with a summary");
+        };
+        assert_eq!(summary.session_id, 1);
+        assert_eq!(summary.operations_count, 2);
+        assert_eq!(summary.transcript_hash, transcript_hash);
+        assert_eq!(summary.won, Amount::ZERO);
+        assert_eq!(u128::from(summary.spent), MIN_BET + ROLL_COST);
+        assert_eq!(summary.net_pnl_atto, format!("-{}", MIN_BET + ROLL_COST));
+        assert_eq!(settlement.outcome, SettlementOutcome::Forfeited);
+        assert_eq!(settlement.forfeited_atto, MIN_BET.to_string());
+        assert!(app.player.active_session.is_none());
+    }
+
+    #[test]
+    fn test_end_session_settles_open_game() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 50 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        let start_game = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::StartSession {
+                expires_in_secs: 3600,
+                permissions: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+        };
+        let end_session = |app: &mut FlashportContract| {
+            match app.execute_operation(Operation::EndSession).blocking_wait() {
+                OperationResponse::SessionEnded {
+                    settlement: Some(settlement),
+                    ..
+                } => settlement,
+                other => panic!("Expected SessionEnded with a settlement, got {:?}", other),
+            }
+        };
+
+        // A game no dice were rolled for is refunded in full
+        start_game(&mut app);
+        let settlement = end_session(&mut app);
+        assert_eq!(settlement.outcome, SettlementOutcome::Refunded);
+        assert_eq!(settlement.credited_atto, MIN_BET.to_string());
+        assert_eq!(u128::from(app.player.player_balance), 50 * MIN_BET);
+        assert_eq!(app.player.current_prize_pool, Amount::ZERO);
+        assert_eq!(app.player.game_phase, GamePhase::Cancelled);
+
+        // A pending prize is claimed instead of discarded
+        start_game(&mut app);
+        for _ in 0..500 {
+            if app.player.game_phase != GamePhase::Active {
+                break;
+            }
+            app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        }
+        assert_eq!(app.player.game_phase, GamePhase::BingoPending);
+        let balance = u128::from(app.player.player_balance);
+        let settlement = end_session(&mut app);
+        assert_eq!(settlement.outcome, SettlementOutcome::PrizeClaimed);
+        let payout: u128 = settlement.credited_atto.parse().unwrap();
+        assert!(payout > 0);
+        assert_eq!(u128::from(app.player.player_balance), balance + payout);
+        assert_eq!(*app.state.total_wins.get(), 1);
+
+        // Under the Forfeit policy even an unplayed bet stays with the house
+        govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    session_end_policy: SessionEndPolicy::Forfeit,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        start_game(&mut app);
+        let settlement = end_session(&mut app);
+        assert_eq!(settlement.outcome, SettlementOutcome::Forfeited);
+        assert_eq!(settlement.forfeited_atto, MIN_BET.to_string());
+    }
+
+    #[test]
+    fn test_deposit() {
+        let mut app = create_app();
+
+        let response = app
+            .execute_operation(Operation::Deposit {
+                amount_atto: 10_000_000_000_000_000_000,
+                credit_to: None,
+            })
+            .now_or_never()
+            .expect("Should not await");
+
+        match response {
+            blitz_bingo::OperationResponse::DepositReceived {
+                receipt_id,
+                new_balance,
+                ..
+            } => {
+                assert_eq!(receipt_id, 1);
+                // Should have 10 LINERA = 10 * 10^18 atto
+                assert_eq!(new_balance, "10000000000000000000");
+            }
+            _ => panic!("Expected DepositReceived response"),
+        }
+    }
+
+    #[test]
+    fn test_scheduled_withdrawal_releases_after_lock() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        let release_at_micros = 1_000_000_000 + 3600 * 1_000_000;
+        let response = app
+            .execute_operation(Operation::ScheduleWithdrawal {
+                amount: Amount::from_attos(4 * MIN_BET),
+                release_at_micros,
+            })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::WithdrawalScheduled { .. }));
+        assert_eq!(u128::from(app.player.player_balance), 6 * MIN_BET);
+
+        let release = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::ReleaseWithdrawal { withdrawal_id: 1 })
+                .blocking_wait()
+        };
+        assert!(matches!(release(&mut app), OperationResponse::Error { .. }));
+
+        // Once the lock has passed anyone can release it
+        app.runtime.set_system_time(Timestamp::from(release_at_micros));
+        app.runtime
+            .set_authenticated_signer(AccountOwner::Address20([2; 20]));
+        let OperationResponse::WithdrawalReleased { receipt_id, .. } = release(&mut app) else {
+            panic!("Expected WithdrawalReleased response");
+        };
+        assert!(matches!(release(&mut app), OperationResponse::Error { .. }));
+
+        let receipt = app.state.receipts.get(&receipt_id).blocking_wait().unwrap().unwrap();
+        assert_eq!(receipt.amount_atto, (4 * MIN_BET).to_string());
+        assert_eq!(app.player.pending_withdrawals, Amount::ZERO);
+        assert_eq!(u128::from(app.player.player_balance), 6 * MIN_BET);
+    }
+
+    #[test]
+    fn test_custodial_deposit_credits_named_player() {
+        let mut app = create_app();
+        let player = AccountOwner::Address20([1; 20]);
+        let other = AccountOwner::Address20([3; 20]);
+        let custodian = AccountOwner::Address20([9; 20]);
+        app.runtime.set_authenticated_signer(custodian);
+
+        // Naming yourself is an ordinary deposit
+        let response = app
+            .execute_operation(Operation::Deposit {
+                amount_atto: MIN_BET,
+                credit_to: Some(custodian),
+            })
+            .blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::DepositReceived { .. }
+        ));
+        assert_eq!(app.player.player_balance, Amount::from_attos(MIN_BET));
+
+        for (owner, amount_atto) in [(player, 2 * MIN_BET), (other, 3 * MIN_BET)] {
+            let response = app
+                .execute_operation(Operation::Deposit {
+                    amount_atto,
+                    credit_to: Some(owner),
+                })
+                .blocking_wait();
+            let OperationResponse::DepositReceived {
+                receipt_id,
+                credited_to,
+                new_balance,
+                ..
+            } = response
+            else {
+                panic!("Expected DepositReceived response");
+            };
+            assert_eq!(credited_to, Some(owner));
+            assert_eq!(new_balance, amount_atto.to_string());
+            let receipt = app
+                .state
+                .receipts
+                .get(&receipt_id)
+                .blocking_wait()
+                .unwrap()
+                .unwrap();
+            assert_eq!(receipt.funded_by, Some(custodian));
+        }
+
+        // Each named player's own account is credited, and the custodian's is untouched
+        assert_eq!(app.player.player_balance, Amount::from_attos(MIN_BET));
+        for (owner, amount_atto) in [(player, 2 * MIN_BET), (other, 3 * MIN_BET)] {
+            let account = app
+                .state
+                .players
+                .get(&owner)
+                .blocking_wait()
+                .unwrap()
+                .unwrap();
+            assert_eq!(account.player_balance, Amount::from_attos(amount_atto));
+            assert_eq!(account.total_deposited, Amount::from_attos(amount_atto));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Ledger overflow in player_balance")]
+    fn test_deposit_overflow_reverts() {
+        let mut app = create_app();
+        app.player.player_balance = Amount::MAX;
+
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 1,
+            credit_to: None,
+        })
+        .blocking_wait();
+    }
+
+    #[test]
+    #[should_panic(expected = "Ledger overflow in payout")]
+    fn test_payout_overflow_reverts() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        let mut card = app.player.current_card.clone().unwrap();
+        card.bet_amount_atto = u128::MAX.to_string();
+        app.player.current_card = Some(card);
+        app.player.game_phase = GamePhase::BingoPending;
+        app.execute_operation(Operation::ClaimPrize).blocking_wait();
+    }
+
+    #[test]
+    fn test_new_game_requires_balance() {
+        let mut app = create_app();
+
+        // Start session first
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .now_or_never()
+        .unwrap();
+
+        // Try to start game without balance - should fail
+        let response = app
+            .execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .now_or_never()
+            .expect("Should not await");
+
+        match response {
+            blitz_bingo::OperationResponse::Error { message, .. } => {
+                assert!(message.contains("Insufficient balance"));
+            }
+            _ => panic!("Expected Error response for insufficient balance"),
+        }
+    }
+
+    #[test]
+    fn test_game_with_deposit() {
+        let mut app = create_app();
+
+        // Deposit first
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10_000_000_000_000_000_000,
+            credit_to: None,
+        })
+        .now_or_never()
+            .unwrap();
+
+        // Start session
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .now_or_never()
+        .unwrap();
+
+        // Now start game should succeed
+        let response = app
+            .execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .now_or_never()
+            .expect("Should not await");
+
+        match response {
+            blitz_bingo::OperationResponse::GameStarted { game_id, card, .. } => {
+                assert_eq!(game_id, 1);
+                // Center should be FREE (marked)
+                assert!(card.marked[12]);
+                assert_eq!(card.art_seed, BingoCard::layout_seed(&card.numbers));
+            }
+            _ => panic!("Expected GameStarted response"),
+        }
+    }
+
+    #[test]
+    fn test_play_points_game_without_deposit() {
+        let mut app = create_app();
+
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .now_or_never()
+        .unwrap();
+
+        // Points games don't need a LINERA deposit
+        let response = app
+            .execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Play,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .now_or_never()
+            .expect("Should not await");
+
+        match response {
+            blitz_bingo::OperationResponse::GameStarted { currency, .. } => {
+                assert_eq!(currency, Currency::Play);
+            }
+            _ => panic!("Expected GameStarted response"),
+        }
+
+        // Real balance untouched, points charged the bet
+        assert_eq!(u128::from(app.player.player_balance), 0);
+        assert_eq!(
+            u128::from(app.player.points_balance),
+            blitz_bingo::PLAY_POINTS_GRANT - MIN_BET
+        );
+    }
+
+    #[test]
+    fn test_count_completed_lines() {
+        let mut card = BingoCard::default();
+        assert_eq!(rules::count_completed_lines(&card.marked), 0);
+
+        // Row 2 plus column 2 share the center cell
+        for i in 0..5 {
+            card.mark(2, i);
+            card.mark(i, 2);
+        }
+        assert_eq!(rules::count_completed_lines(&card.marked), 2);
+
+        card.marked = [true; 25];
+        assert_eq!(rules::count_completed_lines(&card.marked), 12);
+    }
+
+    #[test]
+    fn test_multi_line_bingo_pays_stacked_bonus() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // One winning roll completing row 2 and column 2 together
+        let mut card = app.player.current_card.clone().unwrap();
+        for i in 0..5 {
+            card.mark(2, i);
+            card.mark(i, 2);
+        }
+        assert_eq!(
+            rules::bingo_lines(&card.marked),
+            vec![BingoType::Row2, BingoType::Col2]
+        );
+        app.player.current_card = Some(card.clone());
+        app.player.game_phase = GamePhase::BingoPending;
+
+        let (num, denom, _) = rules::multiplier(&PayoutTier::default_curve(), card.rolls_count);
+        let expected = MIN_BET * num as u128 / denom as u128
+            + MIN_BET * MULTI_LINE_BONUS_BPS as u128 / 10_000;
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        match response {
+            OperationResponse::PrizeClaimed { payout_amount, .. } => {
+                assert_eq!(payout_amount, expected.to_string());
+            }
+            other => panic!("Expected PrizeClaimed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_vesting_payout() {
+        let mut app = create_app();
+        govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    vesting_threshold: Amount::from_attos(MIN_BET),
+                    vesting_installments: 3,
+                    vesting_interval_secs: 60,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        let mut card = app.player.current_card.clone().unwrap();
+        for i in 0..5 {
+            card.mark(0, i);
+        }
+        app.player.current_card = Some(card);
+        app.player.game_phase = GamePhase::BingoPending;
+
+        // Only the first installment is credited with the claim
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        let OperationResponse::PrizeClaimed {
+            payout_amount,
+            vesting: Some(schedule),
+            ..
+        } = response
+        else {
+            panic!("Expected a vesting payout, got {:?}", response);
+        };
+        let payout: u128 = payout_amount.parse().unwrap();
+        assert_eq!(u128::from(schedule.total), payout);
+        assert_eq!(
+            (schedule.installments, schedule.installments_released),
+            (3, 1)
+        );
+        let first = u128::from(schedule.installment_amount(0));
+        assert_eq!(u128::from(app.player.player_balance), 9 * MIN_BET + first);
+        assert_eq!(app.player.vesting_balance, schedule.remaining());
+
+        // Nothing more unlocks before the interval passes
+        let response = app
+            .execute_operation(Operation::ClaimVestedTranche)
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+
+        // Both remaining installments unlock by the end, the last with the remainder
+        app.runtime
+            .set_system_time(Timestamp::from(1_000_000_000 + 180_000_000));
+        let response = app
+            .execute_operation(Operation::ClaimVestedTranche)
+            .blocking_wait();
+        let OperationResponse::VestedTrancheClaimed {
+            released_atto,
+            schedules,
+            ..
+        } = response
+        else {
+            panic!("Expected VestedTrancheClaimed, got {:?}", response);
+        };
+        assert_eq!(released_atto, (payout - first).to_string());
+        assert!(schedules.is_empty());
+        assert_eq!(app.player.vesting_balance, Amount::ZERO);
+        assert_eq!(u128::from(app.player.player_balance), 9 * MIN_BET + payout);
+        let owner = app.runtime.authenticated_signer().unwrap();
+        let stored = app.state.vesting_schedules.get(&owner).blocking_wait();
+        assert_eq!(stored.unwrap(), None);
+    }
+
+    #[test]
+    fn test_open_game_limit() {
+        let mut app = create_app();
+
+        let response = govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    max_active_games: 1,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        assert!(matches!(response, OperationResponse::ConfigUpdated { .. }));
+
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10_000_000_000_000_000_000,
+            credit_to: None,
+        })
+        .now_or_never()
+        .unwrap();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .now_or_never()
+        .unwrap();
+
+        let new_game = || Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        };
+        app.execute_operation(new_game()).now_or_never().unwrap();
+
+        let response = app
+            .execute_operation(new_game())
+            .now_or_never()
+            .expect("Should not await");
+        match response {
+            OperationResponse::TooManyActiveGames {
+                limit,
+                open_game_ids,
+            } => {
+                assert_eq!(limit, 1);
+                assert_eq!(open_game_ids, vec![1]);
+            }
+            _ => panic!("Expected TooManyActiveGames response"),
+        }
+
+        // A game abandoned when its session lapsed stops counting
+        app.runtime
+            .set_system_time(Timestamp::from(1_000_000_000 + 3_600_000_000));
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .now_or_never()
+        .unwrap();
+        let response = app
+            .execute_operation(new_game())
+            .now_or_never()
+            .expect("Should not await");
+        assert!(matches!(response, OperationResponse::GameStarted { .. }));
+    }
+
+    #[test]
+    fn test_auto_withdraw_above_threshold() {
+        let mut app = create_app();
+
+        // A threshold without a payout address is rejected
+        let response = app
+            .execute_operation(Operation::SetPreferences {
+                preferences: PlayerPreferences {
+                    auto_withdraw_above_atto: (5 * MIN_BET).to_string(),
+                    ..PlayerPreferences::default()
+                },
+            })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+
+        app.execute_operation(Operation::SetPreferences {
+            preferences: PlayerPreferences {
+                auto_withdraw_above_atto: (5 * MIN_BET).to_string(),
+                payout_address: Some(AccountOwner::Address20([9; 20])),
+                ..PlayerPreferences::default()
+            },
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // A 10x win takes the balance to 19, so 14 is swept out
+        app.player.game_phase = GamePhase::BingoPending;
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        match response {
+            OperationResponse::PrizeClaimed {
+                new_balance,
+                auto_withdrawal_receipt_id,
+                ..
+            } => {
+                assert_eq!(new_balance, (5 * MIN_BET).to_string());
+                assert_eq!(auto_withdrawal_receipt_id, Some(2));
+            }
+            other => panic!("Expected PrizeClaimed, got {:?}", other),
+        }
+        assert_eq!(app.player.player_balance, Amount::from_tokens(5));
+        let receipt = app.state.receipts.get(&2).blocking_wait().unwrap().unwrap();
+        assert_eq!(receipt.amount_atto, (14 * MIN_BET).to_string());
+    }
+
+    #[test]
+    fn test_loss_guard_blocks_escalated_bet() {
+        let mut app = create_app();
+
+        app.execute_operation(Operation::SetPreferences {
+            preferences: PlayerPreferences {
+                max_bet_multiple_after_loss: 2,
+                ..PlayerPreferences::default()
+            },
+        })
+        .now_or_never()
+        .unwrap();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .now_or_never()
+        .unwrap();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .now_or_never()
+        .unwrap();
+
+        let new_game = |bet_amount_atto| Operation::NewGame {
+            bet_amount_atto,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        };
+        app.execute_operation(new_game(MIN_BET))
+            .now_or_never()
+            .unwrap();
+
+        // Abandoning the first game counts as a loss, so 3x is over the 2x cap
+        let response = app
+            .execute_operation(new_game(3 * MIN_BET))
+            .now_or_never()
+            .expect("Should not await");
+        match response {
+            OperationResponse::Error { message, .. } => assert!(message.contains("loss guard")),
+            _ => panic!("Expected Error response from loss guard"),
+        }
+
+        let response = app
+            .execute_operation(new_game(2 * MIN_BET))
+            .now_or_never()
+            .expect("Should not await");
+        assert!(matches!(response, OperationResponse::GameStarted { .. }));
+        // Ending the session settles the game, and the guard carries into the next one
+        app.execute_operation(Operation::EndSession)
+            .now_or_never()
+            .unwrap();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .now_or_never()
+        .unwrap();
+        let response = app
+            .execute_operation(new_game(5 * MIN_BET))
+            .now_or_never()
+            .expect("Should not await");
+        match response {
+            OperationResponse::Error { code, .. } => assert_eq!(code, ErrorCode::LossGuard),
+            _ => panic!("Expected Error response from loss guard"),
+        }
+        assert!(app.player.last_real_settlement.is_some());
+    }
+
+    #[test]
+    fn test_aggregated_auto_roll() {
+        let mut app = create_app();
+
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .now_or_never()
+        .unwrap();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .now_or_never()
+        .unwrap();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .now_or_never()
+        .unwrap();
+
+        let response = app
+            .execute_operation(Operation::AutoRoll {
+                count: 5,
+                stop_on_bingo: true,
+                aggregate: true,
+            })
+            .now_or_never()
+            .expect("Should not await");
+
+        match response {
+            OperationResponse::AggregatedRolls {
+                handle,
+                dice,
+                marks_delta,
+                rolls_made,
+                marks_gained,
+                rolls_count,
+                ..
+            } => {
+                assert_eq!(handle, 1);
+                assert!((1..=5).contains(&rolls_made));
+                assert_eq!(marks_delta.len() as u32, rolls_made);
+                assert_eq!(dice.len() as u32, rolls_made);
+                assert_eq!(rolls_count, rolls_made);
+                let card = app.player.current_card.clone().unwrap();
+                // Only the FREE center was marked before rolling
+                assert_eq!(card.marked_bitmask().count_ones(), 1 + marks_gained);
+            }
+            _ => panic!("Expected AggregatedRolls response"),
+        }
+
+        // The batch is retrievable under the first handle
+        let outcome = app
+            .player
+            .batch_outcomes
+            .last()
+            .cloned()
+            .expect("Batch outcome should be recorded");
+        assert_eq!((outcome.handle, outcome.game_id), (1, 1));
+        assert_eq!(
+            outcome.rolls_made,
+            app.player.current_card.as_ref().unwrap().rolls_count
+        );
+    }
+
+    #[test]
+    fn test_aggregated_lucky_count_includes_cascades() {
+        let play = |aggregate: bool| {
+            let mut app = create_app();
+            app.execute_operation(Operation::Deposit {
+                amount_atto: 100 * MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::StartSession {
+                expires_in_secs: 3600,
+                permissions: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::BlackoutContinuation,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::AutoRoll {
+                count: 60,
+                stop_on_bingo: false,
+                aggregate,
+            })
+            .blocking_wait()
+        };
+
+        // The same chain rolls the same dice, so the itemized run tells what the
+        // aggregate must have counted
+        let OperationResponse::AutoRollResult { results } = play(false) else {
+            panic!("Expected AutoRollResult response");
+        };
+        let mut cascades = 0;
+        let mut expected = 0;
+        for response in &results {
+            match response {
+                OperationResponse::RollResult { is_lucky, .. } => expected += *is_lucky as u32,
+                OperationResponse::CascadeResult { rolls, .. } => {
+                    cascades += 1;
+                    expected += rolls.iter().filter(|roll| roll.is_lucky).count() as u32;
+                }
+                _ => {}
+            }
+        }
+        assert!(cascades > 0, "the run should complete a line");
+
+        match play(true) {
+            OperationResponse::AggregatedRolls { lucky_count, .. } => {
+                assert_eq!(lucky_count, expected)
+            }
+            _ => panic!("Expected AggregatedRolls response"),
+        }
+    }
+
+    #[test]
+    fn test_api_usage_counts_signed_operations() {
+        let mut app = create_app();
+        let owner = AccountOwner::Address20([7; 20]);
+        app.runtime.set_authenticated_signer(owner);
+
+        for _ in 0..3 {
+            app.execute_operation(Operation::RefillPoints)
+                .blocking_wait();
+        }
+        app.runtime.set_system_time(Timestamp::from(2_000_000_000));
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+
+        // Refused operations count too; other signers' do not
+        assert_eq!(app.player.api_usage.operations, 4);
+        assert_eq!(app.player.api_usage.last_operation_micros, 2_000_000_000);
+        app.runtime
+            .set_authenticated_signer(AccountOwner::Address20([8; 20]));
+        app.execute_operation(Operation::RefillPoints)
+            .blocking_wait();
+        let account = app
+            .state
+            .players
+            .get(&owner)
+            .blocking_wait()
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.api_usage.operations, 4);
+        assert_eq!(app.player.api_usage.operations, 1);
+    }
+
+    #[test]
+    fn test_experiment_bucket_roll_fee() {
+        let mut app = create_app();
+        let arm = |roll_cost| ExperimentArm {
+            payout_scale_bps: 10_000,
+            roll_cost: Amount::from_attos(roll_cost),
+        };
+
+        // A 0 bps split sends every owner to arm B
+        govern(
+            &mut app,
+            Operation::SetExperiment {
+                experiment: Some(Experiment {
+                    arm_a: arm(ROLL_COST),
+                    arm_b: arm(2 * ROLL_COST),
+                    split_a_bps: 0,
+                }),
+            },
+        );
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+
+        let card = app.player.current_card.clone().unwrap();
+        assert_eq!(card.experiment_bucket, Some(ExperimentBucket::B));
+        assert_eq!(
+            u128::from(app.player.player_balance),
+            9 * MIN_BET - 2 * ROLL_COST
+        );
+
+        // The game keeps the arm it was dealt under after the experiment ends
+        govern(&mut app, Operation::SetExperiment { experiment: None });
+        app.execute_operation(Operation::RollAndMatch)
+            .blocking_wait();
+        assert_eq!(
+            u128::from(app.player.player_balance),
+            9 * MIN_BET - 4 * ROLL_COST
+        );
+
+        let stats = app
+            .state
+            .experiment_stats
+            .get(&ExperimentBucket::B)
+            .blocking_wait()
+            .unwrap()
+            .expect("Bucket stats should be recorded");
+        assert_eq!((stats.players, stats.games, stats.rolls), (1, 1, 2));
+    }
+
+    #[test]
+    fn test_high_roller_table() {
+        let mut app = create_app();
+        let bet = 2 * MAX_BET;
+        let table = |exposure_cap| HighRollerTable {
+            max_bet: Amount::from_attos(10 * MAX_BET),
+            roll_cost: Amount::from_attos(3 * ROLL_COST),
+            required_skin_id: BLACKOUT_SKIN_ID,
+            exposure_cap: Amount::from_attos(exposure_cap),
+        };
+        let new_game = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: bet,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait()
+        };
+
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 3 * MAX_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+
+        // Only an achievement skin, not a bought one, can gate the table
+        let response = govern(
+            &mut app,
+            Operation::SetHighRollerTable {
+                table: Some(HighRollerTable {
+                    required_skin_id: 1,
+                    ..table(0)
+                }),
+            },
+        );
+        assert!(matches!(response, OperationResponse::Error { .. }));
+
+        // Without the achievement the table stays closed
+        let worst_loss = 9 * bet;
+        govern(
+            &mut app,
+            Operation::SetHighRollerTable {
+                table: Some(table(worst_loss)),
+            },
+        );
+        match new_game(&mut app) {
+            OperationResponse::Error { code, .. } => assert_eq!(code, ErrorCode::BetTooHigh),
+            other => panic!("Expected BetTooHigh, got {:?}", other),
+        }
+        app.player.skins.owned.push(BLACKOUT_SKIN_ID);
+
+        // A top-tier win would cost the house 10x the bet less the bet itself
+        govern(
+            &mut app,
+            Operation::SetHighRollerTable {
+                table: Some(table(worst_loss - 1)),
+            },
+        );
+        match new_game(&mut app) {
+            OperationResponse::Error { code, .. } => assert_eq!(code, ErrorCode::LimitReached),
+            other => panic!("Expected LimitReached, got {:?}", other),
+        }
+
+        govern(
+            &mut app,
+            Operation::SetHighRollerTable {
+                table: Some(table(worst_loss)),
+            },
+        );
+        assert!(matches!(
+            new_game(&mut app),
+            OperationResponse::GameStarted { .. }
+        ));
+        app.execute_operation(Operation::RollAndMatch)
+            .blocking_wait();
+
+        let card = app.player.current_card.clone().unwrap();
+        assert!(card.high_roller);
+        assert_eq!(
+            u128::from(app.player.player_balance),
+            3 * MAX_BET - bet - 3 * ROLL_COST
+        );
+        let stats = app.state.high_roller_stats.get();
+        assert_eq!((stats.games, stats.rolls), (1, 1));
+        assert_eq!(u128::from(stats.wagered), bet);
+    }
+
+    #[test]
+    fn test_failed_rolls_retain_no_fee() {
+        let mut app = create_app();
+        let balances = |app: &FlashportContract| {
+            (
+                u128::from(app.player.player_balance),
+                u128::from(app.player.total_spent),
+            )
+        };
+        let assert_roll_fails_without_fee = |app: &mut FlashportContract| {
+            let before = balances(app);
+            let response = app
+                .execute_operation(Operation::RollAndMatch)
+                .blocking_wait();
+            assert!(matches!(response, OperationResponse::Error { .. }));
+            assert_eq!(balances(app), before);
+        };
+
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+
+        // No game yet
+        assert_roll_fails_without_fee(&mut app);
+
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // Bingo pending
+        app.player.game_phase = GamePhase::BingoPending;
+        assert_roll_fails_without_fee(&mut app);
+        app.player.game_phase = GamePhase::Active;
+
+        // Rejected after payment: fee total would overflow, so the fee is refunded
+        let mut card = app.player.current_card.clone().unwrap();
+        card.total_roll_fees_atto = u128::MAX.to_string();
+        app.player.current_card = Some(card.clone());
+        assert_roll_fails_without_fee(&mut app);
+        assert!(app.player.roll_history.is_empty());
+
+        // Already claimed
+        card.total_roll_fees_atto = "0".to_string();
+        app.player.current_card = Some(card.clone());
+        app.player.game_phase = GamePhase::Claimed;
+        assert_roll_fails_without_fee(&mut app);
+
+        // Insufficient balance
+        app.player.game_phase = GamePhase::Active;
+        app.player.current_card = Some(card);
+        app.player.player_balance = Amount::ZERO;
+        assert_roll_fails_without_fee(&mut app);
+    }
+
+    #[test]
+    fn test_happy_hour_discounts_roll_fee() {
+        let mut app = create_app();
+        let response = govern(
+            &mut app,
+            Operation::ScheduleHappyHour {
+                starts_at_micros: 0,
+                ends_at_micros: 2_000_000_000,
+                discount_bps: 20_000,
+            },
+        );
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        govern(
+            &mut app,
+            Operation::ScheduleHappyHour {
+                starts_at_micros: 0,
+                ends_at_micros: 2_000_000_000,
+                discount_bps: 5_000,
+            },
+        );
+
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        let roll_fee_paid = |response| match response {
+            OperationResponse::RollResult { roll_fee_paid, .. } => roll_fee_paid,
+            other => panic!("Expected RollResult, got {:?}", other),
+        };
+        let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        assert_eq!(roll_fee_paid(response), (ROLL_COST / 2).to_string());
+
+        // Full price once the happy hour is over
+        app.runtime.set_system_time(Timestamp::from(2_000_000_000));
+        let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        assert_eq!(roll_fee_paid(response), ROLL_COST.to_string());
+
+        let response = govern(&mut app, Operation::CancelHappyHour { happy_hour_id: 1 });
+        assert!(matches!(response, OperationResponse::HappyHourCancelled { .. }));
+        assert!(app.state.happy_hours.get().is_empty());
+    }
+
+    #[test]
+    fn test_publish_stats_once_per_interval() {
+        let mut app = create_app();
+        app.state.total_spent.set(Amount::from_tokens(4));
+        app.state.total_won.set(Amount::from_tokens(3));
+
+        let response = app.execute_operation(Operation::PublishStats).blocking_wait();
+        match response {
+            OperationResponse::StatsPublished {
+                snapshot,
+                event_index,
+            } => {
+                assert_eq!(event_index, 0);
+                assert_eq!(snapshot.schema_version, STATS_SCHEMA_VERSION);
+                assert_eq!(snapshot.rtp_bps, 7_500);
+            }
+            other => panic!("Expected StatsPublished, got {:?}", other),
+        }
+
+        let response = app.execute_operation(Operation::PublishStats).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+
+        app.runtime.set_system_time(Timestamp::from(
+            1_000_000_000 + STATS_PUBLISH_INTERVAL_SECS * 1_000_000,
+        ));
+        let response = app.execute_operation(Operation::PublishStats).blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::StatsPublished { event_index: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_deployment_totals_sum_every_account() {
+        let mut app = create_app();
+        let other = AccountOwner::Address20([2; 20]);
+        for signer in [AccountOwner::Address20([1; 20]), other] {
+            app.runtime.set_authenticated_signer(signer);
+            app.execute_operation(Operation::Deposit {
+                amount_atto: 10 * MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::StartSession {
+                expires_in_secs: 3600,
+                permissions: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: 2 * MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+        }
+
+        // Publishing as the second player reports both players' bets
+        let response = app
+            .execute_operation(Operation::PublishStats)
+            .blocking_wait();
+        let OperationResponse::StatsPublished { snapshot, .. } = response else {
+            panic!("Expected StatsPublished, got {:?}", response);
+        };
+        assert_eq!(u128::from(app.player.total_spent), 2 * MIN_BET);
+        assert_eq!(u128::from(snapshot.volume), 4 * MIN_BET);
+        assert_eq!(*app.state.total_spent.get(), snapshot.volume);
+    }
+
+    #[test]
+    fn test_roll_history_kept_per_owner() {
+        let mut app = create_app();
+        let first = AccountOwner::Address20([1; 20]);
+        for signer in [first, AccountOwner::Address20([2; 20])] {
+            app.runtime.set_authenticated_signer(signer);
+            app.execute_operation(Operation::Deposit {
+                amount_atto: 10 * MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::StartSession {
+                expires_in_secs: 3600,
+                permissions: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+            for _ in 0..2 {
+                app.execute_operation(Operation::RollAndMatch)
+                    .blocking_wait();
+            }
+        }
+
+        // Ending the second player's session leaves the first player's rolls alone
+        app.execute_operation(Operation::EndSession).blocking_wait();
+        assert!(app.player.roll_history.is_empty());
+        let account = app
+            .state
+            .players
+            .get(&first)
+            .blocking_wait()
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.roll_history.len(), 2);
+    }
+
+    #[test]
+    fn test_lobby_registry() {
+        let chain = |byte: u8| -> ChainId { format!("{:02x}", byte).repeat(32).parse().unwrap() };
+        let (lobby, player_chain) = (chain(1), chain(2));
+
+        // A deployment sends its summary to the lobby
+        let mut app = create_app();
+        let response = app
+            .execute_operation(Operation::RegisterWithLobby)
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        app.state.lobby_chain.set(Some(lobby));
+        app.runtime.set_chain_id(player_chain);
+        let response = app
+            .execute_operation(Operation::RegisterWithLobby)
+            .blocking_wait();
+        let config_summary = match response {
+            OperationResponse::LobbyRegistrationSent {
+                lobby_chain,
+                config_summary,
+            } => {
+                assert_eq!(lobby_chain, lobby);
+                config_summary
+            }
+            other => panic!("Expected LobbyRegistrationSent, got {:?}", other),
+        };
+        assert_eq!(app.runtime.created_send_message_requests().len(), 1);
+
+        // The lobby records it, but not a chain registering on another's behalf
+        let mut lobby_app = create_app();
+        lobby_app.state.lobby_chain.set(Some(lobby));
+        lobby_app.runtime.set_chain_id(lobby);
+        lobby_app.runtime.set_message_origin_chain_id(chain(3));
+        lobby_app
+            .execute_message(Message::RegisterInstance {
+                chain: player_chain,
+                config_summary: config_summary.clone(),
+            })
+            .blocking_wait();
+        assert_eq!(lobby_app.state.instances.count().blocking_wait().unwrap(), 0);
+
+        lobby_app.runtime.set_message_origin_chain_id(player_chain);
+        lobby_app
+            .execute_message(Message::RegisterInstance {
+                chain: player_chain,
+                config_summary: config_summary.clone(),
+            })
+            .blocking_wait();
+        let instance = lobby_app
+            .state
+            .instances
+            .get(&player_chain)
+            .blocking_wait()
+            .unwrap()
+            .expect("instance should be registered");
+        assert_eq!(instance.config_summary, config_summary);
+        assert_eq!(instance.config_summary.abi_version, ABI_VERSION);
+    }
+
+    #[test]
+    fn test_raffle_tickets_and_draw() {
+        let mut app = create_app();
+        let owner = AccountOwner::Address20([1; 20]);
+        // The pool is set aside from what the house has taken this epoch
+        let house_take = Amount::from_attos(MIN_BET);
+        app.state.epoch.get_mut().house_contributions = house_take;
+
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: 3 * MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        let contribution = Amount::from_attos(3 * MIN_BET * RAFFLE_POOL_BPS as u128 / 10_000);
+        assert_eq!(*app.state.raffle_pool.get(), contribution);
+        assert_eq!(
+            app.state.epoch.get().house_contributions,
+            house_take.saturating_sub(contribution)
+        );
+        // Play-point games earn no tickets
+        app.player.current_card = None;
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: 5 * MIN_BET,
+            currency: Currency::Play,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        let tickets = app.state.raffle_tickets.get(&owner).blocking_wait().unwrap();
+        assert_eq!(tickets, Some(3));
+        assert_eq!(*app.state.raffle_total_tickets.get(), 3);
+        let pool = *app.state.raffle_pool.get();
+        assert_eq!(pool, contribution);
+
+        // The round is still open
+        let response = app.execute_operation(Operation::DrawRaffle).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+
+        // Anyone may draw; the prize goes to the winner's account
+        app.state.raffle_draw_at_micros.set(0);
+        let balance_before = app.player.player_balance;
+        let drawer = AccountOwner::Address20([2; 20]);
+        app.runtime.set_authenticated_signer(drawer);
+        let response = app.execute_operation(Operation::DrawRaffle).blocking_wait();
+        match response {
+            OperationResponse::RaffleDrawn { draw } => {
+                assert_eq!(draw.draw_id, 1);
+                assert_eq!(draw.winner, owner);
+                assert_eq!(draw.winner_tickets, 3);
+                assert_eq!(draw.prize, pool);
+            }
+            other => panic!("Expected RaffleDrawn, got {:?}", other),
+        }
+        assert_eq!(
+            account_of(&mut app, owner).player_balance,
+            balance_before.saturating_add(pool)
+        );
+        assert_eq!(account_of(&mut app, drawer).player_balance, Amount::ZERO);
+        assert_eq!(*app.state.raffle_total_tickets.get(), 0);
+        assert_eq!(*app.state.raffle_pool.get(), Amount::ZERO);
+        assert_eq!(app.state.raffle_history.count(), 1);
+    }
+
+    #[test]
+    fn test_tournament_shared_card_and_settlement() {
+        let mut app = create_app();
+        let first = AccountOwner::Address20([1; 20]);
+        let second = AccountOwner::Address20([2; 20]);
+
+        let response = app
+            .execute_operation(Operation::CreateTournament {
+                entry_fee_atto: 2 * MIN_BET,
+                duration_secs: 3600,
+            })
+            .blocking_wait();
+        let tournament = match response {
+            OperationResponse::TournamentCreated { tournament } => tournament,
+            other => panic!("Expected TournamentCreated, got {:?}", other),
+        };
+        assert_eq!(tournament.tournament_id, 1);
+
+        // Both owners play the same card, each against their own dice stream
+        let mut dice_seen = Vec::new();
+        for owner in [first, second] {
+            app.runtime.set_authenticated_signer(owner);
+            app.execute_operation(Operation::Deposit {
+                amount_atto: 10 * MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+            let response = app
+                .execute_operation(Operation::EnterTournament { tournament_id: 1 })
+                .blocking_wait();
+            assert!(matches!(response, OperationResponse::TournamentEntered { .. }));
+            let response = app
+                .execute_operation(Operation::EnterTournament { tournament_id: 1 })
+                .blocking_wait();
+            assert!(matches!(response, OperationResponse::Error { .. }));
+
+            let mut dice = Vec::new();
+            loop {
+                match app
+                    .execute_operation(Operation::TournamentRoll { tournament_id: 1 })
+                    .blocking_wait()
+                {
+                    OperationResponse::TournamentRolled {
+                        dice: roll, finished, ..
+                    } => {
+                        dice.push(roll);
+                        if finished {
+                            break;
+                        }
+                    }
+                    other => panic!("Expected TournamentRolled, got {:?}", other),
+                }
+            }
+            dice_seen.push(dice);
+        }
+        assert_ne!(dice_seen[0], dice_seen[1]);
+        // Each entry fee came out of the entrant's own balance
+        for owner in [first, second] {
+            assert_eq!(
+                account_of(&mut app, owner).player_balance,
+                Amount::from_tokens(8)
+            );
+        }
+
+        // Settlement waits for the tournament to close
+        let response = app
+            .execute_operation(Operation::SettleTournament { tournament_id: 1 })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+
+        app.runtime
+            .set_system_time(Timestamp::from(1_000_000_000 + 3_600_000_000));
+        let response = app
+            .execute_operation(Operation::SettleTournament { tournament_id: 1 })
+            .blocking_wait();
+        let tournament = match response {
+            OperationResponse::TournamentSettled { tournament } => tournament,
+            other => panic!("Expected TournamentSettled, got {:?}", other),
+        };
+        // Fewer rolls-to-bingo ranks first; within one block entry order breaks a tie
+        let rolls: Vec<usize> = dice_seen.iter().map(Vec::len).collect();
+        let (winner, runner_up) = if rolls[1] < rolls[0] { (1, 0) } else { (0, 1) };
+        let pot = 4 * MIN_BET;
+        let second_share = pot * TOURNAMENT_PAYOUT_BPS[1] as u128 / 8_000;
+        assert_eq!(tournament.entries[winner].rank, Some(1));
+        assert_eq!(tournament.entries[runner_up].rank, Some(2));
+        assert_eq!(
+            u128::from(tournament.entries[runner_up].payout),
+            second_share
+        );
+        assert_eq!(
+            u128::from(tournament.entries[winner].payout),
+            pot - second_share
+        );
+        for entry in &tournament.entries {
+            assert_eq!(
+                u128::from(account_of(&mut app, entry.owner).player_balance),
+                8 * MIN_BET + u128::from(entry.payout)
+            );
+        }
+
+        let response = app
+            .execute_operation(Operation::SettleTournament { tournament_id: 1 })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+    }
+
+    #[test]
+    fn test_duel_alternating_turns_and_pot() {
+        let mut app = create_app();
+        let opener = AccountOwner::Address20([1; 20]);
+        let challenger = AccountOwner::Address20([2; 20]);
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 50 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        let code = |response: OperationResponse| match response {
+            OperationResponse::Error { code, .. } => Some(code),
+            _ => None,
+        };
+        let roll = |app: &mut FlashportContract, owner| {
+            app.runtime.set_authenticated_signer(owner);
+            app.execute_operation(Operation::DuelRoll { duel_id: 1 })
+                .blocking_wait()
+        };
+
+        let response = app
+            .execute_operation(Operation::OpenDuel {
+                roll_fee_atto: ROLL_COST,
+            })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::DuelOpened { .. }));
+        assert_eq!(code(roll(&mut app, opener)), Some(ErrorCode::TooEarly));
+        let response = app
+            .execute_operation(Operation::JoinDuel { duel_id: 1 })
+            .blocking_wait();
+        assert_eq!(code(response), Some(ErrorCode::NotPermitted));
+
+        app.runtime.set_authenticated_signer(challenger);
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 50 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        let response = app
+            .execute_operation(Operation::JoinDuel { duel_id: 1 })
+            .blocking_wait();
+        let OperationResponse::DuelJoined { duel } = response else {
+            panic!("Expected DuelJoined, got {:?}", response);
+        };
+        assert_eq!(duel.status, DuelStatus::Playing);
+        assert_ne!(duel.seats[0].numbers, duel.seats[1].numbers);
+
+        // The opener rolls first, then turns alternate until someone completes a line
+        assert_eq!(code(roll(&mut app, challenger)), Some(ErrorCode::NotPermitted));
+        let mut rolls = 0;
+        let duel = loop {
+            let owner = if rolls % 2 == 0 { opener } else { challenger };
+            let response = roll(&mut app, owner);
+            let OperationResponse::DuelRolled { duel, .. } = response else {
+                panic!("Expected DuelRolled, got {:?}", response);
+            };
+            rolls += 1;
+            if duel.status == DuelStatus::Settled {
+                break duel;
+            }
+            assert!(rolls < 1_000, "No line completed");
+        };
+        let winner = if rolls % 2 == 1 { opener } else { challenger };
+        assert_eq!(duel.winner, Some(winner));
+        assert_eq!(u128::from(duel.pot), rolls as u128 * ROLL_COST);
+        let rake = u128::from(duel.pot) * DUEL_RAKE_BPS as u128 / 10_000;
+        assert_eq!(u128::from(duel.rake), rake);
+        assert_eq!(u128::from(duel.payout), u128::from(duel.pot) - rake);
+        let balances = [opener, challenger]
+            .map(|owner| u128::from(account_of(&mut app, owner).player_balance));
+        assert_eq!(balances[0] + balances[1], 100 * MIN_BET - rake);
+        assert_eq!(code(roll(&mut app, winner)), Some(ErrorCode::GameOver));
+        // The pot was held in escrow until paid, and the rake went to the house
+        let epoch = app.state.epoch.get();
+        assert_eq!(epoch.prize_pool, Amount::ZERO);
+        assert_eq!(u128::from(epoch.house_contributions), rake);
+    }
+
+    #[test]
+    fn test_duel_pot_claimed_after_turn_runs_out() {
+        let mut app = create_app();
+        let opener = AccountOwner::Address20([1; 20]);
+        let challenger = AccountOwner::Address20([2; 20]);
+        let code = |response: OperationResponse| match response {
+            OperationResponse::Error { code, .. } => Some(code),
+            _ => None,
+        };
+        let act = |app: &mut FlashportContract, owner, operation| {
+            app.runtime.set_authenticated_signer(owner);
+            app.execute_operation(operation).blocking_wait()
+        };
+        for owner in [opener, challenger] {
+            act(
+                &mut app,
+                owner,
+                Operation::StartSession {
+                    expires_in_secs: 3600,
+                    permissions: None,
+                },
+            );
+            act(
+                &mut app,
+                owner,
+                Operation::Deposit {
+                    amount_atto: 10 * MIN_BET,
+                    credit_to: None,
+                },
+            );
+        }
+        let open = Operation::OpenDuel {
+            roll_fee_atto: MIN_BET,
+        };
+        act(&mut app, opener, open);
+        act(&mut app, challenger, Operation::JoinDuel { duel_id: 1 });
+        let response = act(&mut app, opener, Operation::DuelRoll { duel_id: 1 });
+        let OperationResponse::DuelRolled { duel, .. } = response else {
+            panic!("Expected DuelRolled, got {:?}", response);
+        };
+        assert_eq!(duel.status, DuelStatus::Playing);
+
+        // The pot can only be claimed by the waiting player, once the turn has run out
+        let claim = Operation::ClaimDuelPot { duel_id: 1 };
+        let response = act(&mut app, opener, claim.clone());
+        assert_eq!(code(response), Some(ErrorCode::TooEarly));
+        let response = act(&mut app, challenger, claim.clone());
+        assert_eq!(code(response), Some(ErrorCode::NotPermitted));
+        app.runtime
+            .set_system_time(Timestamp::from(duel.turn_deadline_micros + 1));
+        let response = act(&mut app, challenger, Operation::DuelRoll { duel_id: 1 });
+        assert_eq!(code(response), Some(ErrorCode::TooLate));
+        let response = act(&mut app, opener, claim.clone());
+        let OperationResponse::DuelPotClaimed { duel } = response else {
+            panic!("Expected DuelPotClaimed, got {:?}", response);
+        };
+
+        let rake = MIN_BET * DUEL_RAKE_BPS as u128 / 10_000;
+        assert_eq!(duel.winner, Some(opener));
+        assert_eq!(u128::from(duel.payout), MIN_BET - rake);
+        assert_eq!(u128::from(app.player.player_balance), 10 * MIN_BET - rake);
+        assert_eq!(app.state.epoch.get().prize_pool, Amount::ZERO);
+        let response = act(&mut app, opener, claim);
+        assert_eq!(code(response), Some(ErrorCode::GameOver));
+    }
+
+    #[test]
+    fn test_demo_game_needs_no_funds_or_session() {
+        let mut app = create_app();
+        app.runtime.set_authenticated_signer(None);
+
+        let response = app.execute_operation(Operation::DemoGame).blocking_wait();
+        let OperationResponse::DemoResult {
+            seed,
+            numbers,
+            marked,
+            rolls,
+            bingo_type,
+            multiplier_display,
+        } = response
+        else {
+            panic!("Expected DemoResult, got {:?}", response);
+        };
+
+        // Played to the first line or the roll limit, replayable from the seed
+        assert!(bingo_type.is_some() || rolls.len() == DEMO_GAME_ROLLS as usize);
+        assert!(rolls.len() <= DEMO_GAME_ROLLS as usize);
+        assert_eq!(numbers, rules::deal_numbers(seed));
+        let drawn: Vec<u8> = rolls.iter().map(|roll| roll.sum).collect();
+        assert_eq!(marked, rules::expected_marks(&numbers, &drawn, &[]));
+        assert_eq!(bingo_type, rules::bingo_lines(&marked).first().copied());
+        assert_eq!(multiplier_display.is_some(), bingo_type.is_some());
+
+        // Nothing was dealt, charged or paid
+        assert!(app.player.current_card.is_none());
+        assert_eq!(app.player.player_balance, Amount::ZERO);
+        assert_eq!(*app.state.demo_games.get(), 1);
+        let OperationResponse::DemoResult { seed: next_seed, .. } =
+            app.execute_operation(Operation::DemoGame).blocking_wait()
+        else {
+            panic!("Expected DemoResult");
+        };
+        assert_ne!(next_seed, seed);
+    }
+
+    #[test]
+    fn test_syndicate_shares_results_pro_rata() {
+        let mut app = create_app();
+        // Start from an empty house
+        app.state.epoch.get_mut().bonus_pool = Amount::ZERO;
+        let manager = AccountOwner::Address20([1; 20]);
+        let friend = AccountOwner::Address20([2; 20]);
+        let contribute = |app: &mut FlashportContract, amount_atto| {
+            app.execute_operation(Operation::ContributeToSyndicate {
+                syndicate_id: 1,
+                amount_atto,
+            })
+            .blocking_wait()
+        };
+        let play = |app: &mut FlashportContract, bet_amount_atto| {
+            app.execute_operation(Operation::SyndicateGame {
+                syndicate_id: 1,
+                bet_amount_atto,
+            })
+            .blocking_wait()
+        };
+        let error_code = |response| match response {
+            OperationResponse::Error { code, .. } => code,
+            other => panic!("Expected an error, got {:?}", other),
+        };
+
+        app.execute_operation(Operation::CreateSyndicate {
+            name: " Night Owls ".to_string(),
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::SetSyndicateMember {
+            syndicate_id: 1,
+            member: friend,
+            max_bet_atto: 5 * MIN_BET,
+        })
+        .blocking_wait();
+        // An empty bankroll cannot stake a game
+        assert_eq!(error_code(play(&mut app, MIN_BET)), ErrorCode::InsufficientFunds);
+        contribute(&mut app, 30 * MIN_BET);
+        app.runtime.set_authenticated_signer(friend);
+        contribute(&mut app, 10 * MIN_BET);
+
+        // Members play within their own limit; outsiders cannot play at all
+        assert_eq!(error_code(play(&mut app, 6 * MIN_BET)), ErrorCode::BetTooHigh);
+        app.runtime
+            .set_authenticated_signer(AccountOwner::Address20([3; 20]));
+        assert_eq!(error_code(play(&mut app, MIN_BET)), ErrorCode::NotPermitted);
+        app.runtime.set_authenticated_signer(friend);
+        // Winnings are paid by the house, so its pool must cover the best payout
+        assert_eq!(
+            error_code(play(&mut app, MIN_BET)),
+            ErrorCode::InsufficientFunds
+        );
+        app.state.epoch.get_mut().bonus_pool = Amount::from_attos(100 * MIN_BET);
+        let OperationResponse::SyndicateGamePlayed { result, syndicate } = play(&mut app, MIN_BET)
+        else {
+            panic!("Expected SyndicateGamePlayed");
+        };
+
+        // The manager's 30 of 40 LINERA carries three quarters of the result
+        let change = |share: &SyndicateShare| share.change_atto.parse::<i128>().unwrap();
+        let net: i128 = result.net_result_atto.parse().unwrap();
+        let manager_change = change(&result.shares[0]);
+        let friend_change = change(&result.shares[1]);
+        assert_eq!(manager_change + friend_change, net);
+        assert!((manager_change - 3 * friend_change).abs() <= 3);
+        let stakes: Vec<Amount> = syndicate.members.iter().map(|member| member.stake).collect();
+        assert_eq!(stakes[0].saturating_add(stakes[1]), syndicate.bankroll);
+        let cost = MIN_BET + result.rolls as u128 * ROLL_COST;
+        assert_eq!(net, u128::from(result.payout) as i128 - cost as i128);
+        assert_eq!(result.player, friend);
+        // The house took the cost and paid the payout
+        let epoch = app.state.epoch.get();
+        let house_pool = u128::from(epoch.house_contributions) + u128::from(epoch.bonus_pool);
+        assert_eq!(house_pool as i128, (100 * MIN_BET) as i128 - net);
+        assert_eq!(epoch.wagered, Amount::from_attos(MIN_BET));
+        assert_eq!(epoch.paid_out, result.payout);
+
+        // The manager stays until everyone else has left; the last to leave closes it
+        app.runtime.set_authenticated_signer(manager);
+        let leave = Operation::LeaveSyndicate { syndicate_id: 1 };
+        let response = app.execute_operation(leave.clone()).blocking_wait();
+        assert_eq!(error_code(response), ErrorCode::NotPermitted);
+        app.runtime.set_authenticated_signer(friend);
+        match app.execute_operation(leave.clone()).blocking_wait() {
+            OperationResponse::SyndicateLeft { paid_out, .. } => assert_eq!(paid_out, stakes[1]),
+            other => panic!("Expected SyndicateLeft, got {:?}", other),
+        }
+        app.runtime.set_authenticated_signer(manager);
+        match app.execute_operation(leave).blocking_wait() {
+            OperationResponse::SyndicateLeft { syndicate, .. } => assert!(syndicate.is_none()),
+            other => panic!("Expected SyndicateLeft, got {:?}", other),
+        }
+        assert!(app.state.syndicates.get(&1).blocking_wait().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_prune_history() {
+        let mut app = create_app();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        for amount_atto in [10 * MIN_BET, 5 * MIN_BET] {
+            app.execute_operation(Operation::Deposit {
+                amount_atto,
+                credit_to: None,
+            })
+            .blocking_wait();
+        }
+        app.execute_operation(Operation::Withdraw {
+            amount: Amount::from_attos(MIN_BET),
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        // Too few rolls to complete a line
+        for _ in 0..3 {
+            app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        }
+        let oldest_roll = app.player.roll_history[0].clone();
+        for _ in 0..3 {
+            app.execute_operation(Operation::OpenDuel {
+                roll_fee_atto: ROLL_COST,
+            })
+            .blocking_wait();
+        }
+        let mut settled = app.state.duels.get(&1).blocking_wait().unwrap().unwrap();
+        settled.status = DuelStatus::Settled;
+        app.state.duels.insert(&1, settled).unwrap();
+
+        let response = app
+            .execute_operation(Operation::PruneHistory { keep_last: 0 })
+            .blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::Error { code: ErrorCode::InvalidInput, .. }
+        ));
+
+        // The oldest roll, deposit receipt and settled duel go; open duels stay
+        let response = app
+            .execute_operation(Operation::PruneHistory { keep_last: 2 })
+            .blocking_wait();
+        let OperationResponse::HistoryPruned { report } = response else {
+            panic!("Expected HistoryPruned, got {:?}", response);
+        };
+        assert_eq!(
+            (report.rolls_pruned, report.receipts_pruned, report.duels_pruned),
+            (1, 1, 1)
+        );
+        assert_eq!(report.entries_pruned, 3);
+        assert!(report.bytes_reclaimed > 0 && !report.automatic);
+        assert_eq!(app.player.roll_history.len(), 2);
+        assert!(app.state.receipts.get(&1).blocking_wait().unwrap().is_none());
+        assert!(app.state.receipts.get(&2).blocking_wait().unwrap().is_some());
+        assert!(app.state.duels.get(&1).blocking_wait().unwrap().is_none());
+        assert!(app.state.duels.get(&2).blocking_wait().unwrap().is_some());
+
+        let summary = app.state.history_summary.get().clone();
+        assert_eq!((summary.rolls, summary.deposits, summary.duels), (1, 1, 1));
+        assert_eq!(summary.matched_rolls, u64::from(oldest_roll.matched));
+        assert_eq!(summary.deposited, Amount::from_attos(10 * MIN_BET));
+        assert_eq!(
+            u128::from(summary.roll_fees),
+            oldest_roll.fee_paid_atto.parse::<u128>().unwrap()
+        );
+        assert_eq!(summary.bytes_reclaimed, report.bytes_reclaimed);
+
+        // With a retention set, the next epoch rollover prunes on its own
+        govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    history_retention: 1,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        app.runtime.set_system_time(Timestamp::from(
+            1_000_000_000 + EPOCH_PERIOD_SECS * 1_000_000,
+        ));
+        app.execute_operation(Operation::GetVersion).blocking_wait();
+        let report = app.state.last_prune.get().clone().unwrap();
+        assert!(report.automatic);
+        assert_eq!((report.keep_last, report.rolls_pruned), (1, 1));
+        assert_eq!(app.state.history_summary.get().rolls, 2);
+
+        // Admin only
+        app.runtime
+            .set_authenticated_signer(AccountOwner::Address20([2; 20]));
+        let response = app
+            .execute_operation(Operation::PruneHistory { keep_last: 1 })
+            .blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::Error { code: ErrorCode::NotPermitted, .. }
+        ));
+    }
+
+    #[test]
+    fn test_tournament_escrow_interest() {
+        let mut app = create_app();
+        let day_micros = 24 * 3_600_000_000;
+        govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    tournament_interest_bps_per_day: 100,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        app.execute_operation(Operation::CreateTournament {
+            entry_fee_atto: 2 * MIN_BET,
+            duration_secs: 2 * 24 * 3600,
+        })
+        .blocking_wait();
+
+        // The second fee spends one day less in escrow than the first
+        for (signer, entered_at) in [(1, 1_000_000_000), (2, 1_000_000_000 + day_micros)] {
+            app.runtime.set_system_time(Timestamp::from(entered_at));
+            app.runtime.set_authenticated_signer(AccountOwner::Address20([signer; 20]));
+            app.execute_operation(Operation::Deposit {
+                amount_atto: 10 * MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::EnterTournament { tournament_id: 1 })
+                .blocking_wait();
+        }
+
+        app.runtime
+            .set_system_time(Timestamp::from(1_000_000_000 + 3 * day_micros));
+        let response = app
+            .execute_operation(Operation::SettleTournament { tournament_id: 1 })
+            .blocking_wait();
+        let OperationResponse::TournamentSettled { tournament } = response else {
+            panic!("Expected TournamentSettled, got {:?}", response);
+        };
+        // 1% a day on 2 LINERA, accruing only until the tournament closed
+        assert_eq!(u128::from(tournament.entries[0].interest), 4 * MIN_BET / 100);
+        assert_eq!(u128::from(tournament.entries[1].interest), 2 * MIN_BET / 100);
+        assert_eq!(u128::from(tournament.interest_paid), 6 * MIN_BET / 100);
+        // Without finishers both fees are refunded on top of the interest
+        for (signer, interest) in [(1, 4 * MIN_BET / 100), (2, 2 * MIN_BET / 100)] {
+            let account = account_of(&mut app, AccountOwner::Address20([signer; 20]));
+            assert_eq!(
+                account.player_balance,
+                Amount::from_attos(10 * MIN_BET + interest)
+            );
+        }
+        // The interest came out of the treasury
+        assert_eq!(
+            app.state.epoch.get().bonus_pool,
+            Amount::from_tokens(1_000)
+                .try_sub(tournament.interest_paid)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tournament_interest_capped_by_treasury() {
+        let mut app = create_app();
+        govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    tournament_interest_bps_per_day: 100,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        app.execute_operation(Operation::CreateTournament {
+            entry_fee_atto: 2 * MIN_BET,
+            duration_secs: 2 * 24 * 3600,
+        })
+        .blocking_wait();
+        for signer in [1, 2] {
+            app.runtime
+                .set_authenticated_signer(AccountOwner::Address20([signer; 20]));
+            app.execute_operation(Operation::Deposit {
+                amount_atto: 10 * MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::EnterTournament { tournament_id: 1 })
+                .blocking_wait();
+        }
+
+        // Each fee earns 0.04 LINERA over two days, but the treasury holds only 0.05
+        app.state.epoch.get_mut().bonus_pool = Amount::from_attos(5 * MIN_BET / 100);
+        app.runtime
+            .set_system_time(Timestamp::from(1_000_000_000 + 3 * 24 * 3_600_000_000));
+        let response = app
+            .execute_operation(Operation::SettleTournament { tournament_id: 1 })
+            .blocking_wait();
+        let OperationResponse::TournamentSettled { tournament } = response else {
+            panic!("Expected TournamentSettled, got {:?}", response);
+        };
+        assert_eq!(
+            u128::from(tournament.entries[0].interest),
+            4 * MIN_BET / 100
+        );
+        assert_eq!(u128::from(tournament.entries[1].interest), MIN_BET / 100);
+        assert_eq!(u128::from(tournament.interest_paid), 5 * MIN_BET / 100);
+        assert_eq!(app.state.epoch.get().bonus_pool, Amount::ZERO);
+    }
+
+    #[test]
+    fn test_epoch_rollover_carries_house_contributions() {
+        let mut app = create_app();
+        // Start from an empty house
+        app.state.epoch.get_mut().bonus_pool = Amount::ZERO;
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        // Ending the session forfeits the rolled, unfinished game's bet to the house
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: 2 * MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        app.execute_operation(Operation::EndSession).blocking_wait();
+
+        let epoch = app.state.epoch.get().clone();
+        assert_eq!(epoch.epoch_id, 1);
+        assert_eq!(u128::from(epoch.house_contributions), 2 * MIN_BET);
+
+        // The epoch has ended: the next operation rolls it over
+        app.state.epoch.get_mut().ends_at_micros = 0;
+        app.execute_operation(Operation::Deposit {
+            amount_atto: MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+
+        assert_eq!(app.state.epoch_history.count(), 1);
+        let current = app.state.epoch.get();
+        assert_eq!(current.epoch_id, 2);
+        assert_eq!(u128::from(current.bonus_pool), 2 * MIN_BET);
+        assert_eq!(current.house_contributions, Amount::ZERO);
+        assert!(current.ends_at_micros > 1_000_000_000);
+    }
+
+    #[test]
+    fn test_epoch_prize_pool_carries_open_games() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: 2 * MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::RollAndMatch)
+            .blocking_wait();
+        assert_eq!(u128::from(app.state.epoch.get().prize_pool), 2 * MIN_BET);
+
+        // The game is still open when the epoch closes: its bet moves to the next pool
+        app.state.epoch.get_mut().ends_at_micros = 0;
+        app.execute_operation(Operation::GetVersion).blocking_wait();
+        let closed = app
+            .state
+            .epoch_history
+            .get(0)
+            .blocking_wait()
+            .unwrap()
+            .unwrap();
+        assert_eq!(u128::from(closed.prize_pool), 2 * MIN_BET);
+        assert_eq!(app.state.epoch.get().epoch_id, 2);
+        assert_eq!(u128::from(app.state.epoch.get().prize_pool), 2 * MIN_BET);
+
+        // Settling the game empties the new epoch's pool into its house take
+        app.execute_operation(Operation::EndSession).blocking_wait();
+        let current = app.state.epoch.get();
+        assert_eq!(current.prize_pool, Amount::ZERO);
+        assert_eq!(u128::from(current.house_contributions), 2 * MIN_BET);
+    }
+
+    #[test]
+    fn test_cell_heatmap_tracks_marks() {
+        let mut app = create_app();
+        let owner = AccountOwner::Address20([1; 20]);
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        let initial_marks = app.player.current_card.as_ref().unwrap().marked_bitmask();
+
+        app.execute_operation(Operation::AutoRoll {
+            count: 20,
+            stop_on_bingo: true,
+            aggregate: true,
+        })
+        .blocking_wait();
+
+        let card = app.player.current_card.clone().unwrap();
+        let new_marks = card.marked_bitmask() & !initial_marks;
+        let hits = *app.state.cell_hits.get();
+        for (i, cell) in hits.iter().enumerate() {
+            assert_eq!(*cell, (new_marks >> i & 1) as u64);
+        }
+        assert_eq!(account_of(&mut app, owner).cell_hits, hits);
+    }
+
+    #[test]
+    fn test_versioned_operations() {
+        let mut app = create_app();
+        let versioned = |abi_version, operation| Operation::Versioned {
+            abi_version,
+            operation: Box::new(operation),
+        };
+
+        let response = app
+            .execute_operation(versioned(ABI_VERSION, Operation::GetVersion))
+            .blocking_wait();
+        match response {
+            OperationResponse::Version { abi_version } => assert_eq!(abi_version, ABI_VERSION),
+            other => panic!("Expected Version, got {:?}", other),
+        }
+
+        // A stale tag is rejected before the operation runs
+        let deposit = Operation::Deposit {
+            amount_atto: MIN_BET,
+            credit_to: None,
+        };
+        let response = app
+            .execute_operation(versioned(ABI_VERSION + 1, deposit))
+            .blocking_wait();
+        match response {
+            OperationResponse::IncompatibleAbiVersion {
+                supported,
+                requested,
+            } => assert_eq!((supported, requested), (ABI_VERSION, ABI_VERSION + 1)),
+            other => panic!("Expected IncompatibleAbiVersion, got {:?}", other),
+        }
+        assert_eq!(app.player.player_balance, Amount::ZERO);
+    }
+
+    #[test]
+    fn test_sequenced_operation_runs_in_required_session() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        let new_game = Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        };
+        let response = app
+            .execute_operation(Operation::Sequenced {
+                requires_session_id: 1,
+                operation: Box::new(new_game),
+            })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::GameStarted { .. }));
+    }
+
+    #[test]
+    #[should_panic(expected = "requires session 2 but the active session is Some(1)")]
+    fn test_sequenced_operation_reverts_in_other_session() {
+        let mut app = create_app();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::Sequenced {
+            requires_session_id: 2,
+            operation: Box::new(Operation::RollAndMatch),
+        })
+        .blocking_wait();
+    }
+
+    #[test]
+    fn test_relayed_operation_replay_protection() {
+        let mut app = create_app();
+        let chain_id = ChainId(CryptoHash::from([8u64; 4]));
+        let application_id = ApplicationId::new(CryptoHash::from([9u64; 4]));
+        app.runtime.set_chain_id(chain_id);
+        app.runtime.set_application_id(application_id.with_abi::<FlashportAbi>());
+        let secret = AccountSecretKey::generate();
+        let owner = AccountOwner::from(secret.public());
+        let envelope = |nonce, expires_in_secs: u64| RelayEnvelope {
+            owner,
+            chain_id,
+            application_id,
+            nonce,
+            expires_at_micros: 1_000_000_000 + expires_in_secs * 1_000_000,
+        };
+        let relayed = |envelope: RelayEnvelope, operation: Operation| {
+            let signature = secret.sign(&RelayPayload {
+                envelope: envelope.clone(),
+                operation: operation.clone(),
+            });
+            Operation::Relayed {
+                envelope,
+                signature,
+                operation: Box::new(operation),
+            }
+        };
+        let code = |response: OperationResponse| match response {
+            OperationResponse::Error { code, .. } => Some(code),
+            _ => None,
+        };
+
+        // Runs once, then the nonce is spent
+        let operation = relayed(envelope(1, 60), Operation::GetVersion);
+        let response = app.execute_operation(operation.clone()).blocking_wait();
+        assert!(matches!(response, OperationResponse::Version { .. }));
+        let response = app.execute_operation(operation).blocking_wait();
+        assert_eq!(code(response), Some(ErrorCode::AlreadyDone));
+
+        // The relayed owner is the signer, not the relayer: it is not the admin
+        let operation = relayed(
+            envelope(2, 60),
+            Operation::UpdateConfig {
+                config: GameConfig::default(),
+            },
+        );
+        let response = app.execute_operation(operation).blocking_wait();
+        assert_eq!(code(response), Some(ErrorCode::NotPermitted));
+
+        // Another chain, a swapped operation, a past or too distant expiry
+        let mut elsewhere = envelope(3, 60);
+        elsewhere.chain_id = ChainId(CryptoHash::from([10u64; 4]));
+        let response = app
+            .execute_operation(relayed(elsewhere, Operation::GetVersion))
+            .blocking_wait();
+        assert_eq!(code(response), Some(ErrorCode::NotPermitted));
+        let Operation::Relayed {
+            envelope: signed,
+            signature,
+            ..
+        } = relayed(envelope(4, 60), Operation::GetVersion)
+        else {
+            unreachable!()
+        };
+        let swapped = Operation::Relayed {
+            envelope: signed,
+            signature,
+            operation: Box::new(Operation::HealthCheck),
+        };
+        let response = app.execute_operation(swapped).blocking_wait();
+        assert_eq!(code(response), Some(ErrorCode::NotPermitted));
+        let response = app
+            .execute_operation(relayed(envelope(5, 0), Operation::GetVersion))
+            .blocking_wait();
+        assert_eq!(code(response), Some(ErrorCode::TooLate));
+        let response = app
+            .execute_operation(relayed(
+                envelope(6, MAX_RELAY_TTL_SECS + 1),
+                Operation::GetVersion,
+            ))
+            .blocking_wait();
+        assert_eq!(code(response), Some(ErrorCode::InvalidInput));
+
+        // Spent nonces are pruned once their envelopes expire
+        let nonces = |app: &FlashportContract| {
+            app.state
+                .relay_nonces
+                .get(&owner)
+                .blocking_wait()
+                .unwrap()
+                .unwrap_or_default()
+        };
+        assert_eq!(nonces(&app).len(), 2);
+        app.runtime.set_system_time(Timestamp::from(1_000_000_000 + 120_000_000));
+        let mut later = envelope(7, 60);
+        later.expires_at_micros += 120_000_000;
+        app.execute_operation(relayed(later, Operation::GetVersion))
+            .blocking_wait();
+        assert_eq!(nonces(&app).iter().map(|n| n.nonce).collect::<Vec<_>>(), vec![7]);
+    }
+
+    #[test]
+    fn test_withdraw_all_keeps_open_game_reserve() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        let response = app.execute_operation(Operation::WithdrawAll).blocking_wait();
+        match response {
+            OperationResponse::WithdrawAllProcessed {
+                amount,
+                remaining_balance,
+                withheld,
+                ..
+            } => {
+                assert_eq!(amount, (9 * MIN_BET - ROLL_COST).to_string());
+                assert_eq!(remaining_balance, ROLL_COST.to_string());
+                let reasons: Vec<_> = withheld.iter().map(|w| w.reason).collect();
+                assert_eq!(
+                    reasons,
+                    vec![
+                        WithholdReason::LockedBet,
+                        WithholdReason::OpenGameReserve,
+                        WithholdReason::PlayPoints
+                    ]
+                );
+                assert_eq!(withheld[0].amount_atto, MIN_BET.to_string());
+            }
+            other => panic!("Expected WithdrawAllProcessed, got {:?}", other),
+        }
+
+        // Only the reserve is left, so there is nothing more to withdraw
+        let response = app.execute_operation(Operation::WithdrawAll).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+    }
+
+    #[test]
+    fn test_extend_claim_deadlines_rescues_expired_claim() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // A bingo whose claim window closed during an outage
+        let mut card = app.player.current_card.clone().unwrap();
+        card.claim_deadline_micros = 1;
+        app.player.current_card = Some(card);
+        app.player.game_phase = GamePhase::BingoPending;
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        match response {
+            OperationResponse::Error { message, .. } => assert!(message.contains("deadline")),
+            other => panic!("Expected Error, got {:?}", other),
+        }
+        assert_eq!(app.player.game_phase, GamePhase::Expired);
+
+        let response = app
+            .execute_operation(Operation::ExtendClaimDeadlines {
+                window_secs: 3600,
+                reason: "validator outage".to_string(),
+                after: None,
+                limit: MAX_CLAIM_EXTENSION_PAGE,
+            })
+            .blocking_wait();
+        match response {
+            OperationResponse::ClaimDeadlinesExtended {
+                action,
+                visited,
+                next_after,
+            } => {
+                assert_eq!(action.action_id, 1);
+                assert!(action.detail.contains("validator outage"));
+                assert_eq!(visited, 1);
+                assert_eq!(next_after, None);
+            }
+            other => panic!("Expected ClaimDeadlinesExtended, got {:?}", other),
+        }
+        assert_eq!(app.state.admin_log.count(), 1);
+
+        assert_eq!(app.player.game_phase, GamePhase::BingoPending);
+
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        assert!(matches!(response, OperationResponse::PrizeClaimed { .. }));
+        assert_eq!(app.player.game_phase, GamePhase::Claimed);
+        assert!(app.player.current_card.as_ref().unwrap().prize_claimed);
+    }
+
+    #[test]
+    fn test_extend_claim_deadlines_pages_through_accounts() {
+        let mut app = create_app();
+        let owners: Vec<AccountOwner> = (1..=3).map(|n| AccountOwner::Address20([n; 20])).collect();
+        for owner in &owners {
+            app.runtime.set_authenticated_signer(*owner);
+            app.execute_operation(Operation::Deposit {
+                amount_atto: MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+        }
+        app.runtime.set_authenticated_signer(owners[0]);
+
+        let extend = |app: &mut FlashportContract, after, limit| {
+            app.execute_operation(Operation::ExtendClaimDeadlines {
+                window_secs: 3600,
+                reason: "validator outage".to_string(),
+                after,
+                limit,
+            })
+            .blocking_wait()
+        };
+        let response = extend(&mut app, None, 0);
+        assert!(matches!(
+            response,
+            OperationResponse::Error {
+                code: ErrorCode::InvalidInput,
+                ..
+            }
+        ));
+
+        // Two pages of two cover the three accounts, each page logged on its own
+        let mut after = None;
+        let mut pages = Vec::new();
+        loop {
+            match extend(&mut app, after, 2) {
+                OperationResponse::ClaimDeadlinesExtended {
+                    visited,
+                    next_after,
+                    ..
+                } => {
+                    pages.push(visited);
+                    after = next_after;
+                }
+                other => panic!("Expected ClaimDeadlinesExtended, got {:?}", other),
+            }
+            if after.is_none() {
+                break;
+            }
+        }
+        assert_eq!(pages, vec![2, 1]);
+        assert_eq!(app.state.admin_log.count(), 2);
+
+        // A cursor that names no account is refused rather than restarting the sweep
+        let stranger = AccountOwner::Address20([9; 20]);
+        let response = extend(&mut app, Some(stranger), 2);
+        assert!(matches!(
+            response,
+            OperationResponse::Error {
+                code: ErrorCode::NotFound,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_game_phase_transitions() {
+        let mut app = create_app();
+        assert_eq!(app.player.game_phase, GamePhase::Idle);
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        assert_eq!(app.player.game_phase, GamePhase::Active);
+
+        // Claiming before a bingo leaves the game active
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        assert_eq!(app.player.game_phase, GamePhase::Active);
+
+        // Ending the session abandons the open game
+        app.execute_operation(Operation::EndSession).blocking_wait();
+        assert_eq!(app.player.game_phase, GamePhase::Cancelled);
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+    }
+
+    #[test]
+    fn test_card_difficulty_cap_redeals() {
+        let deal = |max_card_difficulty| {
+            let mut app = create_app();
+            govern(
+                &mut app,
+                Operation::UpdateConfig {
+                    config: GameConfig {
+                        max_card_difficulty,
+                        ..GameConfig::default()
+                    },
+                },
+            );
+            app.execute_operation(Operation::Deposit {
+                amount_atto: 10 * MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::StartSession {
+                expires_in_secs: 3600,
+                permissions: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+            app.player.current_card.clone().unwrap()
+        };
+
+        let uncapped = deal(0);
+        assert_eq!(uncapped.difficulty_score, probability::difficulty_score(&uncapped.numbers));
+
+        // An unreachable cap keeps the easiest of the deals, starting from the same one
+        let capped = deal(1);
+        assert_eq!(capped.difficulty_score, probability::difficulty_score(&capped.numbers));
+        assert!(capped.difficulty_score <= uncapped.difficulty_score);
+
+        // The committed seed replays whichever deal was kept
+        for card in [uncapped, capped] {
+            assert_eq!(rules::deal_numbers(card.deal_seed), card.numbers);
+            let commitment = DealCommitment {
+                game_id: card.id,
+                seed: card.deal_seed,
+            };
+            assert_eq!(card.deal_commitment, Some(CryptoHash::new(&commitment)));
+        }
+    }
+
+    #[test]
+    fn test_card_constraints_shape_the_deal() {
+        let mut app = create_app();
+        let update = |app: &mut FlashportContract, card_constraints| {
+            govern(
+                app,
+                Operation::UpdateConfig {
+                    config: GameConfig {
+                        card_constraints,
+                        ..GameConfig::default()
+                    },
+                },
+            )
+        };
+        let impossible = CardConstraints {
+            min_occurrences: 3,
+            ..CardConstraints::default()
+        };
+        assert!(matches!(
+            update(&mut app, impossible),
+            OperationResponse::Error { code: ErrorCode::InvalidInput, .. }
+        ));
+
+        let constraints = CardConstraints {
+            max_occurrences: 2,
+            banned_sums: vec![4, 24],
+            balanced_rows: true,
+            ..CardConstraints::default()
+        };
+        update(&mut app, constraints.clone());
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // The committed seed replays the constrained deal
+        let card = app.player.current_card.clone().unwrap();
+        assert!(!card.numbers.contains(&4) && !card.numbers.contains(&24));
+        assert_eq!(rules::deal_constrained(card.deal_seed, &constraints), card.numbers);
+
+        // A hedged game deals each card from its share of the allowed numbers
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: 2 * MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: true,
+            template_id: None,
+        })
+        .blocking_wait();
+        let card = app.player.current_card.clone().unwrap();
+        let hedge_card = app.player.hedge_card.clone().unwrap();
+        let shares = rules::hedge_shares(card.id, &constraints);
+        for (side, dealt) in [&card, &hedge_card].into_iter().enumerate() {
+            let replayed = rules::deal_hedge(dealt.deal_seed, dealt.id, side, &constraints);
+            assert_eq!(replayed, dealt.numbers);
+            assert!(dealt
+                .numbers
+                .iter()
+                .all(|n| *n == 0 || shares[side].contains(n)));
+        }
+    }
+
+    #[test]
+    fn test_payout_curve_config() {
+        let mut app = create_app();
+        let tier = |max_rolls, numerator, label: &str| PayoutTier {
+            max_rolls,
+            numerator,
+            denominator: 2,
+            label: label.to_string(),
+        };
+        let update = |app: &mut FlashportContract, payout_curve| {
+            govern(
+                app,
+                Operation::UpdateConfig {
+                    config: GameConfig {
+                        payout_curve,
+                        ..GameConfig::default()
+                    },
+                },
+            )
+        };
+        // Slower wins may not pay more, and the house must keep an edge
+        let rising = vec![tier(9, 2, "1x"), tier(u32::MAX, 4, "2x")];
+        let generous = vec![tier(u32::MAX, 10, "5x")];
+        for curve in [rising, generous] {
+            assert!(matches!(
+                update(&mut app, curve),
+                OperationResponse::Error { code: ErrorCode::InvalidInput, .. }
+            ));
+        }
+
+        update(&mut app, vec![tier(20, 4, "2x"), tier(u32::MAX, 1, "0.5x")]);
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // A single line on roll 21 falls past the first tier
+        let mut card = app.player.current_card.clone().unwrap();
+        for col in 0..5 {
+            card.mark(2, col);
+        }
+        card.rolls_count = 21;
+        app.player.current_card = Some(card);
+        app.player.game_phase = GamePhase::BingoPending;
+        match app.execute_operation(Operation::ClaimPrize).blocking_wait() {
+            OperationResponse::PrizeClaimed {
+                multiplier_display,
+                payout_amount,
+                ..
+            } => {
+                assert_eq!(multiplier_display, "0.5x");
+                assert_eq!(payout_amount, (MIN_BET / 2).to_string());
+            }
+            other => panic!("Expected PrizeClaimed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_diagnostics_ring_buffer() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // Off by default: rolling records nothing
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        assert_eq!(app.state.diagnostics.count(), 0);
+
+        app.runtime
+            .set_application_parameters(FlashportParameters { diagnostics: true });
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        let events = app.state.diagnostics.elements().blocking_wait().unwrap();
+        let kinds: Vec<_> = events.iter().map(|event| event.kind).collect();
+        assert_eq!(kinds, [DiagnosticKind::EntropyInputs, DiagnosticKind::FeeCharged]);
+        assert_eq!(events[1].event_id, 2);
+        assert_eq!(events[1].block_height, 100);
+        assert!(events[0].detail.starts_with("block_height=100 "));
+        assert!(events[1].detail.starts_with("fee=roll"));
+
+        // Only the newest DIAGNOSTICS_CAPACITY events are kept
+        for _ in 0..DIAGNOSTICS_CAPACITY {
+            app.trace(DiagnosticKind::TierChosen, None, String::new);
+        }
+        assert_eq!(app.state.diagnostics.count(), DIAGNOSTICS_CAPACITY);
+        let oldest = app.state.diagnostics.front().blocking_wait().unwrap().unwrap();
+        assert_eq!(oldest.event_id, 3);
+    }
+
+    #[test]
+    fn test_hedged_game_pays_first_card_to_bingo() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        let response = app
+            .execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET + 1,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: true,
+                template_id: None,
+            })
+            .blocking_wait();
+        let (card, hedge_card) = match response {
+            OperationResponse::GameStarted {
+                card,
+                hedge_card: Some(hedge_card),
+                ..
+            } => (*card, *hedge_card),
+            other => panic!("Expected hedged GameStarted, got {:?}", other),
+        };
+        assert_eq!(card.bet_amount_atto, (MIN_BET / 2 + 1).to_string());
+        assert_eq!(hedge_card.bet_amount_atto, (MIN_BET / 2).to_string());
+
+        // The cards share only 14, and each replays from its committed seed
+        let numbers = |card: &BingoCard| -> BTreeSet<u8> {
+            card.numbers.iter().copied().filter(|n| *n != 0).collect()
+        };
+        let shared: Vec<u8> = numbers(&card)
+            .intersection(&numbers(&hedge_card))
+            .copied()
+            .collect();
+        assert_eq!(shared, [14]);
+        let constraints = CardConstraints::default();
+        for (side, dealt) in [&card, &hedge_card].into_iter().enumerate() {
+            let replayed = rules::deal_hedge(dealt.deal_seed, dealt.id, side, &constraints);
+            assert_eq!(replayed, dealt.numbers);
+        }
+
+        // A hedge card one mark from bingo wins the next roll that marks it
+        let mut nearly_done = hedge_card.clone();
+        nearly_done.marked = [true; 25];
+        app.player.hedge_card = Some(nearly_done.clone());
+        let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::RollResult {
+                bingo_type: Some(_),
+                ..
+            }
+        ));
+        let current = app.player.current_card.clone().unwrap();
+        assert_eq!(current.numbers, hedge_card.numbers);
+        assert_eq!(current.bet_amount_atto, hedge_card.bet_amount_atto);
+        assert_eq!(current.rolls_count, 1);
+        assert_eq!(
+            app.player.hedge_card.as_ref().unwrap().numbers,
+            card.numbers
+        );
+        assert_eq!(app.player.game_phase, GamePhase::BingoPending);
+    }
+
+    #[test]
+    fn test_roll_result_near_miss() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 100 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // The winning sum was always announced as completing by the roll before
+        let mut announced = Vec::new();
+        for _ in 0..500 {
+            let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+            let OperationResponse::RollResult {
+                sum,
+                bingo_type,
+                cells_to_bingo,
+                completing_sums,
+                ..
+            } = response
+            else {
+                panic!("Expected RollResult, got {:?}", response);
+            };
+            let card = app.player.current_card.clone().unwrap();
+            assert_eq!(cells_to_bingo, rules::cells_to_bingo(&card.marked));
+            if bingo_type.is_some() {
+                assert!(announced.contains(&sum));
+                assert_eq!(cells_to_bingo, 0);
+                return;
+            }
+            for completing in &completing_sums {
+                assert!(card.numbers.contains(&completing.sum));
+                let ways = probability::sum_ways(completing.sum);
+                assert_eq!(completing.chance_bps, ways * 10_000 / probability::ROLL_OUTCOMES);
+            }
+            announced = completing_sums.iter().map(|completing| completing.sum).collect();
+        }
+        panic!("No bingo within 500 rolls");
+    }
+
+    #[test]
+    fn test_entropy_history() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+
+        // The deal and both rolls share the block's record
+        let record = app.state.entropy_block.get().clone().unwrap();
+        assert_eq!((record.block_height, record.draws), (100, 3));
+        assert_eq!(record.timestamp_micros, 1_000_000_000);
+        assert_eq!(app.state.entropy_history.count(), 0);
+
+        // The next block to draw archives it
+        app.runtime.set_block_height(BlockHeight(101));
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        let archived = app.state.entropy_history.front().blocking_wait().unwrap();
+        assert_eq!(archived, Some(record));
+        let latest = app.state.entropy_block.get().clone().unwrap();
+        assert_eq!((latest.block_height, latest.draws), (101, 1));
+    }
+
+    #[test]
+    fn test_operation_metrics() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        app.execute_operation(Operation::Withdraw {
+            amount: Amount::from_attos(100 * MIN_BET),
+        })
+        .blocking_wait();
+
+        let metrics = |app: &FlashportContract, name: &str| {
+            app.state
+                .operation_metrics
+                .get()
+                .iter()
+                .find(|metrics| metrics.operation == name)
+                .cloned()
+                .unwrap_or_default()
+        };
+        let new_game = metrics(&app, "NewGame");
+        assert_eq!((new_game.count, new_game.failures), (1, 0));
+        assert_eq!(u128::from(new_game.fees_charged), MIN_BET);
+        let rolls = metrics(&app, "RollAndMatch");
+        assert_eq!((rolls.count, rolls.failures), (2, 0));
+        assert_eq!(
+            new_game.fees_charged.saturating_add(rolls.fees_charged),
+            app.player.total_spent
+        );
+        let withdraw = metrics(&app, "Withdraw");
+        assert_eq!((withdraw.count, withdraw.failures), (1, 1));
+        assert_eq!(withdraw.fees_charged, Amount::ZERO);
+        assert_eq!(metrics(&app, "Deposit").count, 1);
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let mut app = create_app();
+        let code_of = |app: &mut FlashportContract, operation: Operation| {
+            let response = app.execute_operation(operation).blocking_wait();
+            match response {
+                OperationResponse::Error { code, .. } => code,
+                other => panic!("Expected Error, got {:?}", other),
+            }
+        };
+        assert_eq!(
+            code_of(&mut app, Operation::RollAndMatch),
+            ErrorCode::SessionRequired
+        );
+
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        let deposit = Operation::Deposit {
+            amount_atto: 0,
+            credit_to: None,
+        };
+        assert_eq!(code_of(&mut app, deposit), ErrorCode::InvalidInput);
+        assert_eq!(
+            code_of(&mut app, Operation::RollAndMatch),
+            ErrorCode::NoActiveGame
+        );
+        assert_eq!(code_of(&mut app, Operation::ClaimPrize), ErrorCode::NoPrize);
+        let withdraw = Operation::Withdraw {
+            amount: Amount::from_attos(MIN_BET),
+        };
+        assert_eq!(code_of(&mut app, withdraw), ErrorCode::InsufficientFunds);
+    }
+
+    #[test]
+    fn test_quick_play_without_session() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        let new_game = Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        };
+        // Only a chain owner may skip the session
+        let response = app.execute_operation(new_game.clone()).blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::Error {
+                code: ErrorCode::SessionRequired,
+                ..
+            }
+        ));
+
+        app.runtime
+            .set_chain_ownership(ChainOwnership::single(AccountOwner::Address20([1; 20])));
+        let response = app.execute_operation(new_game.clone()).blocking_wait();
+        assert!(matches!(response, OperationResponse::GameStarted { .. }));
+        let response = app
+            .execute_operation(Operation::RollAndMatch)
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::RollResult { .. }));
+        let response = app
+            .execute_operation(Operation::LockMultiplier)
+            .blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::Error {
+                code: ErrorCode::SessionRequired,
+                ..
+            }
+        ));
+
+        // A live session takes over, with its permissions
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: Some(SESSION_CAN_ROLL),
+        })
+        .blocking_wait();
+        let response = app.execute_operation(new_game).blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::Error {
+                code: ErrorCode::SessionPermission,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_each_owner_plays_from_their_own_account() {
+        let mut app = create_app();
+        let admin = AccountOwner::Address20([1; 20]);
+        let other = AccountOwner::Address20([2; 20]);
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        let start_session = Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        };
+        app.execute_operation(start_session.clone()).blocking_wait();
+        let new_game = Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        };
+        let response = app.execute_operation(new_game.clone()).blocking_wait();
+        assert!(matches!(response, OperationResponse::GameStarted { .. }));
+
+        // Another signer gets a session and account of their own, with no balance to bet
+        // and no card to roll on, even once the first owner's session has expired
+        app.runtime
+            .set_system_time(Timestamp::from(1_000_000_000 + 3_600_000_000));
+        app.runtime.set_authenticated_signer(other);
+        let response = app.execute_operation(start_session).blocking_wait();
+        assert!(matches!(response, OperationResponse::SessionStarted { .. }));
+        let response = app.execute_operation(new_game).blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::Error {
+                code: ErrorCode::InsufficientFunds,
+                ..
+            }
+        ));
+        let response = app
+            .execute_operation(Operation::RollAndMatch)
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        assert_eq!(app.player.player_balance, Amount::ZERO);
+        assert!(app.player.current_card.is_none());
+
+        // Ending that session leaves the first owner's game and balance alone
+        app.execute_operation(Operation::EndSession).blocking_wait();
+        let account = account_of(&mut app, admin);
+        assert_eq!(account.player_balance, Amount::from_attos(9 * MIN_BET));
+        assert_eq!(account.game_phase, GamePhase::Active);
+        assert!(account.active_session.is_some());
+    }
+
+    #[test]
+    fn test_fresh_sum_bonus() {
+        let mut payouts = Vec::new();
+        for bonus_bps in [0, 500] {
+            let mut app = create_app();
+            govern(
+                &mut app,
+                Operation::UpdateConfig {
+                    config: GameConfig {
+                        fresh_sum_bonus_bps: bonus_bps,
+                        ..GameConfig::default()
+                    },
+                },
+            );
+            app.execute_operation(Operation::Deposit {
+                amount_atto: 10 * MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::StartSession {
+                expires_in_secs: 3600,
+                permissions: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+
+            // Only the first draw of a sum counts, and only when it marks the card
+            let mut seen = Vec::new();
+            let mut fresh_marks = 0;
+            for _ in 0..8 {
+                let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+                if let OperationResponse::RollResult { sum, matched, .. } = response {
+                    if matched && !seen.contains(&sum) {
+                        fresh_marks += 1;
+                    }
+                    seen.push(sum);
+                }
+            }
+            let mut card = app.player.current_card.clone().unwrap();
+            assert_eq!(card.fresh_sum_marks, fresh_marks);
+
+            card.fresh_sum_marks = 4;
+            app.player.current_card = Some(card);
+            app.player.game_phase = GamePhase::BingoPending;
+            match app.execute_operation(Operation::ClaimPrize).blocking_wait() {
+                OperationResponse::PrizeClaimed { payout_amount, .. } => {
+                    payouts.push(payout_amount.parse::<u128>().unwrap())
+                }
+                other => panic!("Expected PrizeClaimed, got {:?}", other),
+            }
+        }
+        // 4 fresh marks at 5% of the bet each
+        assert_eq!(payouts[1] - payouts[0], MIN_BET / 5);
+    }
+
+    #[test]
+    fn test_roll_cadence_per_session() {
+        let mut app = create_app();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+
+        // Rolls fail without a game, but every request still counts
+        for secs in [0, 2, 4, 6] {
+            app.runtime
+                .set_system_time(Timestamp::from(1_000_000_000 + secs * 1_000_000));
+            app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        }
+
+        let cadence = app
+            .state
+            .session_cadence
+            .get(&AccountOwner::Address20([1; 20]))
+            .blocking_wait()
+            .unwrap()
+            .expect("Cadence should be recorded");
+        assert_eq!((cadence.requests, cadence.intervals), (4, 3));
+        assert_eq!(cadence.mean_interval_micros(), 2_000_000);
+        assert_eq!(cadence.jitter_bps(), 0);
+    }
+
+    #[test]
+    fn test_outbox_delivers_until_acknowledged() {
+        let mut app = create_app();
+        govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    cadence_min_interval_ms: 5_000,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+
+        // Rolling every second trips the cadence check once, not on every request
+        for secs in 0..15 {
+            app.runtime
+                .set_system_time(Timestamp::from(1_000_000_000 + secs * 1_000_000));
+            app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        }
+
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        app.player.game_phase = GamePhase::BingoPending;
+        app.execute_operation(Operation::ClaimPrize).blocking_wait();
+
+        let entries = app.state.outbox.elements().blocking_wait().unwrap();
+        let kinds: Vec<_> = entries.iter().map(|entry| entry.kind).collect();
+        assert_eq!(kinds, [OutboxKind::SuspiciousActivity, OutboxKind::BigWin]);
+        assert_eq!(entries[0].reference_id, 1);
+        assert_eq!(entries[1].id, 2);
+
+        // Only the admin may acknowledge, and only up to the given id
+        app.runtime
+            .set_authenticated_signer(AccountOwner::Address20([2; 20]));
+        let response = app
+            .execute_operation(Operation::AckOutbox { up_to_id: 1 })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        app.runtime
+            .set_authenticated_signer(AccountOwner::Address20([1; 20]));
+        match app
+            .execute_operation(Operation::AckOutbox { up_to_id: 1 })
+            .blocking_wait()
+        {
+            OperationResponse::OutboxAcked {
+                removed, pending, ..
+            } => assert_eq!((removed, pending), (1, 1)),
+            other => panic!("Expected OutboxAcked, got {:?}", other),
+        }
+        let front = app.state.outbox.front().blocking_wait().unwrap();
+        assert_eq!(front.map(|entry| entry.kind), Some(OutboxKind::BigWin));
+    }
+
+    #[test]
+    fn test_partner_notified_through_outbox() {
+        let mut app = create_app();
+        let partner = ApplicationId::new(CryptoHash::from([6u64; 4]));
+        app.state.reward_partners.set(vec![partner]);
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        app.player.game_phase = GamePhase::BingoPending;
+
+        // The claim only queues the notification; no partner is called yet
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        assert!(matches!(response, OperationResponse::PrizeClaimed { .. }));
+        let entries = app.state.outbox.elements().blocking_wait().unwrap();
+        let kinds: Vec<_> = entries.iter().map(|entry| entry.kind).collect();
+        assert_eq!(kinds, [OutboxKind::BigWin, OutboxKind::PartnerNotification]);
+        let (entry_id, payout) = (entries[1].id, entries[1].amount);
+
+        let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let recorded = calls.clone();
+        app.runtime
+            .set_call_application_handler(move |authenticated, application, bytes| {
+                assert!(authenticated);
+                let notification: WinNotification = bcs::from_bytes(&bytes).unwrap();
+                recorded.borrow_mut().push((application, notification));
+                bcs::to_bytes(&()).unwrap()
+            });
+        app.runtime
+            .set_authenticated_signer(AccountOwner::Address20([2; 20]));
+        let response = app
+            .execute_operation(Operation::DeliverPartnerNotification { entry_id })
+            .blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::PartnerNotified { game_id: 1, .. }
+        ));
+        let calls = calls.borrow();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, partner);
+        assert_eq!(calls[0].1.game_id, 1);
+        assert_eq!(calls[0].1.payout_atto, u128::from(payout));
+
+        // A notice is delivered once
+        let response = app
+            .execute_operation(Operation::DeliverPartnerNotification { entry_id })
+            .blocking_wait();
+        match response {
+            OperationResponse::Error { code, .. } => assert_eq!(code, ErrorCode::NotFound),
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_prize_claimed_reports_tier_statistics() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+
+        let mut claim_after = |rolls_count| {
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+            let mut card = app.player.current_card.clone().unwrap();
+            card.rolls_count = rolls_count;
+            app.player.current_card = Some(card);
+            app.player.game_phase = GamePhase::BingoPending;
+            match app.execute_operation(Operation::ClaimPrize).blocking_wait() {
+                OperationResponse::PrizeClaimed {
+                    tier,
+                    tier_win_chance_bps,
+                    rolls_percentile_bps,
+                    ..
+                } => (tier, tier_win_chance_bps, rolls_percentile_bps),
+                other => panic!("Expected PrizeClaimed, got {:?}", other),
+            }
+        };
+
+        assert_eq!(claim_after(12), ("EPIC".to_string(), TIER_WIN_CHANCE_BPS[1], None));
+        // Slower than the only earlier win, then level with one and faster than one
+        assert_eq!(claim_after(30).2, Some(0));
+        assert_eq!(claim_after(12).2, Some(7_500));
+        assert_eq!(claim_after(50).0, "MINIMAL");
+
+        assert_eq!(app.state.win_archive.count(), 4);
+        let counts = app.state.rolls_to_bingo_counts.get();
+        assert_eq!((counts[12], counts[30], counts[50]), (2, 1, 1));
+    }
+
+    #[test]
+    fn test_win_archive_indexes() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        for rolls_count in [12, 50] {
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+            let mut card = app.player.current_card.clone().unwrap();
+            card.rolls_count = rolls_count;
+            card.marked[..5].fill(true);
+            app.player.current_card = Some(card);
+            app.player.game_phase = GamePhase::BingoPending;
+            app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        }
+
+        // Only the quick win paid back at least its bet
+        let net_wins = app.state.archive_net_wins.read(0..2).blocking_wait().unwrap();
+        assert_eq!(net_wins, vec![0]);
+        let by_mode = app.state.archive_by_mode.get(&GameMode::Classic).blocking_wait();
+        assert_eq!(by_mode.unwrap(), Some(vec![0, 1]));
+        let bucket = archive_bet_bucket(MIN_BET);
+        let by_bet = app.state.archive_by_bet_bucket.get(&bucket).blocking_wait();
+        assert_eq!(by_bet.unwrap(), Some(vec![0, 1]));
+        let day = 1_000_000_000 / ARCHIVE_DAY_MICROS;
+        let by_day = app.state.archive_by_day.get(&day).blocking_wait();
+        assert_eq!(by_day.unwrap(), Some(vec![0, 1]));
+        let by_type = app.state.archive_by_bingo_type.get(&BingoType::Row0);
+        assert_eq!(by_type.blocking_wait().unwrap(), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_dispute_freezes_winnings_until_ruled() {
+        let mut app = create_app();
+        govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    dispute_window_secs: 600,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        let win_game = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+            let mut card = app.player.current_card.clone().unwrap();
+            card.rolls_count = 12;
+            app.player.current_card = Some(card);
+            app.player.game_phase = GamePhase::BingoPending;
+            app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        };
+        let dispute = |app: &mut FlashportContract, game_id, reason: &str| {
+            app.execute_operation(Operation::DisputeGame {
+                game_id,
+                reason: reason.to_string(),
+            })
+            .blocking_wait()
+        };
+        let balance = |app: &FlashportContract| u128::from(app.player.player_balance);
+
+        // The 5x win takes the balance from 9 to 14 LINERA
+        win_game(&mut app);
+        assert!(matches!(dispute(&mut app, 1, " "), OperationResponse::Error { .. }));
+        match dispute(&mut app, 1, "dice looked off") {
+            OperationResponse::DisputeFiled { dispute } => {
+                assert_eq!(u128::from(dispute.frozen), 5 * MIN_BET);
+            }
+            other => panic!("Expected DisputeFiled, got {:?}", other),
+        }
+        assert_eq!(balance(&app), 9 * MIN_BET);
+        assert!(matches!(dispute(&mut app, 1, "again"), OperationResponse::Error { .. }));
+
+        let resolve = |app: &mut FlashportContract, outcome| {
+            app.execute_operation(Operation::ResolveDispute {
+                dispute_id: 1,
+                outcome,
+                award_atto: 0,
+                note: "replayed the dice stream".to_string(),
+            })
+            .blocking_wait()
+        };
+        match resolve(&mut app, DisputeOutcome::Voided) {
+            OperationResponse::DisputeResolved { dispute, action } => {
+                assert_eq!(dispute.resolution.unwrap().action_id, action.action_id);
+            }
+            other => panic!("Expected DisputeResolved, got {:?}", other),
+        }
+        assert!(matches!(
+            resolve(&mut app, DisputeOutcome::Dismissed),
+            OperationResponse::Error { .. }
+        ));
+        assert_eq!(balance(&app), 9 * MIN_BET);
+        assert_eq!(app.player.frozen_balance, Amount::ZERO);
+        assert_eq!(app.player.total_won, Amount::ZERO);
+
+        // Past the window a settled game can no longer be disputed
+        win_game(&mut app);
+        app.runtime
+            .set_system_time(Timestamp::from(1_000_000_000 + 601 * 1_000_000));
+        assert!(matches!(dispute(&mut app, 2, "too late"), OperationResponse::Error { .. }));
+    }
+
+    #[test]
+    fn test_dispute_lost_game_holds_house_take() {
+        let mut app = create_app();
+        // Start from an empty house
+        app.state.epoch.get_mut().bonus_pool = Amount::ZERO;
+        govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    dispute_window_secs: 600,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        let new_game = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+        };
+        let house = |app: &FlashportContract| u128::from(app.state.epoch.get().house_contributions);
+
+        // Starting game 2 forfeits game 1's bet to the house (less the raffle's share)
+        new_game(&mut app);
+        let before = house(&app);
+        new_game(&mut app);
+        let take = house(&app);
+        assert!(take > before);
+        match app
+            .execute_operation(Operation::DisputeGame {
+                game_id: 1,
+                reason: "my game was cut short".to_string(),
+            })
+            .blocking_wait()
+        {
+            OperationResponse::DisputeFiled { dispute } => {
+                assert_eq!(dispute.frozen, Amount::ZERO);
+                assert_eq!(u128::from(dispute.house_held), take);
+            }
+            other => panic!("Expected DisputeFiled, got {:?}", other),
+        }
+        assert_eq!(house(&app), 0);
+
+        // Awards come out of the treasury, so they cannot exceed it
+        let resolve = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::ResolveDispute {
+                dispute_id: 1,
+                outcome: DisputeOutcome::Upheld,
+                award_atto: MIN_BET,
+                note: "refund plus goodwill".to_string(),
+            })
+            .blocking_wait()
+        };
+        assert!(matches!(resolve(&mut app), OperationResponse::Error { .. }));
+        app.state.epoch.get_mut().bonus_pool = Amount::from_attos(2 * MIN_BET);
+        assert!(matches!(
+            resolve(&mut app),
+            OperationResponse::DisputeResolved { .. }
+        ));
+        assert_eq!(
+            u128::from(app.player.player_balance),
+            8 * MIN_BET + take + MIN_BET
+        );
+        assert_eq!(u128::from(app.state.epoch.get().bonus_pool), MIN_BET);
+        assert_eq!(house(&app), 0);
+    }
+
+    #[test]
+    fn test_buy_mark_limits_and_archive() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        // 30 rolls in: the 1.2x tier prices the marks
+        let mut card = app.player.current_card.clone().unwrap();
+        card.rolls_count = 30;
+        card.marked = [false; 25];
+        card.marked[12] = true;
+        app.player.current_card = Some(card);
+        let mut buy_mark = |row, col| {
+            app.execute_operation(Operation::BuyMark { row, col }).blocking_wait()
+        };
+
+        assert!(matches!(buy_mark(2, 2), OperationResponse::Error { .. }));
+        assert!(matches!(buy_mark(5, 0), OperationResponse::Error { .. }));
+        match buy_mark(0, 0) {
+            OperationResponse::MarkPurchased {
+                fee_paid,
+                cells_to_bingo,
+                purchases_left,
+                ..
+            } => {
+                assert_eq!(fee_paid, (6 * MIN_BET / 10).to_string());
+                assert_eq!((cells_to_bingo, purchases_left), (4, 1));
+            }
+            other => panic!("Expected MarkPurchased, got {:?}", other),
+        }
+        // The diagonal now needs three cells, so the next mark costs a step more
+        match buy_mark(1, 1) {
+            OperationResponse::MarkPurchased { fee_paid, .. } => {
+                assert_eq!(fee_paid, (9 * MIN_BET / 10).to_string());
+            }
+            other => panic!("Expected MarkPurchased, got {:?}", other),
+        }
+        assert!(matches!(buy_mark(3, 3), OperationResponse::Error { .. }));
+        assert_eq!(
+            u128::from(app.player.player_balance),
+            9 * MIN_BET - 15 * MIN_BET / 10
+        );
+
+        app.player.game_phase = GamePhase::BingoPending;
+        app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        let win = app.state.win_archive.get(0).blocking_wait().unwrap().unwrap();
+        assert_eq!(win.purchased_marks, vec![0, 6]);
+        assert_eq!(win.mark_fees_atto, (15 * MIN_BET / 10).to_string());
+    }
+
+    #[test]
+    fn test_lock_multiplier_keeps_tier() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        let set_rolls = |app: &mut FlashportContract, rolls_count| {
+            let mut card = app.player.current_card.clone().unwrap();
+            card.rolls_count = rolls_count;
+            app.player.current_card = Some(card);
+        };
+
+        // Locking the 10x tier on roll 9 costs 10% of its payout
+        set_rolls(&mut app, 9);
+        match app.execute_operation(Operation::LockMultiplier).blocking_wait() {
+            OperationResponse::MultiplierLocked {
+                lock,
+                multiplier_display,
+            } => {
+                assert_eq!((lock.expires_after_rolls, lock.fee_atto), (14, MIN_BET.to_string()));
+                assert_eq!(multiplier_display, "10x");
+            }
+            other => panic!("Expected MultiplierLocked, got {:?}", other),
+        }
+        let response = app.execute_operation(Operation::LockMultiplier).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+
+        // A bingo on roll 14 would pay 5x, but the lock still holds 10x
+        set_rolls(&mut app, 14);
+        app.player.game_phase = GamePhase::BingoPending;
+        match app.execute_operation(Operation::ClaimPrize).blocking_wait() {
+            OperationResponse::PrizeClaimed { payout_amount, .. } => {
+                assert_eq!(payout_amount, (10 * MIN_BET).to_string());
+            }
+            other => panic!("Expected PrizeClaimed, got {:?}", other),
+        }
+        let win = app.state.win_archive.get(0).blocking_wait().unwrap().unwrap();
+        assert!(win.multiplier_lock.unwrap().applied);
+    }
+
+    #[test]
+    fn test_attest_game() {
+        let mut app = create_app();
+        let chain: ChainId = "02".repeat(32).parse().unwrap();
+        app.runtime.set_chain_id(chain);
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        let attest = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::AttestGame { game_id: 1 })
+                .blocking_wait()
+        };
+        assert!(matches!(attest(&mut app), OperationResponse::Error { .. }));
+
+        let mut card = app.player.current_card.clone().unwrap();
+        card.rolls_count = 12;
+        for col in 0..5 {
+            card.mark(0, col);
+        }
+        app.player.current_card = Some(card);
+        app.player.game_phase = GamePhase::BingoPending;
+        app.execute_operation(Operation::ClaimPrize).blocking_wait();
+
+        let OperationResponse::GameAttested { attestation } = attest(&mut app) else {
+            panic!("Expected GameAttested");
+        };
+        let outcome = &attestation.outcome;
+        assert_eq!((outcome.chain_id, outcome.game_id), (chain, 1));
+        assert_eq!(outcome.bingo_type, Some(BingoType::Row0));
+        assert_eq!(outcome.payout, Amount::from_attos(5 * MIN_BET));
+        assert_eq!(attestation.hash, CryptoHash::new(outcome));
+        assert!(matches!(
+            attest(&mut app),
+            OperationResponse::GameAttested { attestation: again } if again == attestation
+        ));
+    }
+
+    #[test]
+    fn test_diverging_marks_are_repaired_or_refused() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // A drawn number left unmarked is restored before the roll
+        let card = app.player.current_card.clone().unwrap();
+        app.player.drawn_numbers = vec![card.numbers[0]];
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        let card = app.player.current_card.clone().unwrap();
+        assert!(card.marked[0]);
+        assert_eq!(card.rolls_count, 1);
+
+        // A mark no drawn number explains suspends rolling without charging the fee
+        let drawn = app.player.drawn_numbers.clone();
+        let stray = (0..25)
+            .find(|i| *i != 12 && !drawn.contains(&card.numbers[*i]))
+            .unwrap();
+        let mut corrupted = card.clone();
+        corrupted.marked[stray] = true;
+        app.player.current_card = Some(corrupted);
+        let balance = app.player.player_balance;
+        let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        assert_eq!(app.player.player_balance, balance);
+
+        let entries = app.state.outbox.elements().blocking_wait().unwrap();
+        let kinds: Vec<_> = entries.iter().map(|entry| entry.kind).collect();
+        assert_eq!(kinds, [OutboxKind::MarkDivergence, OutboxKind::MarkDivergence]);
+    }
+
+    #[test]
+    fn test_congestion_gap_extends_deadlines() {
+        let mut app = create_app();
+        govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    claim_window_secs: 200,
+                    congestion_pause_max_secs: 120,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // A player rolling every second, then a bingo with 200s left to claim
+        let mut session = app.player.active_session.clone().unwrap();
+        session.cadence = SessionCadence {
+            requests: CADENCE_MIN_SAMPLES + 1,
+            intervals: CADENCE_MIN_SAMPLES,
+            interval_sum_micros: CADENCE_MIN_SAMPLES * 1_000_000,
+            ..SessionCadence::default()
+        };
+        app.player.active_session = Some(session);
+        let mut card = app.player.current_card.clone().unwrap();
+        card.claim_deadline_micros = 1_200_000_000;
+        app.player.current_card = Some(card);
+        app.player.game_phase = GamePhase::BingoPending;
+
+        // A 300s stall is congestion; 120s of it (the cap) is credited back
+        app.runtime.set_system_time(Timestamp::from(1_300_000_000));
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        assert!(matches!(response, OperationResponse::PrizeClaimed { .. }));
+        let card = app.player.current_card.clone().unwrap();
+        assert_eq!(card.claim_deadline_micros, 1_320_000_000);
+        let session = app.player.active_session.clone().unwrap();
+        assert_eq!(session.paused_micros, 120_000_000);
+        assert_eq!(session.expires_at_micros, 4_720_000_000);
+        assert_eq!(session.last_operation_micros, 1_300_000_000);
+    }
+
+    #[test]
+    fn test_airdrop_bonus() {
+        let mut app = create_app();
+        let (alice, bob) = (AccountOwner::Address20([2; 20]), AccountOwner::Address20([3; 20]));
+        let airdrop = |app: &mut FlashportContract, recipients| {
+            app.execute_operation(Operation::AirdropBonus { recipients })
+                .blocking_wait()
+        };
+        app.state.epoch.get_mut().bonus_pool = Amount::from_attos(3 * MIN_BET);
+
+        // Over budget, duplicated or oversized batches credit nobody
+        let over_budget = vec![(alice, 2 * MIN_BET), (bob, 2 * MIN_BET)];
+        assert!(matches!(airdrop(&mut app, over_budget), OperationResponse::Error { .. }));
+        let duplicated = vec![(alice, MIN_BET), (alice, MIN_BET)];
+        assert!(matches!(airdrop(&mut app, duplicated), OperationResponse::Error { .. }));
+        let oversized = vec![(alice, 1); MAX_AIRDROP_RECIPIENTS + 1];
+        assert!(matches!(airdrop(&mut app, oversized), OperationResponse::Error { .. }));
+        assert!(app.state.bonus_balances.get(&alice).blocking_wait().unwrap().is_none());
+
+        let response = airdrop(&mut app, vec![(alice, 2 * MIN_BET), (bob, MIN_BET)]);
+        let OperationResponse::BonusAirdropped { batch } = response else {
+            panic!("Expected BonusAirdropped, got {:?}", response);
+        };
+        assert_eq!((batch.batch_id, batch.total), (1, Amount::from_attos(3 * MIN_BET)));
+        let bonus = |app: &FlashportContract, owner: AccountOwner| {
+            app.state.bonus_balances.get(&owner).blocking_wait().unwrap()
+        };
+        assert_eq!(bonus(&app, alice), Some(Amount::from_attos(2 * MIN_BET)));
+        assert_eq!(bonus(&app, bob), Some(Amount::from_attos(MIN_BET)));
+        assert_eq!(app.state.epoch.get().bonus_pool, Amount::ZERO);
+        assert_eq!(app.state.airdrops.count(), 1);
+
+        // Admin only
+        app.state.epoch.get_mut().bonus_pool = Amount::from_attos(MIN_BET);
+        app.runtime.set_authenticated_signer(alice);
+        assert!(matches!(airdrop(&mut app, vec![(alice, 1)]), OperationResponse::Error { .. }));
+    }
+
+    #[test]
+    fn test_pause_and_treasury() {
+        let mut app = create_app();
+        let set_paused = |app: &mut FlashportContract, paused| {
+            app.execute_operation(Operation::SetPaused {
+                paused,
+                reason: "maintenance".to_string(),
+            })
+            .blocking_wait()
+        };
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+
+        let response = set_paused(&mut app, true);
+        let OperationResponse::PauseChanged { paused, action } = response else {
+            panic!("Expected PauseChanged, got {:?}", response);
+        };
+        assert!(paused);
+        assert_eq!(action.kind, AdminActionKind::Paused);
+        assert!(matches!(
+            set_paused(&mut app, true),
+            OperationResponse::Error { code: ErrorCode::AlreadyDone, .. }
+        ));
+
+        // No new games while paused, but funds can still leave
+        let response = app
+            .execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { code: ErrorCode::Paused, .. }));
+        let response = app
+            .execute_operation(Operation::Withdraw {
+                amount: Amount::from_attos(MIN_BET),
+            })
+            .blocking_wait();
+        assert!(!matches!(response, OperationResponse::Error { .. }));
+        assert!(matches!(set_paused(&mut app, false), OperationResponse::PauseChanged { .. }));
+        assert!(!*app.state.paused.get());
+
+        // Treasury withdrawals come out of the bonus pool only
+        app.state.epoch.get_mut().bonus_pool = Amount::from_attos(3 * MIN_BET);
+        let withdraw = |app: &mut FlashportContract, amount_atto| {
+            govern(
+                app,
+                Operation::WithdrawTreasury {
+                    amount: Amount::from_attos(amount_atto),
+                    reason: "operations".to_string(),
+                },
+            )
+        };
+        assert!(matches!(
+            withdraw(&mut app, 0),
+            OperationResponse::Error { code: ErrorCode::InvalidInput, .. }
+        ));
+        assert!(matches!(
+            withdraw(&mut app, 4 * MIN_BET),
+            OperationResponse::Error { code: ErrorCode::InsufficientFunds, .. }
+        ));
+        let response = withdraw(&mut app, 2 * MIN_BET);
+        let OperationResponse::TreasuryWithdrawn { bonus_pool, action, .. } = response else {
+            panic!("Expected TreasuryWithdrawn, got {:?}", response);
+        };
+        assert_eq!(bonus_pool, Amount::from_attos(MIN_BET));
+        assert_eq!(action.kind, AdminActionKind::TreasuryWithdrawn);
+        // Both withdrawal proposals were logged as well
+        assert_eq!(app.state.admin_log.count(), 5);
+
+        // Admin only
+        app.runtime.set_authenticated_signer(AccountOwner::Address20([2; 20]));
+        assert!(matches!(
+            set_paused(&mut app, true),
+            OperationResponse::Error { code: ErrorCode::NotPermitted, .. }
+        ));
+    }
+
+    #[test]
+    fn test_withdrawal_hold() {
+        let mut app = create_app();
+        let owner = AccountOwner::Address20([1; 20]);
+        let place_hold = |app: &mut FlashportContract, expires_in_secs| {
+            app.execute_operation(Operation::PlaceWithdrawalHold {
+                owner,
+                reason: HoldReason::AmlReview,
+                expires_in_secs,
+                note: "source of funds".to_string(),
+            })
+            .blocking_wait()
+        };
+        let withdraw = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::Withdraw {
+                amount: Amount::from_attos(MIN_BET),
+            })
+            .blocking_wait()
+        };
+        let release = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::ReleaseWithdrawalHold {
+                owner,
+                note: "cleared".to_string(),
+            })
+            .blocking_wait()
+        };
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+
+        let response = place_hold(&mut app, 0);
+        let OperationResponse::WithdrawalHoldPlaced { hold, action } = response else {
+            panic!("Expected WithdrawalHoldPlaced, got {:?}", response);
+        };
+        assert_eq!((hold.reason, hold.expires_at_micros), (HoldReason::AmlReview, 0));
+        assert_eq!(hold.action_id, action.action_id);
+        assert_eq!(action.kind, AdminActionKind::WithdrawalHoldPlaced);
+
+        // Every way of taking funds out is refused, with a typed error
+        let held = |response: OperationResponse| {
+            matches!(response, OperationResponse::Error { code: ErrorCode::WithdrawalHeld, .. })
+        };
+        assert!(held(withdraw(&mut app)));
+        assert!(held(app.execute_operation(Operation::WithdrawAll).blocking_wait()));
+        assert_eq!(app.player.player_balance, Amount::from_attos(10 * MIN_BET));
+
+        let response = release(&mut app);
+        let OperationResponse::WithdrawalHoldReleased { action, .. } = response else {
+            panic!("Expected WithdrawalHoldReleased, got {:?}", response);
+        };
+        assert_eq!(action.kind, AdminActionKind::WithdrawalHoldReleased);
+        assert!(!held(withdraw(&mut app)));
+        assert!(matches!(
+            release(&mut app),
+            OperationResponse::Error { code: ErrorCode::NotFound, .. }
+        ));
+
+        // A hold with an expiry lapses on its own and is cleared when next checked
+        place_hold(&mut app, 60);
+        assert!(held(withdraw(&mut app)));
+        app.runtime.set_system_time(Timestamp::from(1_000_000_000 + 60_000_000));
+        assert!(!held(withdraw(&mut app)));
+        assert!(app.state.withdrawal_holds.get(&owner).blocking_wait().unwrap().is_none());
+        assert_eq!(app.state.admin_log.count(), 3);
+
+        // Admin only
+        app.runtime.set_authenticated_signer(AccountOwner::Address20([2; 20]));
+        assert!(matches!(
+            place_hold(&mut app, 0),
+            OperationResponse::Error { code: ErrorCode::NotPermitted, .. }
+        ));
+    }
+
+    #[test]
+    fn test_governance_timelock() {
+        let mut app = create_app();
+        assert_eq!(app.state.config.get().governance_timelock_secs, 0);
+
+        // A config change is only proposed, and waits at least the minimum timelock
+        let config = GameConfig {
+            stale_game_warning_secs: 42,
+            ..app.state.config.get().clone()
+        };
+        let response = app
+            .execute_operation(Operation::UpdateConfig { config })
+            .blocking_wait();
+        let OperationResponse::ChangeProposed { proposal, action } = response else {
+            panic!("Expected ChangeProposed, got {:?}", response);
+        };
+        assert_eq!(proposal.proposal_id, 1);
+        assert_eq!(proposal.status, ProposalStatus::Pending);
+        let delay_micros = u64::from(MIN_GOVERNANCE_TIMELOCK_SECS) * 1_000_000;
+        assert_eq!(proposal.executable_at_micros, 1_000_000_000 + delay_micros);
+        assert_eq!(action.kind, AdminActionKind::ChangeProposed);
+        assert_ne!(app.state.config.get().stale_game_warning_secs, 42);
+
+        // So is a treasury withdrawal, which leaves the bonus pool alone until executed
+        app.state.epoch.get_mut().bonus_pool = Amount::from_attos(3 * MIN_BET);
+        let response = app
+            .execute_operation(Operation::WithdrawTreasury {
+                amount: Amount::from_attos(2 * MIN_BET),
+                reason: "operations".to_string(),
+            })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::ChangeProposed { .. }));
+        assert_eq!(
+            app.state.epoch.get().bonus_pool,
+            Amount::from_attos(3 * MIN_BET)
+        );
+
+        let execute = |app: &mut FlashportContract, proposal_id| {
+            app.execute_operation(Operation::ExecuteProposal { proposal_id })
+                .blocking_wait()
+        };
+        assert!(matches!(
+            execute(&mut app, 1),
+            OperationResponse::Error { code: ErrorCode::TooEarly, .. }
+        ));
+        assert!(matches!(
+            execute(&mut app, 3),
+            OperationResponse::Error { code: ErrorCode::NotFound, .. }
+        ));
+
+        app.runtime
+            .set_system_time(Timestamp::from(1_000_000_000 + delay_micros));
+        let response = execute(&mut app, 1);
+        let OperationResponse::ProposalExecuted { proposal, outcome } = response else {
+            panic!("Expected ProposalExecuted, got {:?}", response);
+        };
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+        assert!(matches!(*outcome, OperationResponse::ConfigUpdated { .. }));
+        assert_eq!(app.state.config.get().stale_game_warning_secs, 42);
+        assert!(matches!(
+            execute(&mut app, 1),
+            OperationResponse::Error { code: ErrorCode::AlreadyDone, .. }
+        ));
+
+        // A cancelled withdrawal never pays out
+        let response = app
+            .execute_operation(Operation::CancelProposal {
+                proposal_id: 2,
+                reason: "not needed".to_string(),
+            })
+            .blocking_wait();
+        let OperationResponse::ProposalCancelled { proposal, .. } = response else {
+            panic!("Expected ProposalCancelled, got {:?}", response);
+        };
+        assert_eq!(proposal.status, ProposalStatus::Cancelled);
+        assert!(matches!(
+            execute(&mut app, 2),
+            OperationResponse::Error { code: ErrorCode::AlreadyDone, .. }
+        ));
+        assert_eq!(
+            app.state.epoch.get().bonus_pool,
+            Amount::from_attos(3 * MIN_BET)
+        );
+
+        // Other rule changes are queued too
+        let response = app
+            .execute_operation(Operation::SetExperiment { experiment: None })
+            .blocking_wait();
+        let OperationResponse::ChangeProposed { proposal, .. } = response else {
+            panic!("Expected ChangeProposed, got {:?}", response);
+        };
+        assert_eq!(proposal.kind, ProposalKind::Experiment);
+    }
+
+    #[test]
+    fn test_onboarding_progress() {
+        let mut app = create_app();
+        let owner = AccountOwner::Address20([1; 20]);
+        let progress = |app: &mut FlashportContract| account_of(app, owner).onboarding;
+        assert_eq!(
+            progress(&mut app).pending_step(),
+            Some(OnboardingStep::Deposited)
+        );
+
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 50 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        assert_eq!(progress(&mut app).deposited_at_micros, Some(1_000_000_000));
+        assert_eq!(
+            progress(&mut app).pending_step(),
+            Some(OnboardingStep::PlayedGame)
+        );
+
+        // Steps can be reached out of order; a repeat keeps the first time
+        app.runtime.set_system_time(Timestamp::from(2_000_000_000));
+        app.execute_operation(Operation::Withdraw {
+            amount: Amount::from_attos(MIN_BET),
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        let onboarding = progress(&mut app);
+        assert_eq!(onboarding.deposited_at_micros, Some(1_000_000_000));
+        assert_eq!(onboarding.withdrew_at_micros, Some(2_000_000_000));
+        assert_eq!(onboarding.played_game_at_micros, Some(2_000_000_000));
+        assert_eq!(onboarding.pending_step(), Some(OnboardingStep::Won));
+
+        for _ in 0..500 {
+            if app.player.game_phase != GamePhase::Active {
+                break;
+            }
+            app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        }
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        assert!(matches!(response, OperationResponse::PrizeClaimed { .. }));
+        assert_eq!(progress(&mut app).pending_step(), None);
+    }
+
+    #[test]
+    fn test_roll_results_hash() {
+        let mut app = create_app();
+        assert_eq!(*app.state.roll_results_hash.get(), None);
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        // Too few rolls to complete a line
+        for _ in 0..3 {
+            app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        }
+
+        // Folding the full roll history reproduces the head; a doctored dump does not
+        let mut rolls = app.player.roll_history.clone();
+        assert_eq!(rolls.len(), 3);
+        let head = *app.state.roll_results_hash.get();
+        assert!(head.is_some());
+        assert_eq!(chain_roll_results(None, &rolls), head);
+        let first = chain_roll_results(None, &rolls[..1]);
+        assert_eq!(chain_roll_results(first, &rolls[1..]), head);
+        rolls[1].matched = !rolls[1].matched;
+        assert_ne!(chain_roll_results(None, &rolls), head);
+    }
+
+    #[test]
+    fn test_points_per_mark_game() {
+        let mut app = create_app();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::PointsPerMark,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        let response = app
+            .execute_operation(Operation::BuyMark { row: 0, col: 0 })
+            .blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::Error { code: ErrorCode::Unsupported, .. }
+        ));
+
+        // Lines don't end the game: it plays exactly POINTS_GAME_ROLLS rolls
+        let mut total_points = 0;
+        let mut settlement = None;
+        for roll in 1..=POINTS_GAME_ROLLS {
+            let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+            let OperationResponse::RollResult {
+                bingo_type,
+                game_over,
+                points: Some(points),
+                ..
+            } = response
+            else {
+                panic!("Expected a scored RollResult, got {:?}", response);
+            };
+            assert_eq!(bingo_type, None);
+            assert_eq!(game_over, roll == POINTS_GAME_ROLLS);
+            assert_eq!(points.rolls_left, POINTS_GAME_ROLLS - roll);
+            assert_eq!(points.total_points, total_points + points.points_scored);
+            total_points = points.total_points;
+            settlement = points.settlement;
+        }
+
+        // The final roll settled the game on its points
+        let settlement = settlement.expect("The final roll settles the game");
+        let card = app.player.current_card.clone().unwrap();
+        assert_eq!(
+            total_points,
+            rules::cells_points(&card.numbers, card.marked_bitmask())
+        );
+        let payout = rules::points_payout(MIN_BET, total_points).unwrap();
+        assert_eq!(settlement.payout_atto, payout.to_string());
+        let fees = POINTS_GAME_ROLLS as u128 * ROLL_COST;
+        assert_eq!(
+            u128::from(app.player.player_balance),
+            9 * MIN_BET - fees + payout
+        );
+        assert_eq!(app.player.current_prize_pool, Amount::ZERO);
+        assert_eq!(app.player.game_phase, GamePhase::Claimed);
+        assert!(app.player.current_card.as_ref().unwrap().prize_claimed);
+        let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::Error { code: ErrorCode::GameOver, .. }
+        ));
+    }
+
+    #[test]
+    fn test_read_model_projections() {
+        let mut app = create_app();
+        let owner = AccountOwner::Address20([1; 20]);
+
+        // The RTP window keeps the last ROLLING_RTP_EPOCHS closed epochs
+        let period_micros = EPOCH_PERIOD_SECS * 1_000_000;
+        for epoch in 1..=ROLLING_RTP_EPOCHS as u64 + 1 {
+            let pool = app.state.epoch.get_mut();
+            pool.wagered = Amount::from_attos(epoch as u128 * MIN_BET);
+            pool.paid_out = Amount::from_attos(epoch as u128 * MIN_BET / 2);
+            app.runtime
+                .set_system_time(Timestamp::from(1_000_000_000 + epoch * period_micros));
+            app.execute_operation(Operation::GetVersion).blocking_wait();
+        }
+        let rtp = app.state.rolling_rtp.get().clone();
+        assert_eq!(rtp.epochs.len(), ROLLING_RTP_EPOCHS);
+        assert_eq!(rtp.epochs[0].epoch_id, 2);
+        assert_eq!(rtp.wagered, Amount::from_attos(35 * MIN_BET));
+        assert_eq!(rtp.paid_out, Amount::from_attos(35 * MIN_BET / 2));
+
+        // The summary follows the open game and clears once it settles
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        assert_eq!(app.player.active_game, None);
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        let summary = app.player.active_game.clone().unwrap();
+        assert_eq!((summary.game_id, summary.phase), (1, GamePhase::Active));
+        assert_eq!(summary.bet, Amount::from_attos(MIN_BET));
+        assert_eq!(summary.rolls_count, 1);
 
-        let mut dice = [0u8; 4];
-        for die in dice.iter_mut() {
-            // Better PRNG: xorshift64
-            rng_state ^= rng_state << 13;
-            rng_state ^= rng_state >> 7;
-            rng_state ^= rng_state << 17;
-            *die = ((rng_state % 6) + 1) as u8;
-        }
+        // A full board of tiny earlier totals: the new winner goes to the top and
+        // the last entry drops off
+        let ranked: Vec<LeaderboardEntry> = (0..LEADERBOARD_SIZE as u8)
+            .map(|i| LeaderboardEntry {
+                owner: AccountOwner::Address20([10 + i; 20]),
+                total_won: Amount::from_attos(1),
+                wins: 1,
+                best_payout: Amount::from_attos(1),
+            })
+            .collect();
+        app.state.leaderboard.set(ranked);
+        let mut card = app.player.current_card.clone().unwrap();
+        card.marked = [true; 25];
+        app.player.current_card = Some(card);
+        app.player.game_phase = GamePhase::BingoPending;
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        assert!(matches!(response, OperationResponse::PrizeClaimed { .. }));
+        assert_eq!(app.player.active_game, None);
 
-        dice
+        let leaderboard = app.state.leaderboard.get().clone();
+        assert_eq!(leaderboard.len(), LEADERBOARD_SIZE);
+        assert_eq!((leaderboard[0].owner, leaderboard[0].wins), (owner, 1));
+        assert_eq!(leaderboard[0].total_won, leaderboard[0].best_payout);
+        assert!(leaderboard[0].total_won > Amount::from_attos(MIN_BET));
+        let last = AccountOwner::Address20([10 + LEADERBOARD_SIZE as u8 - 2; 20]);
+        assert_eq!(leaderboard[LEADERBOARD_SIZE - 1].owner, last);
+        let winnings = app.state.owner_winnings.get(&owner).blocking_wait().unwrap();
+        assert_eq!(winnings, Some(leaderboard[0].clone()));
     }
 
-    /// Create a seed from block data for verifiable randomness
-    fn create_seed(&mut self, nonce: u64) -> u64 {
-        let block_height = self.runtime.block_height().0;
-        let timestamp = self.runtime.system_time().micros();
-        let counter = *self.state.game_counter.get();
+    #[test]
+    fn test_payout_route_through_bridge() {
+        let mut app = create_app();
+        let owner = AccountOwner::Address20([1; 20]);
+        let set_route = |app: &mut FlashportContract, target_asset: Option<&str>| {
+            let target_asset = target_asset.map(str::to_string);
+            app.execute_operation(Operation::SetPayoutRoute { target_asset })
+                .blocking_wait()
+        };
+        assert!(matches!(set_route(&mut app, Some("USDC")), OperationResponse::Error { .. }));
 
-        // Use xorshift-style mixing
-        let mut seed = block_height
-            .wrapping_mul(0xc6a4a7935bd1e995)
-            .wrapping_add(timestamp)
-            .wrapping_add(nonce.wrapping_mul(0x5851f42d4c957f2d))
-            .wrapping_add(counter.wrapping_mul(0x9e3779b97f4a7c15));
-        
-        seed ^= seed >> 33;
-        seed = seed.wrapping_mul(0xff51afd7ed558ccd);
-        seed ^= seed >> 33;
-        seed
-    }
+        // The bridge accepts the first payout and refuses the second
+        let bridge = ApplicationId::new(CryptoHash::from([7u64; 4]));
+        app.state.payout_bridge.set(Some(bridge));
+        let mut bridge_calls = 0;
+        app.runtime
+            .set_call_application_handler(move |authenticated, application, bytes| {
+                assert!(authenticated);
+                assert_eq!(application, bridge);
+                let request: BridgeRequest = bcs::from_bytes(&bytes).unwrap();
+                assert_eq!((request.owner, request.target_asset.as_str()), (owner, "USDC"));
+                bridge_calls += 1;
+                let response: Result<String, String> = match bridge_calls {
+                    1 => Ok(format!("bridge-{}", request.game_id)),
+                    _ => Err("No liquidity".to_string()),
+                };
+                bcs::to_bytes(&response).unwrap()
+            });
+        assert!(matches!(set_route(&mut app, Some("  ")), OperationResponse::Error { .. }));
+        let response = set_route(&mut app, Some(" USDC "));
+        let OperationResponse::PayoutRouteSet { route, .. } = response else {
+            panic!("Expected PayoutRouteSet, got {:?}", response);
+        };
+        assert_eq!(route.map(|route| route.target_asset), Some("USDC".to_string()));
+
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        let win = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::RollAndMatch).blocking_wait();
+            let mut card = app.player.current_card.clone().unwrap();
+            card.marked = [true; 25];
+            app.player.current_card = Some(card);
+            app.player.game_phase = GamePhase::BingoPending;
+            let balance = app.player.player_balance;
+            let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+            let OperationResponse::PrizeClaimed { routed_payout, .. } = response else {
+                panic!("Expected PrizeClaimed, got {:?}", response);
+            };
+            (balance, routed_payout)
+        };
+
+        // A routed payout leaves through the bridge with a withdrawal receipt
+        let (balance, routed) = win(&mut app);
+        let routed = routed.expect("Payout should be routed");
+        assert_eq!(routed.bridge_reference, Some("bridge-1".to_string()));
+        assert_eq!(routed.error, None);
+        let receipt_id = routed.receipt_id.expect("Routed payout should have a receipt");
+        let receipt = app.state.receipts.get(&receipt_id).blocking_wait().unwrap();
+        assert!(receipt.is_some());
+        assert_eq!(app.player.player_balance, balance);
 
-    /// Simple LCG-style PRNG for deterministic randomness
-    fn next_random(state: u64) -> u64 {
-        // LCG parameters (same as MINSTD)
-        state.wrapping_mul(48271).wrapping_add(1) % 2147483647
+        // A refused payout falls back to the internal balance
+        let (balance, routed) = win(&mut app);
+        let routed = routed.expect("Payout should be offered to the bridge");
+        assert_eq!((routed.receipt_id, routed.error.as_deref()), (None, Some("No liquidity")));
+        assert!(app.player.player_balance > balance);
+
+        // Without a route, payouts never reach the bridge
+        let response = set_route(&mut app, None);
+        assert!(matches!(response, OperationResponse::PayoutRouteSet { route: None, .. }));
+        let (balance, routed) = win(&mut app);
+        assert_eq!(routed, None);
+        assert!(app.player.player_balance > balance);
     }
 
-    /// Find and mark ALL occurrences of a number on the card
-    /// Returns (matched, match_pos, match_count)
-    fn mark_number_on_card(card: &mut BingoCard, sum: u8) -> (bool, Option<(u8, u8)>, u32) {
-        let mut matched = false;
-        let mut last_pos = None;
-        let mut count = 0;
-
-        for row in 0..5 {
-            for col in 0..5 {
-                let idx = row * 5 + col;
-                if card.numbers[idx] == sum && !card.marked[idx] {
-                    card.marked[idx] = true;
-                    matched = true;
-                    last_pos = Some((row as u8, col as u8));
-                    count += 1;
-                }
-            }
+    #[test]
+    fn test_dice_skins() {
+        let mut app = create_app();
+        let owner = AccountOwner::Address20([1; 20]);
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        let buy = |app: &mut FlashportContract, skin_id: u32| {
+            app.execute_operation(Operation::BuySkin { skin_id })
+                .blocking_wait()
+        };
+        assert!(matches!(buy(&mut app, LIGHTNING_SKIN_ID), OperationResponse::Error { .. }));
+        assert!(matches!(buy(&mut app, 99), OperationResponse::Error { .. }));
+        let OperationResponse::SkinPurchased { inventory, .. } = buy(&mut app, 1) else {
+            panic!("Expected SkinPurchased");
+        };
+        assert_eq!(inventory.owned, [1]);
+        assert_eq!(app.player.player_balance, Amount::from_attos(9 * MIN_BET));
+        assert!(matches!(buy(&mut app, 1), OperationResponse::Error { .. }));
+
+        let response = app
+            .execute_operation(Operation::EquipSkin { skin_id: Some(2) })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        app.execute_operation(Operation::EquipSkin { skin_id: Some(1) })
+            .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        match app.execute_operation(Operation::RollAndMatch).blocking_wait() {
+            OperationResponse::RollResult { skin_id, .. } => assert_eq!(skin_id, Some(1)),
+            other => panic!("Expected RollResult, got {:?}", other),
         }
-        (matched, last_pos, count)
+
+        // A fully marked card won within the top tier unlocks both achievement skins
+        let mut card = app.player.current_card.clone().unwrap();
+        card.marked = [true; 25];
+        app.player.current_card = Some(card);
+        app.player.game_phase = GamePhase::BingoPending;
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        let OperationResponse::PrizeClaimed { skins_unlocked, .. } = response else {
+            panic!("Expected PrizeClaimed, got {:?}", response);
+        };
+        assert_eq!(skins_unlocked, [LIGHTNING_SKIN_ID, BLACKOUT_SKIN_ID]);
+        assert_eq!(account_of(&mut app, owner).skins.owned, vec![1, 3, 4]);
     }
 
-    /// Check for bingo (any complete line) - static method
-    fn check_bingo_on_card(card: &BingoCard) -> Option<BingoType> {
-        // Check rows
-        for row in 0..5 {
-            if (0..5).all(|col| card.marked[row * 5 + col]) {
-                return Some(match row {
-                    0 => BingoType::Row0,
-                    1 => BingoType::Row1,
-                    2 => BingoType::Row2,
-                    3 => BingoType::Row3,
-                    4 => BingoType::Row4,
-                    _ => unreachable!(),
-                });
-            }
-        }
+    #[test]
+    fn test_delta_versions_track_changed_fields() {
+        let mut app = create_app();
+        let delta_version = |app: &FlashportContract, field: DeltaField| {
+            app.state.delta_versions.get(&field).blocking_wait().unwrap()
+        };
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        assert_eq!(delta_version(&app, DeltaField::Balance), Some(1));
+        assert_eq!(delta_version(&app, DeltaField::Session), Some(2));
+        assert_eq!(delta_version(&app, DeltaField::CurrentCard), None);
 
-        // Check columns
-        for col in 0..5 {
-            if (0..5).all(|row| card.marked[row * 5 + col]) {
-                return Some(match col {
-                    0 => BingoType::Col0,
-                    1 => BingoType::Col1,
-                    2 => BingoType::Col2,
-                    3 => BingoType::Col3,
-                    4 => BingoType::Col4,
-                    _ => unreachable!(),
-                });
-            }
-        }
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        assert_eq!(delta_version(&app, DeltaField::Balance), Some(3));
+        assert_eq!(delta_version(&app, DeltaField::CurrentCard), Some(3));
+        assert_eq!(delta_version(&app, DeltaField::HedgeCard), None);
+    }
 
-        // Check main diagonal (top-left to bottom-right)
-        if (0..5).all(|i| card.marked[i * 5 + i]) {
-            return Some(BingoType::DiagonalMain);
-        }
+    #[test]
+    fn test_data_purge_after_grace_period() {
+        let mut app = create_app();
+        let owner = AccountOwner::Address20([1; 20]);
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::SetPreferences {
+            preferences: PlayerPreferences {
+                max_bet_multiple_after_loss: 2,
+                ..PlayerPreferences::default()
+            },
+        })
+        .blocking_wait();
 
-        // Check anti-diagonal (top-right to bottom-left)
-        if (0..5).all(|i| card.marked[i * 5 + (4 - i)]) {
-            return Some(BingoType::DiagonalAnti);
+        let response = app.execute_operation(Operation::PurgeMyData).blocking_wait();
+        let OperationResponse::DataPurgeRequested { request } = response else {
+            panic!("Expected DataPurgeRequested, got {:?}", response);
+        };
+        let response = app.execute_operation(Operation::PurgeMyData).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        let response = app.execute_operation(Operation::ConfirmDataPurge).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+
+        // After the grace period only the requester can confirm
+        let other = AccountOwner::Address20([2; 20]);
+        for cadence_owner in [owner, other] {
+            app.state
+                .session_cadence
+                .insert(&cadence_owner, SessionCadence::default())
+                .unwrap();
         }
+        app.runtime.set_system_time(Timestamp::from(request.confirmable_at_micros));
+        app.runtime.set_authenticated_signer(other);
+        let response = app.execute_operation(Operation::ConfirmDataPurge).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        app.runtime.set_authenticated_signer(owner);
+        let response = app.execute_operation(Operation::ConfirmDataPurge).blocking_wait();
+        assert!(matches!(response, OperationResponse::DataPurged { .. }));
+
+        assert_eq!(app.player.preferences.max_bet_multiple_after_loss, 0);
+        assert_eq!(app.player.api_usage.operations, 0);
+        assert!(app.player.data_purge.is_none());
+        assert_eq!(app.player.player_balance, Amount::from_attos(10 * MIN_BET));
+        // Only the requester's own entries are purged
+        let cadence = |owner| {
+            app.state
+                .session_cadence
+                .get(&owner)
+                .blocking_wait()
+                .unwrap()
+        };
+        assert!(cadence(owner).is_none());
+        assert!(cadence(other).is_some());
+    }
 
-        // Check full card (blackout)
-        if (0..25).all(|i| card.marked[i]) {
-            return Some(BingoType::FullCard);
+    #[test]
+    fn test_health_check() {
+        let mut app = create_app();
+        // Two players with withdrawals pending: each is checked against their own account
+        let release_at_micros = 1_000_000_000 + 3600 * 1_000_000;
+        for (signer, amount) in [(2, 2 * MIN_BET), (1, MIN_BET)] {
+            app.runtime
+                .set_authenticated_signer(AccountOwner::Address20([signer; 20]));
+            app.execute_operation(Operation::Deposit {
+                amount_atto: 10 * MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+            app.execute_operation(Operation::ScheduleWithdrawal {
+                amount: Amount::from_attos(amount),
+                release_at_micros,
+            })
+            .blocking_wait();
         }
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        let health_check = |app: &mut FlashportContract| {
+            match app.execute_operation(Operation::HealthCheck).blocking_wait() {
+                OperationResponse::HealthChecked { report } => report,
+                other => panic!("Expected HealthChecked, got {:?}", other),
+            }
+        };
+        let report = health_check(&mut app);
+        assert!(report.healthy, "{:?}", report.violations);
+        assert_eq!(report.counters.game_counter, 1);
 
-        None
+        // A drained prize pool and a rewound game counter are both reported
+        app.player.current_prize_pool = Amount::ZERO;
+        app.state.game_counter.set(0);
+        let report = health_check(&mut app);
+        assert!(!report.healthy);
+        assert_eq!(report.violations.len(), 3);
+        assert_eq!(app.state.last_health_check.get().as_ref(), Some(&report));
     }
 
+    #[test]
+    fn test_budget_alerts() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        let invalid = PlayerPreferences {
+            daily_spend_alert_atto: "lots".to_string(),
+            ..PlayerPreferences::default()
+        };
+        let response = app
+            .execute_operation(Operation::SetPreferences { preferences: invalid })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        let preferences = PlayerPreferences {
+            daily_spend_alert_atto: (MIN_BET + ROLL_COST).to_string(),
+            game_loss_alert_atto: (MIN_BET + ROLL_COST).to_string(),
+            ..PlayerPreferences::default()
+        };
+        app.execute_operation(Operation::SetPreferences {
+            preferences: preferences.clone(),
+        })
+        .blocking_wait();
 
-}
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        assert!(app.player.budget_alerts.is_empty());
 
-#[cfg(test)]
-mod tests {
-    use futures::FutureExt as _;
-    use linera_sdk::{
-        linera_base_types::{Amount, BlockHeight, Timestamp},
-        util::BlockingWait,
-        views::View,
-        Contract, ContractRuntime,
-    };
+        // The first roll crosses both thresholds; later rolls raise nothing new
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        let alerts = app.player.budget_alerts.clone();
+        let kinds: Vec<_> = alerts.iter().map(|alert| alert.kind).collect();
+        assert_eq!(kinds, vec![BudgetAlertKind::DailySpend, BudgetAlertKind::GameLoss]);
+        assert_eq!(alerts[1].game_id, Some(1));
+        assert_eq!(alerts[1].spent, Amount::from_attos(MIN_BET + ROLL_COST));
 
-    use blitz_bingo::Operation;
+        // A new day starts a fresh daily budget, the game's alert stays
+        app.runtime.set_system_time(Timestamp::from(
+            1_000_000_000 + EPOCH_PERIOD_SECS * 1_000_000,
+        ));
+        app.execute_operation(Operation::SetPreferences { preferences })
+            .blocking_wait();
+        let kinds: Vec<_> = app
+            .player
+            .budget_alerts
+            .iter()
+            .map(|alert| alert.kind)
+            .collect();
+        assert_eq!(kinds, vec![BudgetAlertKind::GameLoss]);
+    }
 
-    use super::{FlashportContract, FlashportState};
+    #[test]
+    fn test_insurance_table() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Insurance,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+
+        // Rolls cost INSURANCE_ROLL_COST instead of ROLL_COST
+        let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        assert!(matches!(response, OperationResponse::RollResult { .. }));
+        assert_eq!(
+            app.player.player_balance,
+            Amount::from_attos(9 * MIN_BET - INSURANCE_ROLL_COST)
+        );
+
+        // A 12-roll win pays 2x where Classic would pay 5x
+        let mut card = app.player.current_card.clone().unwrap();
+        card.rolls_count = 12;
+        app.player.current_card = Some(card);
+        app.player.game_phase = GamePhase::BingoPending;
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        let OperationResponse::PrizeClaimed { payout_amount, .. } = response else {
+            panic!("Expected PrizeClaimed, got {:?}", response);
+        };
+        assert_eq!(payout_amount, (2 * MIN_BET).to_string());
+
+        let stats = app.state.insurance_stats.get();
+        assert_eq!((stats.games, stats.rolls), (1, 1));
+        assert_eq!(stats.fees, Amount::from_attos(INSURANCE_ROLL_COST));
+        assert_eq!(stats.paid_out, Amount::from_attos(2 * MIN_BET));
+        assert_eq!(app.state.high_roller_stats.get().games, 0);
+    }
 
     #[test]
-    fn test_start_session() {
+    fn test_season_mode_rolls_extra_dice() {
         let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        let double_dice = SeasonMode {
+            mode_id: 0,
+            name: "Double Dice Weekend".to_string(),
+            dice_count: 8,
+            min_number: 8,
+            max_number: 48,
+            enabled: false,
+            starts_at_micros: 0,
+            ends_at_micros: 2_000_000_000,
+        };
+        let upsert = |app: &mut FlashportContract, mode: SeasonMode| {
+            govern(app, Operation::UpsertSeasonMode { mode })
+        };
+        let too_many_dice = SeasonMode {
+            dice_count: 9,
+            ..double_dice.clone()
+        };
+        assert!(matches!(upsert(&mut app, too_many_dice), OperationResponse::Error { .. }));
+        match upsert(&mut app, double_dice.clone()) {
+            OperationResponse::SeasonModeUpdated { mode } => assert_eq!(mode.mode_id, 1),
+            other => panic!("Expected SeasonModeUpdated, got {:?}", other),
+        }
 
-        let response = app
-            .execute_operation(Operation::StartSession {
-                expires_in_secs: 3600,
+        let new_season_game = |app: &mut FlashportContract| {
+            app.execute_operation(Operation::NewSeasonGame {
+                mode_id: 1,
+                bet_amount_atto: MIN_BET,
             })
-            .now_or_never()
-            .expect("Should not await");
+            .blocking_wait()
+        };
+        assert!(matches!(new_season_game(&mut app), OperationResponse::Error { .. }));
+        upsert(
+            &mut app,
+            SeasonMode {
+                mode_id: 1,
+                enabled: true,
+                ..double_dice
+            },
+        );
+        let OperationResponse::GameStarted { card, .. } = new_season_game(&mut app) else {
+            panic!("Expected GameStarted");
+        };
+        assert_eq!((card.season_mode_id, card.extra_dice), (Some(1), 4));
+        assert!(card.numbers.iter().all(|n| *n == 0 || (8..=48).contains(n)));
 
-        match response {
-            blitz_bingo::OperationResponse::SessionStarted { session_id, .. } => {
-                assert_eq!(session_id, 1);
+        match app.execute_operation(Operation::RollAndMatch).blocking_wait() {
+            OperationResponse::RollResult {
+                dice,
+                extra_dice,
+                sum,
+                ..
+            } => {
+                assert_eq!(extra_dice.len(), 4);
+                let total: u8 = dice.iter().chain(&extra_dice).sum();
+                assert_eq!(sum, total);
             }
-            _ => panic!("Expected SessionStarted response"),
+            other => panic!("Expected RollResult, got {:?}", other),
         }
     }
 
     #[test]
-    fn test_deposit() {
+    fn test_game_template_rules() {
         let mut app = create_app();
+        let organizer = AccountOwner::Address20([1; 20]);
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
+        let blackout = GameTemplate {
+            template_id: 0,
+            name: "Friday Blackout League".to_string(),
+            organizer: None,
+            mode: GameMode::Classic,
+            pattern: WinPattern::Blackout,
+            entry_fee: Amount::from_attos(2 * MIN_BET),
+            roll_cost: Amount::from_attos(2 * ROLL_COST),
+            max_card_difficulty: 0,
+        };
+        let upsert = |app: &mut FlashportContract, template: GameTemplate| {
+            app.execute_operation(Operation::UpsertGameTemplate { template })
+                .blocking_wait()
+        };
+        let cheap_rolls = GameTemplate {
+            roll_cost: Amount::from_attos(ROLL_COST - 1),
+            ..blackout.clone()
+        };
+        assert!(matches!(upsert(&mut app, cheap_rolls), OperationResponse::Error { .. }));
+        match upsert(&mut app, blackout.clone()) {
+            OperationResponse::GameTemplateUpdated { template } => {
+                assert_eq!((template.template_id, template.organizer), (1, Some(organizer)));
+            }
+            other => panic!("Expected GameTemplateUpdated, got {:?}", other),
+        }
 
-        let response = app
-            .execute_operation(Operation::Deposit)
-            .now_or_never()
-            .expect("Should not await");
+        // Only the organizer may replace the template
+        app.runtime
+            .set_authenticated_signer(AccountOwner::Address20([2; 20]));
+        let hijack = GameTemplate {
+            template_id: 1,
+            ..blackout
+        };
+        assert!(matches!(upsert(&mut app, hijack), OperationResponse::Error { .. }));
+        app.runtime.set_authenticated_signer(organizer);
 
-        match response {
-            blitz_bingo::OperationResponse::DepositReceived { new_balance, .. } => {
-                // Should have 10 LINERA = 10 * 10^18 atto
-                assert_eq!(new_balance, "10000000000000000000");
+        let new_game = |app: &mut FlashportContract, bet_amount_atto: u128| {
+            app.execute_operation(Operation::NewGame {
+                bet_amount_atto,
+                currency: Currency::Real,
+                mode: GameMode::Insurance,
+                hedge: false,
+                template_id: Some(1),
+            })
+            .blocking_wait()
+        };
+        assert!(matches!(new_game(&mut app, MIN_BET), OperationResponse::Error { .. }));
+        let OperationResponse::GameStarted { card, .. } = new_game(&mut app, 2 * MIN_BET) else {
+            panic!("Expected GameStarted");
+        };
+        assert_eq!((card.template_id, card.pattern), (Some(1), WinPattern::Blackout));
+        assert_eq!(card.mode, GameMode::Classic);
+
+        // Rolls cost the template's fee, and single lines never win
+        for _ in 0..20 {
+            let balance = u128::from(app.player.player_balance);
+            app.execute_operation(Operation::RollAndMatch)
+                .blocking_wait();
+            assert_eq!(
+                u128::from(app.player.player_balance),
+                balance - 2 * ROLL_COST
+            );
+            let card = app.player.current_card.clone().unwrap();
+            if !card.marked.iter().all(|m| *m) {
+                assert_eq!(app.player.game_phase, GamePhase::Active);
             }
-            _ => panic!("Expected DepositReceived response"),
         }
     }
 
     #[test]
-    fn test_new_game_requires_balance() {
+    fn test_scoped_session_permissions() {
+        let mut app = create_app();
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        let start_session = |app: &mut FlashportContract, permissions: Option<u32>| {
+            app.execute_operation(Operation::StartSession {
+                expires_in_secs: 3600,
+                permissions,
+            })
+            .blocking_wait()
+        };
+        let play_only = SESSION_CAN_ROLL | SESSION_CAN_NEW_GAME;
+        match start_session(&mut app, Some(play_only)) {
+            OperationResponse::SessionStarted { permissions, .. } => {
+                assert_eq!(permissions, play_only)
+            }
+            other => panic!("Expected SessionStarted, got {:?}", other),
+        }
+
+        // The key can play, but cannot move funds out or widen its own session
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
+        let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        assert!(matches!(response, OperationResponse::RollResult { .. }));
+        let balance = app.player.player_balance;
+        let withdraw = Operation::Withdraw {
+            amount: Amount::from_attos(MIN_BET),
+        };
+        let response = app.execute_operation(withdraw.clone()).blocking_wait();
+        assert!(matches!(
+            response,
+            OperationResponse::Error {
+                code: ErrorCode::SessionPermission,
+                ..
+            }
+        ));
+        let response = app.execute_operation(Operation::WithdrawAll).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        assert_eq!(app.player.player_balance, balance);
+        assert!(matches!(start_session(&mut app, None), OperationResponse::Error { .. }));
+
+        // Without a session the mask no longer applies
+        app.execute_operation(Operation::EndSession).blocking_wait();
+        app.execute_operation(withdraw).blocking_wait();
+        assert!(app.player.player_balance < balance);
+    }
+
+    #[test]
+    fn test_operation_auth_matrix() {
         let mut app = create_app();
+        let stranger = AccountOwner::Address20([2; 20]);
+        let sequenced = Operation::Sequenced {
+            requires_session_id: 1,
+            operation: Box::new(Operation::AckOutbox { up_to_id: 0 }),
+        };
+        assert_eq!(sequenced.required_auth(), OperationAuth::Admin);
+        assert_eq!(Operation::ClaimPrize.required_auth(), OperationAuth::Delegate);
 
-        // Start session first
+        // Sessions gate game operations
+        let new_game = Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Play,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        };
+        let response = app.execute_operation(new_game.clone()).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
         app.execute_operation(Operation::StartSession {
             expires_in_secs: 3600,
+            permissions: None,
         })
-        .now_or_never()
-        .unwrap();
+        .blocking_wait();
+        let response = app.execute_operation(new_game).blocking_wait();
+        assert!(matches!(response, OperationResponse::GameStarted { .. }));
 
-        // Try to start game without balance - should fail
+        // Unsigned blocks can still run public operations, but not signer ones
+        app.runtime.set_authenticated_signer(None);
         let response = app
-            .execute_operation(Operation::NewGame)
-            .now_or_never()
-            .expect("Should not await");
+            .execute_operation(Operation::EquipSkin { skin_id: None })
+            .blocking_wait();
+        match response {
+            OperationResponse::Error { message, .. } => assert!(message.contains("signer")),
+            other => panic!("Expected an error, got {:?}", other),
+        }
+        let response = app
+            .execute_operation(Operation::Deposit {
+                amount_atto: MIN_BET,
+                credit_to: None,
+            })
+            .blocking_wait();
+        assert!(matches!(response, OperationResponse::DepositReceived { .. }));
 
+        // Admin operations need the admin, whatever else the caller holds
+        app.runtime.set_authenticated_signer(stranger);
+        let response = govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig::default(),
+            },
+        );
         match response {
-            blitz_bingo::OperationResponse::Error { message } => {
-                assert!(message.contains("Insufficient balance"));
-            }
-            _ => panic!("Expected Error response for insufficient balance"),
+            OperationResponse::Error { message, .. } => assert!(message.contains("admin")),
+            other => panic!("Expected an error, got {:?}", other),
         }
     }
 
     #[test]
-    fn test_game_with_deposit() {
+    fn test_sponsored_game_pays_sponsored_player() {
         let mut app = create_app();
+        let sponsor = AccountOwner::Address20([1; 20]);
+        let friend = AccountOwner::Address20([2; 20]);
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
+        app.execute_operation(Operation::StartSession {
+            expires_in_secs: 3600,
+            permissions: None,
+        })
+        .blocking_wait();
 
-        // Deposit first
-        app.execute_operation(Operation::Deposit)
-            .now_or_never()
-            .unwrap();
+        let response = app
+            .execute_operation(Operation::SponsorGame {
+                for_owner: friend,
+                bet_amount_atto: MIN_BET,
+            })
+            .blocking_wait();
+        let OperationResponse::GameStarted { card, .. } = response else {
+            panic!("Expected GameStarted, got {:?}", response);
+        };
+        assert_eq!((card.sponsor, card.sponsored_player), (Some(sponsor), Some(friend)));
+        assert_eq!(app.player.player_balance, Amount::from_attos(9 * MIN_BET));
 
-        // Start session
+        // Only the sponsored player may play the card
+        let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        let mut card = app.player.current_card.clone().unwrap();
+        card.rolls_count = 12;
+        app.player.current_card = Some(card);
+        app.player.game_phase = GamePhase::BingoPending;
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+
+        // The 5x win is paid straight out to the friend, not into the sponsor's balance
+        app.runtime.set_authenticated_signer(friend);
+        let response = app.execute_operation(Operation::ClaimPrize).blocking_wait();
+        let OperationResponse::PrizeClaimed {
+            sponsored_payout_receipt_id: Some(receipt_id),
+            ..
+        } = response
+        else {
+            panic!("Expected a sponsored payout, got {:?}", response);
+        };
+        let receipt = app.state.receipts.get(&receipt_id).blocking_wait().unwrap().unwrap();
+        assert_eq!(receipt.paid_to, Some(friend));
+        assert_eq!(u128::from(app.player.player_balance), 9 * MIN_BET);
+
+        let win = app.state.win_archive.get(0).blocking_wait().unwrap().unwrap();
+        assert_eq!((win.owner, win.sponsor), (Some(friend), Some(sponsor)));
+    }
+
+    #[test]
+    fn test_auto_roll_resumes_after_block_cap() {
+        let mut app = create_app();
+        govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig {
+                    max_rolls_per_block: 3,
+                    ..GameConfig::default()
+                },
+            },
+        );
+        app.execute_operation(Operation::Deposit {
+            amount_atto: 10 * MIN_BET,
+            credit_to: None,
+        })
+        .blocking_wait();
         app.execute_operation(Operation::StartSession {
             expires_in_secs: 3600,
+            permissions: None,
         })
-        .now_or_never()
-        .unwrap();
+        .blocking_wait();
+        app.execute_operation(Operation::NewGame {
+            bet_amount_atto: MIN_BET,
+            currency: Currency::Real,
+            mode: GameMode::Classic,
+            hedge: false,
+            template_id: None,
+        })
+        .blocking_wait();
 
-        // Now start game should succeed
-        let response = app
-            .execute_operation(Operation::NewGame)
-            .now_or_never()
-            .expect("Should not await");
+        let auto_roll = Operation::AutoRoll {
+            count: 5,
+            stop_on_bingo: false,
+            aggregate: false,
+        };
+        match app.execute_operation(auto_roll).blocking_wait() {
+            OperationResponse::AutoRollResult { results } => assert_eq!(results.len(), 3),
+            other => panic!("Expected AutoRollResult, got {:?}", other),
+        }
+        let pending = app.player.pending_auto_roll.clone().unwrap();
+        assert_eq!((pending.remaining, pending.paused_at_block), (2, 100));
+
+        // Still capped in the same block; the batch stays parked
+        let response = app.execute_operation(Operation::ContinueAutoRoll).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+        assert_eq!(app.player.pending_auto_roll.as_ref().unwrap().remaining, 2);
+
+        // A plain roll is just one roll and leaves the batch alone
+        app.runtime.set_block_height(BlockHeight(101));
+        let response = app.execute_operation(Operation::RollAndMatch).blocking_wait();
+        assert!(matches!(response, OperationResponse::RollResult { .. }));
+        assert_eq!(app.player.pending_auto_roll.as_ref().unwrap().remaining, 2);
+
+        // Continuing picks the batch up where it stopped
+        match app.execute_operation(Operation::ContinueAutoRoll).blocking_wait() {
+            OperationResponse::AutoRollResult { results } => assert_eq!(results.len(), 2),
+            other => panic!("Expected AutoRollResult, got {:?}", other),
+        }
+        assert!(app.player.pending_auto_roll.is_none());
+        let card = app.player.current_card.clone().unwrap();
+        assert_eq!(card.rolls_count, 6);
+
+        let response = app.execute_operation(Operation::ContinueAutoRoll).blocking_wait();
+        assert!(matches!(response, OperationResponse::Error { .. }));
+    }
 
+    #[test]
+    fn test_update_config_requires_admin() {
+        let mut app = create_app();
+        app.runtime
+            .set_authenticated_signer(AccountOwner::Address20([2; 20]));
+
+        let response = govern(
+            &mut app,
+            Operation::UpdateConfig {
+                config: GameConfig::default(),
+            },
+        );
         match response {
-            blitz_bingo::OperationResponse::GameStarted { game_id, card, .. } => {
-                assert_eq!(game_id, 1);
-                // Center should be FREE (marked)
-                assert!(card.marked[12]);
-            }
-            _ => panic!("Expected GameStarted response"),
+            OperationResponse::Error { message, .. } => assert!(message.contains("admin")),
+            _ => panic!("Expected Error response for non-admin"),
+        }
+    }
+
+    /// `owner`'s account as the contract stores it
+    fn account_of(app: &mut FlashportContract, owner: AccountOwner) -> PlayerAccount {
+        app.store_player();
+        app.state
+            .players
+            .get(&owner)
+            .blocking_wait()
+            .expect("Failed to read player account")
+            .unwrap_or_else(PlayerAccount::open)
+    }
+
+    /// Run an admin operation, pushing a governed one through the proposal queue with
+    /// its timelock skipped, and return how it applied
+    fn govern(app: &mut FlashportContract, operation: Operation) -> OperationResponse {
+        let response = app.execute_operation(operation).blocking_wait();
+        let OperationResponse::ChangeProposed { mut proposal, .. } = response else {
+            return response;
+        };
+        proposal.executable_at_micros = 0;
+        app.state
+            .proposals
+            .insert(&proposal.proposal_id, proposal.clone())
+            .unwrap();
+        let proposal_id = proposal.proposal_id;
+        match app
+            .execute_operation(Operation::ExecuteProposal { proposal_id })
+            .blocking_wait()
+        {
+            OperationResponse::ProposalExecuted { outcome, .. } => *outcome,
+            response => response,
         }
     }
 
     fn create_app() -> FlashportContract {
         let runtime = ContractRuntime::new()
-            .with_application_parameters(())
+            .with_application_parameters(FlashportParameters::default())
+            .with_chain_id(ChainId(CryptoHash::from([3u64; 4])))
+            .with_application_id(ApplicationId::new(CryptoHash::from([4u64; 4])).with_abi())
             .with_system_time(Timestamp::from(1000000000))
-            .with_block_height(BlockHeight(100));
+            .with_block_height(BlockHeight(100))
+            .with_authenticated_signer(AccountOwner::Address20([1; 20]))
+            // The signer does not own the chain, so games need a session
+            .with_chain_ownership(ChainOwnership::single(AccountOwner::Address20([9; 20])));
 
         let mut contract = FlashportContract {
             state: FlashportState::load(runtime.root_view_storage_context())
                 .blocking_wait()
                 .expect("Failed to load state"),
             runtime,
+            relayed_owner: None,
+            player_owner: None,
+            player: PlayerAccount::default(),
+            stored_totals: (Amount::ZERO, Amount::ZERO),
+            quick_play: false,
         };
 
         contract
-            .instantiate(())
+            .instantiate(InstantiationArgument::default())
             .now_or_never()
             .expect("Should not await");
+        // The house starts with a bankroll, so wins above the bet can be paid
+        contract.state.epoch.get_mut().bonus_pool = Amount::from_tokens(1_000);
 
         contract
     }
@@ -0,0 +1,756 @@
+// FlashPort: Bet Escrow
+// Every movement of player funds goes through here so the ledgers stay consistent
+
+use std::fmt;
+
+use blitz_bingo::{Currency, EpochPool, PlayerAccount};
+use linera_sdk::linera_base_types::Amount;
+
+/// Why a balance or ledger update failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerError {
+    /// The debit is larger than the balance; nothing was changed
+    Insufficient(String),
+    /// The named ledger would exceed the largest representable amount
+    Overflow(&'static str),
+    /// The named ledger would go below zero
+    Underflow(&'static str),
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::Insufficient(message) => write!(f, "{}", message),
+            LedgerError::Overflow(ledger) => write!(f, "Ledger overflow in {}", ledger),
+            LedgerError::Underflow(ledger) => write!(f, "Ledger underflow in {}", ledger),
+        }
+    }
+}
+
+/// `a + b`, or an Overflow error naming `ledger`
+pub fn add(ledger: &'static str, a: Amount, b: Amount) -> Result<Amount, LedgerError> {
+    a.try_add(b).map_err(|_| LedgerError::Overflow(ledger))
+}
+
+/// `a - b`, or an Underflow error naming `ledger`
+pub fn sub(ledger: &'static str, a: Amount, b: Amount) -> Result<Amount, LedgerError> {
+    a.try_sub(b).map_err(|_| LedgerError::Underflow(ledger))
+}
+
+/// `a * factor`, or an Overflow error naming `ledger`
+pub fn mul(ledger: &'static str, a: Amount, factor: u128) -> Result<Amount, LedgerError> {
+    a.try_mul(factor).map_err(|_| LedgerError::Overflow(ledger))
+}
+
+/// What the house can pay out of: this epoch's take plus the bonus pool
+pub fn house_pool(epoch: &EpochPool) -> Result<Amount, LedgerError> {
+    add("house pool", epoch.house_contributions, epoch.bonus_pool)
+}
+
+/// Book a game staked from a bankroll outside any player account (a syndicate's): its
+/// cost is house revenue and its payout comes out of the house pool, this epoch's take
+/// first and then the bonus pool
+pub fn settle_house_game(
+    epoch: &mut EpochPool,
+    bet: Amount,
+    cost: Amount,
+    payout: Amount,
+) -> Result<(), LedgerError> {
+    let take = add("epoch.house_contributions", epoch.house_contributions, cost)?;
+    let from_take = payout.min(take);
+    let from_bonus = sub("payout", payout, from_take)?;
+    let bonus_pool = sub("epoch.bonus_pool", epoch.bonus_pool, from_bonus)?;
+    epoch.wagered = add("epoch.wagered", epoch.wagered, bet)?;
+    epoch.paid_out = add("epoch.paid_out", epoch.paid_out, payout)?;
+    epoch.house_contributions = sub("epoch.house_contributions", take, from_take)?;
+    epoch.bonus_pool = bonus_pool;
+    Ok(())
+}
+
+/// Fund handling over the player's balances and the current game's prize pool.
+///
+/// Invariants:
+/// - Balances never go negative: debits that cannot be covered fail without side effects.
+/// - Ledgers never saturate: arithmetic is checked and an overflow or underflow is
+///   returned as an error, which the contract turns into a reverted operation.
+/// - Real-money debits add to `total_spent` and payouts add to `total_won`; play points
+///   never touch either total.
+/// - `current_prize_pool` holds the bet locked by the current game until that game is
+///   released (paid out), refunded or forfeited; it is zero otherwise.
+/// - A real-money bet is also held in the epoch's `prize_pool` while its game is open,
+///   and so are duel roll fees until the duel's pot is paid out.
+/// - Real-money locks, releases and forfeits are tallied in the current `epoch`; the part
+///   of a locked bet that is not paid back out counts as a house contribution, and the
+///   part of a payout above the locked bet is paid out of the house pool.
+/// - `pending_withdrawals` holds scheduled withdrawals taken out of `player_balance`
+///   until they are released to the player.
+/// - `vesting_balance` holds the installments of vesting payouts taken out of
+///   `player_balance` until they unlock and are claimed back.
+/// - `frozen_balance` holds disputed winnings taken out of `player_balance` until the
+///   dispute is ruled on; voided winnings leave `total_won` and go to the house.
+/// - A disputed game's house take is held out of `house_contributions` until the ruling,
+///   then returns to the house or is refunded to the player.
+/// - Promotions, dispute awards and treasury withdrawals are paid out of the epoch
+///   `bonus_pool` only, never out of player funds.
+/// - Games staked from outside a player account are booked against the house pool with
+///   `settle_house_game`.
+pub struct Escrow<'a> {
+    player: &'a mut PlayerAccount,
+    epoch: &'a mut EpochPool,
+}
+
+impl<'a> Escrow<'a> {
+    pub fn new(player: &'a mut PlayerAccount, epoch: &'a mut EpochPool) -> Self {
+        Escrow { player, epoch }
+    }
+
+    /// Spendable balance in the given currency
+    pub fn available(&self, currency: Currency) -> Amount {
+        match currency {
+            Currency::Play => self.player.points_balance,
+            Currency::Real => self.player.player_balance,
+        }
+    }
+
+    /// Credit a real-money deposit, returning the new balance
+    pub fn deposit(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        let new_balance = add("player_balance", self.available(Currency::Real), amount)?;
+        let total_dep = add("total_deposited", self.player.total_deposited, amount)?;
+        self.player.player_balance = new_balance;
+        self.player.total_deposited = total_dep;
+        Ok(new_balance)
+    }
+
+    /// Debit a real-money withdrawal, returning the remaining balance
+    pub fn withdraw(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        let current = self.available(Currency::Real);
+        let remaining = current.try_sub(amount).map_err(|_| {
+            LedgerError::Insufficient(format!(
+                "Insufficient balance. Available: {} atto, Requested: {} atto",
+                u128::from(current),
+                u128::from(amount)
+            ))
+        })?;
+        self.player.player_balance = remaining;
+        Ok(remaining)
+    }
+
+    /// Move a withdrawal into the time-locked `pending_withdrawals`, returning the
+    /// remaining balance
+    pub fn schedule_withdrawal(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        let pending = add(
+            "pending_withdrawals",
+            self.player.pending_withdrawals,
+            amount,
+        )?;
+        let remaining = self.withdraw(amount)?;
+        self.player.pending_withdrawals = pending;
+        Ok(remaining)
+    }
+
+    /// Pay out a scheduled withdrawal from `pending_withdrawals`
+    pub fn release_withdrawal(&mut self, amount: Amount) -> Result<(), LedgerError> {
+        let pending = sub(
+            "pending_withdrawals",
+            self.player.pending_withdrawals,
+            amount,
+        )?;
+        self.player.pending_withdrawals = pending;
+        Ok(())
+    }
+
+    /// Move the not-yet-unlocked part of a payout into `vesting_balance`, returning
+    /// the remaining balance
+    pub fn vest(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        let vesting = add("vesting_balance", self.player.vesting_balance, amount)?;
+        let remaining = sub("player_balance", self.available(Currency::Real), amount)?;
+        self.player.vesting_balance = vesting;
+        self.player.player_balance = remaining;
+        Ok(remaining)
+    }
+
+    /// Return unlocked installments from `vesting_balance`, returning the new balance
+    pub fn release_vested(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        let vesting = sub("vesting_balance", self.player.vesting_balance, amount)?;
+        let new_balance = add("player_balance", self.available(Currency::Real), amount)?;
+        self.player.vesting_balance = vesting;
+        self.player.player_balance = new_balance;
+        Ok(new_balance)
+    }
+
+    /// Reset play points to a fresh grant
+    pub fn refill_points(&mut self, grant: Amount) {
+        self.player.points_balance = grant;
+    }
+
+    /// Take a fee (bet or roll cost) from the given balance
+    pub fn charge(&mut self, currency: Currency, fee: Amount) -> Result<(), LedgerError> {
+        let current = self.available(currency);
+        let remaining = current.try_sub(fee).map_err(|_| {
+            LedgerError::Insufficient(match currency {
+                Currency::Play => format!(
+                    "Insufficient play points. Need {} atto, have {} atto. Refill your points.",
+                    u128::from(fee),
+                    u128::from(current)
+                ),
+                Currency::Real => format!(
+                    "Insufficient balance. Need {} atto, have {} atto. Deposit more LINERA.",
+                    u128::from(fee),
+                    u128::from(current)
+                ),
+            })
+        })?;
+        if currency == Currency::Real {
+            let total_spent = add("total_spent", self.player.total_spent, fee)?;
+            self.player.total_spent = total_spent;
+        }
+        self.set_available(currency, remaining);
+        Ok(())
+    }
+
+    /// Undo a `charge` that was rejected after payment
+    pub fn refund(&mut self, currency: Currency, fee: Amount) -> Result<(), LedgerError> {
+        let restored = add(Self::balance_ledger(currency), self.available(currency), fee)?;
+        if currency == Currency::Real {
+            let total_spent = sub("total_spent", self.player.total_spent, fee)?;
+            self.player.total_spent = total_spent;
+        }
+        self.set_available(currency, restored);
+        Ok(())
+    }
+
+    /// Charge a new game's bet and hold it in the prize pool.
+    /// Any bet still locked by a previous game is forfeited first.
+    pub fn lock(&mut self, currency: Currency, bet: Amount) -> Result<(), LedgerError> {
+        self.charge(currency, bet)?;
+        self.forfeit()?;
+        self.player.current_prize_pool = bet;
+        if currency == Currency::Real {
+            let epoch = &mut *self.epoch;
+            epoch.wagered = add("epoch.wagered", epoch.wagered, bet)?;
+            epoch.prize_pool = add("epoch.prize_pool", epoch.prize_pool, bet)?;
+        }
+        Ok(())
+    }
+
+    /// Settle the current game with a payout, returning the new balance. A real-money
+    /// payout above the locked bet comes out of the house pool, this epoch's take first
+    /// and then the bonus pool; if the house cannot cover it, nothing is changed.
+    pub fn release(&mut self, currency: Currency, payout: Amount) -> Result<Amount, LedgerError> {
+        if currency == Currency::Real {
+            let locked = self.player.current_prize_pool;
+            let shortfall = payout.try_sub(locked).unwrap_or(Amount::ZERO);
+            let house = house_pool(self.epoch)?;
+            if shortfall > house {
+                return Err(LedgerError::Insufficient(format!(
+                    "Insufficient house pool. Need {} atto, have {} atto.",
+                    u128::from(shortfall),
+                    u128::from(house)
+                )));
+            }
+            let kept = locked.try_sub(payout).unwrap_or(Amount::ZERO);
+            let epoch = &mut *self.epoch;
+            let take = add("epoch.house_contributions", epoch.house_contributions, kept)?;
+            let from_take = shortfall.min(take);
+            let from_bonus = sub("payout", shortfall, from_take)?;
+            epoch.bonus_pool = sub("epoch.bonus_pool", epoch.bonus_pool, from_bonus)?;
+            epoch.house_contributions = sub("epoch.house_contributions", take, from_take)?;
+            epoch.paid_out = add("epoch.paid_out", epoch.paid_out, payout)?;
+        }
+        self.unlock(currency)?;
+        self.credit(currency, payout)
+    }
+
+    /// Charge a duel roll fee and hold it in the epoch's prize pool with the duel's pot
+    pub fn stake_duel_fee(&mut self, fee: Amount) -> Result<(), LedgerError> {
+        let prize_pool = add("epoch.prize_pool", self.epoch.prize_pool, fee)?;
+        let wagered = add("epoch.wagered", self.epoch.wagered, fee)?;
+        self.charge(Currency::Real, fee)?;
+        self.epoch.prize_pool = prize_pool;
+        self.epoch.wagered = wagered;
+        Ok(())
+    }
+
+    /// Pay a settled duel's `pot` out of the epoch's prize pool: `payout` to the winner
+    /// and the rest to the house as rake. Returns the winner's new balance.
+    pub fn release_duel_pot(&mut self, pot: Amount, payout: Amount) -> Result<Amount, LedgerError> {
+        let rake = sub("duel.rake", pot, payout)?;
+        let epoch = &mut *self.epoch;
+        epoch.prize_pool = sub("epoch.prize_pool", epoch.prize_pool, pot)?;
+        epoch.paid_out = add("epoch.paid_out", epoch.paid_out, payout)?;
+        epoch.house_contributions =
+            add("epoch.house_contributions", epoch.house_contributions, rake)?;
+        self.credit(Currency::Real, payout)
+    }
+
+    /// Settle the current game by handing the locked bet back, returning it; the bet
+    /// comes off `total_spent` and counts as paid out of the epoch
+    pub fn refund_bet(&mut self, currency: Currency) -> Result<Amount, LedgerError> {
+        let locked = self.unlock(currency)?;
+        if currency == Currency::Real {
+            let epoch = &mut *self.epoch;
+            epoch.paid_out = add("epoch.paid_out", epoch.paid_out, locked)?;
+        }
+        self.refund(currency, locked)?;
+        Ok(locked)
+    }
+
+    /// Settle the current game without a payout; the locked bet stays with the house
+    pub fn forfeit(&mut self) -> Result<Amount, LedgerError> {
+        let forfeited = self.unlock(self.locked_currency())?;
+        if self.locked_currency() == Currency::Real {
+            let epoch = &mut *self.epoch;
+            epoch.house_contributions =
+                add("epoch.house_contributions", epoch.house_contributions, forfeited)?;
+        }
+        Ok(forfeited)
+    }
+
+    /// Credit winnings that are not tied to the current game (e.g. the raffle)
+    pub fn award(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        self.credit(Currency::Real, amount)
+    }
+
+    /// Set aside up to `amount` of the epoch's house take for the raffle pool,
+    /// returning what was set aside
+    pub fn fund_raffle(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        self.take_from_house(amount)
+    }
+
+    /// Hold up to `amount` of the epoch's house take back while a dispute is open,
+    /// returning what was held
+    pub fn hold_house_take(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        self.take_from_house(amount)
+    }
+
+    /// Hand a dispute's held house take back to the house
+    pub fn return_house_take(&mut self, amount: Amount) -> Result<(), LedgerError> {
+        let epoch = &mut *self.epoch;
+        epoch.house_contributions = add(
+            "epoch.house_contributions",
+            epoch.house_contributions,
+            amount,
+        )?;
+        Ok(())
+    }
+
+    /// Credit winnings paid out of the treasury (the epoch's bonus pool)
+    pub fn award_from_treasury(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        let epoch = &mut *self.epoch;
+        epoch.bonus_pool = epoch.bonus_pool.try_sub(amount).map_err(|_| {
+            LedgerError::Insufficient(format!(
+                "Insufficient treasury. Need {} atto, have {} atto.",
+                u128::from(amount),
+                u128::from(epoch.bonus_pool)
+            ))
+        })?;
+        self.credit(Currency::Real, amount)
+    }
+
+    fn take_from_house(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        let epoch = &mut *self.epoch;
+        let funded = amount.min(epoch.house_contributions);
+        epoch.house_contributions = sub(
+            "epoch.house_contributions",
+            epoch.house_contributions,
+            funded,
+        )?;
+        Ok(funded)
+    }
+
+    /// Move up to `amount` of the real-money balance into `frozen_balance`, returning
+    /// what was frozen (winnings already withdrawn cannot be held back)
+    pub fn freeze(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        let current = self.available(Currency::Real);
+        let frozen = amount.min(current);
+        let total_frozen = add("frozen_balance", self.player.frozen_balance, frozen)?;
+        self.player.frozen_balance = total_frozen;
+        self.player.player_balance = sub("player_balance", current, frozen)?;
+        Ok(frozen)
+    }
+
+    /// Take a promotion's budget out of the epoch bonus pool
+    pub fn fund_promotion(&mut self, amount: Amount) -> Result<(), LedgerError> {
+        let epoch = &mut *self.epoch;
+        epoch.bonus_pool = epoch.bonus_pool.try_sub(amount).map_err(|_| {
+            LedgerError::Insufficient(format!(
+                "Insufficient bonus pool. Need {} atto, have {} atto.",
+                u128::from(amount),
+                u128::from(epoch.bonus_pool)
+            ))
+        })?;
+        Ok(())
+    }
+
+    /// Pay `amount` of house money out of the epoch bonus pool, returning what is left
+    pub fn withdraw_treasury(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        self.fund_promotion(amount)?;
+        Ok(self.epoch.bonus_pool)
+    }
+
+    /// Return frozen winnings to the balance, returning the new balance
+    pub fn unfreeze(&mut self, amount: Amount) -> Result<Amount, LedgerError> {
+        let total_frozen = sub("frozen_balance", self.player.frozen_balance, amount)?;
+        let new_balance = add("player_balance", self.available(Currency::Real), amount)?;
+        self.player.frozen_balance = total_frozen;
+        self.player.player_balance = new_balance;
+        Ok(new_balance)
+    }
+
+    /// Reverse frozen winnings: they come off `total_won` and stay with the house
+    pub fn void_frozen(&mut self, amount: Amount) -> Result<(), LedgerError> {
+        let total_frozen = sub("frozen_balance", self.player.frozen_balance, amount)?;
+        let total_won = sub("total_won", self.player.total_won, amount)?;
+        let epoch = &mut *self.epoch;
+        epoch.house_contributions =
+            add("epoch.house_contributions", epoch.house_contributions, amount)?;
+        self.player.frozen_balance = total_frozen;
+        self.player.total_won = total_won;
+        Ok(())
+    }
+
+    /// Empty the current game's prize pool, taking a real-money bet out of the epoch's
+    /// as well, and return the bet it held
+    fn unlock(&mut self, currency: Currency) -> Result<Amount, LedgerError> {
+        let locked = self.player.current_prize_pool;
+        if currency == Currency::Real {
+            let epoch = &mut *self.epoch;
+            epoch.prize_pool = sub("epoch.prize_pool", epoch.prize_pool, locked)?;
+        }
+        self.player.current_prize_pool = Amount::ZERO;
+        Ok(locked)
+    }
+
+    fn credit(&mut self, currency: Currency, amount: Amount) -> Result<Amount, LedgerError> {
+        let new_balance = add(Self::balance_ledger(currency), self.available(currency), amount)?;
+        if currency == Currency::Real {
+            let total_won = add("total_won", self.player.total_won, amount)?;
+            self.player.total_won = total_won;
+        }
+        self.set_available(currency, new_balance);
+        Ok(new_balance)
+    }
+
+    /// Currency of the bet held in the prize pool: the current card's, if any
+    fn locked_currency(&self) -> Currency {
+        self.player
+            .current_card
+            .as_ref()
+            .map(|card| card.currency)
+            .unwrap_or_default()
+    }
+
+    /// Ledger name reported when the balance of `currency` over- or underflows
+    fn balance_ledger(currency: Currency) -> &'static str {
+        match currency {
+            Currency::Play => "points_balance",
+            Currency::Real => "player_balance",
+        }
+    }
+
+    fn set_available(&mut self, currency: Currency, amount: Amount) {
+        match currency {
+            Currency::Play => self.player.points_balance = amount,
+            Currency::Real => self.player.player_balance = amount,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blitz_bingo::{Currency, EpochPool, PlayerAccount};
+    use linera_sdk::linera_base_types::Amount;
+
+    use super::{Escrow, LedgerError};
+
+    fn lin(amount: u128) -> Amount {
+        Amount::from_tokens(amount)
+    }
+
+    #[test]
+    fn test_deposit_and_withdraw() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+
+        assert_eq!(escrow.deposit(lin(10)), Ok(lin(10)));
+        assert_eq!(escrow.withdraw(lin(4)), Ok(lin(6)));
+        assert!(escrow.withdraw(lin(7)).is_err());
+        assert_eq!(escrow.available(Currency::Real), lin(6));
+        assert_eq!(player.total_deposited, lin(10));
+    }
+
+    #[test]
+    fn test_vest_and_release() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.award(lin(10)).unwrap();
+
+        assert_eq!(escrow.vest(lin(6)), Ok(lin(4)));
+        assert_eq!(
+            escrow.vest(lin(5)),
+            Err(LedgerError::Underflow("player_balance"))
+        );
+        assert_eq!(escrow.release_vested(lin(2)), Ok(lin(6)));
+        assert_eq!(
+            escrow.release_vested(lin(5)),
+            Err(LedgerError::Underflow("vesting_balance"))
+        );
+        assert_eq!(player.vesting_balance, lin(4));
+        assert_eq!(player.total_won, lin(10));
+    }
+
+    #[test]
+    fn test_freeze_and_rule() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.award(lin(10)).unwrap();
+        escrow.withdraw(lin(4)).unwrap();
+
+        // Only what is still in the balance can be held back
+        assert_eq!(escrow.freeze(lin(10)), Ok(lin(6)));
+        assert_eq!(escrow.available(Currency::Real), Amount::ZERO);
+        assert_eq!(escrow.unfreeze(lin(2)), Ok(lin(2)));
+        assert!(escrow.unfreeze(lin(5)).is_err());
+        escrow.void_frozen(lin(4)).unwrap();
+
+        assert_eq!(player.frozen_balance, Amount::ZERO);
+        assert_eq!(player.total_won, lin(6));
+        assert_eq!(epoch.house_contributions, lin(4));
+    }
+
+    #[test]
+    fn test_scheduled_withdrawal() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(10)).unwrap();
+
+        assert_eq!(escrow.schedule_withdrawal(lin(4)), Ok(lin(6)));
+        assert!(escrow.schedule_withdrawal(lin(7)).is_err());
+        assert!(escrow.release_withdrawal(lin(5)).is_err());
+        escrow.release_withdrawal(lin(4)).unwrap();
+
+        assert_eq!(player.pending_withdrawals, Amount::ZERO);
+        assert_eq!(player.player_balance, lin(6));
+    }
+
+    #[test]
+    fn test_charge_and_refund() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(10)).unwrap();
+        escrow.refill_points(lin(10));
+
+        escrow.charge(Currency::Real, lin(3)).unwrap();
+        escrow.charge(Currency::Play, lin(2)).unwrap();
+        assert!(escrow.charge(Currency::Real, lin(8)).is_err());
+        assert_eq!(escrow.available(Currency::Real), lin(7));
+        assert_eq!(escrow.available(Currency::Play), lin(8));
+
+        escrow.refund(Currency::Real, lin(3)).unwrap();
+        escrow.refund(Currency::Play, lin(2)).unwrap();
+        assert_eq!(escrow.available(Currency::Real), lin(10));
+        assert_eq!(escrow.available(Currency::Play), lin(10));
+        assert_eq!(player.total_spent, Amount::ZERO);
+    }
+
+    #[test]
+    fn test_lock_and_release() {
+        let mut player = PlayerAccount::default();
+        let mut epoch = EpochPool {
+            house_contributions: lin(3),
+            bonus_pool: lin(5),
+            ..EpochPool::default()
+        };
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(10)).unwrap();
+
+        escrow.lock(Currency::Real, lin(5)).unwrap();
+        assert_eq!(escrow.available(Currency::Real), lin(5));
+        assert_eq!(escrow.release(Currency::Real, lin(12)), Ok(lin(17)));
+
+        assert_eq!(player.current_prize_pool, Amount::ZERO);
+        assert_eq!(player.total_spent, lin(5));
+        assert_eq!(player.total_won, lin(12));
+        // The 7 above the bet came out of the take first, then the bonus pool
+        assert_eq!(epoch.house_contributions, Amount::ZERO);
+        assert_eq!(epoch.bonus_pool, lin(1));
+        assert_eq!(epoch.paid_out, lin(12));
+    }
+
+    #[test]
+    fn test_release_above_bet_needs_house() {
+        let mut player = PlayerAccount::default();
+        let mut epoch = EpochPool {
+            bonus_pool: lin(2),
+            ..EpochPool::default()
+        };
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(10)).unwrap();
+        escrow.lock(Currency::Real, lin(5)).unwrap();
+
+        assert!(matches!(
+            escrow.release(Currency::Real, lin(8)),
+            Err(LedgerError::Insufficient(_))
+        ));
+        assert_eq!(escrow.available(Currency::Real), lin(5));
+        assert_eq!(escrow.release(Currency::Real, lin(7)), Ok(lin(12)));
+        assert_eq!(player.current_prize_pool, Amount::ZERO);
+        assert_eq!(epoch.bonus_pool, Amount::ZERO);
+        assert_eq!(epoch.paid_out, lin(7));
+    }
+
+    #[test]
+    fn test_duel_pot_held_until_paid() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(10)).unwrap();
+
+        escrow.stake_duel_fee(lin(2)).unwrap();
+        escrow.stake_duel_fee(lin(2)).unwrap();
+        assert!(escrow.stake_duel_fee(lin(7)).is_err());
+        assert_eq!(escrow.epoch.prize_pool, lin(4));
+        assert_eq!(escrow.release_duel_pot(lin(4), lin(3)), Ok(lin(9)));
+
+        assert_eq!(epoch.prize_pool, Amount::ZERO);
+        assert_eq!(epoch.house_contributions, lin(1));
+        assert_eq!((epoch.wagered, epoch.paid_out), (lin(4), lin(3)));
+        assert_eq!((player.total_spent, player.total_won), (lin(4), lin(3)));
+    }
+
+    #[test]
+    fn test_lock_fails_without_side_effects() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(10)).unwrap();
+        escrow.lock(Currency::Real, lin(4)).unwrap();
+
+        assert!(escrow.lock(Currency::Real, lin(7)).is_err());
+        assert_eq!(escrow.available(Currency::Real), lin(6));
+        assert_eq!(player.current_prize_pool, lin(4));
+    }
+
+    #[test]
+    fn test_lock_forfeits_previous_game() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(10)).unwrap();
+
+        escrow.lock(Currency::Real, lin(2)).unwrap();
+        escrow.lock(Currency::Real, lin(3)).unwrap();
+        assert_eq!(escrow.available(Currency::Real), lin(5));
+        assert_eq!(escrow.forfeit(), Ok(lin(3)));
+        assert_eq!(escrow.forfeit(), Ok(Amount::ZERO));
+        assert_eq!(player.total_won, Amount::ZERO);
+    }
+
+    #[test]
+    fn test_epoch_tallies_house_contributions() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(20)).unwrap();
+
+        // Forfeited by the next lock, then paid out below the bet
+        escrow.lock(Currency::Real, lin(3)).unwrap();
+        escrow.lock(Currency::Real, lin(5)).unwrap();
+        escrow.release(Currency::Real, lin(1)).unwrap();
+
+        assert_eq!(epoch.wagered, lin(8));
+        assert_eq!(epoch.paid_out, lin(1));
+        assert_eq!(epoch.house_contributions, lin(7));
+    }
+
+    #[test]
+    fn test_epoch_prize_pool_holds_open_bets() {
+        let mut player = PlayerAccount::default();
+        let mut epoch = EpochPool {
+            bonus_pool: lin(3),
+            ..EpochPool::default()
+        };
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(20)).unwrap();
+        escrow.refill_points(lin(100));
+
+        escrow.lock(Currency::Real, lin(4)).unwrap();
+        assert_eq!(escrow.refund_bet(Currency::Real), Ok(lin(4)));
+        escrow.lock(Currency::Real, lin(6)).unwrap();
+        assert_eq!(escrow.epoch.prize_pool, lin(6));
+        escrow.release(Currency::Real, lin(9)).unwrap();
+        assert_eq!(escrow.epoch.prize_pool, Amount::ZERO);
+
+        // Play points never enter the epoch's pool
+        escrow.lock(Currency::Play, lin(10)).unwrap();
+        assert_eq!(epoch.prize_pool, Amount::ZERO);
+        assert_eq!(player.current_prize_pool, lin(10));
+    }
+
+    #[test]
+    fn test_play_points_skip_real_totals() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.refill_points(lin(100));
+
+        escrow.lock(Currency::Play, lin(10)).unwrap();
+        assert_eq!(escrow.release(Currency::Play, lin(25)), Ok(lin(115)));
+        assert_eq!(escrow.available(Currency::Real), Amount::ZERO);
+        assert_eq!(player.total_spent, Amount::ZERO);
+        assert_eq!(player.total_won, Amount::ZERO);
+        assert_eq!(epoch, EpochPool::default());
+    }
+
+    #[test]
+    fn test_award_credits_real_balance() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(1)).unwrap();
+        escrow.lock(Currency::Real, lin(1)).unwrap();
+
+        assert_eq!(escrow.award(lin(3)), Ok(lin(3)));
+        // Awards leave the current game's locked bet alone
+        assert_eq!(player.current_prize_pool, lin(1));
+        assert_eq!(player.total_won, lin(3));
+    }
+
+    #[test]
+    fn test_overflow_is_reported_without_saturating() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(Amount::MAX).unwrap();
+
+        assert_eq!(
+            escrow.deposit(Amount::from_attos(1)),
+            Err(LedgerError::Overflow("player_balance"))
+        );
+        assert_eq!(escrow.award(lin(1)), Err(LedgerError::Overflow("player_balance")));
+        assert_eq!(escrow.available(Currency::Real), Amount::MAX);
+        assert_eq!(player.total_deposited, Amount::MAX);
+        assert_eq!(player.total_won, Amount::ZERO);
+    }
+
+    #[test]
+    fn test_total_overflow_fails_the_charge() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        player.total_spent = Amount::MAX;
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+        escrow.deposit(lin(10)).unwrap();
+
+        assert_eq!(
+            escrow.charge(Currency::Real, lin(1)),
+            Err(LedgerError::Overflow("total_spent"))
+        );
+        assert_eq!(escrow.available(Currency::Real), lin(10));
+    }
+
+    #[test]
+    fn test_refund_underflow() {
+        let (mut player, mut epoch) = (PlayerAccount::default(), EpochPool::default());
+        let mut escrow = Escrow::new(&mut player, &mut epoch);
+
+        // Refunding a fee that was never charged would take total_spent below zero
+        assert_eq!(
+            escrow.refund(Currency::Real, lin(1)),
+            Err(LedgerError::Underflow("total_spent"))
+        );
+        assert_eq!(escrow.available(Currency::Real), Amount::ZERO);
+        assert!(matches!(escrow.withdraw(lin(1)), Err(LedgerError::Insufficient(_))));
+    }
+}
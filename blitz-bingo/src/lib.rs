@@ -1,13 +1,25 @@
 // FlashPort Phase 1: Dice-Bingo Gaming Engine
 // ABI Definitions with Token Economics and Cross-Chain Messaging
 
-use async_graphql::{Enum, InputObject, Request, Response, SimpleObject};
-use linera_sdk::linera_base_types::{AccountOwner, Amount, ChainId, ContractAbi, ServiceAbi};
+use async_graphql::{ComplexObject, Enum, InputObject, Request, Response, SimpleObject, ID};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use linera_sdk::linera_base_types::{
+    AccountOwner, AccountSignature, Amount, ApplicationId, BcsHashable, BcsSignable, ChainId,
+    ContractAbi, CryptoHash, ServiceAbi,
+};
 use serde::{Deserialize, Serialize};
 
+pub mod amounts;
+pub mod probability;
+pub mod rules;
+
 /// Main ABI type for the FlashPort application
 pub struct FlashportAbi;
 
+/// Version of the operation/response format. Bumped on any breaking ABI change;
+/// operations wrapped in `Operation::Versioned` must carry this exact value.
+pub const ABI_VERSION: u32 = 1;
+
 // === Configuration Constants ===
 /// Minimum bet amount (1 LINERA = 1_000_000_000_000_000_000 atto)
 pub const MIN_BET: u128 = 1_000_000_000_000_000_000;
@@ -15,6 +27,144 @@ pub const MIN_BET: u128 = 1_000_000_000_000_000_000;
 pub const MAX_BET: u128 = 100_000_000_000_000_000_000;
 /// Cost per roll (0.05 LINERA = 50_000_000_000_000_000 atto)
 pub const ROLL_COST: u128 = 50_000_000_000_000_000;
+/// Cost per roll at the Insurance table (0.02 LINERA)
+pub const INSURANCE_ROLL_COST: u128 = 20_000_000_000_000_000;
+/// Play points granted on instantiation and on refill (1000 points, same 10^18 scale as LINERA)
+pub const PLAY_POINTS_GRANT: u128 = 1_000_000_000_000_000_000_000;
+/// Deals tried per card when `max_card_difficulty` rejects a layout
+pub const MAX_CARD_DEALS: u32 = 8;
+/// Layouts `rules::deal_constrained` draws before keeping one with an unbalanced row
+pub const MAX_CONSTRAINED_DEALS: u32 = 64;
+/// Recipients one AirdropBonus batch may credit
+pub const MAX_AIRDROP_RECIPIENTS: usize = 100;
+/// Player accounts one ExtendClaimDeadlines page may visit
+pub const MAX_CLAIM_EXTENSION_PAGE: u32 = 100;
+/// Longest a signed relay envelope may stay valid, which bounds how long its nonce
+/// must be remembered
+pub const MAX_RELAY_TTL_SECS: u64 = 3600;
+/// Longest an admin query grant may stay valid (see `AdminQueryGrant`)
+pub const MAX_ADMIN_GRANT_TTL_SECS: u64 = 3600;
+/// Median difficulty score of a freshly dealt card (see `probability::difficulty_score`)
+pub const MEDIAN_CARD_DIFFICULTY: u32 = 4_771;
+/// Cells a player may buy on one card with BuyMark
+pub const MAX_MARK_PURCHASES: u32 = 2;
+/// Fee for a bought mark per step closer to bingo, as a share of the card's current
+/// tier payout (25%: half the payout five cells out, 125% for the last cell)
+pub const MARK_PURCHASE_FEE_BPS: u32 = 2_500;
+/// Rolls after a LockMultiplier that still pay at the locked tier
+pub const MULTIPLIER_LOCK_ROLLS: u32 = 5;
+/// Price of a LockMultiplier, as a share of the card's current tier payout (10%)
+pub const MULTIPLIER_LOCK_FEE_BPS: u32 = 1_000;
+/// Most dice a season mode may roll at once
+pub const MAX_SEASON_DICE: u8 = 8;
+/// Grace period between PurgeMyData and its ConfirmDataPurge (24 hours)
+pub const DATA_PURGE_GRACE_SECS: u64 = 24 * 60 * 60;
+/// Longest reason accepted with a dispute, in bytes
+pub const MAX_DISPUTE_REASON_LEN: usize = 500;
+/// Longest target asset name accepted by SetPayoutRoute, in bytes
+pub const MAX_TARGET_ASSET_LEN: usize = 32;
+/// Maximum rolls a single AutoRoll operation may perform
+pub const MAX_AUTO_ROLLS: u32 = 100;
+/// Session permission bits (see `GameSession::permissions`): roll, buy marks and lock
+/// multipliers on the open card
+pub const SESSION_CAN_ROLL: u32 = 1;
+/// Session permission bit: start, sponsor or enter games
+pub const SESSION_CAN_NEW_GAME: u32 = 1 << 1;
+/// Session permission bit: claim prizes
+pub const SESSION_CAN_CLAIM: u32 = 1 << 2;
+/// Session permission bit: withdraw, or change where payouts are sent
+pub const SESSION_CAN_WITHDRAW: u32 = 1 << 3;
+/// Permissions of a session started without a mask. Keys handed to streaming or
+/// automation setups should leave out SESSION_CAN_WITHDRAW.
+pub const ALL_SESSION_PERMISSIONS: u32 =
+    SESSION_CAN_ROLL | SESSION_CAN_NEW_GAME | SESSION_CAN_CLAIM | SESSION_CAN_WITHDRAW;
+/// Inter-roll intervals a session needs before its cadence can be flagged
+pub const CADENCE_MIN_SAMPLES: u64 = 10;
+/// A gap between a session's operations longer than this many mean roll intervals
+/// is treated as chain congestion (see `GameConfig::congestion_pause_max_secs`)
+pub const CONGESTION_GAP_FACTOR: u64 = 10;
+/// Default maximum GraphQL query depth accepted by the service
+pub const DEFAULT_MAX_QUERY_DEPTH: usize = 16;
+/// Default maximum GraphQL query complexity (one point per field) accepted by the service
+pub const DEFAULT_MAX_QUERY_COMPLEXITY: usize = 2_000;
+/// Blackout-continuation payout bonus per completed line beyond the first (10% of bet)
+pub const BLACKOUT_LINE_BONUS_BPS: u32 = 1_000;
+/// Classic payout bonus per extra line completed by the winning roll (50% of bet)
+pub const MULTI_LINE_BONUS_BPS: u32 = 5_000;
+/// Length of one raffle round (weekly draw)
+pub const RAFFLE_PERIOD_SECS: u64 = 7 * 24 * 60 * 60;
+/// Share of every real-money bet set aside from the house take for the raffle pool (1%)
+pub const RAFFLE_POOL_BPS: u32 = 100;
+/// Length of one prize pool accounting epoch (daily)
+pub const EPOCH_PERIOD_SECS: u64 = 24 * 60 * 60;
+/// Installments a vesting payout is split into when the config leaves it at 0
+pub const DEFAULT_VESTING_INSTALLMENTS: u32 = 4;
+/// Owners kept in the winnings leaderboard projection
+pub const LEADERBOARD_SIZE: usize = 10;
+/// Closed epochs the rolling RTP projection covers, besides the current one
+pub const ROLLING_RTP_EPOCHS: usize = 7;
+/// Blocks of randomness inputs kept in the entropy history
+pub const ENTROPY_HISTORY_LEN: usize = 256;
+/// Diagnostics events kept while `FlashportParameters::diagnostics` is on (oldest dropped first)
+pub const DIAGNOSTICS_CAPACITY: usize = 200;
+/// Minimum time between two PublishStats operations (hourly)
+pub const STATS_PUBLISH_INTERVAL_SECS: u64 = 60 * 60;
+/// Shortest governance timelock, whatever `GameConfig::governance_timelock_secs` says
+pub const MIN_GOVERNANCE_TIMELOCK_SECS: u32 = 60 * 60;
+/// Event stream that PublishStats snapshots are emitted on
+pub const STATS_STREAM_NAME: &[u8] = b"stats";
+/// Version of the StatsSnapshot layout; bumped only on breaking changes so
+/// external indexers can keep decoding older events
+pub const STATS_SCHEMA_VERSION: u32 = 1;
+/// Event stream that EndSession summaries are emitted on
+pub const SESSION_STREAM_NAME: &[u8] = b"sessions";
+/// Event stream that budget alerts are emitted on, for companion apps to notify the player
+pub const ALERTS_STREAM_NAME: &[u8] = b"alerts";
+/// Tournament pot share per finishing rank; shares of empty ranks go to the finishers
+pub const TOURNAMENT_PAYOUT_BPS: [u32; 3] = [5_000, 3_000, 2_000];
+/// House rake on a settled duel pot, in basis points
+pub const DUEL_RAKE_BPS: u32 = 500;
+/// Time a duel player has to take their turn before the other player can claim the pot
+pub const DUEL_TURN_SECS: u64 = 10 * 60;
+/// Rolls a DemoGame plays at most; it stops early at the first line
+pub const DEMO_GAME_ROLLS: u32 = 30;
+/// Rolls a SyndicateGame plays at most; a game without a line by then is lost
+pub const SYNDICATE_GAME_MAX_ROLLS: u32 = 100;
+/// Members a syndicate may hold, its manager included
+pub const MAX_SYNDICATE_MEMBERS: usize = 50;
+/// Longest syndicate name, in bytes
+pub const MAX_SYNDICATE_NAME_LEN: usize = 32;
+/// Rolls a PointsPerMark game plays; the last one settles it on its points
+pub const POINTS_GAME_ROLLS: u32 = 20;
+/// Points a marked cell scores in a PointsPerMark game by how far its number lies from
+/// 14, the likeliest four-dice sum: the rare edge numbers score most
+pub const MARK_POINTS_BY_DISTANCE: [u32; 11] = [1, 1, 1, 2, 2, 3, 4, 5, 6, 8, 10];
+/// Fewest points of each PointsPerMark payout tier, best first; a game short of the
+/// last one pays nothing
+pub const POINTS_TIER_MIN_POINTS: [u32; 5] = [45, 38, 32, 27, 22];
+/// Last roll count of each multiplier tier, best first; later wins fall in the final tier
+pub const MULTIPLIER_TIER_MAX_ROLLS: [u32; 6] = [9, 14, 19, 24, 34, 44];
+/// Name of each multiplier tier, best first
+pub const MULTIPLIER_TIER_NAMES: [&str; 7] =
+    ["LEGENDARY", "EPIC", "RARE", "GOOD", "NORMAL", "REDUCED", "MINIMAL"];
+/// Chance that a fresh card's first bingo lands in each multiplier tier, in basis points
+/// (precomputed from `probability::bingo_chance_within`; the final tier takes the rest)
+pub const TIER_WIN_CHANCE_BPS: [u32; 7] = [308, 1_039, 1_465, 1_442, 2_173, 1_303, 2_270];
+/// Classic payout curve while `GameConfig::payout_curve` is empty: last roll count,
+/// numerator, denominator and label of each tier, best first
+pub const DEFAULT_PAYOUT_CURVE: [(u32, u32, u32, &str); 7] = [
+    (MULTIPLIER_TIER_MAX_ROLLS[0], 10, 1, "10x"),
+    (MULTIPLIER_TIER_MAX_ROLLS[1], 5, 1, "5x"),
+    (MULTIPLIER_TIER_MAX_ROLLS[2], 3, 1, "3x"),
+    (MULTIPLIER_TIER_MAX_ROLLS[3], 2, 1, "2x"),
+    (MULTIPLIER_TIER_MAX_ROLLS[4], 12, 10, "1.2x"),
+    (MULTIPLIER_TIER_MAX_ROLLS[5], 8, 10, "0.8x"),
+    (u32::MAX, 2, 10, "0.2x"),
+];
+/// Most tiers a payout curve may have
+pub const MAX_PAYOUT_TIERS: usize = 16;
+/// Longest payout tier label, in bytes
+pub const MAX_PAYOUT_LABEL_LEN: usize = 16;
 
 // Legacy constants for backward compatibility
 /// Entry fee (deprecated - now using bet_amount)
@@ -32,26 +182,85 @@ pub enum Operation {
     StartSession {
         /// How long the session should last (in seconds)
         expires_in_secs: u64,
+        /// `SESSION_CAN_*` bits the session may use; operations outside them are refused
+        /// until it ends (None = ALL_SESSION_PERMISSIONS)
+        permissions: Option<u32>,
     },
 
     /// End the current session
     EndSession,
 
     /// Start a new bingo game with a bet amount
-    /// Requires bet_amount between MIN_BET (1 LINERA) and MAX_BET (100 LINERA)
+    /// Requires bet_amount between MIN_BET (1 LINERA) and MAX_BET (100 LINERA); VIP
+    /// owners may bet up to the high-roller table's limit
     /// The bet is held in escrow until game ends
     NewGame {
         /// Bet amount in atto LINERA (1 LINERA = 10^18 atto)
         bet_amount_atto: u128,
+        /// Which balance the bet and roll fees are charged to
+        currency: Currency,
+        /// Game rules to play the card under
+        mode: GameMode,
+        /// Split the bet across two cards sharing as few numbers as possible; the
+        /// first to bingo pays (Classic mode only)
+        hedge: bool,
+        /// Play under a published game template, whose mode replaces `mode` (None =
+        /// the table's standard rules)
+        template_id: Option<u64>,
+    },
+
+    /// Start a Classic real-money game under a live season mode: the card is dealt from
+    /// the mode's number range and every roll throws its number of dice
+    NewSeasonGame {
+        mode_id: u64,
+        /// Bet amount in atto LINERA (1 LINERA = 10^18 atto)
+        bet_amount_atto: u128,
+    },
+
+    /// Buy a Classic real-money game for another owner: the bet is escrowed from this
+    /// chain's balance, but only `for_owner` may roll or claim and the payout goes to them
+    SponsorGame {
+        /// Player the card and any winnings belong to
+        for_owner: AccountOwner,
+        /// Bet amount in atto LINERA (1 LINERA = 10^18 atto)
+        bet_amount_atto: u128,
     },
 
     /// Roll 4 dice and mark the sum on the card
-    /// Requires payment of ROLL_COST (0.1 LINERA)
+    /// Requires payment of ROLL_COST (0.05 LINERA)
     /// This is the main game operation - atomic: roll -> sum -> mark -> check win
     RollAndMatch,
+
+    /// Pay to mark one unmarked cell without rolling (at most MAX_MARK_PURCHASES per
+    /// game); the fee grows as the card gets closer to bingo (see MARK_PURCHASE_FEE_BPS)
+    BuyMark {
+        row: u8,
+        col: u8,
+    },
+
+    /// Pay to keep the current multiplier tier for the next MULTIPLIER_LOCK_ROLLS rolls
+    /// (once per game), so a bingo just past a tier boundary still pays the old tier
+    LockMultiplier,
+
+    /// Roll repeatedly in one operation (capped at MAX_AUTO_ROLLS)
+    AutoRoll {
+        /// Number of paid rolls to attempt
+        count: u32,
+        /// Stop as soon as a line completes
+        stop_on_bingo: bool,
+        /// Return a compact `AggregatedRolls` summary instead of every `RollResult`
+        aggregate: bool,
+    },
+
+    /// Resume an AutoRoll batch paused by the per-block roll cap (a plain
+    /// RollAndMatch rolls once and leaves the batch paused)
+    ContinueAutoRoll,
     
     /// Claim winnings after a bingo
     ClaimPrize,
+
+    /// Move every unlocked installment of the signer's vesting prizes into the balance
+    ClaimVestedTranche,
     
     // === Dice-Bingo Operations ===
     
@@ -59,12 +268,613 @@ pub enum Operation {
     Deposit {
         /// Amount to deposit in atto LINERA (1 LINERA = 10^18 atto)
         amount_atto: u128,
+        /// Player whose account a custodian (e.g. an exchange) deposits into on their
+        /// behalf (None = signer)
+        credit_to: Option<AccountOwner>,
     },
     
     /// Withdraw available balance
     Withdraw {
         amount: Amount,
     },
+
+    /// Withdraw everything that is not locked or reserved, down to the last atto
+    WithdrawAll,
+
+    /// Move `amount` out of the balance into a time lock that anyone can release once
+    /// `release_at_micros` has passed (cooling-off periods, compliance holds)
+    ScheduleWithdrawal {
+        amount: Amount,
+        release_at_micros: u64,
+    },
+
+    /// Pay out a scheduled withdrawal whose time lock has passed (anyone may call)
+    ReleaseWithdrawal {
+        withdrawal_id: u64,
+    },
+
+    /// Top play points back up to PLAY_POINTS_GRANT once they run below MIN_BET
+    RefillPoints,
+
+    /// Replace the player's self-imposed preferences
+    SetPreferences {
+        preferences: PlayerPreferences,
+    },
+
+    /// Have the signer's real-money winnings forwarded through the payout bridge as
+    /// `target_asset` (None = keep them in the internal balance)
+    SetPayoutRoute {
+        target_asset: Option<String>,
+    },
+
+    /// Ask for the signer's personal data to be purged; it can be confirmed with
+    /// ConfirmDataPurge once DATA_PURGE_GRACE_SECS have passed (no session may be active)
+    PurgeMyData,
+
+    /// Carry out the signer's purge request after its grace period
+    ConfirmDataPurge,
+
+    /// Withdraw the signer's pending purge request
+    CancelDataPurge,
+
+    // === Dice Skins ===
+
+    /// Buy a skin from `DICE_SKINS` into the signer's inventory
+    BuySkin {
+        skin_id: u32,
+    },
+
+    /// Show an owned skin on the signer's dice from their next game (None = plain dice)
+    EquipSkin {
+        skin_id: Option<u32>,
+    },
+
+    // === Game Templates ===
+
+    /// Publish a game template (`template_id` 0) or replace one the signer published
+    UpsertGameTemplate {
+        template: GameTemplate,
+    },
+
+    // === Admin Operations ===
+
+    /// Replace the game configuration (admin only). Like every governed operation (see
+    /// `Operation::is_governed`) this only proposes the change; ExecuteProposal
+    /// applies it once the governance timelock has passed.
+    UpdateConfig {
+        config: GameConfig,
+    },
+
+    /// Start, replace or (with None) stop the A/B configuration experiment (admin only)
+    SetExperiment {
+        experiment: Option<Experiment>,
+    },
+
+    /// Open, replace or (with None) close the high-roller table (admin only)
+    SetHighRollerTable {
+        table: Option<HighRollerTable>,
+    },
+
+    /// Add a season mode (`mode_id` 0) or replace an existing one, e.g. to enable or
+    /// disable it (admin only)
+    UpsertSeasonMode {
+        mode: SeasonMode,
+    },
+
+    /// Push the deadline of every pending prize out by `window_secs`, e.g. after a
+    /// validator outage kept players from claiming (admin only, logged publicly).
+    /// Accounts are visited a page at a time in key order; repeat with the returned
+    /// `next_after` until it comes back None
+    ExtendClaimDeadlines {
+        window_secs: u64,
+        reason: String,
+        /// Last owner of the previous page; None starts from the first account
+        after: Option<AccountOwner>,
+        /// Accounts to visit, between 1 and `MAX_CLAIM_EXTENSION_PAGE`
+        limit: u32,
+    },
+
+    /// Stop or resume new games and rolls; claims and withdrawals stay open either
+    /// way (admin only, logged publicly)
+    SetPaused {
+        paused: bool,
+        reason: String,
+    },
+
+    /// Pay house money out of the epoch bonus pool to the operator (admin only,
+    /// logged publicly). This only proposes the withdrawal; see ExecuteProposal.
+    WithdrawTreasury {
+        amount: Amount,
+        reason: String,
+    },
+
+    /// Apply a pending governed change whose timelock has passed (admin only, logged
+    /// publicly)
+    ExecuteProposal {
+        proposal_id: u64,
+    },
+
+    /// Drop a pending proposal without applying it (admin only, logged publicly)
+    CancelProposal {
+        proposal_id: u64,
+        reason: String,
+    },
+
+    /// Block `owner`'s withdrawals for a compliance review, replacing any hold already
+    /// in place; gameplay is unaffected. The hold lapses after `expires_in_secs`
+    /// (0 = until released). Admin only, logged publicly.
+    PlaceWithdrawalHold {
+        owner: AccountOwner,
+        reason: HoldReason,
+        expires_in_secs: u64,
+        note: String,
+    },
+
+    /// Lift the compliance hold on `owner`'s withdrawals (admin only, logged publicly)
+    ReleaseWithdrawalHold {
+        owner: AccountOwner,
+        note: String,
+    },
+
+    /// Discount roll fees by `discount_bps` between the two timestamps (admin only)
+    ScheduleHappyHour {
+        starts_at_micros: u64,
+        ends_at_micros: u64,
+        discount_bps: u32,
+    },
+
+    /// Remove a scheduled or running happy hour (admin only)
+    CancelHappyHour {
+        happy_hour_id: u64,
+    },
+
+    /// Credit promotional bonuses (owner, atto) out of the epoch bonus pool in one
+    /// all-or-nothing batch of at most `MAX_AIRDROP_RECIPIENTS` (admin only)
+    AirdropBonus {
+        recipients: Vec<(AccountOwner, u128)>,
+    },
+
+    // === Raffle ===
+
+    /// Draw the weekly raffle once its draw time has passed (anyone may trigger it)
+    DrawRaffle,
+
+    // === Tournaments ===
+
+    /// Open a house-card tournament: one card layout and dice stream for every
+    /// entrant, ranked by rolls-to-bingo (admin only)
+    CreateTournament {
+        /// Entry fee in atto LINERA, between MIN_BET and MAX_BET
+        entry_fee_atto: u128,
+        /// How long entries and rolls are accepted
+        duration_secs: u64,
+    },
+
+    /// Pay the entry fee and join a tournament (one entry per owner)
+    EnterTournament {
+        tournament_id: u64,
+    },
+
+    /// Take the signer's next roll from the tournament's dice stream
+    TournamentRoll {
+        tournament_id: u64,
+    },
+
+    /// Rank the finishers and split the pot once the tournament has closed
+    /// (anyone may trigger it)
+    SettleTournament {
+        tournament_id: u64,
+    },
+
+    // === Duels ===
+
+    /// Open a two-player duel and take its first seat. Each roll costs `roll_fee_atto`
+    /// (between ROLL_COST and MAX_BET), paid into a pot the winner takes.
+    OpenDuel {
+        roll_fee_atto: u128,
+    },
+
+    /// Take the second seat of a waiting duel; the opener rolls first
+    JoinDuel {
+        duel_id: u64,
+    },
+
+    /// Pay the roll fee into the pot and roll for the signer's own card, on their
+    /// turn only. The first to complete a line takes the pot less DUEL_RAKE_BPS.
+    DuelRoll {
+        duel_id: u64,
+    },
+
+    /// Take the pot, less DUEL_RAKE_BPS, of a duel whose other player let their turn
+    /// run past DUEL_TURN_SECS
+    ClaimDuelPot {
+        duel_id: u64,
+    },
+
+    // === Syndicates ===
+
+    /// Start a syndicate managed by the signer, who joins it as its first member
+    CreateSyndicate {
+        name: String,
+    },
+
+    /// Add `member` to the signer's syndicate, or change their bet limit (manager
+    /// only). `max_bet_atto` = 0 makes the member a backer who cannot play.
+    SetSyndicateMember {
+        syndicate_id: u64,
+        member: AccountOwner,
+        max_bet_atto: u128,
+    },
+
+    /// Pay into the bankroll of a syndicate the signer belongs to, raising their stake
+    ContributeToSyndicate {
+        syndicate_id: u64,
+        amount_atto: u128,
+    },
+
+    /// Leave a syndicate and take out the signer's stake. The manager can only leave
+    /// once every other member has.
+    LeaveSyndicate {
+        syndicate_id: u64,
+    },
+
+    /// Play a Classic game for the syndicate, staked from its bankroll within the
+    /// signer's bet limit. It rolls (at ROLL_COST each, also from the bankroll) until
+    /// the first line or SYNDICATE_GAME_MAX_ROLLS, and the net result is shared out
+    /// over the members pro rata to their stakes.
+    SyndicateGame {
+        syndicate_id: u64,
+        bet_amount_atto: u128,
+    },
+
+    // === Lobby Registry ===
+
+    /// Announce this deployment and its parameters to the lobby chain (admin only)
+    RegisterWithLobby,
+
+    // === Public Statistics ===
+
+    /// Emit a StatsSnapshot event for external aggregators (anyone may trigger it,
+    /// at most once per STATS_PUBLISH_INTERVAL_SECS)
+    PublishStats,
+
+    // === Operator Outbox ===
+
+    /// Drop every outbox entry with an id up to and including `up_to_id` once the
+    /// operator has delivered it (admin only)
+    AckOutbox {
+        up_to_id: u64,
+    },
+
+    /// Call the partner application of a queued `PartnerNotification` outbox entry
+    /// (anyone may deliver). A partner that fails its call fails only this operation,
+    /// and the notification stays queued.
+    DeliverPartnerNotification {
+        entry_id: u64,
+    },
+
+    // === Disputes ===
+
+    /// Contest a settled real-money game within the configured dispute window; its
+    /// winnings are frozen until the admin rules
+    DisputeGame {
+        game_id: u64,
+        reason: String,
+    },
+
+    /// Rule on an open dispute, optionally awarding `award_atto` when upholding it
+    /// (admin only, logged publicly)
+    ResolveDispute {
+        dispute_id: u64,
+        outcome: DisputeOutcome,
+        award_atto: u128,
+        note: String,
+    },
+
+    // === Attestations ===
+
+    /// Produce (or return the existing) hashed attestation of a claimed game's
+    /// outcome for bridges and oracles on other chains (anyone may call)
+    AttestGame {
+        game_id: u64,
+    },
+
+    // === Health Checks ===
+
+    /// Validate the chain's internal invariants and record the result as the
+    /// `lastHealthCheck` (anyone may call, e.g. from an operator's cron job)
+    HealthCheck,
+
+    // === Storage ===
+
+    /// Fold everything but the newest `keep_last` roll records, ledger receipts and
+    /// settled tournaments and duels into `historySummary` and delete them (admin
+    /// only; also runs at each epoch rollover when `history_retention` is set)
+    PruneHistory {
+        keep_last: u32,
+    },
+
+    // === Demo ===
+
+    /// Play a whole Classic game at once on a throwaway card, up to DEMO_GAME_ROLLS
+    /// rolls, for players trying the game out. Needs no funds or session: nothing is
+    /// charged or paid and the signer's own game is untouched.
+    DemoGame,
+
+    // === Versioning ===
+
+    /// Report the contract's ABI_VERSION
+    GetVersion,
+
+    /// Run `operation` only if `abi_version` matches the contract's ABI_VERSION.
+    /// Clients wrap operations in this so an upgrade rejects them instead of
+    /// misreading them; untagged operations are always accepted.
+    Versioned {
+        abi_version: u32,
+        operation: Box<Operation>,
+    },
+
+    // === Sequencing ===
+
+    /// Run `operation` only inside the session with id `requires_session_id`. A block
+    /// chaining StartSession, NewGame and rolls tags the later operations with the id
+    /// the StartSession will issue; if it is not the active one when they run, the
+    /// whole block is rejected rather than half-applied.
+    Sequenced {
+        requires_session_id: u64,
+        operation: Box<Operation>,
+    },
+
+    // === Relaying ===
+
+    /// Run `operation` as `envelope.owner`, who signed it off-chain, so a relayer can
+    /// submit it in a block of its own. The signature covers the operation and its
+    /// envelope; see `RelayEnvelope` for what makes it single-use.
+    Relayed {
+        envelope: RelayEnvelope,
+        signature: AccountSignature,
+        operation: Box<Operation>,
+    },
+}
+
+// === Authorization ===
+
+/// What an operation needs from its caller before it runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum OperationAuth {
+    /// Nothing beyond a block on this chain
+    Public,
+    /// An authenticated signer
+    Signer,
+    /// An active, unexpired session, or quick play (see `Operation::allows_quick_play`)
+    Session,
+    /// An active session or quick play, and while a sponsored game is open, its
+    /// sponsored player as the signer: the sponsor has handed play of that card over
+    /// to them
+    Delegate,
+    /// The instance admin as the signer
+    Admin,
+}
+
+impl Operation {
+    /// The authorization the contract checks before dispatching this operation.
+    /// The match has no catch-all, so a new operation cannot be added without
+    /// deciding who may run it.
+    pub fn required_auth(&self) -> OperationAuth {
+        match self {
+            Operation::NewGame { .. }
+            | Operation::NewSeasonGame { .. }
+            | Operation::SponsorGame { .. } => OperationAuth::Session,
+
+            Operation::RollAndMatch
+            | Operation::BuyMark { .. }
+            | Operation::LockMultiplier
+            | Operation::AutoRoll { .. }
+            | Operation::ContinueAutoRoll
+            | Operation::ClaimPrize
+            | Operation::ClaimVestedTranche => OperationAuth::Delegate,
+
+            Operation::SetPayoutRoute { .. }
+            | Operation::PurgeMyData
+            | Operation::ConfirmDataPurge
+            | Operation::CancelDataPurge
+            | Operation::BuySkin { .. }
+            | Operation::EquipSkin { .. }
+            | Operation::UpsertGameTemplate { .. }
+            | Operation::EnterTournament { .. }
+            | Operation::TournamentRoll { .. }
+            | Operation::OpenDuel { .. }
+            | Operation::JoinDuel { .. }
+            | Operation::DuelRoll { .. }
+            | Operation::ClaimDuelPot { .. }
+            | Operation::CreateSyndicate { .. }
+            | Operation::SetSyndicateMember { .. }
+            | Operation::ContributeToSyndicate { .. }
+            | Operation::LeaveSyndicate { .. }
+            | Operation::SyndicateGame { .. } => OperationAuth::Signer,
+
+            Operation::UpdateConfig { .. }
+            | Operation::SetExperiment { .. }
+            | Operation::SetHighRollerTable { .. }
+            | Operation::UpsertSeasonMode { .. }
+            | Operation::ExtendClaimDeadlines { .. }
+            | Operation::SetPaused { .. }
+            | Operation::WithdrawTreasury { .. }
+            | Operation::ExecuteProposal { .. }
+            | Operation::CancelProposal { .. }
+            | Operation::PlaceWithdrawalHold { .. }
+            | Operation::ReleaseWithdrawalHold { .. }
+            | Operation::ScheduleHappyHour { .. }
+            | Operation::CancelHappyHour { .. }
+            | Operation::AirdropBonus { .. }
+            | Operation::CreateTournament { .. }
+            | Operation::RegisterWithLobby
+            | Operation::AckOutbox { .. }
+            | Operation::ResolveDispute { .. }
+            | Operation::PruneHistory { .. } => OperationAuth::Admin,
+
+            Operation::StartSession { .. }
+            | Operation::EndSession
+            | Operation::Deposit { .. }
+            | Operation::Withdraw { .. }
+            | Operation::WithdrawAll
+            | Operation::ScheduleWithdrawal { .. }
+            | Operation::ReleaseWithdrawal { .. }
+            | Operation::RefillPoints
+            | Operation::SetPreferences { .. }
+            | Operation::DrawRaffle
+            | Operation::SettleTournament { .. }
+            | Operation::PublishStats
+            | Operation::DisputeGame { .. }
+            | Operation::AttestGame { .. }
+            | Operation::HealthCheck
+            | Operation::DemoGame
+            | Operation::DeliverPartnerNotification { .. }
+            | Operation::GetVersion => OperationAuth::Public,
+
+            // Tags take on the requirement of the operation they wrap
+            Operation::Versioned { operation, .. }
+            | Operation::Sequenced { operation, .. }
+            | Operation::Relayed { operation, .. } => operation.required_auth(),
+        }
+    }
+
+    /// Whether this admin operation changes the game's rules, promotions or treasury,
+    /// and so is queued as a governance proposal instead of applied at once. Pausing,
+    /// compliance holds and player-protective rescues stay immediate.
+    pub fn is_governed(&self) -> bool {
+        matches!(
+            self,
+            Operation::UpdateConfig { .. }
+                | Operation::SetExperiment { .. }
+                | Operation::SetHighRollerTable { .. }
+                | Operation::UpsertSeasonMode { .. }
+                | Operation::ScheduleHappyHour { .. }
+                | Operation::CancelHappyHour { .. }
+                | Operation::WithdrawTreasury { .. }
+        )
+    }
+
+    /// Whether a chain owner may run this operation without a session by signing it
+    /// directly (quick play): enough to deal, roll and claim a single game
+    pub fn allows_quick_play(&self) -> bool {
+        matches!(
+            self,
+            Operation::NewGame { .. } | Operation::RollAndMatch | Operation::ClaimPrize
+        )
+    }
+
+    /// The variant name, used as the key for per-operation metrics
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operation::StartSession { .. } => "StartSession",
+            Operation::EndSession => "EndSession",
+            Operation::NewGame { .. } => "NewGame",
+            Operation::NewSeasonGame { .. } => "NewSeasonGame",
+            Operation::SponsorGame { .. } => "SponsorGame",
+            Operation::RollAndMatch => "RollAndMatch",
+            Operation::BuyMark { .. } => "BuyMark",
+            Operation::LockMultiplier => "LockMultiplier",
+            Operation::AutoRoll { .. } => "AutoRoll",
+            Operation::ContinueAutoRoll => "ContinueAutoRoll",
+            Operation::ClaimPrize => "ClaimPrize",
+            Operation::ClaimVestedTranche => "ClaimVestedTranche",
+            Operation::Deposit { .. } => "Deposit",
+            Operation::Withdraw { .. } => "Withdraw",
+            Operation::WithdrawAll => "WithdrawAll",
+            Operation::ScheduleWithdrawal { .. } => "ScheduleWithdrawal",
+            Operation::ReleaseWithdrawal { .. } => "ReleaseWithdrawal",
+            Operation::RefillPoints => "RefillPoints",
+            Operation::SetPreferences { .. } => "SetPreferences",
+            Operation::SetPayoutRoute { .. } => "SetPayoutRoute",
+            Operation::PurgeMyData => "PurgeMyData",
+            Operation::ConfirmDataPurge => "ConfirmDataPurge",
+            Operation::CancelDataPurge => "CancelDataPurge",
+            Operation::BuySkin { .. } => "BuySkin",
+            Operation::EquipSkin { .. } => "EquipSkin",
+            Operation::UpsertGameTemplate { .. } => "UpsertGameTemplate",
+            Operation::UpdateConfig { .. } => "UpdateConfig",
+            Operation::SetExperiment { .. } => "SetExperiment",
+            Operation::SetHighRollerTable { .. } => "SetHighRollerTable",
+            Operation::UpsertSeasonMode { .. } => "UpsertSeasonMode",
+            Operation::ExtendClaimDeadlines { .. } => "ExtendClaimDeadlines",
+            Operation::SetPaused { .. } => "SetPaused",
+            Operation::WithdrawTreasury { .. } => "WithdrawTreasury",
+            Operation::ExecuteProposal { .. } => "ExecuteProposal",
+            Operation::CancelProposal { .. } => "CancelProposal",
+            Operation::PlaceWithdrawalHold { .. } => "PlaceWithdrawalHold",
+            Operation::ReleaseWithdrawalHold { .. } => "ReleaseWithdrawalHold",
+            Operation::ScheduleHappyHour { .. } => "ScheduleHappyHour",
+            Operation::CancelHappyHour { .. } => "CancelHappyHour",
+            Operation::AirdropBonus { .. } => "AirdropBonus",
+            Operation::DrawRaffle => "DrawRaffle",
+            Operation::CreateTournament { .. } => "CreateTournament",
+            Operation::EnterTournament { .. } => "EnterTournament",
+            Operation::TournamentRoll { .. } => "TournamentRoll",
+            Operation::SettleTournament { .. } => "SettleTournament",
+            Operation::OpenDuel { .. } => "OpenDuel",
+            Operation::JoinDuel { .. } => "JoinDuel",
+            Operation::DuelRoll { .. } => "DuelRoll",
+            Operation::ClaimDuelPot { .. } => "ClaimDuelPot",
+            Operation::CreateSyndicate { .. } => "CreateSyndicate",
+            Operation::SetSyndicateMember { .. } => "SetSyndicateMember",
+            Operation::ContributeToSyndicate { .. } => "ContributeToSyndicate",
+            Operation::LeaveSyndicate { .. } => "LeaveSyndicate",
+            Operation::SyndicateGame { .. } => "SyndicateGame",
+            Operation::RegisterWithLobby => "RegisterWithLobby",
+            Operation::PublishStats => "PublishStats",
+            Operation::AckOutbox { .. } => "AckOutbox",
+            Operation::DeliverPartnerNotification { .. } => "DeliverPartnerNotification",
+            Operation::DisputeGame { .. } => "DisputeGame",
+            Operation::ResolveDispute { .. } => "ResolveDispute",
+            Operation::AttestGame { .. } => "AttestGame",
+            Operation::HealthCheck => "HealthCheck",
+            Operation::PruneHistory { .. } => "PruneHistory",
+            Operation::DemoGame => "DemoGame",
+            Operation::GetVersion => "GetVersion",
+            Operation::Versioned { operation, .. }
+            | Operation::Sequenced { operation, .. }
+            | Operation::Relayed { operation, .. } => operation.name(),
+        }
+    }
+
+    /// The `SESSION_CAN_*` bit an active session needs to run this operation (None =
+    /// any session may)
+    pub fn session_permission(&self) -> Option<u32> {
+        match self {
+            Operation::RollAndMatch
+            | Operation::BuyMark { .. }
+            | Operation::LockMultiplier
+            | Operation::AutoRoll { .. }
+            | Operation::ContinueAutoRoll
+            | Operation::TournamentRoll { .. }
+            | Operation::DuelRoll { .. } => Some(SESSION_CAN_ROLL),
+
+            Operation::NewGame { .. }
+            | Operation::NewSeasonGame { .. }
+            | Operation::SponsorGame { .. }
+            | Operation::EnterTournament { .. }
+            | Operation::OpenDuel { .. }
+            | Operation::JoinDuel { .. }
+            | Operation::SyndicateGame { .. } => Some(SESSION_CAN_NEW_GAME),
+
+            Operation::ClaimPrize
+            | Operation::ClaimVestedTranche
+            | Operation::ClaimDuelPot { .. } => Some(SESSION_CAN_CLAIM),
+
+            Operation::Withdraw { .. }
+            | Operation::WithdrawAll
+            | Operation::ScheduleWithdrawal { .. }
+            | Operation::LeaveSyndicate { .. }
+            | Operation::SetPayoutRoute { .. }
+            | Operation::SetPreferences { .. } => Some(SESSION_CAN_WITHDRAW),
+
+            Operation::Versioned { operation, .. }
+            | Operation::Sequenced { operation, .. }
+            | Operation::Relayed { operation, .. } => operation.session_permission(),
+            _ => None,
+        }
+    }
 }
 
 // === Response Types ===
@@ -76,17 +886,34 @@ pub enum OperationResponse {
     SessionStarted {
         session_id: u64,
         expires_at_micros: u64,
+        permissions: u32,
     },
 
     /// Session ended
-    SessionEnded,
+    SessionEnded {
+        /// Summary emitted on the sessions stream (None if no session was active)
+        summary: Option<SessionSummary>,
+        /// Index of the event in the stream
+        event_index: Option<u32>,
+        /// How the game left open was settled (None if there was none)
+        settlement: Option<SessionSettlement>,
+    },
 
     /// New game started with a fresh card
     GameStarted {
         game_id: u64,
-        card: BingoCard,
+        card: Box<BingoCard>,
+        /// Second card of a hedged game
+        hedge_card: Option<Box<BingoCard>>,
+        currency: Currency,
         entry_fee_paid: String,
         prize_pool: String,
+        /// The card's difficulty score (see `BingoCard::difficulty_score`)
+        difficulty_score: u32,
+        /// Payout bonus the card's difficulty earns, in basis points
+        difficulty_bonus_bps: u32,
+        /// Deployment and block the game was started in
+        context: RuntimeContext,
     },
 
     /// Result of a roll operation
@@ -100,8 +927,10 @@ pub enum OperationResponse {
         /// Position where the number was marked (row, col) if matched
         match_row: Option<u8>,
         match_col: Option<u8>,
-        /// Type of bingo achieved, if any
+        /// Type of bingo achieved, if any (the first of `bingo_lines`)
         bingo_type: Option<BingoType>,
+        /// Every line complete on the card after this roll
+        bingo_lines: Vec<BingoType>,
         /// Whether the game is over (bingo achieved)
         game_over: bool,
         /// Current roll count for this game
@@ -112,8 +941,97 @@ pub enum OperationResponse {
         total_roll_fees: String,
         /// Whether this was a "lucky" match (multiple numbers matched)
         is_lucky: bool,
+        /// Dice thrown beyond the standard four in a season mode (counted in `sum`)
+        extra_dice: Vec<u8>,
+        /// Cosmetic skin the dice were shown with
+        skin_id: Option<u32>,
+        /// Fewest cells still unmarked on a winning line after the mark (0 once one
+        /// is complete)
+        cells_to_bingo: u32,
+        /// Sums that would complete a new winning line on the next roll, with their
+        /// chances
+        completing_sums: Vec<CompletingSum>,
+        /// Scoring of a PointsPerMark game (None in other modes)
+        points: Option<PointsRoll>,
+        /// Deployment and block the roll was made in
+        context: RuntimeContext,
     },
     
+    /// A cell was bought with BuyMark
+    MarkPurchased {
+        row: u8,
+        col: u8,
+        /// Fee charged for the mark
+        fee_paid: String,
+        /// Fewest cells any line still needed before the purchase
+        cells_to_bingo: u32,
+        /// Every line complete on the card after the purchase
+        bingo_lines: Vec<BingoType>,
+        /// Marks still purchasable on this card
+        purchases_left: u32,
+    },
+
+    /// The card's multiplier tier was locked with LockMultiplier
+    MultiplierLocked {
+        lock: MultiplierLock,
+        /// Multiplier the lock holds (as string like "5x")
+        multiplier_display: String,
+    },
+
+    /// Result of a blackout-continuation roll that completed a line, followed by
+    /// every free bonus roll it chained into
+    CascadeResult {
+        /// The paid roll first, then each bonus roll in order
+        rolls: Vec<CascadeRoll>,
+        /// Total lines completed on the card after the cascade
+        lines_completed: u32,
+        /// Type of bingo achieved, if any
+        bingo_type: Option<BingoType>,
+        /// Whether the card is now full
+        game_over: bool,
+        /// Current paid roll count for this game
+        rolls_count: u32,
+        /// Roll fee paid
+        roll_fee_paid: String,
+        /// Total spent on rolls this game
+        total_roll_fees: String,
+    },
+
+    /// Full per-roll results of an AutoRoll
+    AutoRollResult {
+        results: Vec<OperationResponse>,
+    },
+
+    /// Compact summary of an AutoRoll
+    AggregatedRolls {
+        /// Handle the batch outcome was recorded under (see `lastOperationResults`)
+        handle: u64,
+        /// Dice of every roll in order, bonus rolls included
+        dice: Vec<[u8; 4]>,
+        /// Per paid roll, bitmap of the cells it newly marked (bit = row * 5 + col)
+        marks_delta: Vec<u32>,
+        /// Paid rolls performed
+        rolls_made: u32,
+        /// Cells marked across all rolls
+        marks_gained: u32,
+        /// Roll fees charged by this AutoRoll
+        fees_paid: String,
+        /// Rolls that marked more than one cell
+        lucky_count: u32,
+        /// Type of bingo achieved, if any
+        bingo_type: Option<BingoType>,
+        /// Whether the game is over
+        game_over: bool,
+        /// Current roll count for this game
+        rolls_count: u32,
+        /// Total spent on rolls this game
+        total_roll_fees: String,
+        /// Why rolling stopped before `count`, if it hit an error
+        stop_reason: Option<String>,
+        /// Code of that error
+        stop_code: Option<ErrorCode>,
+    },
+
     /// Prize claimed successfully
     PrizeClaimed {
         /// Original bet amount
@@ -124,84 +1042,771 @@ pub enum OperationResponse {
         multiplier_display: String,
         /// Calculated payout amount
         payout_amount: String,
-        /// New player balance
+        /// Balance the payout was settled into
+        currency: Currency,
+        /// New balance in that currency
+        new_balance: String,
+        /// Receipt of the automatic withdrawal the claim triggered, if any
+        auto_withdrawal_receipt_id: Option<u64>,
+        /// Receipt of the payout sent to a sponsored game's player, if any
+        sponsored_payout_receipt_id: Option<u64>,
+        /// Name of the multiplier tier the win fell in
+        tier: String,
+        /// Chance of a fresh card winning within that tier (basis points)
+        tier_win_chance_bps: u32,
+        /// Share of earlier wins that needed more rolls (basis points, ties count
+        /// half); None for the first recorded win
+        rolls_percentile_bps: Option<u32>,
+        /// Achievement skins this win added to the winner's inventory
+        skins_unlocked: Vec<u32>,
+        /// Outcome of forwarding the payout through the payout bridge, if the
+        /// winner has a route set
+        routed_payout: Option<RoutedPayout>,
+        /// Installments still to come, if the payout reached the vesting threshold;
+        /// only the first installment was credited with the claim
+        vesting: Option<VestingSchedule>,
+    },
+
+    /// Unlocked vesting installments credited to the balance
+    VestedTrancheClaimed {
+        /// Total credited by this claim (in atto)
+        released_atto: String,
         new_balance: String,
+        /// The signer's schedules that still have installments to come
+        schedules: Vec<VestingSchedule>,
     },
     
     /// Deposit received
     DepositReceived {
+        /// Ledger receipt id for this deposit
+        receipt_id: u64,
         amount: String,
+        /// Balance of the credited account after the deposit
         new_balance: String,
+        /// Player credited by a custodial deposit
+        credited_to: Option<AccountOwner>,
     },
     
     /// Withdrawal processed
     WithdrawalProcessed {
+        /// Ledger receipt id for this withdrawal
+        receipt_id: u64,
         amount: String,
         remaining_balance: String,
     },
 
-    /// Error response
-    Error {
-        message: String,
+    /// Funds moved into a time-locked withdrawal
+    WithdrawalScheduled {
+        withdrawal: ScheduledWithdrawal,
+        remaining_balance: String,
     },
-}
 
-// === Bingo Card ===
+    /// Time-locked withdrawal paid out
+    WithdrawalReleased {
+        withdrawal: ScheduledWithdrawal,
+        /// Ledger receipt id for the payout
+        receipt_id: u64,
+    },
 
-/// A 5x5 Bingo card with numbers from 4-24
-#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
-pub struct BingoCard {
-    /// Unique identifier for this card
-    pub id: u64,
-    /// 5x5 grid of numbers (4-24, 0 = FREE space)
-    /// Stored as a flat array for simplicity: row-major order
-    pub numbers: [u8; 25],
-    /// Which cells are marked (matched or FREE)
-    pub marked: [bool; 25],
-    /// Number of rolls made on this card
-    pub rolls_count: u32,
-    /// The player's bet amount for this game (in atto)
-    pub bet_amount_atto: String,
-    /// Total LINERA spent on rolls for this game (in atto)
-    pub total_roll_fees_atto: String,
-    /// Whether prize has been claimed
-    pub prize_claimed: bool,
-}
+    /// Withdraw-all processed, with what was held back
+    WithdrawAllProcessed {
+        /// Ledger receipt id for this withdrawal
+        receipt_id: u64,
+        amount: String,
+        remaining_balance: String,
+        /// Funds not withdrawn and why
+        withheld: Vec<Withheld>,
+    },
 
-impl BingoCard {
-    /// Get the number at a specific position
-    pub fn get_number(&self, row: usize, col: usize) -> u8 {
-        self.numbers[row * 5 + col]
-    }
+    /// Play points refilled
+    PointsRefilled {
+        new_points_balance: String,
+    },
 
-    /// Check if a cell is marked
-    pub fn is_marked(&self, row: usize, col: usize) -> bool {
-        self.marked[row * 5 + col]
-    }
+    /// Player preferences replaced
+    PreferencesUpdated {
+        preferences: PlayerPreferences,
+    },
 
-    /// Mark a cell
-    pub fn mark(&mut self, row: usize, col: usize) {
-        self.marked[row * 5 + col] = true;
-    }
-}
+    /// Payout route replaced or (with None) cleared
+    PayoutRouteSet {
+        owner: AccountOwner,
+        route: Option<PayoutRoute>,
+    },
 
-// === Win Types ===
+    /// Data purge requested; confirmable from `request.confirmable_at_micros`
+    DataPurgeRequested {
+        request: DataPurgeRequest,
+    },
 
-/// Types of bingo wins
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
-pub enum BingoType {
-    /// Completed a horizontal row (index 0-4)
-    Row0,
-    Row1,
-    Row2,
-    Row3,
-    Row4,
-    /// Completed a vertical column (index 0-4)
-    Col0,
-    Col1,
-    Col2,
-    Col3,
-    Col4,
+    /// Personal data purged
+    DataPurged {
+        owner: AccountOwner,
+        purged_at_micros: u64,
+    },
+
+    /// Pending data purge withdrawn
+    DataPurgeCancelled,
+
+    /// Skin bought into the signer's inventory
+    SkinPurchased {
+        skin_id: u32,
+        price_paid: String,
+        inventory: SkinInventory,
+    },
+
+    /// Equipped skin changed
+    SkinEquipped {
+        inventory: SkinInventory,
+    },
+
+    /// Game configuration replaced
+    ConfigUpdated {
+        config: GameConfig,
+    },
+
+    /// A/B experiment replaced
+    ExperimentUpdated {
+        experiment: Option<Experiment>,
+    },
+
+    /// High-roller table opened, replaced or closed
+    HighRollerTableUpdated {
+        table: Option<HighRollerTable>,
+    },
+
+    /// Season mode added or replaced
+    SeasonModeUpdated {
+        mode: SeasonMode,
+    },
+
+    /// Game template published or replaced
+    GameTemplateUpdated {
+        template: GameTemplate,
+    },
+
+    /// Happy hour added to the schedule
+    HappyHourScheduled {
+        happy_hour: HappyHour,
+    },
+
+    /// Happy hour removed from the schedule
+    HappyHourCancelled {
+        happy_hour_id: u64,
+    },
+
+    /// Bonus airdrop credited and recorded
+    BonusAirdropped {
+        batch: AirdropBatch,
+    },
+
+    /// Registration sent to the lobby chain
+    LobbyRegistrationSent {
+        lobby_chain: ChainId,
+        config_summary: ConfigSummary,
+    },
+
+    /// Statistics snapshot emitted on the stats stream
+    StatsPublished {
+        snapshot: StatsSnapshot,
+        /// Index of the event in the stream
+        event_index: u32,
+    },
+
+    /// Dispute filed and the game's winnings frozen
+    DisputeFiled {
+        dispute: Dispute,
+    },
+
+    /// Dispute ruled on
+    DisputeResolved {
+        dispute: Dispute,
+        action: AdminAction,
+    },
+
+    /// Attestation of a claimed game's outcome
+    GameAttested {
+        attestation: GameAttestation,
+    },
+
+    /// Result of a deployment health check
+    HealthChecked {
+        report: HealthReport,
+    },
+
+    /// Old history folded into the summary and deleted
+    HistoryPruned {
+        report: PruneReport,
+    },
+
+    /// Delivered outbox entries dropped
+    OutboxAcked {
+        up_to_id: u64,
+        /// Entries removed by this acknowledgement
+        removed: u32,
+        /// Entries still waiting for delivery
+        pending: u32,
+    },
+
+    /// Queued win notification handed to its partner application
+    PartnerNotified {
+        entry_id: u64,
+        partner: ApplicationId,
+        game_id: u64,
+    },
+
+    /// Raffle drawn and the pool credited to the winner
+    RaffleDrawn {
+        draw: RaffleDraw,
+    },
+
+    /// Tournament opened
+    TournamentCreated {
+        tournament: Tournament,
+    },
+
+    /// Entry fee paid and the signer added to the tournament
+    TournamentEntered {
+        tournament_id: u64,
+        entrants: u32,
+        pot: Amount,
+    },
+
+    /// One roll from a tournament's dice stream
+    TournamentRolled {
+        tournament_id: u64,
+        dice: [u8; 4],
+        sum: u8,
+        matched: bool,
+        /// Rolls the signer has taken in this tournament
+        rolls: u32,
+        /// Whether this roll completed a line
+        finished: bool,
+    },
+
+    /// Tournament ranked and its pot paid out
+    TournamentSettled {
+        tournament: Tournament,
+    },
+
+    /// Duel opened, waiting for a challenger
+    DuelOpened {
+        duel: Duel,
+    },
+
+    /// Challenger seated; the duel is under way
+    DuelJoined {
+        duel: Duel,
+    },
+
+    /// A duel roll played; the duel is settled if it completed a line
+    DuelRolled {
+        dice: [u8; 4],
+        sum: u8,
+        /// Whether the sum marked a cell of the roller's card
+        matched: bool,
+        duel: Duel,
+    },
+
+    /// Duel settled for the signer because the other player's turn ran out
+    DuelPotClaimed { duel: Duel },
+
+    /// Syndicate created with the signer as its manager
+    SyndicateCreated { syndicate: Syndicate },
+
+    /// A member added, or their bet limit changed
+    SyndicateMemberSet { syndicate: Syndicate },
+
+    /// A contribution added to the bankroll and the member's stake
+    SyndicateFunded { syndicate: Syndicate },
+
+    /// A member left and was paid out their stake
+    SyndicateLeft {
+        syndicate_id: u64,
+        member: AccountOwner,
+        paid_out: Amount,
+        /// None once the last member has left and the syndicate is closed
+        syndicate: Option<Syndicate>,
+    },
+
+    /// A SyndicateGame played out and settled against the bankroll
+    SyndicateGamePlayed {
+        result: SyndicateGameResult,
+        syndicate: Syndicate,
+    },
+
+    /// A DemoGame played out
+    DemoResult {
+        /// Seed the demo card and dice were drawn from
+        seed: u64,
+        /// The demo card's numbers (0 = FREE center)
+        numbers: [u8; 25],
+        /// Cells marked when the demo ended
+        marked: [bool; 25],
+        /// Every roll, in order
+        rolls: Vec<DemoRoll>,
+        /// First line completed, if the demo reached a bingo
+        bingo_type: Option<BingoType>,
+        /// Multiplier the bingo would have paid (as string like "5x"); None without one
+        multiplier_display: Option<String>,
+    },
+
+    /// Claim deadlines extended for one page of accounts
+    ClaimDeadlinesExtended {
+        action: AdminAction,
+        /// Accounts visited on this page
+        visited: u32,
+        /// Cursor for the next page; None once every account has been visited
+        next_after: Option<AccountOwner>,
+    },
+
+    /// New games and rolls stopped or resumed
+    PauseChanged {
+        paused: bool,
+        action: AdminAction,
+    },
+
+    /// House money paid out of the epoch bonus pool
+    TreasuryWithdrawn {
+        amount: Amount,
+        /// Bonus pool left afterwards
+        bonus_pool: Amount,
+        action: AdminAction,
+    },
+
+    /// An owner's withdrawals put on compliance hold
+    WithdrawalHoldPlaced {
+        hold: WithdrawalHold,
+        action: AdminAction,
+    },
+
+    /// An owner's compliance hold lifted
+    WithdrawalHoldReleased {
+        owner: AccountOwner,
+        action: AdminAction,
+    },
+
+    /// A governed change queued behind the governance timelock
+    ChangeProposed {
+        proposal: GovernanceProposal,
+        action: AdminAction,
+    },
+
+    /// A pending proposal applied; `outcome` is the response of the applied change
+    ProposalExecuted {
+        proposal: GovernanceProposal,
+        outcome: Box<OperationResponse>,
+    },
+
+    /// A pending proposal dropped
+    ProposalCancelled {
+        proposal: GovernanceProposal,
+        action: AdminAction,
+    },
+
+    /// New game rejected: the owner already has the maximum number of games in play
+    TooManyActiveGames {
+        /// Configured maximum games in play per owner
+        limit: u32,
+        /// Ids of the games still in play
+        open_game_ids: Vec<u64>,
+    },
+
+    /// ABI version of the running contract
+    Version {
+        abi_version: u32,
+    },
+
+    /// Versioned operation rejected: it was built for a different ABI
+    IncompatibleAbiVersion {
+        /// ABI_VERSION of the running contract
+        supported: u32,
+        /// Version the operation was tagged with
+        requested: u32,
+    },
+
+    /// Error response
+    Error {
+        /// Stable reason clients branch on and localize (see the service's
+        /// `messages` catalog)
+        code: ErrorCode,
+        /// English detail for logs and debugging; its wording may change
+        message: String,
+    },
+}
+
+// === Error Codes ===
+
+/// Why an operation was refused. Codes are stable across releases, so frontends can
+/// show localized text without matching on English messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum ErrorCode {
+    /// An argument is out of range, empty or malformed
+    InvalidInput,
+    /// The balance or pool cannot cover the amount
+    InsufficientFunds,
+    /// The bet is below the minimum
+    BetTooLow,
+    /// The bet is above the table's maximum
+    BetTooHigh,
+    /// The player's loss guard caps the bet after a loss
+    LossGuard,
+    /// The operation must be signed
+    SignerRequired,
+    /// The signer is not the admin, owner or player the operation belongs to
+    NotPermitted,
+    /// No session is active
+    SessionRequired,
+    /// The active session has expired
+    SessionExpired,
+    /// The active session's permissions do not cover the operation
+    SessionPermission,
+    /// The feature is not configured on this deployment
+    NotConfigured,
+    /// The referenced item does not exist
+    NotFound,
+    /// The item is not open right now (a season mode off, a tournament closed)
+    Closed,
+    /// The action was already taken and cannot be repeated
+    AlreadyDone,
+    /// The action is not allowed yet
+    TooEarly,
+    /// The window for the action has passed
+    TooLate,
+    /// The game, mode or table does not support the action
+    Unsupported,
+    /// A per-game, per-batch or per-block limit was reached
+    LimitReached,
+    /// There is no game to act on
+    NoActiveGame,
+    /// The game has ended
+    GameOver,
+    /// The card has a bingo waiting to be claimed
+    BingoPending,
+    /// The card has no cells left to mark
+    CardFull,
+    /// There is no prize to claim
+    NoPrize,
+    /// The claim deadline has passed
+    ClaimExpired,
+    /// Rolling is suspended until an operator reviews the card
+    RollingSuspended,
+    /// The deployment is paused: no new games or rolls until the admin resumes it
+    Paused,
+    /// The signer's withdrawals are on compliance hold
+    WithdrawalHeld,
+    /// Stored state failed a consistency check
+    InconsistentState,
+}
+
+// === Bingo Card ===
+
+/// A 5x5 Bingo card with numbers from 4-24
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, SimpleObject)]
+#[graphql(complex)]
+pub struct BingoCard {
+    /// Unique identifier for this card
+    pub id: u64,
+    /// 5x5 grid of numbers (4-24, 0 = FREE space)
+    /// Stored as a flat array for simplicity: row-major order
+    pub numbers: [u8; 25],
+    /// Which cells are marked (matched or FREE)
+    pub marked: [bool; 25],
+    /// Number of rolls made on this card
+    pub rolls_count: u32,
+    /// The player's bet amount for this game (in atto)
+    pub bet_amount_atto: String,
+    /// Total LINERA spent on rolls for this game (in atto)
+    pub total_roll_fees_atto: String,
+    /// Whether prize has been claimed. Superseded by the account's `game_phase`;
+    /// still set on claim so stored cards and older clients read the same value
+    #[graphql(deprecation = "Use `gamePhase`")]
+    pub prize_claimed: bool,
+    /// Which balance this game is staked and settled in
+    pub currency: Currency,
+    /// Rules this card is played under
+    pub mode: GameMode,
+    /// Payout credited when the prize was claimed (in atto)
+    pub payout_atto: String,
+    /// Experiment bucket this game settles under, if one was running when it started
+    pub experiment_bucket: Option<ExperimentBucket>,
+    /// That bucket's arm as it stood when the game started: the game's roll fee and
+    /// payout scale, even if the experiment later changes or ends
+    pub experiment_arm: Option<ExperimentArm>,
+    /// Seed for generative card art, derived only from `numbers` (see `layout_seed`)
+    pub art_seed: u64,
+    /// When the pending prize stops being claimable (microseconds since epoch, 0 = never)
+    pub claim_deadline_micros: u64,
+    /// Rolls that marked the card with a sum not drawn before in this game
+    pub fresh_sum_marks: u32,
+    /// Played at the high-roller table: its fees apply and it settles in its bucket
+    pub high_roller: bool,
+    /// When the card was dealt or last rolled (microseconds since epoch)
+    pub last_active_micros: u64,
+    /// Expected rolls to complete the easiest line, times 100 (see
+    /// `probability::difficulty_score`); 0 for season cards, whose dice it doesn't model
+    pub difficulty_score: u32,
+    /// Bonus on the tier payout earned by the card's difficulty, in basis points
+    pub difficulty_bonus_bps: u32,
+    /// Owner whose balance paid for a sponsored game
+    pub sponsor: Option<AccountOwner>,
+    /// Only owner allowed to roll or claim a sponsored game; receives the payout
+    pub sponsored_player: Option<AccountOwner>,
+    /// Cells marked with BuyMark rather than by a roll (row-major indexes)
+    pub purchased_marks: Vec<u8>,
+    /// Total paid for purchased marks (in atto)
+    pub mark_fees_atto: String,
+    /// Multiplier tier bought with LockMultiplier, if any
+    pub multiplier_lock: Option<MultiplierLock>,
+    /// Season mode the card was dealt under, if any
+    pub season_mode_id: Option<u64>,
+    /// Game template the card was dealt under, if any
+    pub template_id: Option<u64>,
+    /// Lines that count as a bingo on this card
+    pub pattern: WinPattern,
+    /// Roll fee set by the card's template (None = the table's usual fee)
+    pub template_roll_cost: Option<Amount>,
+    /// Dice each roll throws beyond the standard four (season modes only)
+    pub extra_dice: u8,
+    /// Skin the player had equipped when the card was dealt (cosmetic only)
+    pub skin_id: Option<u32>,
+    /// Seed the numbers were shuffled from, after any redeals: `rules::deal_numbers`
+    /// (or `deal_constrained`, `deal_hedge` or `deal_range`, as the card was dealt)
+    /// replays the layout from it
+    pub deal_seed: u64,
+    /// Hash of the card's `DealCommitment`, binding the seed to the game
+    pub deal_commitment: Option<CryptoHash>,
+}
+
+/// A card's frozen multiplier tier (see `Operation::LockMultiplier`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct MultiplierLock {
+    /// Roll count the payout is priced at while the lock holds
+    pub locked_at_rolls: u32,
+    /// Last roll count the lock covers
+    pub expires_after_rolls: u32,
+    /// Price paid for the lock (in atto)
+    pub fee_atto: String,
+    /// Whether the claimed prize paid a better tier thanks to the lock (set at claim)
+    pub applied: bool,
+}
+
+#[ComplexObject]
+impl BingoCard {
+    /// Globally unique opaque node id
+    pub async fn node_id(&self) -> ID {
+        node_id("BingoCard", self.id)
+    }
+
+    /// The card's `id`, named like the game id fields of other objects
+    pub async fn game_id(&self) -> u64 {
+        self.id
+    }
+
+    /// Expected rolls to complete the card's easiest line
+    pub async fn expected_rolls(&self) -> f64 {
+        self.difficulty_score as f64 / 100.0
+    }
+}
+
+impl BingoCard {
+    /// Roll count the payout is priced at: the locked one while a multiplier lock holds
+    pub fn payout_rolls(&self) -> u32 {
+        match &self.multiplier_lock {
+            Some(lock) if self.rolls_count <= lock.expires_after_rolls => lock.locked_at_rolls,
+            _ => self.rolls_count,
+        }
+    }
+
+    /// Get the number at a specific position
+    pub fn get_number(&self, row: usize, col: usize) -> u8 {
+        self.numbers[row * 5 + col]
+    }
+
+    /// Check if a cell is marked
+    pub fn is_marked(&self, row: usize, col: usize) -> bool {
+        self.marked[row * 5 + col]
+    }
+
+    /// Mark a cell
+    pub fn mark(&mut self, row: usize, col: usize) {
+        self.marked[row * 5 + col] = true;
+    }
+
+    /// Content-addressed seed of a number layout: FNV-1a over the 25 cells, so the
+    /// same layout always renders the same artwork
+    pub fn layout_seed(numbers: &[u8; 25]) -> u64 {
+        numbers.iter().fold(0xcbf29ce484222325u64, |hash, number| {
+            (hash ^ *number as u64).wrapping_mul(0x100000001b3)
+        })
+    }
+
+    /// Record the seed the numbers were dealt from, together with its commitment
+    pub fn commit_deal_seed(&mut self, seed: u64) {
+        self.deal_seed = seed;
+        self.deal_commitment = Some(CryptoHash::new(&DealCommitment {
+            game_id: self.id,
+            seed,
+        }));
+    }
+
+    /// Marked cells as a bitmap (bit = row * 5 + col)
+    pub fn marked_bitmask(&self) -> u32 {
+        self.marked
+            .iter()
+            .enumerate()
+            .filter(|(_, marked)| **marked)
+            .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+}
+
+/// The seed a card was dealt from. A third party checks a card by hashing this to
+/// its `deal_commitment` and replaying the shuffle from `seed`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DealCommitment {
+    pub game_id: u64,
+    pub seed: u64,
+}
+
+impl BcsHashable<'_> for DealCommitment {}
+
+// === Game Lifecycle ===
+
+/// Where the current game is in its lifecycle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum GamePhase {
+    /// No game has been dealt
+    #[default]
+    Idle,
+    /// Card dealt and rolling
+    Active,
+    /// A line is complete and the prize is claimable (blackout-continuation games
+    /// may keep rolling)
+    BingoPending,
+    /// Prize paid out
+    Claimed,
+    /// Claim deadline passed before the prize was claimed
+    Expired,
+    /// Abandoned before settling when the session ended
+    Cancelled,
+}
+
+impl GamePhase {
+    /// Whether the game is still unsettled and playable
+    pub fn is_open(self) -> bool {
+        matches!(self, GamePhase::Active | GamePhase::BingoPending)
+    }
+}
+
+// === Currency ===
+
+/// Which balance a game is staked and settled in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum Currency {
+    /// Free-to-play points: same engine, no withdrawal path
+    Play,
+    /// Real LINERA stakes from the deposited balance
+    #[default]
+    Real,
+}
+
+// === Game Modes ===
+
+/// Rules a game is played under
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum GameMode {
+    /// First completed line ends the game
+    #[default]
+    Classic,
+    /// Keep rolling after a line until blackout; each new line grants a free bonus roll
+    BlackoutContinuation,
+    /// Classic play on a flatter multiplier table (3x down to 0.8x) with
+    /// INSURANCE_ROLL_COST roll fees, settled in its own stats bucket
+    Insurance,
+    /// No bingo: every marked cell scores points (`rules::mark_points`) over exactly
+    /// POINTS_GAME_ROLLS rolls, and the last roll settles the game on its total
+    /// (`rules::points_multiplier`)
+    PointsPerMark,
+}
+
+/// One roll within a blackout-continuation cascade
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CascadeRoll {
+    /// The four dice values (1-6 each)
+    pub dice: [u8; 4],
+    /// Sum of the dice (4-24)
+    pub sum: u8,
+    /// Whether the sum was found and marked on the card
+    pub matched: bool,
+    /// Lines this roll completed
+    pub new_lines: u32,
+    /// Whether this was a free bonus roll
+    pub bonus: bool,
+    /// Whether the roll marked more than one cell
+    pub is_lucky: bool,
+}
+
+/// One roll of a DemoGame
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DemoRoll {
+    /// The four dice values (1-6 each)
+    pub dice: [u8; 4],
+    /// Sum of the dice (4-24)
+    pub sum: u8,
+    /// Whether the sum marked a cell of the demo card
+    pub matched: bool,
+}
+
+/// Scoring of one PointsPerMark roll
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PointsRoll {
+    /// Points of the cells this roll marked
+    pub points_scored: u32,
+    /// Points of every cell marked so far
+    pub total_points: u32,
+    /// Rolls left before the game settles
+    pub rolls_left: u32,
+    /// How the game settled, on its final roll
+    pub settlement: Option<PointsSettlement>,
+}
+
+/// A PointsPerMark game settled on its points
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PointsSettlement {
+    pub game_id: u64,
+    pub total_points: u32,
+    /// Payout tier the points reached, e.g. "1.5x" ("0x" short of every tier)
+    pub multiplier_display: String,
+    /// Payout credited (in atto; 0 leaves the whole bet with the house)
+    pub payout_atto: String,
+    pub new_balance: String,
+}
+
+/// A sum that would complete a new winning line if rolled next
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CompletingSum {
+    pub sum: u8,
+    /// Chance of rolling the sum, in basis points
+    pub chance_bps: u32,
+}
+
+// === Win Types ===
+
+/// Types of bingo wins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum BingoType {
+    /// Completed a horizontal row (index 0-4)
+    Row0,
+    Row1,
+    Row2,
+    Row3,
+    Row4,
+    /// Completed a vertical column (index 0-4)
+    Col0,
+    Col1,
+    Col2,
+    Col3,
+    Col4,
     /// Completed main diagonal (top-left to bottom-right)
     DiagonalMain,
     /// Completed anti-diagonal (top-right to bottom-left)
@@ -210,10 +1815,195 @@ pub enum BingoType {
     FullCard,
 }
 
+/// Index into MULTIPLIER_TIER_NAMES of a win taking `rolls` rolls (0 = best)
+pub fn multiplier_tier(rolls: u32) -> usize {
+    MULTIPLIER_TIER_MAX_ROLLS
+        .iter()
+        .take_while(|max_rolls| rolls > **max_rolls)
+        .count()
+}
+
+/// Archived record of a claimed prize
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+#[graphql(complex)]
+pub struct WinRecord {
+    pub game_id: u64,
+    pub owner: Option<AccountOwner>,
+    /// Owner who paid for the game when it was sponsored for `owner`
+    pub sponsor: Option<AccountOwner>,
+    pub currency: Currency,
+    /// Rules the winning card was played under
+    pub mode: GameMode,
+    /// Bet the winning card carried (in atto)
+    pub bet_amount_atto: String,
+    /// Amount paid out (in atto)
+    pub payout_atto: String,
+    pub rolls_count: u32,
+    /// First line the winning card completed
+    pub bingo_type: Option<BingoType>,
+    /// Cells the player bought instead of rolling for (row-major indexes)
+    pub purchased_marks: Vec<u8>,
+    /// Total paid for those marks (in atto)
+    pub mark_fees_atto: String,
+    /// Multiplier lock the card carried, and whether it raised the payout
+    pub multiplier_lock: Option<MultiplierLock>,
+    /// Multiplier tier name, as in PrizeClaimed
+    pub tier: String,
+    pub tier_win_chance_bps: u32,
+    pub rolls_percentile_bps: Option<u32>,
+    pub claimed_at_micros: u64,
+    /// Deployment and block the prize was claimed in
+    pub context: RuntimeContext,
+}
+
+#[ComplexObject]
+impl WinRecord {
+    /// Globally unique opaque node id
+    pub async fn node_id(&self) -> ID {
+        node_id("WinRecord", self.game_id)
+    }
+}
+
+impl WinRecord {
+    /// Whether the payout came to at least the bet, as the loss guard counts a win
+    pub fn net_win(&self) -> bool {
+        let bet: u128 = self.bet_amount_atto.parse().unwrap_or(0);
+        let payout: u128 = self.payout_atto.parse().unwrap_or(0);
+        payout >= bet
+    }
+
+    /// Day bucket of the claim in the archive's date index
+    pub fn archive_day(&self) -> u64 {
+        self.claimed_at_micros / ARCHIVE_DAY_MICROS
+    }
+}
+
+/// Width of one day bucket in the archive's date index
+pub const ARCHIVE_DAY_MICROS: u64 = 86_400_000_000;
+
+/// Bucket of `bet_amount_atto` in the archive's bet index: its bit length, so every
+/// bucket spans a doubling of the bet
+pub fn archive_bet_bucket(bet_amount_atto: u128) -> u8 {
+    (u128::BITS - bet_amount_atto.leading_zeros()) as u8
+}
+
+/// Filters for searching the win archive; unset fields match every win
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, InputObject)]
+#[serde(default)]
+pub struct ArchiveFilter {
+    /// Only wins whose payout came to at least the bet
+    #[graphql(default)]
+    pub net_wins_only: bool,
+    /// Smallest bet to include (in atto)
+    pub min_bet_atto: Option<String>,
+    /// Largest bet to include (in atto)
+    pub max_bet_atto: Option<String>,
+    pub bingo_type: Option<BingoType>,
+    pub mode: Option<GameMode>,
+    /// Earliest claim time to include
+    pub from_micros: Option<u64>,
+    /// Latest claim time to include
+    pub to_micros: Option<u64>,
+}
+
+impl ArchiveFilter {
+    /// Bet bounds in atto, `None` if either bound is not a number
+    pub fn bet_range(&self) -> Option<(u128, u128)> {
+        let parse = |bound: &Option<String>, unset: u128| match bound {
+            Some(atto) => atto.parse().ok(),
+            None => Some(unset),
+        };
+        Some((
+            parse(&self.min_bet_atto, 0)?,
+            parse(&self.max_bet_atto, u128::MAX)?,
+        ))
+    }
+
+    /// Whether `win` passes every filter
+    pub fn matches(&self, win: &WinRecord) -> bool {
+        let bet: u128 = win.bet_amount_atto.parse().unwrap_or(0);
+        let Some((min_bet, max_bet)) = self.bet_range() else {
+            return false;
+        };
+        let claimed_at = win.claimed_at_micros;
+        (!self.net_wins_only || win.net_win())
+            && (min_bet..=max_bet).contains(&bet)
+            && (self.bingo_type.is_none() || win.bingo_type == self.bingo_type)
+            && self.mode.is_none_or(|mode| win.mode == mode)
+            && self.from_micros.is_none_or(|from| claimed_at >= from)
+            && self.to_micros.is_none_or(|to| claimed_at <= to)
+    }
+}
+
+/// Order of win archive search results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum ArchiveSort {
+    #[default]
+    Newest,
+    Oldest,
+    LargestPayout,
+    LargestBet,
+    FewestRolls,
+}
+
+// === Dice Skins ===
+
+/// A collectible dice skin. Skins are purely cosmetic: they never touch the dice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiceSkin {
+    pub skin_id: u32,
+    pub name: &'static str,
+    /// Price of a BuySkin in atto LINERA (0 = earned through an achievement only)
+    pub price_atto: u128,
+}
+
+/// Skin granted for a bingo within the top multiplier tier
+pub const LIGHTNING_SKIN_ID: u32 = 3;
+/// Skin granted for a claimed win on a fully marked card
+pub const BLACKOUT_SKIN_ID: u32 = 4;
+
+/// Every skin that can be collected
+pub const DICE_SKINS: [DiceSkin; 4] = [
+    DiceSkin {
+        skin_id: 1,
+        name: "Obsidian",
+        price_atto: MIN_BET,
+    },
+    DiceSkin {
+        skin_id: 2,
+        name: "Gilded",
+        price_atto: 5 * MIN_BET,
+    },
+    DiceSkin {
+        skin_id: LIGHTNING_SKIN_ID,
+        name: "Lightning",
+        price_atto: 0,
+    },
+    DiceSkin {
+        skin_id: BLACKOUT_SKIN_ID,
+        name: "Blackout",
+        price_atto: 0,
+    },
+];
+
+/// The skin with id `skin_id`, if it exists
+pub fn dice_skin(skin_id: u32) -> Option<DiceSkin> {
+    DICE_SKINS.into_iter().find(|skin| skin.skin_id == skin_id)
+}
+
+/// Skins an owner has collected and the one shown on their dice
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct SkinInventory {
+    /// Owned skin ids, in the order they were collected
+    pub owned: Vec<u32>,
+    pub equipped: Option<u32>,
+}
+
 // === Session ===
 
 /// Game session for authorizing rapid operations
-#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, SimpleObject)]
+#[graphql(complex)]
 pub struct GameSession {
     /// Unique session identifier
     pub session_id: u64,
@@ -223,13 +2013,172 @@ pub struct GameSession {
     pub expires_at_micros: u64,
     /// Total operations performed in this session
     pub operations_count: u64,
+    /// Head of the hash chain over the operations executed in this session
+    /// (see `TranscriptLink`; None until the first one)
+    pub transcript_hash: Option<CryptoHash>,
+    /// `total_won` when the session started
+    pub won_at_start: Amount,
+    /// `total_spent` when the session started
+    pub spent_at_start: Amount,
+    /// When the session's latest operation ran (microseconds since epoch)
+    pub last_operation_micros: u64,
+    /// Congestion gaps credited back to the session's deadlines so far
+    pub paused_micros: u64,
+    /// `SESSION_CAN_*` bits the session may use
+    pub permissions: u32,
+    /// Timing of the session's roll requests so far
+    #[graphql(skip)]
+    pub cadence: SessionCadence,
 }
 
-// === Roll Record ===
-
+#[ComplexObject]
+impl GameSession {
+    /// Globally unique opaque node id
+    pub async fn node_id(&self) -> ID {
+        node_id("GameSession", self.session_id)
+    }
+}
+
+/// One step of a session transcript: each operation executed while the session is
+/// active (after unwrapping version tags) is hashed together with the previous head
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TranscriptLink {
+    pub previous: Option<CryptoHash>,
+    pub operation: Operation,
+}
+
+impl BcsHashable<'_> for TranscriptLink {}
+
+/// Results of a finished session, emitted on the sessions stream so auditors and
+/// overlays can check a player's claims against the chain
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct SessionSummary {
+    pub session_id: u64,
+    /// Signer that ended the session
+    pub player: Option<AccountOwner>,
+    pub operations_count: u64,
+    /// Real money won during the session
+    pub won: Amount,
+    /// Real money bet and paid in fees during the session
+    pub spent: Amount,
+    /// `won` minus `spent` in atto, as a signed decimal string
+    pub net_pnl_atto: String,
+    /// Head of the session's operation hash chain (None if no operation ran)
+    pub transcript_hash: Option<CryptoHash>,
+    pub ended_at_micros: u64,
+}
+
+/// How EndSession settled the game a session left open
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct SessionSettlement {
+    pub game_id: u64,
+    pub outcome: SettlementOutcome,
+    /// Prize or refunded bet credited to the balance the game was staked in (atto)
+    pub credited_atto: String,
+    /// Bet kept by the house (atto)
+    pub forfeited_atto: String,
+}
+
+/// How a game was settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum SettlementOutcome {
+    /// The prize was claimed (by EndSession, on the player's behalf)
+    PrizeClaimed,
+    /// The prize's claim deadline had passed; the bet stays with the house
+    ClaimExpired,
+    /// The unfinished game's bet was handed back
+    Refunded,
+    /// The unfinished game's bet stays with the house
+    Forfeited,
+}
+
+/// How EndSession settles a game that has not reached a bingo
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum SessionEndPolicy {
+    /// Refund games no dice were rolled for; forfeit the rest
+    #[default]
+    RefundUnplayed,
+    /// Refund every unfinished game
+    Refund,
+    /// Forfeit every unfinished game to the house
+    Forfeit,
+}
+
+// === Roll Cadence ===
+
+/// Timing between a session's roll requests, kept to spot automated play. Only
+/// integer sums are stored (BCS has no floats); the mean and jitter derive from them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct SessionCadence {
+    /// Session the requests were made in
+    pub session_id: u64,
+    /// Roll requests made (RollAndMatch and AutoRoll operations)
+    pub requests: u64,
+    /// When the latest request was made (microseconds since epoch)
+    pub last_request_micros: u64,
+    /// Intervals measured between consecutive requests
+    pub intervals: u64,
+    /// Sum of the intervals (microseconds)
+    pub interval_sum_micros: u64,
+    /// Sum of the squared intervals (microseconds squared)
+    #[graphql(skip)]
+    pub interval_square_sum: u128,
+}
+
+impl SessionCadence {
+    /// Fold in a roll request made at `now_micros`
+    pub fn record(&mut self, now_micros: u64) {
+        if self.requests > 0 {
+            let interval = now_micros.saturating_sub(self.last_request_micros);
+            self.intervals += 1;
+            self.interval_sum_micros = self.interval_sum_micros.saturating_add(interval);
+            self.interval_square_sum = self
+                .interval_square_sum
+                .saturating_add(u128::from(interval) * u128::from(interval));
+        }
+        self.requests += 1;
+        self.last_request_micros = now_micros;
+    }
+
+    /// Mean of the intervals (microseconds, 0 before the first one)
+    pub fn mean_interval_micros(&self) -> u64 {
+        self.interval_sum_micros.checked_div(self.intervals).unwrap_or(0)
+    }
+
+    /// Whether the cadence trips the configured (too_fast, too_regular) thresholds.
+    /// Sessions need CADENCE_MIN_SAMPLES intervals before they can be flagged.
+    pub fn flags(&self, config: &GameConfig) -> (bool, bool) {
+        if self.intervals < CADENCE_MIN_SAMPLES {
+            return (false, false);
+        }
+        let too_fast = config.cadence_min_interval_ms > 0
+            && self.mean_interval_micros() < u64::from(config.cadence_min_interval_ms) * 1_000;
+        let too_regular =
+            config.cadence_min_jitter_bps > 0 && self.jitter_bps() < config.cadence_min_jitter_bps;
+        (too_fast, too_regular)
+    }
+
+    /// Standard deviation of the intervals as a share of their mean, in basis points
+    pub fn jitter_bps(&self) -> u32 {
+        if self.intervals < 2 || self.interval_sum_micros == 0 {
+            return 0;
+        }
+        let count = self.intervals as f64;
+        let sum = self.interval_sum_micros as f64;
+        let variance = (self.interval_square_sum as f64 - sum * sum / count) / (count - 1.0);
+        let std_dev = variance.max(0.0).sqrt();
+        (std_dev / (sum / count) * 10_000.0).min(u32::MAX as f64) as u32
+    }
+}
+
+// === Roll Record ===
+
 /// Record of a single dice roll
 #[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+#[graphql(complex)]
 pub struct RollRecord {
+    /// Sequential roll id across all games, bonus rolls included (starts at 1)
+    pub roll_id: u64,
     /// The four dice values
     pub dice: [u8; 4],
     /// Sum of the dice
@@ -242,6 +2191,114 @@ pub struct RollRecord {
     pub fee_paid_atto: String,
     /// Whether this was a lucky match
     pub is_lucky: bool,
+    /// Dice thrown beyond the standard four in a season mode (counted in `sum`)
+    pub extra_dice: Vec<u8>,
+    /// Cosmetic skin the dice were shown with
+    pub skin_id: Option<u32>,
+}
+
+#[ComplexObject]
+impl RollRecord {
+    /// Globally unique opaque node id
+    pub async fn node_id(&self) -> ID {
+        node_id("RollRecord", self.roll_id)
+    }
+}
+
+/// One step of the roll results hash: every roll record is hashed together with the
+/// previous head, so a dump of the whole roll history can be checked against one value
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RollResultLink {
+    pub previous: Option<CryptoHash>,
+    pub roll: RollRecord,
+}
+
+impl BcsHashable<'_> for RollResultLink {}
+
+/// Chain `rolls`, oldest first, onto the roll results hash `head` (None before the
+/// first roll). Folding every roll since the first from None gives `rollResultsHash`.
+pub fn chain_roll_results<'a>(
+    head: Option<CryptoHash>,
+    rolls: impl IntoIterator<Item = &'a RollRecord>,
+) -> Option<CryptoHash> {
+    rolls.into_iter().fold(head, |previous, roll| {
+        Some(CryptoHash::new(&RollResultLink {
+            previous,
+            roll: roll.clone(),
+        }))
+    })
+}
+
+/// Head of the roll results hash, with the roll it covers up to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct RollResultsHash {
+    /// Id of the last roll chained in (0 before the first roll)
+    pub last_roll_id: u64,
+    /// Hash over every roll record up to `last_roll_id` (None before the first roll)
+    pub hash: Option<CryptoHash>,
+}
+
+// === Runtime Context ===
+
+/// Deployment and block a result came from, so frontends following several chains can
+/// tell results apart without extra RPC calls
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct RuntimeContext {
+    pub chain_id: ChainId,
+    pub application_id: ApplicationId,
+    pub block_height: u64,
+}
+
+// === Node Ids ===
+
+/// Opaque GraphQL node id for federated gateways: URL-safe base64 of "<type>:<key>"
+pub fn node_id(type_name: &str, key: u64) -> ID {
+    ID(URL_SAFE_NO_PAD.encode(format!("{}:{}", type_name, key)))
+}
+
+/// Split a node id back into its type name and key
+pub fn parse_node_id(id: &str) -> Option<(String, u64)> {
+    let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(id).ok()?).ok()?;
+    let (type_name, key) = decoded.split_once(':')?;
+    Some((type_name.to_string(), key.parse().ok()?))
+}
+
+// === Batch Outcomes ===
+
+/// Summary of one AutoRoll batch, kept so clients can look it up by handle
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct BatchOutcome {
+    /// Sequential handle (starts at 1), as returned by the `rollBurst` mutation
+    pub handle: u64,
+    /// Game the batch rolled on (0 if there was none)
+    pub game_id: u64,
+    /// Paid rolls performed
+    pub rolls_made: u32,
+    /// Cells marked across all rolls
+    pub marks_gained: u32,
+    /// Roll fees charged by this batch (in atto)
+    pub fees_paid_atto: String,
+    /// Type of bingo achieved, if any
+    pub bingo_type: Option<BingoType>,
+    /// Whether the game is over
+    pub game_over: bool,
+    /// Why rolling stopped before `count`, if it hit an error
+    pub stop_reason: Option<String>,
+    /// Code of that error
+    pub stop_code: Option<ErrorCode>,
+}
+
+/// Remainder of an AutoRoll batch paused by the per-block roll cap
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct PendingAutoRoll {
+    /// Game the batch was rolling on; the batch is dropped once that game ends
+    pub game_id: u64,
+    /// Paid rolls still to perform
+    pub remaining: u32,
+    pub stop_on_bingo: bool,
+    pub aggregate: bool,
+    /// Block the batch was paused in
+    pub paused_at_block: u64,
 }
 
 // === Player Balance ===
@@ -257,9 +2314,1843 @@ pub struct PlayerBalance {
     pub total_won_atto: String,
     /// Total spent on fees (in atto LINERA)
     pub total_spent_atto: String,
+    /// Free-to-play points balance (not withdrawable)
+    pub points_atto: String,
+    /// Winnings held back by open disputes (in atto LINERA)
+    pub frozen_atto: String,
+    /// Scheduled withdrawals not yet released (in atto LINERA)
+    pub pending_withdrawals_atto: String,
+    /// Vesting prize installments not yet claimed (in atto LINERA)
+    pub vesting_atto: String,
+}
+
+// === Player Accounts ===
+
+/// Everything the contract keeps for one owner: their balances, their game and session,
+/// and their settings. Each signer plays out of their own account.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct PlayerAccount {
+    /// Available balance (deposited - spent + won)
+    pub player_balance: Amount,
+    /// Total deposited
+    pub total_deposited: Amount,
+    /// Total won
+    pub total_won: Amount,
+    /// Total spent on fees
+    pub total_spent: Amount,
+    /// Bet locked by the current game
+    pub current_prize_pool: Amount,
+    /// Free-to-play points balance (Currency::Play games), never withdrawable
+    pub points_balance: Amount,
+    /// Winnings held back from the balance by open disputes
+    pub frozen_balance: Amount,
+    /// Total of scheduled withdrawals not yet released
+    pub pending_withdrawals: Amount,
+    /// Vesting payout installments not yet claimed
+    pub vesting_balance: Amount,
+    /// Current bingo card
+    pub current_card: Option<BingoCard>,
+    /// The other card of a hedged game; it is swapped in if it reaches bingo first
+    pub hedge_card: Option<BingoCard>,
+    /// All numbers drawn in the current game
+    pub drawn_numbers: Vec<u8>,
+    /// Recent roll results, oldest first (keeps last 50; cleared when a session ends)
+    pub roll_history: Vec<RollRecord>,
+    /// Lifecycle phase of the current game
+    pub game_phase: GamePhase,
+    /// Current session (None if not started)
+    pub active_session: Option<GameSession>,
+    /// Games dealt
+    pub games_played: u64,
+    /// Games won (bingo achieved)
+    pub games_won: u64,
+    /// Latest real-money game that settled other than by a refund; the loss guard
+    /// measures the next bet against it
+    pub last_real_settlement: Option<SettledGame>,
+    /// Self-imposed rules set via SetPreferences
+    pub preferences: PlayerPreferences,
+    /// Where today's and the current game's spending is measured from
+    pub budget_tracker: BudgetTracker,
+    /// Budget alerts raised today or in the current game
+    pub budget_alerts: Vec<BudgetAlert>,
+    /// Purge requested with PurgeMyData and not yet confirmed or cancelled
+    pub data_purge: Option<DataPurgeRequest>,
+    /// Summary of the open game, refreshed after every operation
+    pub active_game: Option<ActiveGameSummary>,
+    /// AutoRoll batch paused by the per-block roll cap, if any
+    pub pending_auto_roll: Option<PendingAutoRoll>,
+    /// Outcomes of recent AutoRoll batches, oldest first (keeps last 20)
+    pub batch_outcomes: Vec<BatchOutcome>,
+    /// Owner whose account holds the game sponsored for this one, if any
+    pub sponsored_by: Option<AccountOwner>,
+    /// First-run checklist
+    pub onboarding: OnboardingProgress,
+    /// Collected and equipped dice skins
+    pub skins: SkinInventory,
+    /// Rolls that marked each card cell in this account's games (row-major)
+    pub cell_hits: [u64; 25],
+    /// Raffle round `raffle_tickets` were earned in
+    pub raffle_round: u64,
+    /// Raffle tickets held in that round
+    pub raffle_tickets: u64,
+    /// Operations this owner has signed
+    pub api_usage: ApiUsage,
+}
+
+impl PlayerAccount {
+    /// Open a new owner's account, holding the free play points grant
+    pub fn open() -> Self {
+        PlayerAccount {
+            points_balance: Amount::from_attos(PLAY_POINTS_GRANT),
+            ..PlayerAccount::default()
+        }
+    }
+}
+
+// === Player Preferences ===
+
+/// Player-set rules the contract enforces on their behalf
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "PlayerPreferencesInput")]
+pub struct PlayerPreferences {
+    /// Anti-martingale guard: after losing a real-money game, a new bet may be at most
+    /// this many times the previous bet (0 = off)
+    pub max_bet_multiple_after_loss: u32,
+    /// Real balance (in atto) above which a prize claim sends the excess to
+    /// `payout_address` (empty or 0 = off)
+    pub auto_withdraw_above_atto: String,
+    /// Where automatic withdrawals are sent; required for auto-withdraw
+    pub payout_address: Option<AccountOwner>,
+    /// Real money (in atto) spent in one UTC day that raises a budget alert
+    /// (empty or 0 = off)
+    pub daily_spend_alert_atto: String,
+    /// Real money (in atto) put into a single game that raises a budget alert
+    /// (empty or 0 = off)
+    pub game_loss_alert_atto: String,
+}
+
+impl PlayerPreferences {
+    /// Auto-withdraw threshold in atto (0 = off)
+    pub fn auto_withdraw_threshold(&self) -> u128 {
+        self.auto_withdraw_above_atto.parse().unwrap_or(0)
+    }
+
+    /// Daily spend alert threshold in atto (0 = off)
+    pub fn daily_spend_alert(&self) -> u128 {
+        self.daily_spend_alert_atto.parse().unwrap_or(0)
+    }
+
+    /// Single-game loss alert threshold in atto (0 = off)
+    pub fn game_loss_alert(&self) -> u128 {
+        self.game_loss_alert_atto.parse().unwrap_or(0)
+    }
+}
+
+/// A player's pending request to have their personal data purged
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct DataPurgeRequest {
+    pub owner: AccountOwner,
+    pub requested_at_micros: u64,
+    /// Earliest time ConfirmDataPurge is accepted
+    pub confirmable_at_micros: u64,
+}
+
+/// Budget a budget alert was raised for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum BudgetAlertKind {
+    /// Real money spent since the start of the UTC day
+    DailySpend,
+    /// Real money put into the current game
+    GameLoss,
+}
+
+/// A soft budget threshold the player crossed. Alerts only inform; play is never blocked.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct BudgetAlert {
+    pub kind: BudgetAlertKind,
+    pub threshold: Amount,
+    /// Amount spent when the alert was raised
+    pub spent: Amount,
+    /// Game the alert was raised in (GameLoss only)
+    pub game_id: Option<u64>,
+    pub raised_at_micros: u64,
+}
+
+/// Baselines the budget alerts measure spending from
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct BudgetTracker {
+    /// UTC day (days since the epoch) of the daily baseline
+    pub day: u64,
+    /// `total_spent` when `day` began
+    pub spent_at_day_start: Amount,
+    /// Game of the game baseline (0 = none)
+    pub game_id: u64,
+    /// `total_spent` before `game_id` started
+    pub spent_at_game_start: Amount,
+}
+
+// === Receipts ===
+
+/// Kind of balance movement a receipt records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum ReceiptKind {
+    Deposit,
+    Withdrawal,
+}
+
+/// Ledger entry for a deposit or withdrawal, used to reconcile support requests
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct Receipt {
+    /// Monotonically increasing receipt id (starts at 1)
+    pub id: u64,
+    /// Deposit or withdrawal
+    pub kind: ReceiptKind,
+    /// Amount moved (in atto LINERA)
+    pub amount_atto: String,
+    /// Available balance right after the movement (in atto LINERA)
+    pub balance_after_atto: String,
+    /// Block height the movement was executed in
+    pub block_height: u64,
+    /// Custodian that paid a deposit on the player's behalf
+    pub funded_by: Option<AccountOwner>,
+    /// Sponsored player a withdrawal paid their winnings to
+    pub paid_to: Option<AccountOwner>,
+    /// Timestamp in microseconds
+    pub timestamp_micros: u64,
+}
+
+/// Why part of the player's funds was left out of a WithdrawAll
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum WithholdReason {
+    /// Bet locked in the current game's prize pool until it is settled
+    LockedBet,
+    /// One roll fee kept back so the open real-money game can still be rolled
+    OpenGameReserve,
+    /// Free-to-play points have no withdrawal path
+    PlayPoints,
+    /// Already waiting out the time lock of scheduled withdrawals
+    ScheduledWithdrawals,
+}
+
+/// Withdrawal held until its release time
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct ScheduledWithdrawal {
+    /// Sequential withdrawal id, starting at 1
+    pub withdrawal_id: u64,
+    pub amount: Amount,
+    pub scheduled_at_micros: u64,
+    /// Earliest time the withdrawal can be released
+    pub release_at_micros: u64,
+    /// Receipt of the payout, once released
+    pub receipt_id: Option<u64>,
+    /// Owner whose balance the withdrawal was taken from
+    pub owner: AccountOwner,
+}
+
+/// An amount held back from a WithdrawAll
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Withheld {
+    pub reason: WithholdReason,
+    /// Amount held back (in atto)
+    pub amount_atto: String,
+}
+
+// === Configuration ===
+
+/// Admin-tunable game configuration
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "GameConfigInput")]
+pub struct GameConfig {
+    /// Maximum games an owner may have in play at once: unsettled, played in the
+    /// running session and still claimable (0 = unlimited)
+    pub max_active_games: u32,
+    /// Maximum GraphQL query depth (0 = DEFAULT_MAX_QUERY_DEPTH)
+    pub max_query_depth: u32,
+    /// Maximum GraphQL query complexity (0 = DEFAULT_MAX_QUERY_COMPLEXITY)
+    pub max_query_complexity: u32,
+    /// Reject schema introspection queries on public endpoints
+    pub disable_introspection: bool,
+    /// Time allowed to claim a bingo before the claim is refused (0 = no deadline)
+    pub claim_window_secs: u32,
+    /// Idle time after which `staleGameWarning` nudges the player to roll (0 = off)
+    pub stale_game_warning_secs: u32,
+    /// Time after a prize is claimed during which the game can be disputed (0 = no
+    /// disputes)
+    pub dispute_window_secs: u32,
+    /// Redeal cards whose difficulty score is above this, keeping the easiest of
+    /// MAX_CARD_DEALS deals (0 = off)
+    pub max_card_difficulty: u32,
+    /// Tier payout bonus for cards harder than MEDIAN_CARD_DIFFICULTY, in basis points,
+    /// reached in full at twice the median (0 = off)
+    pub difficulty_bonus_bps: u32,
+    /// Payout bonus per roll that marked the card with a first-drawn sum, in basis
+    /// points of the bet (0 = off)
+    pub fresh_sum_bonus_bps: u32,
+    /// Paid AutoRoll rolls a single block may execute; batches hitting it are paused
+    /// until a ContinueAutoRoll resumes them (0 = unlimited)
+    pub max_rolls_per_block: u32,
+    /// Flag sessions whose mean time between rolls is below this (0 = off)
+    pub cadence_min_interval_ms: u32,
+    /// Flag sessions whose interval std-dev is below this share of the mean, in
+    /// basis points (0 = off); humans are rarely that regular
+    pub cadence_min_jitter_bps: u32,
+    /// Daily simple interest on escrowed tournament entry fees, in basis points, paid
+    /// by the house at settlement; fixed per tournament when it is created (0 = off)
+    pub tournament_interest_bps_per_day: u32,
+    /// Most congestion time credited back to one session's expiry and claim deadline
+    /// (0 = off)
+    pub congestion_pause_max_secs: u32,
+    /// How EndSession settles an unfinished game (a pending prize is always claimed)
+    pub session_end_policy: SessionEndPolicy,
+    /// Real-money payouts at or above this are paid in vesting installments
+    /// (0 = off)
+    pub vesting_threshold: Amount,
+    /// Installments a vesting payout is split into, the first credited with the
+    /// claim (0 = DEFAULT_VESTING_INSTALLMENTS)
+    pub vesting_installments: u32,
+    /// Time between a vesting payout's installments unlocking (0 = EPOCH_PERIOD_SECS)
+    pub vesting_interval_secs: u32,
+    /// Entries of each history PruneHistory keeps when it runs at every epoch
+    /// rollover (0 = off)
+    pub history_retention: u32,
+    /// How numbers are spread over newly dealt standard cards (the default deals as
+    /// `rules::deal_numbers` does)
+    pub card_constraints: CardConstraints,
+    /// Delay between proposing a governed change (see `Operation::is_governed`) and
+    /// executing it, so players see it coming (at least MIN_GOVERNANCE_TIMELOCK_SECS)
+    pub governance_timelock_secs: u32,
+    /// Multipliers Classic and BlackoutContinuation wins pay by roll count, best
+    /// first (empty = DEFAULT_PAYOUT_CURVE)
+    pub payout_curve: Vec<PayoutTier>,
+}
+
+impl GameConfig {
+    /// Installments and seconds between them for payouts at or above
+    /// `vesting_threshold`, with the defaults filled in
+    pub fn vesting_terms(&self) -> (u32, u64) {
+        let installments = match self.vesting_installments {
+            0 => DEFAULT_VESTING_INSTALLMENTS,
+            installments => installments,
+        };
+        let interval_secs = match self.vesting_interval_secs {
+            0 => EPOCH_PERIOD_SECS,
+            secs => u64::from(secs),
+        };
+        (installments, interval_secs)
+    }
+
+    /// The payout curve wins are paid from, with the default filled in
+    pub fn payout_tiers(&self) -> Vec<PayoutTier> {
+        if self.payout_curve.is_empty() {
+            PayoutTier::default_curve()
+        } else {
+            self.payout_curve.clone()
+        }
+    }
+}
+
+// === Card Constraints ===
+
+/// Limits on the numbers dealt onto a standard card, so operators can tune difficulty
+/// without a code change. Every field defaults to off.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject, InputObject,
+)]
+#[graphql(input_name = "CardConstraintsInput")]
+#[serde(default)]
+pub struct CardConstraints {
+    /// Fewest cells every allowed number must fill (0 = off)
+    pub min_occurrences: u8,
+    /// Most cells any number may fill (0 = off)
+    pub max_occurrences: u8,
+    /// Sums never dealt onto a card
+    pub banned_sums: Vec<u8>,
+    /// Every row must hold a number below 14 and one above it (the FREE cell counts
+    /// as neither)
+    pub balanced_rows: bool,
+}
+
+impl CardConstraints {
+    /// Whether any constraint is set; without one, cards are dealt the standard way
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Numbers a card may hold: 4-24 less the banned sums, ascending
+    pub fn allowed_numbers(&self) -> Vec<u8> {
+        (4..=24).filter(|n| !self.banned_sums.contains(n)).collect()
+    }
+
+    /// Check that some card satisfies every constraint
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(sum) = self.banned_sums.iter().find(|sum| !(4..=24).contains(*sum)) {
+            return Err(format!("Banned sum {} is not a four-dice sum", sum));
+        }
+        let allowed = self.allowed_numbers();
+        let (min, max) = (self.min_occurrences as usize, self.max_occurrences as usize);
+        if allowed.is_empty() {
+            return Err("Every sum is banned".to_string());
+        }
+        if max != 0 && min > max {
+            return Err("`minOccurrences` is above `maxOccurrences`".to_string());
+        }
+        if min * allowed.len() > 24 {
+            return Err(format!(
+                "{} numbers at least {} times each need more than 24 cells",
+                allowed.len(),
+                min
+            ));
+        }
+        if max != 0 && max * allowed.len() < 24 {
+            return Err(format!(
+                "{} numbers at most {} times each cannot fill 24 cells",
+                allowed.len(),
+                max
+            ));
+        }
+        let has_low = allowed.iter().any(|n| *n < 14);
+        let has_high = allowed.iter().any(|n| *n > 14);
+        if self.balanced_rows && !(has_low && has_high) {
+            return Err("Balanced rows need numbers both below and above 14".to_string());
+        }
+        Ok(())
+    }
+}
+
+// === Payout Curve ===
+
+/// One step of a payout curve: a win taking at most `max_rolls` rolls (and more than
+/// the tier before allows) pays `numerator / denominator` times the bet
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "PayoutTierInput")]
+pub struct PayoutTier {
+    /// Last roll count the tier covers; the final tier also takes every slower win
+    pub max_rolls: u32,
+    pub numerator: u32,
+    pub denominator: u32,
+    /// Display string, e.g. "1.2x"
+    pub label: String,
+}
+
+impl PayoutTier {
+    /// The built-in curve, DEFAULT_PAYOUT_CURVE
+    pub fn default_curve() -> Vec<PayoutTier> {
+        DEFAULT_PAYOUT_CURVE
+            .iter()
+            .map(|(max_rolls, numerator, denominator, label)| PayoutTier {
+                max_rolls: *max_rolls,
+                numerator: *numerator,
+                denominator: *denominator,
+                label: label.to_string(),
+            })
+            .collect()
+    }
+
+    /// Check that `curve` can pay wins: tiers in roll order, none paying more than a
+    /// faster one, and a house edge on a MIN_BET game. The edge is judged on the
+    /// TIER_WIN_CHANCE_BPS model, pricing each of its tiers at its fastest win, so it
+    /// holds however the curve's tiers fall between them.
+    pub fn validate_curve(curve: &[PayoutTier]) -> Result<(), String> {
+        if curve.is_empty() || curve.len() > MAX_PAYOUT_TIERS {
+            return Err(format!(
+                "A payout curve needs 1 to {} tiers",
+                MAX_PAYOUT_TIERS
+            ));
+        }
+        for (index, tier) in curve.iter().enumerate() {
+            if tier.denominator == 0 {
+                return Err(format!("Tier {} has a zero denominator", index));
+            }
+            if tier.label.is_empty() || tier.label.len() > MAX_PAYOUT_LABEL_LEN {
+                return Err(format!(
+                    "Tier {} needs a label of 1 to {} bytes",
+                    index, MAX_PAYOUT_LABEL_LEN
+                ));
+            }
+        }
+        for (index, pair) in curve.windows(2).enumerate() {
+            let (faster, slower) = (&pair[0], &pair[1]);
+            if slower.max_rolls <= faster.max_rolls {
+                return Err(format!(
+                    "Tier {} does not end after tier {}",
+                    index + 1,
+                    index
+                ));
+            }
+            let slower_pays = u64::from(slower.numerator) * u64::from(faster.denominator);
+            let faster_pays = u64::from(faster.numerator) * u64::from(slower.denominator);
+            if slower_pays > faster_pays {
+                return Err(format!("Tier {} pays more than tier {}", index + 1, index));
+            }
+        }
+
+        // Expected payout and stake of a MIN_BET game, times 10_000
+        let mut payout = 0u128;
+        let mut stake = MIN_BET * 10_000;
+        let mut fastest_win = 1;
+        for (tier, chance_bps) in TIER_WIN_CHANCE_BPS.iter().enumerate() {
+            let (numerator, denominator, _) = rules::multiplier(curve, fastest_win);
+            payout +=
+                MIN_BET * u128::from(*chance_bps) * u128::from(numerator) / u128::from(denominator);
+            stake += ROLL_COST * u128::from(*chance_bps) * u128::from(fastest_win);
+            fastest_win = MULTIPLIER_TIER_MAX_ROLLS
+                .get(tier)
+                .map_or(fastest_win, |max_rolls| max_rolls + 1);
+        }
+        if payout >= stake {
+            return Err(
+                "The curve pays out more than a minimum bet and its roll fees take in".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+// === Season Modes ===
+
+/// Limited-time special mode kept as data in state, so a new event needs no
+/// redeployment. Games use Classic rules with the mode's dice and number range.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "SeasonModeInput")]
+pub struct SeasonMode {
+    /// Registry id (0 when adding a new mode)
+    pub mode_id: u64,
+    /// Display name, e.g. "Double Dice Weekend"
+    pub name: String,
+    /// Dice per roll, 4 to MAX_SEASON_DICE
+    pub dice_count: u8,
+    /// Lowest and highest number dealt on a card; both must be reachable sums
+    pub min_number: u8,
+    pub max_number: u8,
+    /// Whether new games may start under the mode
+    pub enabled: bool,
+    /// Window new games may start in (microseconds since epoch)
+    pub starts_at_micros: u64,
+    pub ends_at_micros: u64,
+}
+
+impl SeasonMode {
+    /// Whether a game may start under the mode at `now_micros`
+    pub fn is_live(&self, now_micros: u64) -> bool {
+        self.enabled && self.starts_at_micros <= now_micros && now_micros < self.ends_at_micros
+    }
+}
+
+// === Game Templates ===
+
+/// Lines that win under a game template
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum WinPattern {
+    /// Any row, column or diagonal (the standard rules)
+    #[default]
+    AnyLine,
+    Rows,
+    Columns,
+    Diagonals,
+    /// Only a fully marked card
+    Blackout,
+}
+
+impl WinPattern {
+    /// Whether completing `line` wins under the pattern
+    pub fn counts(self, line: BingoType) -> bool {
+        use BingoType::*;
+        match self {
+            WinPattern::AnyLine => true,
+            WinPattern::Rows => matches!(line, Row0 | Row1 | Row2 | Row3 | Row4),
+            WinPattern::Columns => matches!(line, Col0 | Col1 | Col2 | Col3 | Col4),
+            WinPattern::Diagonals => matches!(line, DiagonalMain | DiagonalAnti),
+            WinPattern::Blackout => line == FullCard,
+        }
+    }
+}
+
+/// House rules a tournament organizer publishes under an id, so any player can start
+/// a game under them with `NewGame { template_id }`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "GameTemplateInput")]
+pub struct GameTemplate {
+    /// Registry id (0 when publishing a new template)
+    pub template_id: u64,
+    /// Display name, e.g. "Friday Blackout League"
+    pub name: String,
+    /// Owner who published the template and alone may replace it (set by the contract)
+    pub organizer: Option<AccountOwner>,
+    /// Game rules every card is played under
+    pub mode: GameMode,
+    /// Lines that count as a bingo
+    pub pattern: WinPattern,
+    /// Bet every game must stake (zero = any bet the table accepts)
+    pub entry_fee: Amount,
+    /// Roll fee, at least the mode's usual one (zero = the usual fee)
+    pub roll_cost: Amount,
+    /// Redeal cards scoring above this difficulty (0 = the deployment's cap)
+    pub max_card_difficulty: u32,
+}
+
+// === Happy Hours ===
+
+/// A window in which roll fees are discounted
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct HappyHour {
+    /// Sequential id, starting at 1
+    pub happy_hour_id: u64,
+    pub starts_at_micros: u64,
+    /// First moment the discount no longer applies
+    pub ends_at_micros: u64,
+    /// Discount on roll fees in basis points (10000 = free rolls)
+    pub discount_bps: u32,
+}
+
+impl HappyHour {
+    /// Whether the discount applies at `now_micros`
+    pub fn is_active(&self, now_micros: u64) -> bool {
+        (self.starts_at_micros..self.ends_at_micros).contains(&now_micros)
+    }
+}
+
+// === Admin Log ===
+
+/// Kind of admin intervention recorded in the public log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum AdminActionKind {
+    ClaimDeadlinesExtended,
+    DisputeResolved,
+    Paused,
+    Resumed,
+    TreasuryWithdrawn,
+    ChangeProposed,
+    ProposalCancelled,
+    WithdrawalHoldPlaced,
+    WithdrawalHoldReleased,
+}
+
+/// Public record of an admin intervention
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct AdminAction {
+    /// Sequential action id, starting at 1
+    pub action_id: u64,
+    pub admin: AccountOwner,
+    pub kind: AdminActionKind,
+    /// Human-readable summary, including the admin's stated reason
+    pub detail: String,
+    pub timestamp_micros: u64,
+}
+
+// === Governance ===
+
+/// What a governance proposal changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum ProposalKind {
+    ConfigUpdate,
+    TreasuryWithdrawal,
+    Experiment,
+    HighRollerTable,
+    SeasonMode,
+    HappyHour,
+    HappyHourCancellation,
+}
+
+/// Where a governance proposal stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum ProposalStatus {
+    /// Waiting out its timelock, or ready to execute
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+/// A governed admin change queued behind the governance timelock
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct GovernanceProposal {
+    /// Sequential proposal id, starting at 1
+    pub proposal_id: u64,
+    pub kind: ProposalKind,
+    /// The admin operation ExecuteProposal runs
+    #[graphql(skip)]
+    pub change: Operation,
+    /// What the change does, as recorded in the admin log
+    pub detail: String,
+    /// Configuration a ConfigUpdate installs
+    pub config: Option<GameConfig>,
+    /// Amount a TreasuryWithdrawal pays out
+    pub amount: Option<Amount>,
+    /// Reason a TreasuryWithdrawal was proposed for
+    pub reason: String,
+    pub status: ProposalStatus,
+    pub proposed_at_micros: u64,
+    /// Earliest time ExecuteProposal may apply it
+    pub executable_at_micros: u64,
+    /// When it was executed or cancelled
+    pub closed_at_micros: Option<u64>,
+    /// Admin log entry recording the proposal
+    pub action_id: u64,
+}
+
+// === Compliance Holds ===
+
+/// Why an owner's withdrawals are on hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum HoldReason {
+    /// Identity documents are being verified
+    IdentityReview,
+    /// Funds are being checked against anti-money-laundering rules
+    AmlReview,
+    /// The owner may match a sanctions list
+    SanctionsScreening,
+    /// Play or payments on the account are under fraud investigation
+    FraudInvestigation,
+    /// A court or regulator ordered the funds frozen
+    LegalOrder,
+    /// See the hold's note
+    Other,
+}
+
+/// Compliance hold blocking one owner's withdrawals
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct WithdrawalHold {
+    pub owner: AccountOwner,
+    pub reason: HoldReason,
+    pub note: String,
+    pub placed_at_micros: u64,
+    /// When the hold lapses on its own (0 = only when released)
+    pub expires_at_micros: u64,
+    /// Admin log entry that placed the hold
+    pub action_id: u64,
+}
+
+impl WithdrawalHold {
+    /// Whether the hold still blocks withdrawals at `now_micros`
+    pub fn is_active(&self, now_micros: u64) -> bool {
+        self.expires_at_micros == 0 || now_micros < self.expires_at_micros
+    }
+}
+
+// === Disputes ===
+
+/// How a game ended, won or not; disputes are filed against these
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct SettledGame {
+    pub game_id: u64,
+    pub owner: Option<AccountOwner>,
+    /// Owner who paid for the game when it was sponsored for `owner`
+    pub sponsor: Option<AccountOwner>,
+    pub currency: Currency,
+    pub outcome: SettlementOutcome,
+    /// Bet locked in the game's prize pool
+    pub stake: Amount,
+    /// Prize or refund credited to the player
+    pub payout: Amount,
+    pub settled_at_micros: u64,
+}
+
+impl SettledGame {
+    /// Part of the stake the house kept
+    pub fn house_take(&self) -> Amount {
+        self.stake.saturating_sub(self.payout)
+    }
+}
+
+/// Admin ruling on a dispute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum DisputeOutcome {
+    /// The settlement stands; frozen winnings go back to the player and the held
+    /// house take back to the house
+    Dismissed,
+    /// The player was shorted; frozen winnings and the held house take go to the
+    /// player, along with any award
+    Upheld,
+    /// The settlement is reversed; frozen winnings go to the house and the held
+    /// house take back to the player
+    Voided,
+}
+
+/// How and when a dispute was ruled on
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct DisputeResolution {
+    pub outcome: DisputeOutcome,
+    /// Paid to the player out of the treasury (`EpochPool::bonus_pool`)
+    pub award: Amount,
+    pub note: String,
+    /// Entry in the public admin log
+    pub action_id: u64,
+    pub resolved_at_micros: u64,
+}
+
+/// A player's challenge to a settled game
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct Dispute {
+    /// Sequential dispute id, starting at 1
+    pub dispute_id: u64,
+    pub game_id: u64,
+    pub owner: Option<AccountOwner>,
+    pub reason: String,
+    /// Winnings moved out of the balance while the dispute is open (less if some
+    /// had already been withdrawn)
+    pub frozen: Amount,
+    /// The game's house take, held out of the epoch while the dispute is open (less
+    /// if the epoch's take cannot cover it)
+    pub house_held: Amount,
+    pub filed_at_micros: u64,
+    /// None while the dispute is open
+    pub resolution: Option<DisputeResolution>,
+}
+
+// === Relayed Operations ===
+
+/// Domain a relayed operation is signed for. Binding the chain and application stops
+/// it being replayed on another deployment; the nonce is consumed on first use and
+/// remembered until `expires_at_micros`, after which the envelope is refused anyway.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct RelayEnvelope {
+    /// Owner the operation runs as; must be the signer
+    pub owner: AccountOwner,
+    pub chain_id: ChainId,
+    pub application_id: ApplicationId,
+    /// Any value the owner has not used in an envelope that is still valid
+    pub nonce: u64,
+    /// At most `MAX_RELAY_TTL_SECS` ahead of the block time it runs in
+    pub expires_at_micros: u64,
+}
+
+/// What the owner of a relayed operation signs
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RelayPayload {
+    pub envelope: RelayEnvelope,
+    pub operation: Operation,
+}
+
+impl BcsSignable<'_> for RelayPayload {}
+
+/// A nonce consumed by a relayed operation, kept until its envelope expires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct ConsumedNonce {
+    pub nonce: u64,
+    pub expires_at_micros: u64,
+}
+
+// === Admin Queries ===
+
+/// What the admin signs to read the service's admin-only queries. Binding the chain
+/// and application keeps a grant from unlocking another deployment; it is refused
+/// once `expires_at_micros` has passed, which may be at most
+/// `MAX_ADMIN_GRANT_TTL_SECS` ahead.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AdminQueryGrant {
+    pub chain_id: ChainId,
+    pub application_id: ApplicationId,
+    pub expires_at_micros: u64,
+}
+
+impl BcsSignable<'_> for AdminQueryGrant {}
+
+impl AdminQueryGrant {
+    /// How the service expects the grant's signature: URL-safe base64 of its bytes
+    pub fn encode_signature(signature: &AccountSignature) -> String {
+        URL_SAFE_NO_PAD.encode(signature.to_bytes())
+    }
+
+    /// Inverse of `encode_signature`
+    pub fn decode_signature(encoded: &str) -> Option<AccountSignature> {
+        let bytes = URL_SAFE_NO_PAD.decode(encoded).ok()?;
+        AccountSignature::from_slice(&bytes).ok()
+    }
+}
+
+// === Attestations ===
+
+/// The facts of a claimed game that a `GameAttestation` vouches for
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct AttestedOutcome {
+    /// Chain the game was played on
+    pub chain_id: ChainId,
+    pub game_id: u64,
+    /// Player who claimed the prize
+    pub owner: Option<AccountOwner>,
+    pub currency: Currency,
+    pub payout: Amount,
+    pub bingo_type: Option<BingoType>,
+    pub rolls_count: u32,
+    pub claimed_at_micros: u64,
+}
+
+impl BcsHashable<'_> for AttestedOutcome {}
+
+/// Compact proof of a game's outcome: a verifier on another chain recomputes
+/// `CryptoHash::new(&outcome)` and compares it with `hash` read from this chain,
+/// without replaying the game
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct GameAttestation {
+    pub outcome: AttestedOutcome,
+    pub hash: CryptoHash,
+    /// Block the attestation was produced in
+    pub attested_at_height: u64,
+}
+
+// === Entropy History ===
+
+/// Randomness inputs of one block that drew dice or dealt cards, kept so the pipeline
+/// can be scrutinized over time. Every draw mixes the block height and timestamp (the
+/// chain's beacon) with per-draw nonces and counters into a state.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct EntropyRecord {
+    pub block_height: u64,
+    /// Block timestamp mixed into every draw (microseconds since epoch)
+    pub timestamp_micros: u64,
+    /// Dice rolls and card deals drawn in the block
+    pub draws: u32,
+    /// Head of the hash chain over the block's mixed states, in draw order (see
+    /// `EntropyLink`)
+    pub digest: CryptoHash,
+}
+
+/// One step of a block's entropy digest: each mixed state is hashed together with the
+/// previous head (None for the block's first draw)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EntropyLink {
+    pub previous: Option<CryptoHash>,
+    pub mixed_state: u64,
+}
+
+impl BcsHashable<'_> for EntropyLink {}
+
+// === Diagnostics ===
+
+/// Decision a diagnostics event records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum DiagnosticKind {
+    /// A roll, mark or multiplier lock fee and the currency it was taken in
+    FeeCharged,
+    /// The payout tier a claim or PointsPerMark settlement fell in
+    TierChosen,
+    /// The inputs mixed into a dice roll, and the dice they gave
+    EntropyInputs,
+}
+
+/// One decision recorded while `FlashportParameters::diagnostics` was on
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct DiagnosticEvent {
+    pub event_id: u64,
+    pub kind: DiagnosticKind,
+    /// Game the decision was made for, if it belongs to one
+    pub game_id: Option<u64>,
+    pub block_height: u64,
+    pub at_micros: u64,
+    /// Values behind the decision, as `name=value` pairs
+    pub detail: String,
+}
+
+// === A/B Experiments ===
+
+/// Experiment bucket an owner is deterministically assigned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, Enum)]
+pub enum ExperimentBucket {
+    A,
+    B,
+}
+
+/// One side of an A/B experiment
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "ExperimentArmInput")]
+pub struct ExperimentArm {
+    /// Scale applied to the tier payout in basis points (10_000 = unchanged)
+    pub payout_scale_bps: u32,
+    /// Roll fee charged to games in this arm
+    pub roll_cost: Amount,
+}
+
+/// Two multiplier/fee configurations split across owners
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "ExperimentInput")]
+pub struct Experiment {
+    pub arm_a: ExperimentArm,
+    pub arm_b: ExperimentArm,
+    /// Share of owners assigned to arm A, in basis points (0-10_000)
+    pub split_a_bps: u32,
+}
+
+impl Experiment {
+    /// The arm games in `bucket` play under
+    pub fn arm(&self, bucket: ExperimentBucket) -> &ExperimentArm {
+        match bucket {
+            ExperimentBucket::A => &self.arm_a,
+            ExperimentBucket::B => &self.arm_b,
+        }
+    }
+}
+
+/// Aggregated outcomes of the games played in one experiment or high-roller bucket
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct BucketStats {
+    /// Owners assigned to this bucket (experiments only)
+    pub players: u64,
+    /// Games started
+    pub games: u64,
+    /// Paid rolls made
+    pub rolls: u64,
+    /// Total bets placed
+    pub wagered: Amount,
+    /// Total roll fees charged
+    pub fees: Amount,
+    /// Total payouts credited
+    pub paid_out: Amount,
+}
+
+// === High Rollers ===
+
+/// Table for bets above MAX_BET, open only to owners holding the achievement the
+/// admin picks
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "HighRollerTableInput")]
+pub struct HighRollerTable {
+    /// Largest bet accepted at the table (must exceed MAX_BET)
+    pub max_bet: Amount,
+    /// Roll fee charged to high-roller games
+    pub roll_cost: Amount,
+    /// Achievement skin that unlocks the table (one earned only, not bought)
+    pub required_skin_id: u32,
+    /// Largest house loss the table's bucket may reach if a new game wins the top tier
+    pub exposure_cap: Amount,
+}
+
+// === API Usage ===
+
+/// Operations one owner has signed, counted by the contract as it handles them
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct ApiUsage {
+    /// Operations signed by this owner, refused ones included
+    pub operations: u64,
+    /// When the last one ran (microseconds since epoch)
+    pub last_operation_micros: u64,
+}
+
+// === Bot API ===
+
+/// Minimal state snapshot for high-frequency clients, served as one compact JSON
+/// string by the `fastState` query. Keys are single letters to keep payloads small.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FastState {
+    /// Real-money balance (atto)
+    #[serde(rename = "b")]
+    pub balance_atto: String,
+    /// Play points balance (atto)
+    #[serde(rename = "p")]
+    pub points_atto: String,
+    /// Current game id (0 when no card has been dealt)
+    #[serde(rename = "g")]
+    pub game_id: u64,
+    /// Marked cells of the current card (bit = row * 5 + col)
+    #[serde(rename = "m")]
+    pub marked: u32,
+    /// Paid rolls on the current card
+    #[serde(rename = "r")]
+    pub rolls: u32,
+    /// Payout multiplier of the current tier in basis points (10x = 100000)
+    #[serde(rename = "t")]
+    pub tier_bps: u32,
+    /// Whether a bingo is waiting to be claimed
+    #[serde(rename = "w")]
+    pub unclaimed_win: bool,
+}
+
+/// Part of the player-facing state whose last change is versioned for `stateDelta`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, Enum)]
+pub enum DeltaField {
+    Balance,
+    Points,
+    GamePhase,
+    CurrentCard,
+    HedgeCard,
+    Session,
+    BudgetAlerts,
+}
+
+// === Raffle ===
+
+/// A completed raffle draw
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct RaffleDraw {
+    /// Sequential draw number, starting at 1
+    pub draw_id: u64,
+    pub winner: AccountOwner,
+    /// Tickets the winner held in the round
+    pub winner_tickets: u64,
+    /// All tickets issued in the round
+    pub total_tickets: u64,
+    /// Pool credited to the winner
+    pub prize: Amount,
+    pub drawn_at_micros: u64,
+}
+
+// === House-Card Tournaments ===
+
+/// A house-card event: every entrant plays the same card against their own dice stream
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct Tournament {
+    /// Sequential tournament number, starting at 1
+    pub tournament_id: u64,
+    /// Shared card layout (row-major, 0 = FREE center)
+    pub numbers: [u8; 25],
+    pub entry_fee: Amount,
+    /// Entry fees collected so far
+    pub pot: Amount,
+    /// Entries and rolls are refused from this time on (microseconds since epoch)
+    pub closes_at_micros: u64,
+    pub settled: bool,
+    /// Every entrant's progress, in entry order
+    pub entries: Vec<TournamentEntry>,
+    /// Daily interest on escrowed entry fees, in basis points (see
+    /// `GameConfig::tournament_interest_bps_per_day`)
+    pub interest_bps_per_day: u32,
+    /// Interest paid out at settlement
+    pub interest_paid: Amount,
+}
+
+impl Tournament {
+    /// The owner's entry, if they joined
+    pub fn entry_mut(&mut self, owner: &AccountOwner) -> Option<&mut TournamentEntry> {
+        self.entries.iter_mut().find(|entry| entry.owner == *owner)
+    }
+
+    /// Interest `entry`'s fee has earned in escrow by `now_micros` (accrual stops when
+    /// the tournament closes; None on overflow)
+    pub fn accrued_interest(&self, entry: &TournamentEntry, now_micros: u64) -> Option<u128> {
+        let until = now_micros.min(self.closes_at_micros);
+        rules::escrow_interest(
+            u128::from(self.entry_fee),
+            self.interest_bps_per_day,
+            until.saturating_sub(entry.entered_at_micros),
+        )
+    }
+}
+
+/// One entrant's progress through a tournament
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct TournamentEntry {
+    pub owner: AccountOwner,
+    /// Marked cells of the shared card (FREE center starts marked)
+    pub marked: [bool; 25],
+    /// Rolls taken so far
+    pub rolls: u32,
+    /// Rolls it took to complete a line, once finished
+    pub rolls_to_bingo: Option<u32>,
+    /// When the line was completed (breaks ties in rolls-to-bingo)
+    pub finished_at_micros: u64,
+    /// Finishing rank from 1, assigned at settlement
+    pub rank: Option<u32>,
+    /// Share of the pot credited at settlement
+    pub payout: Amount,
+    /// When the entry fee went into escrow
+    pub entered_at_micros: u64,
+    /// Interest on the entry fee credited at settlement
+    pub interest: Amount,
+    /// Seed of the entrant's dice stream, drawn on entry and never published
+    #[graphql(skip)]
+    pub dice_seed: u64,
+}
+
+impl TournamentEntry {
+    /// Dice of the `roll`-th roll (0-based) of this entrant's stream
+    pub fn stream_dice(&self, roll: u32) -> [u8; 4] {
+        let mut rng_state = self
+            .dice_seed
+            .wrapping_add((roll as u64 + 1).wrapping_mul(0x9e3779b97f4a7c15));
+        rng_state ^= rng_state >> 33;
+        rng_state = rng_state.wrapping_mul(0xff51afd7ed558ccd);
+        rng_state ^= rng_state >> 33;
+        rng_state |= 1;
+        rules::dice_from_state(rng_state)
+    }
+}
+
+// === Duels ===
+
+/// Where a duel stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum DuelStatus {
+    /// Opened, waiting for a second player
+    Waiting,
+    /// Both seats taken; players alternate rolls
+    Playing,
+    /// A player completed a line, or outlasted the other's turn, and took the pot
+    Settled,
+}
+
+/// A two-player "last roll wins" match: each player rolls against their own card in
+/// turn, every roll fee goes into one pot, and the first to complete a line takes it
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct Duel {
+    /// Sequential duel number, starting at 1
+    pub duel_id: u64,
+    pub roll_fee: Amount,
+    /// Roll fees paid in so far
+    pub pot: Amount,
+    /// The opener, then the challenger once they join
+    pub seats: Vec<DuelSeat>,
+    /// Index of the seat to roll next
+    pub turn: u32,
+    /// When the seat to roll runs out of time and the other can claim the pot (0 while
+    /// waiting for a challenger)
+    pub turn_deadline_micros: u64,
+    pub status: DuelStatus,
+    pub winner: Option<AccountOwner>,
+    /// Pot credited to the winner, less the rake
+    pub payout: Amount,
+    /// Share of the pot kept by the house
+    pub rake: Amount,
+    pub opened_at_micros: u64,
+}
+
+impl Duel {
+    /// Seat index of `owner`, if they are playing
+    pub fn seat_of(&self, owner: &AccountOwner) -> Option<usize> {
+        self.seats.iter().position(|seat| seat.owner == *owner)
+    }
+
+    /// Split the pot into the winner's payout and the house rake
+    pub fn split_pot(&self) -> (Amount, Amount) {
+        let pot = u128::from(self.pot);
+        let rake = pot * DUEL_RAKE_BPS as u128 / 10_000;
+        (Amount::from_attos(pot - rake), Amount::from_attos(rake))
+    }
+}
+
+/// One player's card in a duel
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct DuelSeat {
+    pub owner: AccountOwner,
+    /// Card layout (row-major, 0 = FREE center)
+    pub numbers: [u8; 25],
+    /// Marked cells (FREE center starts marked)
+    pub marked: [bool; 25],
+    /// Rolls taken so far
+    pub rolls: u32,
+}
+
+// === Syndicates ===
+
+/// A named group of owners playing from one shared bankroll. Every game's net result
+/// is shared out over the members pro rata to their stakes.
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct Syndicate {
+    /// Sequential syndicate number, starting at 1
+    pub syndicate_id: u64,
+    pub name: String,
+    /// Owner who created the syndicate and manages its members
+    pub manager: AccountOwner,
+    pub members: Vec<SyndicateMember>,
+    /// Funds held for the members: always the sum of their stakes
+    pub bankroll: Amount,
+    pub games_played: u64,
+    /// Bets and roll fees paid out of the bankroll
+    pub wagered: Amount,
+    /// Payouts won back into the bankroll
+    pub paid_out: Amount,
+    pub created_at_micros: u64,
+}
+
+impl Syndicate {
+    /// Index of `owner` among the members, if they belong
+    pub fn member_index(&self, owner: &AccountOwner) -> Option<usize> {
+        self.members
+            .iter()
+            .position(|member| member.owner == *owner)
+    }
+
+    /// Split `amount` over the members pro rata to their stakes, by largest remainder so
+    /// the shares add up to `amount` exactly. A share never exceeds its stake while
+    /// `amount` is at most the bankroll, so a loss split this way can always be covered.
+    pub fn pro_rata_shares(&self, amount: Amount) -> Vec<Amount> {
+        let amount = u128::from(amount);
+        let total = u128::from(self.bankroll);
+        if total == 0 {
+            return vec![Amount::ZERO; self.members.len()];
+        }
+        let mut shares: Vec<(usize, u128, u128)> = self
+            .members
+            .iter()
+            .enumerate()
+            .map(|(index, member)| {
+                // A stake is at most the bankroll, so the share is at most `amount`
+                let (share, remainder) =
+                    amounts::mul_div(amount, u128::from(member.stake), total).unwrap_or_default();
+                (index, share, remainder)
+            })
+            .collect();
+        let assigned: u128 = shares.iter().map(|(_, share, _)| share).sum();
+        // Under one atto per member is left over: hand it out by largest remainder
+        let dust = amount.saturating_sub(assigned) as usize;
+        shares.sort_by_key(|(index, _, remainder)| (std::cmp::Reverse(*remainder), *index));
+        for (_, share, _) in shares.iter_mut().take(dust) {
+            *share += 1;
+        }
+        shares.sort_by_key(|(index, _, _)| *index);
+        shares
+            .into_iter()
+            .map(|(_, share, _)| Amount::from_attos(share))
+            .collect()
+    }
+}
+
+/// One owner's place in a syndicate
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct SyndicateMember {
+    pub owner: AccountOwner,
+    /// Total the member has paid in
+    pub contributed: Amount,
+    /// The member's claim on the bankroll: contributions plus their share of results
+    pub stake: Amount,
+    /// Largest bet the member may place for the syndicate (zero = backer only)
+    pub max_bet: Amount,
+    pub joined_at_micros: u64,
+}
+
+/// Outcome of one SyndicateGame
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct SyndicateGameResult {
+    pub syndicate_id: u64,
+    pub player: AccountOwner,
+    /// Seed the card and dice were drawn from
+    pub seed: u64,
+    pub bet: Amount,
+    pub rolls: u32,
+    pub roll_fees: Amount,
+    /// First line completed, None if the game ran out of rolls
+    pub bingo_type: Option<BingoType>,
+    pub payout: Amount,
+    /// `payout` minus bet and roll fees in atto, as a signed decimal string
+    pub net_result_atto: String,
+    /// Each member's share of the net result, in member order
+    pub shares: Vec<SyndicateShare>,
+}
+
+/// A member's share of a SyndicateGame result
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct SyndicateShare {
+    pub owner: AccountOwner,
+    /// Signed change to the member's stake, in atto
+    pub change_atto: String,
+    pub stake: Amount,
+}
+
+// === Prize Pool Epochs ===
+
+/// Prize pool accounting for one epoch. Epochs roll over lazily on the first
+/// operation after `ends_at_micros`, carrying unwon house contributions forward.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct EpochPool {
+    /// Sequential epoch number, starting at 1 (epochs without any operation are skipped)
+    pub epoch_id: u64,
+    pub started_at_micros: u64,
+    pub ends_at_micros: u64,
+    /// Real-money bets locked into games this epoch
+    pub wagered: Amount,
+    /// Real-money bets held by games in progress; those still open when the epoch
+    /// closes carry into the next one's pool
+    pub prize_pool: Amount,
+    /// Real-money payouts released to winners this epoch
+    pub paid_out: Amount,
+    /// Unwon bets kept by the house this epoch (forfeits and payouts below the bet)
+    pub house_contributions: Amount,
+    /// House contributions rolled over from earlier epochs
+    pub bonus_pool: Amount,
+}
+
+// === Bonus Airdrops ===
+
+/// One owner's credit in an airdrop batch
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "AirdropCreditInput")]
+pub struct AirdropCredit {
+    pub owner: AccountOwner,
+    pub amount: Amount,
+}
+
+/// A recorded AirdropBonus batch
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct AirdropBatch {
+    /// Sequential batch id, starting at 1
+    pub batch_id: u64,
+    pub credits: Vec<AirdropCredit>,
+    /// Sum of the credits, taken from the epoch bonus pool
+    pub total: Amount,
+    pub executed_at_micros: u64,
+}
+
+// === Lobby Registry ===
+
+/// Cross-chain messages between FlashPort deployments
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum Message {
+    /// A deployment announcing itself to the lobby chain's registry
+    RegisterInstance {
+        chain: ChainId,
+        config_summary: ConfigSummary,
+    },
+}
+
+/// Parameters a wallet needs to pick a deployment to play on
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct ConfigSummary {
+    pub abi_version: u32,
+    pub min_bet: Amount,
+    pub max_bet: Amount,
+    pub roll_cost: Amount,
+    /// Maximum games in play per owner (0 = unlimited)
+    pub max_active_games: u32,
+    /// Time allowed to claim a bingo (0 = no deadline)
+    pub claim_window_secs: u32,
+}
+
+/// A deployment known to the lobby registry
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct RegisteredInstance {
+    pub chain_id: ChainId,
+    pub config_summary: ConfigSummary,
+    pub first_registered_micros: u64,
+    /// When the deployment last re-registered (parameters may have changed)
+    pub updated_at_micros: u64,
+}
+
+// === Public Statistics ===
+
+/// Events emitted by the contract. New variants are only ever appended so that
+/// existing consumers keep decoding the stream.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FlashportEvent {
+    Stats(StatsSnapshot),
+    SessionSummary(SessionSummary),
+    BudgetAlert(BudgetAlert),
+}
+
+/// Aggregate real-money statistics published on the stats stream
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct StatsSnapshot {
+    /// STATS_SCHEMA_VERSION of this snapshot
+    pub schema_version: u32,
+    pub published_at_micros: u64,
+    pub total_games: u64,
+    pub total_wins: u64,
+    /// Real money wagered on bets and roll fees
+    pub volume: Amount,
+    /// Real money paid out in prizes and awards
+    pub paid_out: Amount,
+    /// Return to player: paid_out / volume in basis points (0 before any volume)
+    pub rtp_bps: u64,
+    /// Raffle pool paid out at the next draw
+    pub jackpot: Amount,
+}
+
+// === Read Models ===
+
+/// An owner's lifetime real-money winnings, as ranked by the leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct LeaderboardEntry {
+    pub owner: AccountOwner,
+    /// Total real-money payouts claimed
+    pub total_won: Amount,
+    /// Real-money prizes claimed
+    pub wins: u64,
+    /// Largest single payout
+    pub best_payout: Amount,
+}
+
+/// Real-money wagers and payouts of one closed epoch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct EpochVolume {
+    pub epoch_id: u64,
+    pub wagered: Amount,
+    pub paid_out: Amount,
+}
+
+/// Wagers and payouts of the last ROLLING_RTP_EPOCHS closed epochs, with their sums
+/// kept up to date as epochs close
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct RollingRtp {
+    /// Closed epochs in the window, oldest first
+    pub epochs: Vec<EpochVolume>,
+    pub wagered: Amount,
+    pub paid_out: Amount,
+}
+
+/// The open game at a glance
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct ActiveGameSummary {
+    pub game_id: u64,
+    pub phase: GamePhase,
+    pub mode: GameMode,
+    pub currency: Currency,
+    pub bet: Amount,
+    pub rolls_count: u32,
+    pub marked_cells: u32,
+    pub completed_lines: u32,
+    /// Multiplier tier the game would pay in if it were claimed now
+    pub tier: String,
+    /// When the prize stops being claimable (0 = no deadline or no bingo yet)
+    pub claim_deadline_micros: u64,
+}
+
+/// A first-run milestone, in the order the guided onboarding walks a player through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum OnboardingStep {
+    Deposited,
+    StartedSession,
+    PlayedGame,
+    Won,
+    Withdrew,
+}
+
+impl OnboardingStep {
+    /// Every step, in onboarding order
+    pub const ALL: [OnboardingStep; 5] = [
+        OnboardingStep::Deposited,
+        OnboardingStep::StartedSession,
+        OnboardingStep::PlayedGame,
+        OnboardingStep::Won,
+        OnboardingStep::Withdrew,
+    ];
+}
+
+/// An owner's first-run checklist: when each step was first reached (None = not yet).
+/// Steps are recorded in whatever order the owner reaches them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+#[graphql(complex)]
+pub struct OnboardingProgress {
+    pub deposited_at_micros: Option<u64>,
+    pub started_session_at_micros: Option<u64>,
+    pub played_game_at_micros: Option<u64>,
+    pub won_at_micros: Option<u64>,
+    pub withdrew_at_micros: Option<u64>,
+}
+
+impl OnboardingProgress {
+    /// When `step` was first reached (None = not yet)
+    pub fn reached_at(&self, step: OnboardingStep) -> Option<u64> {
+        match step {
+            OnboardingStep::Deposited => self.deposited_at_micros,
+            OnboardingStep::StartedSession => self.started_session_at_micros,
+            OnboardingStep::PlayedGame => self.played_game_at_micros,
+            OnboardingStep::Won => self.won_at_micros,
+            OnboardingStep::Withdrew => self.withdrew_at_micros,
+        }
+    }
+
+    /// Record `step` as reached at `now_micros`; false if it already was
+    pub fn reach(&mut self, step: OnboardingStep, now_micros: u64) -> bool {
+        let slot = match step {
+            OnboardingStep::Deposited => &mut self.deposited_at_micros,
+            OnboardingStep::StartedSession => &mut self.started_session_at_micros,
+            OnboardingStep::PlayedGame => &mut self.played_game_at_micros,
+            OnboardingStep::Won => &mut self.won_at_micros,
+            OnboardingStep::Withdrew => &mut self.withdrew_at_micros,
+        };
+        if slot.is_some() {
+            return false;
+        }
+        *slot = Some(now_micros);
+        true
+    }
+
+    /// First step not reached yet, in onboarding order (None once all are)
+    pub fn pending_step(&self) -> Option<OnboardingStep> {
+        OnboardingStep::ALL
+            .into_iter()
+            .find(|step| self.reached_at(*step).is_none())
+    }
+}
+
+#[ComplexObject]
+impl OnboardingProgress {
+    /// Steps reached so far, in onboarding order
+    pub async fn completed_steps(&self) -> Vec<OnboardingStep> {
+        OnboardingStep::ALL
+            .into_iter()
+            .filter(|step| self.reached_at(*step).is_some())
+            .collect()
+    }
+
+    /// Step the first-run guide should point the owner to next (None once every step
+    /// is reached)
+    pub async fn next_step(&self) -> Option<OnboardingStep> {
+        self.pending_step()
+    }
+}
+
+// === Health Checks ===
+
+/// Id counters a health check verifies never move backwards between checks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct HealthCounters {
+    pub state_version: u64,
+    pub game_counter: u64,
+    pub session_counter: u64,
+    pub roll_counter: u64,
+    pub receipt_counter: u64,
+    pub outbox_counter: u64,
+}
+
+/// Outcome of a HealthCheck operation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct HealthReport {
+    pub checked_at_micros: u64,
+    pub block_height: u64,
+    /// Whether every invariant held
+    pub healthy: bool,
+    /// One message per broken invariant
+    pub violations: Vec<String>,
+    /// Counters seen by this check, compared against by the next one
+    pub counters: HealthCounters,
+}
+
+// === History Pruning ===
+
+/// Lifetime aggregates of the history PruneHistory has deleted, so totals survive it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct HistorySummary {
+    /// Roll records deleted
+    pub rolls: u64,
+    /// How many of them marked a cell
+    pub matched_rolls: u64,
+    /// Roll fees they paid
+    pub roll_fees: Amount,
+    /// Deposit receipts deleted, and what they deposited
+    pub deposits: u64,
+    pub deposited: Amount,
+    /// Withdrawal receipts deleted, and what they withdrew
+    pub withdrawals: u64,
+    pub withdrawn: Amount,
+    /// Settled tournaments deleted, and their pots
+    pub tournaments: u64,
+    pub tournament_pots: Amount,
+    /// Settled duels deleted, and their pots
+    pub duels: u64,
+    pub duel_pots: Amount,
+    /// Serialized size of everything deleted
+    pub bytes_reclaimed: u64,
+    pub last_pruned_micros: u64,
+}
+
+/// What one PruneHistory run deleted
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct PruneReport {
+    pub keep_last: u32,
+    /// Whether an epoch rollover ran it rather than the admin
+    pub automatic: bool,
+    pub rolls_pruned: u64,
+    pub receipts_pruned: u64,
+    pub tournaments_pruned: u64,
+    pub duels_pruned: u64,
+    /// Entries deleted across every history
+    pub entries_pruned: u64,
+    /// Serialized size of the deleted entries
+    pub bytes_reclaimed: u64,
+}
+
+// === Prize Vesting ===
+
+/// A large payout released in installments. The first is credited with the claim;
+/// installment `i` unlocks `i * interval_secs` after it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct VestingSchedule {
+    pub game_id: u64,
+    /// Whole payout, including installments already credited
+    pub total: Amount,
+    pub installments: u32,
+    /// Installments credited so far
+    pub installments_released: u32,
+    pub vested_at_micros: u64,
+    pub interval_secs: u64,
+}
+
+impl VestingSchedule {
+    /// Size of installment `index`; the last one also takes the remainder
+    pub fn installment_amount(&self, index: u32) -> Amount {
+        let total = u128::from(self.total);
+        let share = total / u128::from(self.installments);
+        if index + 1 == self.installments {
+            Amount::from_attos(total - share * u128::from(self.installments - 1))
+        } else {
+            Amount::from_attos(share)
+        }
+    }
+
+    /// Installments unlocked at `now_micros`, counting those already credited
+    pub fn unlocked_installments(&self, now_micros: u64) -> u32 {
+        let elapsed_secs = now_micros.saturating_sub(self.vested_at_micros) / 1_000_000;
+        let intervals = elapsed_secs / self.interval_secs.max(1);
+        (intervals + 1).min(u64::from(self.installments)) as u32
+    }
+
+    /// Part of the payout not yet credited
+    pub fn remaining(&self) -> Amount {
+        (self.installments_released..self.installments)
+            .map(|index| self.installment_amount(index))
+            .fold(Amount::ZERO, Amount::saturating_add)
+    }
 }
 
+// === Operation Metrics ===
+
+/// Running totals for one operation type, keyed by `Operation::name`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct OperationMetrics {
+    pub operation: String,
+    /// Times the operation was executed, failed or not
+    pub count: u64,
+    /// Executions that ended in `OperationResponse::Error`
+    pub failures: u64,
+    /// Fees and stakes the operation moved out of the player's balance
+    pub fees_charged: Amount,
+}
+
+// === Operator Outbox ===
+
+/// What an outbox entry reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Enum)]
+pub enum OutboxKind {
+    /// A real-money prize at or above the reward notification threshold
+    BigWin,
+    /// A raffle pool paid to its winner
+    Jackpot,
+    /// A session whose roll cadence started looking automated
+    SuspiciousActivity,
+    /// A card whose marks no longer matched the drawn numbers (see `reconcile_marks`)
+    MarkDivergence,
+    /// A win notification waiting for DeliverPartnerNotification, one entry per
+    /// partner application
+    PartnerNotification,
+}
+
+/// Notification waiting for the operator daemon. Entries stay queued until
+/// acknowledged with AckOutbox, so each is delivered at least once.
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct OutboxEntry {
+    /// Sequential entry id, starting at 1
+    pub id: u64,
+    pub kind: OutboxKind,
+    pub created_at_micros: u64,
+    /// Player the notification is about, if known
+    pub owner: Option<AccountOwner>,
+    /// Game, raffle draw or session id the notification refers to
+    pub reference_id: u64,
+    /// Amount won (zero for suspicious activity)
+    pub amount: Amount,
+    /// Human-readable summary
+    pub detail: String,
+}
 
+// === Instantiation ===
+
+/// Arguments supplied when the application is created (`--json-argument '{}'` for defaults)
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct InstantiationArgument {
+    /// Partner applications notified through `RewardHookAbi` of qualifying wins, by
+    /// way of the outbox (see `Operation::DeliverPartnerNotification`)
+    pub reward_partners: Vec<ApplicationId>,
+    /// Minimum real-money payout (in atto LINERA) that triggers a partner notification
+    pub reward_notify_threshold_atto: u128,
+    /// Chain whose instance keeps the registry of FlashPort deployments. The instance
+    /// on that chain accepts registrations; every instance may register with it.
+    pub lobby_chain: Option<ChainId>,
+    /// Bridge or swap application that converts payouts for players with a
+    /// payout route (see `PayoutBridgeAbi`)
+    pub payout_bridge: Option<ApplicationId>,
+    /// Starting `GameConfig::card_constraints`; the admin can change them later
+    pub card_constraints: CardConstraints,
+    /// Starting `GameConfig::governance_timelock_secs`; governed changes never wait
+    /// less than `MIN_GOVERNANCE_TIMELOCK_SECS`
+    pub governance_timelock_secs: u32,
+}
+
+/// Application parameters, fixed for the deployment's lifetime
+/// (`--json-parameters '{}'` for defaults)
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FlashportParameters {
+    /// Record fee, tier and entropy decisions in the diagnostics ring buffer, to
+    /// debug the deployment without an instrumented build
+    pub diagnostics: bool,
+}
+
+// === Partner Reward Hook ===
+
+/// ABI that partner reward applications implement to hear about FlashPort wins.
+/// Partners receive a `WinNotification` as their operation and return nothing.
+pub struct RewardHookAbi;
+
+/// Win reported to partner reward applications
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct WinNotification {
+    /// Game that was won
+    pub game_id: u64,
+    /// Owner who claimed the prize, if the claim was signed
+    pub owner: Option<AccountOwner>,
+    /// Original bet (in atto LINERA)
+    pub bet_amount_atto: u128,
+    /// Payout credited (in atto LINERA)
+    pub payout_atto: u128,
+    /// Rolls it took to win
+    pub rolls_count: u32,
+}
+
+/// Win notification queued for one partner until DeliverPartnerNotification calls it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PartnerNotice {
+    pub partner: ApplicationId,
+    pub notification: WinNotification,
+}
+
+impl ContractAbi for RewardHookAbi {
+    type Operation = WinNotification;
+    type Response = ();
+}
+
+// === Payout Bridge ===
+
+/// ABI of the bridge or swap application that pays winnings out in another asset.
+/// The bridge takes custody of `amount_atto` and answers with its own reference
+/// for the transfer, or an error if it cannot route the payout.
+pub struct PayoutBridgeAbi;
+
+/// Payout handed to the bridge application
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BridgeRequest {
+    /// Game the payout was won in
+    pub game_id: u64,
+    /// Winner to deliver the converted payout to
+    pub owner: AccountOwner,
+    /// Payout to convert (in atto LINERA)
+    pub amount_atto: u128,
+    /// Asset the owner asked for, as set with SetPayoutRoute
+    pub target_asset: String,
+}
+
+impl ContractAbi for PayoutBridgeAbi {
+    type Operation = BridgeRequest;
+    type Response = Result<String, String>;
+}
+
+/// Where an owner wants their real-money winnings sent
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct PayoutRoute {
+    /// Asset the payout bridge should deliver, e.g. "USDC"
+    pub target_asset: String,
+    pub set_at_micros: u64,
+}
+
+/// What happened to a payout offered to the payout bridge. On failure the payout
+/// stays in the internal balance.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct RoutedPayout {
+    pub target_asset: String,
+    /// Withdrawal receipt for the routed payout; None if the bridge refused it
+    pub receipt_id: Option<u64>,
+    /// The bridge's reference for the transfer
+    pub bridge_reference: Option<String>,
+    /// Why the bridge refused the payout
+    pub error: Option<String>,
+}
 
 // === ABI Implementation ===
 
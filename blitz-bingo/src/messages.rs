@@ -0,0 +1,116 @@
+// FlashPort: Message Catalog
+// Display text for every error code, kept out of the contract so frontends can localize
+
+use async_graphql::resolver_utils::EnumType;
+use blitz_bingo::ErrorCode;
+
+/// Locales with their own translations; any other locale gets English
+pub const LOCALES: [&str; 2] = ["en", "es"];
+
+/// Every error code with its text in `locale`, in declaration order
+pub fn catalog(locale: &str) -> Vec<(ErrorCode, &'static str)> {
+    ErrorCode::items()
+        .iter()
+        .map(|item| (item.value, text(item.value, locale)))
+        .collect()
+}
+
+/// Text for `code` in `locale`
+pub fn text(code: ErrorCode, locale: &str) -> &'static str {
+    match locale {
+        "es" => spanish(code),
+        _ => english(code),
+    }
+}
+
+fn english(code: ErrorCode) -> &'static str {
+    match code {
+        ErrorCode::InvalidInput => "Check the values you entered and try again.",
+        ErrorCode::InsufficientFunds => "Your balance is too low for this.",
+        ErrorCode::BetTooLow => "The bet is below the minimum.",
+        ErrorCode::BetTooHigh => "The bet is above the table maximum.",
+        ErrorCode::LossGuard => "Your loss guard limits the bet after a loss.",
+        ErrorCode::SignerRequired => "Sign in with your wallet to do this.",
+        ErrorCode::NotPermitted => "You are not allowed to do this.",
+        ErrorCode::SessionRequired => "Start a session first.",
+        ErrorCode::SessionExpired => "Your session has expired. Start a new one.",
+        ErrorCode::SessionPermission => "This session is not allowed to do this.",
+        ErrorCode::NotConfigured => "This feature is not available here.",
+        ErrorCode::NotFound => "We couldn't find that.",
+        ErrorCode::Closed => "This is not open right now.",
+        ErrorCode::AlreadyDone => "That has already been done.",
+        ErrorCode::TooEarly => "It's too early for this. Try again later.",
+        ErrorCode::TooLate => "The time for this has passed.",
+        ErrorCode::Unsupported => "This game or table doesn't support that.",
+        ErrorCode::LimitReached => "A limit has been reached.",
+        ErrorCode::NoActiveGame => "No active game. Start a new game.",
+        ErrorCode::GameOver => "This game is over. Start a new game.",
+        ErrorCode::BingoPending => "BINGO! Claim your prize or start a new game.",
+        ErrorCode::CardFull => "Your card is full. Claim your prize or start a new game.",
+        ErrorCode::NoPrize => "No prize to claim yet. Win a bingo first!",
+        ErrorCode::ClaimExpired => "The claim window has closed. Start a new game.",
+        ErrorCode::RollingSuspended => "Rolling is paused while we review this game.",
+        ErrorCode::Paused => "New games are paused for maintenance. Try again soon.",
+        ErrorCode::WithdrawalHeld => "Withdrawals on your account are on hold for a review.",
+        ErrorCode::InconsistentState => "Something went wrong. Please contact support.",
+    }
+}
+
+fn spanish(code: ErrorCode) -> &'static str {
+    match code {
+        ErrorCode::InvalidInput => "Revisa los valores introducidos e inténtalo de nuevo.",
+        ErrorCode::InsufficientFunds => "Tu saldo no es suficiente.",
+        ErrorCode::BetTooLow => "La apuesta está por debajo del mínimo.",
+        ErrorCode::BetTooHigh => "La apuesta supera el máximo de la mesa.",
+        ErrorCode::LossGuard => "Tu límite de pérdidas restringe la apuesta tras perder.",
+        ErrorCode::SignerRequired => "Conecta tu billetera para hacer esto.",
+        ErrorCode::NotPermitted => "No tienes permiso para hacer esto.",
+        ErrorCode::SessionRequired => "Inicia una sesión primero.",
+        ErrorCode::SessionExpired => "Tu sesión ha caducado. Inicia una nueva.",
+        ErrorCode::SessionPermission => "Esta sesión no tiene permiso para hacer esto.",
+        ErrorCode::NotConfigured => "Esta función no está disponible aquí.",
+        ErrorCode::NotFound => "No lo hemos encontrado.",
+        ErrorCode::Closed => "No está abierto en este momento.",
+        ErrorCode::AlreadyDone => "Eso ya está hecho.",
+        ErrorCode::TooEarly => "Aún es pronto. Inténtalo más tarde.",
+        ErrorCode::TooLate => "El plazo ya ha pasado.",
+        ErrorCode::Unsupported => "Este juego o mesa no lo permite.",
+        ErrorCode::LimitReached => "Se ha alcanzado un límite.",
+        ErrorCode::NoActiveGame => "No hay ninguna partida activa. Empieza una nueva.",
+        ErrorCode::GameOver => "Esta partida ha terminado. Empieza una nueva.",
+        ErrorCode::BingoPending => "¡BINGO! Reclama tu premio o empieza una nueva partida.",
+        ErrorCode::CardFull => "Tu cartón está completo. Reclama tu premio o empieza otra partida.",
+        ErrorCode::NoPrize => "Aún no hay premio que reclamar. ¡Consigue un bingo primero!",
+        ErrorCode::ClaimExpired => "El plazo para reclamar ha terminado. Empieza otra partida.",
+        ErrorCode::RollingSuspended => "Las tiradas están en pausa mientras revisamos la partida.",
+        ErrorCode::Paused => "Las partidas están en pausa por mantenimiento. Vuelve pronto.",
+        ErrorCode::WithdrawalHeld => "Los retiros de tu cuenta están retenidos por una revisión.",
+        ErrorCode::InconsistentState => "Algo ha fallado. Contacta con soporte.",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blitz_bingo::ErrorCode;
+
+    use super::{catalog, text, LOCALES};
+
+    #[test]
+    fn test_catalog_covers_every_locale() {
+        let english = catalog("en");
+        assert_eq!(
+            english.first().map(|(code, _)| *code),
+            Some(ErrorCode::InvalidInput)
+        );
+        for locale in LOCALES {
+            let entries = catalog(locale);
+            assert_eq!(entries.len(), english.len());
+            assert!(entries.iter().all(|(_, text)| !text.is_empty()));
+        }
+        assert_eq!(catalog("fr"), english);
+        assert_ne!(
+            text(ErrorCode::NoPrize, "es"),
+            text(ErrorCode::NoPrize, "en")
+        );
+    }
+}
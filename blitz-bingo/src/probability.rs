@@ -0,0 +1,295 @@
+// FlashPort: Dice Probabilities
+// Exact odds of the four-dice sum and the expectations built on them, kept as integer
+// fractions so the contract can price payouts from them without floating point
+
+use async_graphql::{ComplexObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::rules::{fill_card, line_cells};
+
+/// Equally likely outcomes of one roll of four dice
+pub const ROLL_OUTCOMES: u32 = 6 * 6 * 6 * 6;
+/// Longest game `bingo_chance_within` models
+pub const MAX_MODELLED_ROLLS: u32 = 200;
+/// Numbers a fresh card is dealt from, one of each sum 4-24
+const FRESH_POOL_SIZE: usize = 21;
+
+/// Exact chance of rolling one sum with four dice: `ways` out of `outcomes`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+#[graphql(complex)]
+pub struct SumProbability {
+    pub sum: u8,
+    /// Four-dice outcomes adding up to `sum`
+    pub ways: u32,
+    /// Equally likely outcomes of a roll (`ROLL_OUTCOMES`)
+    pub outcomes: u32,
+}
+
+#[ComplexObject]
+impl SumProbability {
+    /// The chance as a decimal (0-1), for display only
+    pub async fn chance(&self) -> f64 {
+        self.ways as f64 / self.outcomes as f64
+    }
+}
+
+/// Exact chance of rolling `sum` with four dice
+pub fn sum_probability(sum: u8) -> SumProbability {
+    SumProbability {
+        sum,
+        ways: sum_ways(sum),
+        outcomes: ROLL_OUTCOMES,
+    }
+}
+
+/// Chance of every sum four dice can roll, 4 through 24; the ways add up to `ROLL_OUTCOMES`
+pub fn sum_distribution() -> Vec<SumProbability> {
+    (4..=24).map(sum_probability).collect()
+}
+
+/// Number of four-dice outcomes that add up to `sum` (out of `ROLL_OUTCOMES`)
+pub fn sum_ways(sum: u8) -> u32 {
+    let mut ways = 0;
+    for a in 1..=6u8 {
+        for b in 1..=6u8 {
+            for c in 1..=6u8 {
+                for d in 1..=6u8 {
+                    if a + b + c + d == sum {
+                        ways += 1;
+                    }
+                }
+            }
+        }
+    }
+    ways
+}
+
+/// Number of ways `dice_count` dice can add up to `sum` (out of 6^`dice_count`)
+pub fn dice_sum_ways(dice_count: u8, sum: u8) -> u64 {
+    // ways[s] = ways the dice thrown so far add up to s
+    let mut ways = vec![1u64];
+    for _ in 0..dice_count {
+        let mut next = vec![0u64; ways.len() + 6];
+        for (total, count) in ways.iter().enumerate() {
+            for face in 1..=6 {
+                next[total + face] += count;
+            }
+        }
+        ways = next;
+    }
+    ways.get(sum as usize).copied().unwrap_or(0)
+}
+
+/// Expected cells the next roll marks on a card, times `ROLL_OUTCOMES`: every unmarked
+/// cell adds the ways its number can be rolled
+pub fn expected_marks_per_roll(numbers: &[u8; 25], marked: &[bool; 25]) -> u32 {
+    numbers
+        .iter()
+        .zip(marked)
+        .filter(|(_, marked)| !**marked)
+        .map(|(number, _)| sum_ways(*number))
+        .sum()
+}
+
+/// Expected rolls, times 100, until every number of `line` has been drawn (FREE cells
+/// and repeats count once), by inclusion-exclusion over the numbers' per-roll chances
+pub fn expected_rolls_to_complete(line: &[u8]) -> u64 {
+    // Extra precision so the alternating sum is only rounded once, at the end
+    const PRECISION: i128 = 1_000_000;
+    let mut ways: Vec<u32> = Vec::new();
+    let mut numbers: Vec<u8> = line.iter().copied().filter(|n| *n != 0).collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+    for number in numbers {
+        match sum_ways(number) {
+            0 => return u64::MAX,
+            number_ways => ways.push(number_ways),
+        }
+    }
+
+    let mut total: i128 = 0;
+    for subset in 1..(1u32 << ways.len()) {
+        let subset_ways: u32 = (0..ways.len())
+            .filter(|i| subset & (1 << i) != 0)
+            .map(|i| ways[i])
+            .sum();
+        let term = ROLL_OUTCOMES as i128 * 100 * PRECISION / subset_ways as i128;
+        if subset.count_ones() % 2 == 1 {
+            total += term;
+        } else {
+            total -= term;
+        }
+    }
+    (total / PRECISION) as u64
+}
+
+/// Card difficulty: expected rolls, times 100, to complete its easiest line. The
+/// contract prices a card's difficulty bonus from it.
+pub fn difficulty_score(numbers: &[u8; 25]) -> u32 {
+    line_cells()
+        .map(|cells| expected_rolls_to_complete(&cells.map(|i| numbers[i])))
+        .min()
+        .map_or(u32::MAX, |rolls| u32::try_from(rolls).unwrap_or(u32::MAX))
+}
+
+/// Chance (0-1) that a freshly dealt card completes a line within `rolls` rolls
+/// (capped at MAX_MODELLED_ROLLS), exact over every shuffle `rules::deal_numbers` can
+/// deal. A card holds every sum, so a shuffle is equally likely to line up with any set
+/// of sums rolled: the chance only depends on how many distinct sums the rolls showed.
+pub fn bingo_chance_within(rolls: u32) -> f64 {
+    let rolls = rolls.min(MAX_MODELLED_ROLLS) as usize;
+    // shown[k][m]: chance that m rolls show exactly k distinct sums, each seen at least
+    // once, counted over the sums added so far
+    let mut shown = vec![vec![0.0; rolls + 1]; FRESH_POOL_SIZE + 1];
+    shown[0][0] = 1.0;
+    for sum in 4..=24 {
+        let chance = f64::from(sum_ways(sum)) / f64::from(ROLL_OUTCOMES);
+        let powers: Vec<f64> = std::iter::successors(Some(1.0), |power| Some(power * chance))
+            .take(rolls + 1)
+            .collect();
+        for k in (1..=FRESH_POOL_SIZE).rev() {
+            for m in (1..=rolls).rev() {
+                // Rolls that land on `sum` j times, anywhere among the m
+                let mut ways = 1.0;
+                let mut added = 0.0;
+                for (j, power) in powers.iter().enumerate().take(m + 1).skip(1) {
+                    ways = ways * (m - j + 1) as f64 / j as f64;
+                    added += ways * power * shown[k - 1][m - j];
+                }
+                shown[k][m] += added;
+            }
+        }
+    }
+    line_chances()
+        .iter()
+        .zip(&shown)
+        .map(|(line_chance, shown)| line_chance * shown[rolls])
+        .sum()
+}
+
+/// Chance of losing `games` independent games in a row when each is won with `win_chance`
+pub fn ruin_chance(win_chance: f64, games: u64) -> f64 {
+    (1.0 - win_chance).powf(games as f64)
+}
+
+/// Chance, for each count k of distinct sums rolled, that a fresh card holds a whole
+/// line of them: the share of k-sets of pool positions covering some line's cells, by
+/// inclusion-exclusion over the lines
+fn line_chances() -> Vec<f64> {
+    let pool: Vec<u8> = (1..=FRESH_POOL_SIZE as u8).collect();
+    let positions = fill_card(&pool);
+    let lines: Vec<u32> = line_cells()
+        .map(|cells| {
+            cells
+                .iter()
+                .filter(|cell| positions[**cell] != 0)
+                .fold(0, |mask, cell| mask | 1 << (positions[*cell] - 1))
+        })
+        .collect();
+
+    let mut covering = [0i64; FRESH_POOL_SIZE + 1];
+    for subset in 1..(1u32 << lines.len()) {
+        let union = (0..lines.len())
+            .filter(|i| subset & (1 << i) != 0)
+            .fold(0, |mask, i| mask | lines[i]);
+        let size = union.count_ones() as usize;
+        let sign = if subset.count_ones() % 2 == 1 { 1 } else { -1 };
+        for (k, count) in covering.iter_mut().enumerate().skip(size) {
+            *count += sign * choose(FRESH_POOL_SIZE - size, k - size) as i64;
+        }
+    }
+    covering
+        .iter()
+        .enumerate()
+        .map(|(k, count)| *count as f64 / choose(FRESH_POOL_SIZE, k) as f64)
+        .collect()
+}
+
+/// Ways to choose `k` of `n` items
+fn choose(n: usize, k: usize) -> u64 {
+    (0..k as u64).fold(1, |ways, i| ways * (n as u64 - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bingo_chance_within, dice_sum_ways, difficulty_score, expected_marks_per_roll,
+        expected_rolls_to_complete, ruin_chance, sum_distribution, sum_probability, sum_ways,
+        MAX_MODELLED_ROLLS, ROLL_OUTCOMES,
+    };
+    use crate::rules::deal_numbers;
+    use crate::{MULTIPLIER_TIER_MAX_ROLLS, TIER_WIN_CHANCE_BPS};
+
+    #[test]
+    fn test_sum_distribution() {
+        assert_eq!(sum_ways(3), 0);
+        assert_eq!(sum_ways(4), 1);
+        assert_eq!(sum_ways(14), 146);
+        assert_eq!(sum_ways(24), 1);
+        let distribution = sum_distribution();
+        assert_eq!(distribution.len(), 21);
+        assert_eq!(distribution.iter().map(|p| p.ways).sum::<u32>(), ROLL_OUTCOMES);
+        assert_eq!(distribution[10], sum_probability(14));
+        assert!((4..=24).all(|sum| dice_sum_ways(4, sum) == u64::from(sum_ways(sum))));
+        assert_eq!(dice_sum_ways(8, 48), 1);
+    }
+
+    #[test]
+    fn test_expected_marks_per_roll() {
+        let numbers = deal_numbers(42);
+        let mut marked = [false; 25];
+        marked[12] = true;
+        // A fresh card holds every sum 4-24 once, plus three repeats that count again
+        let repeats: u32 = (0..25)
+            .filter(|i| *i != 12 && numbers[..*i].contains(&numbers[*i]))
+            .map(|i| sum_ways(numbers[i]))
+            .sum();
+        assert_eq!(expected_marks_per_roll(&numbers, &marked), ROLL_OUTCOMES + repeats);
+        assert_eq!(expected_marks_per_roll(&numbers, &[true; 25]), 0);
+    }
+
+    #[test]
+    fn test_expected_rolls_to_complete() {
+        assert_eq!(expected_rolls_to_complete(&[0; 5]), 0);
+        assert_eq!(expected_rolls_to_complete(&[4, 4, 0]), 129_600);
+        // 1296 + 1296 - 1296 / 2 rolls to see both extremes
+        assert_eq!(expected_rolls_to_complete(&[4, 24]), 194_400);
+        assert_eq!(expected_rolls_to_complete(&[3]), u64::MAX);
+        let middle = expected_rolls_to_complete(&[13, 14, 0, 15, 12]);
+        assert!(middle < expected_rolls_to_complete(&[13, 14, 0, 15, 4]));
+        // No line beats the four likeliest sums around the FREE center
+        assert!(u64::from(difficulty_score(&deal_numbers(42))) >= middle);
+    }
+
+    #[test]
+    fn test_bingo_chance_grows_with_rolls() {
+        // A line needs at least four distinct sums
+        assert_eq!(bingo_chance_within(0), 0.0);
+        assert_eq!(bingo_chance_within(3), 0.0);
+        let short = bingo_chance_within(10);
+        assert!(short > 0.0 && short < bingo_chance_within(40));
+        assert!(bingo_chance_within(MAX_MODELLED_ROLLS) > 0.99);
+        assert_eq!(
+            bingo_chance_within(u32::MAX),
+            bingo_chance_within(MAX_MODELLED_ROLLS)
+        );
+    }
+
+    #[test]
+    fn test_tier_win_chances_match_model() {
+        let bps = |rolls| (bingo_chance_within(rolls) * 10_000.0).round() as u32;
+        let mut previous = 0;
+        for (tier, max_rolls) in MULTIPLIER_TIER_MAX_ROLLS.iter().enumerate() {
+            assert_eq!(TIER_WIN_CHANCE_BPS[tier], bps(*max_rolls) - previous);
+            previous = bps(*max_rolls);
+        }
+        assert_eq!(TIER_WIN_CHANCE_BPS[6], 10_000 - previous);
+    }
+
+    #[test]
+    fn test_ruin_chance() {
+        assert_eq!(ruin_chance(0.25, 0), 1.0);
+        assert_eq!(ruin_chance(0.5, 2), 0.25);
+        assert_eq!(ruin_chance(1.0, 3), 0.0);
+    }
+}
@@ -0,0 +1,795 @@
+// FlashPort: Game Rules
+// Pure per-roll logic (dealing, dice, marking, line checks, payout math) shared by the
+// contract and the native benchmarks; nothing here touches the runtime or state
+
+use crate::{
+    multiplier_tier, probability::dice_sum_ways, BingoCard, BingoType, CardConstraints,
+    CompletingSum, GameMode, PayoutTier, WinPattern, MARK_POINTS_BY_DISTANCE,
+    MARK_PURCHASE_FEE_BPS, MAX_CONSTRAINED_DEALS, MEDIAN_CARD_DIFFICULTY, POINTS_TIER_MIN_POINTS,
+};
+
+/// Simple LCG-style PRNG for deterministic randomness
+pub fn next_random(state: u64) -> u64 {
+    // LCG parameters (same as MINSTD)
+    state.wrapping_mul(48271).wrapping_add(1) % 2147483647
+}
+
+/// Deal card numbers from `seed`: shuffle 4-24 (see `shuffled_pool`) and fill the 24 numbered cells
+/// row-major, wrapping around the pool (0 = FREE center)
+pub fn deal_numbers(seed: u64) -> [u8; 25] {
+    // Pool of numbers 4-24 (21 unique numbers)
+    fill_card(&shuffled_pool(seed, 4, 24))
+}
+
+/// Deal one card of hedged game `game_id` from `seed`: card `side` (0 or 1) holds
+/// only its share of the numbers (see `hedge_shares`), shuffled and wrapped around
+/// the cells like `deal_numbers`, or dealt like `deal_constrained` under active
+/// constraints
+pub fn deal_hedge(seed: u64, game_id: u64, side: usize, constraints: &CardConstraints) -> [u8; 25] {
+    let mut share = hedge_shares(game_id, constraints)[side].clone();
+    if constraints.is_active() {
+        return deal_from(seed, &share, constraints);
+    }
+    let mut rng_state = seed;
+    shuffle(&mut share, &mut rng_state);
+    fill_card(&share)
+}
+
+/// Split the numbers `constraints` allow between the two cards of hedged game
+/// `game_id`, so the cards share as few numbers as possible at equal odds. Sums
+/// equally likely on four dice (n and 28 - n) go to opposite cards, alternating
+/// over a shuffle so each card gets low and high numbers alike; 14, the one sum
+/// without a partner, goes on both. A number whose partner is banned goes to the
+/// smaller share. A share `max_occurrences` keeps from filling 24 cells borrows the
+/// fewest numbers it needs from the other.
+pub fn hedge_shares(game_id: u64, constraints: &CardConstraints) -> [Vec<u8>; 2] {
+    let allowed = constraints.allowed_numbers();
+    let (mut paired, unpaired): (Vec<u8>, Vec<u8>) = allowed
+        .iter()
+        .copied()
+        .filter(|number| *number != 14)
+        .partition(|number| allowed.contains(&(28 - number)));
+    paired.retain(|number| *number < 14);
+    let mut rng_state = game_id;
+    shuffle(&mut paired, &mut rng_state);
+
+    let mut shares = [Vec::new(), Vec::new()];
+    for (i, low) in paired.into_iter().enumerate() {
+        shares[i % 2].push(low);
+        shares[1 - i % 2].push(28 - low);
+    }
+    for number in unpaired {
+        let side = usize::from(shares[1].len() < shares[0].len());
+        shares[side].push(number);
+    }
+    if allowed.contains(&14) {
+        shares.iter_mut().for_each(|share| share.push(14));
+    }
+
+    let max = constraints.max_occurrences as usize;
+    for side in 0..2 {
+        let mut borrowed: Vec<u8> = shares[1 - side]
+            .iter()
+            .copied()
+            .filter(|number| !shares[side].contains(number))
+            .collect();
+        borrowed.reverse();
+        while shares[side].is_empty() || (max > 0 && shares[side].len() * max < 24) {
+            match borrowed.pop() {
+                Some(number) => shares[side].push(number),
+                None => break,
+            }
+        }
+    }
+    shares.iter_mut().for_each(|share| share.sort_unstable());
+    shares
+}
+
+/// Deal a season-mode card from `seed` over the numbers `low..=high`, the same way
+/// `deal_numbers` deals 4-24
+pub fn deal_range(seed: u64, low: u8, high: u8) -> [u8; 25] {
+    fill_card(&shuffled_pool(seed, low, high))
+}
+
+/// `low..=high` shuffled by Fisher-Yates over the SplitMix64 stream of `seed`, so
+/// every order is equally likely and anyone holding the seed can replay it
+fn shuffled_pool(seed: u64, low: u8, high: u8) -> Vec<u8> {
+    let mut pool: Vec<u8> = (low..=high).collect();
+    let mut rng_state = seed;
+    shuffle(&mut pool, &mut rng_state);
+    pool
+}
+
+/// Fisher-Yates shuffle drawing from the SplitMix64 stream at `rng_state`
+fn shuffle(items: &mut [u8], rng_state: &mut u64) {
+    for i in (1..items.len()).rev() {
+        let j = uniform_below(i as u64 + 1, || splitmix64(rng_state)) as usize;
+        items.swap(i, j);
+    }
+}
+
+/// Deal card numbers from `seed` under `constraints`, which must pass
+/// `CardConstraints::validate`. Every allowed number first fills its
+/// `min_occurrences` cells; the rest come from the allowed numbers, reshuffled each
+/// round, skipping any at `max_occurrences`; then the cells are shuffled into place.
+/// A layout with an unbalanced row is rejected and redrawn further along the seed's
+/// stream; after MAX_CONSTRAINED_DEALS draws the last one is kept.
+pub fn deal_constrained(seed: u64, constraints: &CardConstraints) -> [u8; 25] {
+    deal_from(seed, &constraints.allowed_numbers(), constraints)
+}
+
+/// `deal_constrained` over `allowed`, a subset of the numbers the constraints allow
+/// that can still fill 24 cells under `max_occurrences`
+fn deal_from(seed: u64, allowed: &[u8], constraints: &CardConstraints) -> [u8; 25] {
+    let min = constraints.min_occurrences as usize;
+    let max = constraints.max_occurrences as usize;
+    let mut rng_state = seed;
+    let mut numbers = [0u8; 25];
+    for _ in 0..MAX_CONSTRAINED_DEALS {
+        let mut cells: Vec<u8> = allowed.iter().flat_map(|n| [*n].repeat(min)).collect();
+        while cells.len() < 24 {
+            let mut round = allowed.to_vec();
+            shuffle(&mut round, &mut rng_state);
+            for number in round {
+                let count = cells.iter().filter(|cell| **cell == number).count();
+                if cells.len() < 24 && (max == 0 || count < max) {
+                    cells.push(number);
+                }
+            }
+        }
+        shuffle(&mut cells, &mut rng_state);
+        numbers = fill_card(&cells);
+        if !constraints.balanced_rows || rows_balanced(&numbers) {
+            break;
+        }
+    }
+    numbers
+}
+
+/// Whether every row holds a number below 14 and one above it
+fn rows_balanced(numbers: &[u8; 25]) -> bool {
+    numbers
+        .chunks(5)
+        .all(|row| row.iter().any(|n| *n != 0 && *n < 14) && row.iter().any(|n| *n > 14))
+}
+
+/// Next output of the SplitMix64 generator, advancing `state`
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Next output of the xorshift64 generator, advancing `state`
+fn xorshift64(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Uniform draw from `0..bound` out of the generator `next`: outputs past the last
+/// whole multiple of `bound` are redrawn instead of folded in with a modulo, which
+/// would favour low values
+fn uniform_below(bound: u64, mut next: impl FnMut() -> u64) -> u64 {
+    let limit = u64::MAX - u64::MAX % bound;
+    loop {
+        let value = next();
+        if value < limit {
+            return value % bound;
+        }
+    }
+}
+
+/// Fill the 24 numbered cells row-major from `pool`, wrapping around it (center is FREE)
+pub(crate) fn fill_card(pool: &[u8]) -> [u8; 25] {
+    let mut numbers = [0u8; 25];
+    for (i, number) in (0..25).filter(|i| *i != 12).zip(pool.iter().cycle()) {
+        numbers[i] = *number;
+    }
+    numbers
+}
+
+/// Draw four dice (1-6 each) from a mixed entropy state, every face equally likely
+pub fn dice_from_state(mut rng_state: u64) -> [u8; 4] {
+    let mut dice = [0u8; 4];
+    for die in dice.iter_mut() {
+        *die = uniform_below(6, || xorshift64(&mut rng_state)) as u8 + 1;
+    }
+    dice
+}
+
+/// Find and mark ALL occurrences of a number on the card
+/// Returns (matched, match_pos, match_count)
+pub fn mark_number_on_card(card: &mut BingoCard, sum: u8) -> (bool, Option<(u8, u8)>, u32) {
+    let mut matched = false;
+    let mut last_pos = None;
+    let mut count = 0;
+
+    for row in 0..5 {
+        for col in 0..5 {
+            let idx = row * 5 + col;
+            if card.numbers[idx] == sum && !card.marked[idx] {
+                card.marked[idx] = true;
+                matched = true;
+                last_pos = Some((row as u8, col as u8));
+                count += 1;
+            }
+        }
+    }
+    (matched, last_pos, count)
+}
+
+/// Marks a card must carry given its layout, the sums drawn this game and the cells
+/// bought with BuyMark: the FREE center plus every cell whose number was drawn
+pub fn expected_marks(numbers: &[u8; 25], drawn: &[u8], purchased: &[u8]) -> [bool; 25] {
+    let mut marked = [false; 25];
+    for (i, cell) in marked.iter_mut().enumerate() {
+        *cell = i == 12 || drawn.contains(&numbers[i]) || purchased.contains(&(i as u8));
+    }
+    marked
+}
+
+/// Count completed rows, columns and diagonals (FREE center counts as marked)
+pub fn count_completed_lines(marked: &[bool; 25]) -> u32 {
+    let rows = (0..5)
+        .filter(|row| (0..5).all(|col| marked[row * 5 + col]))
+        .count();
+    let cols = (0..5)
+        .filter(|col| (0..5).all(|row| marked[row * 5 + col]))
+        .count();
+    let main = (0..5).all(|i| marked[i * 5 + i]) as usize;
+    let anti = (0..5).all(|i| marked[i * 5 + (4 - i)]) as usize;
+    (rows + cols + main + anti) as u32
+}
+
+/// Every complete line: rows, then columns, then diagonals, then full card
+pub fn bingo_lines(marked: &[bool; 25]) -> Vec<BingoType> {
+    const ROWS: [BingoType; 5] = [
+        BingoType::Row0,
+        BingoType::Row1,
+        BingoType::Row2,
+        BingoType::Row3,
+        BingoType::Row4,
+    ];
+    const COLS: [BingoType; 5] = [
+        BingoType::Col0,
+        BingoType::Col1,
+        BingoType::Col2,
+        BingoType::Col3,
+        BingoType::Col4,
+    ];
+    let mut lines = Vec::new();
+
+    // Check rows
+    for (row, line) in ROWS.into_iter().enumerate() {
+        if (0..5).all(|col| marked[row * 5 + col]) {
+            lines.push(line);
+        }
+    }
+
+    // Check columns
+    for (col, line) in COLS.into_iter().enumerate() {
+        if (0..5).all(|row| marked[row * 5 + col]) {
+            lines.push(line);
+        }
+    }
+
+    // Check main diagonal (top-left to bottom-right)
+    if (0..5).all(|i| marked[i * 5 + i]) {
+        lines.push(BingoType::DiagonalMain);
+    }
+
+    // Check anti-diagonal (top-right to bottom-left)
+    if (0..5).all(|i| marked[i * 5 + (4 - i)]) {
+        lines.push(BingoType::DiagonalAnti);
+    }
+
+    // Check full card (blackout)
+    if marked.iter().all(|m| *m) {
+        lines.push(BingoType::FullCard);
+    }
+
+    lines
+}
+
+/// The complete lines that win under `pattern`, in `bingo_lines` order
+pub fn pattern_lines(pattern: WinPattern, marked: &[bool; 25]) -> Vec<BingoType> {
+    bingo_lines(marked)
+        .into_iter()
+        .filter(|line| pattern.counts(*line))
+        .collect()
+}
+
+/// Line each entry of `line_cells` stands for
+const LINE_TYPES: [BingoType; 12] = [
+    BingoType::Row0,
+    BingoType::Row1,
+    BingoType::Row2,
+    BingoType::Row3,
+    BingoType::Row4,
+    BingoType::Col0,
+    BingoType::Col1,
+    BingoType::Col2,
+    BingoType::Col3,
+    BingoType::Col4,
+    BingoType::DiagonalMain,
+    BingoType::DiagonalAnti,
+];
+
+/// Cell indexes of every line: rows, then columns, then diagonals
+pub(crate) fn line_cells() -> impl Iterator<Item = [usize; 5]> {
+    let rows = (0..5).map(|row| [0, 1, 2, 3, 4].map(|col| row * 5 + col));
+    let cols = (0..5).map(|col| [0, 1, 2, 3, 4].map(|row| row * 5 + col));
+    rows.chain(cols).chain([[0, 6, 12, 18, 24], [4, 8, 12, 16, 20]])
+}
+
+/// Payout bonus in basis points for a card of difficulty `score`: nothing up to the
+/// median, then growing linearly to `max_bps` at twice the median
+pub fn difficulty_bonus_bps(score: u32, max_bps: u32) -> u32 {
+    let excess = score.saturating_sub(MEDIAN_CARD_DIFFICULTY).min(MEDIAN_CARD_DIFFICULTY);
+    (max_bps as u64 * excess as u64 / MEDIAN_CARD_DIFFICULTY as u64) as u32
+}
+
+/// Returns (numerator, denominator, display_string) of a win taking `rolls` rolls on
+/// `curve`; wins slower than every tier take the last one, and an empty curve pays
+/// nothing. Using integer math to avoid floating point issues
+pub fn multiplier(curve: &[PayoutTier], rolls: u32) -> (u32, u32, String) {
+    curve
+        .iter()
+        .find(|tier| rolls <= tier.max_rolls)
+        .or(curve.last())
+        .map_or((0, 1, "0x".to_string()), |tier| {
+            (tier.numerator, tier.denominator, tier.label.clone())
+        })
+}
+
+/// Flatter table of the Insurance mode, over the roll tiers of MULTIPLIER_TIER_MAX_ROLLS
+pub fn insurance_multiplier(rolls: u32) -> (u32, u32, String) {
+    match multiplier_tier(rolls) {
+        0 => (3, 1, "3x".to_string()),     // 3x (0-9 rolls)
+        1 => (2, 1, "2x".to_string()),     // 2x (10-14)
+        2 => (15, 10, "1.5x".to_string()), // 1.5x (15-19)
+        3 => (12, 10, "1.2x".to_string()), // 1.2x (20-24)
+        4 => (1, 1, "1x".to_string()),     // 1x (25-34)
+        5 => (9, 10, "0.9x".to_string()),  // 0.9x (35-44)
+        _ => (8, 10, "0.8x".to_string()),  // 0.8x (45+)
+    }
+}
+
+/// Multiplier table a game of `mode` pays from, `curve` being the configured Classic
+/// one. PointsPerMark games settle on their points instead (`points_multiplier`) and
+/// only report the Classic tier.
+pub fn mode_multiplier(curve: &[PayoutTier], mode: GameMode, rolls: u32) -> (u32, u32, String) {
+    match mode {
+        GameMode::Insurance => insurance_multiplier(rolls),
+        GameMode::Classic | GameMode::BlackoutContinuation | GameMode::PointsPerMark => {
+            multiplier(curve, rolls)
+        }
+    }
+}
+
+/// Tier payout on `curve` for a bet won in `rolls` rolls (None on overflow)
+pub fn tier_payout(curve: &[PayoutTier], bet_atto: u128, rolls: u32) -> Option<u128> {
+    mode_tier_payout(curve, GameMode::Classic, bet_atto, rolls)
+}
+
+/// Tier payout for a `mode` game's bet won in `rolls` rolls (None on overflow)
+pub fn mode_tier_payout(
+    curve: &[PayoutTier],
+    mode: GameMode,
+    bet_atto: u128,
+    rolls: u32,
+) -> Option<u128> {
+    let (numerator, denominator, _) = mode_multiplier(curve, mode, rolls);
+    Some(bet_atto.checked_mul(numerator as u128)? / denominator as u128)
+}
+
+/// Points a cell holding `number` scores in a PointsPerMark game (0 for the FREE
+/// center and numbers four dice cannot roll)
+pub fn mark_points(number: u8) -> u32 {
+    match number {
+        4..=24 => MARK_POINTS_BY_DISTANCE[number.abs_diff(14) as usize],
+        _ => 0,
+    }
+}
+
+/// Points the cells in `cells` (a row-major bitmask) score on a card of `numbers`
+pub fn cells_points(numbers: &[u8; 25], cells: u32) -> u32 {
+    (0..25)
+        .filter(|i| cells & (1 << i) != 0)
+        .map(|i| mark_points(numbers[i]))
+        .sum()
+}
+
+/// Index of the PointsPerMark payout tier `points` reach (0 = best; the index past
+/// POINTS_TIER_MIN_POINTS pays nothing)
+pub fn points_tier(points: u32) -> usize {
+    POINTS_TIER_MIN_POINTS
+        .iter()
+        .take_while(|min_points| points < **min_points)
+        .count()
+}
+
+/// Payout table of PointsPerMark games, over the tiers of POINTS_TIER_MIN_POINTS. A
+/// fresh card returns about 89.5% of the bet on average.
+pub fn points_multiplier(points: u32) -> (u32, u32, String) {
+    match points_tier(points) {
+        0 => (8, 1, "8x".to_string()),     // 8x (45+ points)
+        1 => (3, 1, "3x".to_string()),     // 3x (38-44)
+        2 => (15, 10, "1.5x".to_string()), // 1.5x (32-37)
+        3 => (1, 1, "1x".to_string()),     // 1x (27-31)
+        4 => (5, 10, "0.5x".to_string()),  // 0.5x (22-26)
+        _ => (0, 1, "0x".to_string()),     // nothing (under 22)
+    }
+}
+
+/// Payout for a PointsPerMark game's bet settled on `points` (None on overflow)
+pub fn points_payout(bet_atto: u128, points: u32) -> Option<u128> {
+    let (numerator, denominator, _) = points_multiplier(points);
+    Some(bet_atto.checked_mul(numerator as u128)? / denominator as u128)
+}
+
+/// Simple interest of `bps_per_day` basis points a day on `principal_atto` held for
+/// `elapsed_micros` (None on overflow)
+pub fn escrow_interest(
+    principal_atto: u128,
+    bps_per_day: u32,
+    elapsed_micros: u64,
+) -> Option<u128> {
+    const DAY_MICROS: u128 = 24 * 60 * 60 * 1_000_000;
+    Some(
+        principal_atto
+            .checked_mul(bps_per_day as u128)?
+            .checked_mul(elapsed_micros as u128)?
+            / (10_000 * DAY_MICROS),
+    )
+}
+
+/// `count` stacked bonuses of `bps` basis points of the bet (None on overflow)
+pub fn stacked_bonus(bet_atto: u128, bps: u32, count: u128) -> Option<u128> {
+    Some(bet_atto.checked_mul(bps as u128)?.checked_mul(count)? / 10_000)
+}
+
+/// Fewest unmarked cells left on any line (0 once a line is complete)
+pub fn cells_to_bingo(marked: &[bool; 25]) -> u32 {
+    line_cells()
+        .map(|cells| cells.iter().filter(|i| !marked[**i]).count() as u32)
+        .min()
+        .unwrap_or(0)
+}
+
+/// Fewest unmarked cells left on a line that wins under `pattern` (0 once one is
+/// complete); a blackout needs every cell
+pub fn pattern_cells_to_bingo(pattern: WinPattern, marked: &[bool; 25]) -> u32 {
+    if pattern == WinPattern::Blackout {
+        return marked.iter().filter(|m| !**m).count() as u32;
+    }
+    line_cells()
+        .zip(LINE_TYPES)
+        .filter(|(_, line)| pattern.counts(*line))
+        .map(|(cells, _)| cells.iter().filter(|i| !marked[**i]).count() as u32)
+        .min()
+        .unwrap_or(0)
+}
+
+/// Sums, lowest first, whose next roll of `dice_count` dice would complete a new line
+/// winning under `pattern`, with the chance of rolling each
+pub fn completing_sums(
+    pattern: WinPattern,
+    numbers: &[u8; 25],
+    marked: &[bool; 25],
+    dice_count: u8,
+) -> Vec<CompletingSum> {
+    let lines_before = pattern_lines(pattern, marked).len();
+    let outcomes = 6u64.pow(dice_count as u32);
+    let mut sums: Vec<u8> = (0..25).filter(|i| !marked[*i]).map(|i| numbers[i]).collect();
+    sums.sort_unstable();
+    sums.dedup();
+    sums.into_iter()
+        .filter(|sum| {
+            let mut after = *marked;
+            for (cell, number) in after.iter_mut().zip(numbers) {
+                *cell |= number == sum;
+            }
+            pattern_lines(pattern, &after).len() > lines_before
+        })
+        .map(|sum| CompletingSum {
+            sum,
+            chance_bps: (dice_sum_ways(dice_count, sum) * 10_000 / outcomes) as u32,
+        })
+        .collect()
+}
+
+/// Fee for buying a mark `cells_to_bingo` cells out: one MARK_PURCHASE_FEE_BPS step
+/// of the tier payout at five cells, one more for every cell closer (None on overflow)
+pub fn mark_purchase_fee(tier_payout_atto: u128, cells_to_bingo: u32) -> Option<u128> {
+    let steps = 6 - cells_to_bingo.clamp(1, 5);
+    stacked_bonus(tier_payout_atto, MARK_PURCHASE_FEE_BPS, steps as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cells_points, cells_to_bingo, completing_sums, deal_constrained, deal_hedge, deal_numbers,
+        deal_range, dice_from_state, difficulty_bonus_bps, escrow_interest, expected_marks,
+        hedge_shares, mark_points, mark_purchase_fee, mode_tier_payout, pattern_cells_to_bingo,
+        pattern_lines, points_payout, points_tier, rows_balanced, shuffled_pool, stacked_bonus,
+        tier_payout,
+    };
+    use crate::{
+        BingoType, CardConstraints, CompletingSum, GameMode, PayoutTier, WinPattern,
+        MARK_POINTS_BY_DISTANCE, MEDIAN_CARD_DIFFICULTY, POINTS_TIER_MIN_POINTS,
+    };
+
+    #[test]
+    fn test_deal_numbers_uses_every_number() {
+        let numbers = deal_numbers(42);
+        assert_eq!(numbers[12], 0);
+        assert!((4..=24).all(|n| numbers.contains(&n)));
+        assert_eq!(deal_numbers(42), numbers);
+    }
+
+    #[test]
+    fn test_hedge_shares() {
+        // Partners go to opposite cards and 14 to both, so the odds match
+        let shares = hedge_shares(7, &CardConstraints::default());
+        assert_eq!((shares[0].len(), shares[1].len()), (11, 11));
+        for number in shares[0].iter().filter(|n| **n != 14) {
+            assert!(!shares[1].contains(number));
+            assert!(shares[1].contains(&(28 - number)));
+        }
+        let numbers = deal_hedge(42, 7, 1, &CardConstraints::default());
+        assert!(numbers.iter().all(|n| *n == 0 || shares[1].contains(n)));
+        assert!(shares[1].iter().all(|n| numbers.contains(n)));
+
+        // Under constraints each share borrows just enough to fill 24 cells
+        let constraints = CardConstraints {
+            max_occurrences: 2,
+            banned_sums: vec![4, 5, 23],
+            balanced_rows: true,
+            ..CardConstraints::default()
+        };
+        let shares = hedge_shares(7, &constraints);
+        assert_eq!((shares[0].len(), shares[1].len()), (12, 12));
+        assert_eq!(
+            shares[0].iter().filter(|n| shares[1].contains(n)).count(),
+            6
+        );
+        for seed in 0..50 {
+            for (side, share) in shares.iter().enumerate() {
+                let numbers = deal_hedge(seed, 7, side, &constraints);
+                for number in share {
+                    assert!(numbers.iter().filter(|n| *n == number).count() <= 2);
+                }
+                assert!(numbers.iter().all(|n| *n == 0 || share.contains(n)));
+                assert_eq!(deal_hedge(seed, 7, side, &constraints), numbers);
+            }
+        }
+    }
+
+    #[test]
+    fn test_deal_constrained() {
+        let constraints = CardConstraints {
+            min_occurrences: 1,
+            max_occurrences: 2,
+            banned_sums: vec![4, 5, 23, 24],
+            balanced_rows: true,
+        };
+        assert_eq!(constraints.validate(), Ok(()));
+        for seed in 0..200 {
+            let numbers = deal_constrained(seed, &constraints);
+            assert_eq!(numbers[12], 0);
+            for number in constraints.allowed_numbers() {
+                let count = numbers.iter().filter(|n| **n == number).count();
+                assert!((1..=2).contains(&count));
+            }
+            assert!(numbers.iter().all(|n| !constraints.banned_sums.contains(n)));
+            assert!(rows_balanced(&numbers));
+            assert_eq!(deal_constrained(seed, &constraints), numbers);
+        }
+
+        // Constraints no card can meet are refused
+        let banned = |banned_sums: Vec<u8>| CardConstraints {
+            banned_sums,
+            ..CardConstraints::default()
+        };
+        assert!(banned(vec![3]).validate().is_err());
+        assert!(banned((4..=24).collect()).validate().is_err());
+        let crowded = CardConstraints {
+            min_occurrences: 2,
+            ..CardConstraints::default()
+        };
+        assert!(crowded.validate().is_err());
+        let sparse = CardConstraints {
+            max_occurrences: 2,
+            ..banned((4..=13).collect())
+        };
+        assert!(sparse.validate().is_err());
+        let lopsided = CardConstraints {
+            balanced_rows: true,
+            ..banned((15..=24).collect())
+        };
+        assert!(lopsided.validate().is_err());
+    }
+
+    #[test]
+    fn test_shuffle_is_unbiased() {
+        // Each of the 21 numbers lands first about 1/21 of the time
+        let mut first = [0u32; 25];
+        for seed in 0..21_000 {
+            first[shuffled_pool(seed, 4, 24)[0] as usize] += 1;
+        }
+        assert!(first[4..].iter().all(|count| (800..1_200).contains(count)));
+        assert_ne!(shuffled_pool(1, 4, 24), shuffled_pool(2, 4, 24));
+    }
+
+    #[test]
+    fn test_deal_range() {
+        let numbers = deal_range(42, 8, 48);
+        assert_eq!(numbers[12], 0);
+        assert!(numbers.iter().all(|n| *n == 0 || (8..=48).contains(n)));
+        let mut dealt: Vec<u8> = numbers.iter().copied().filter(|n| *n != 0).collect();
+        dealt.sort_unstable();
+        dealt.dedup();
+        assert_eq!(dealt.len(), 24);
+    }
+
+    #[test]
+    fn test_dice_stay_in_range() {
+        for state in 1..100 {
+            assert!(dice_from_state(state).iter().all(|die| (1..=6).contains(die)));
+        }
+    }
+
+    #[test]
+    fn test_dice_faces_are_uniform() {
+        // 4 million dice: each face within 0.5% of 1/6 (over four standard deviations)
+        let mut faces = [0u64; 7];
+        for state in 1..=1_000_000u64 {
+            for die in dice_from_state(state.wrapping_mul(0x2545f4914f6cdd1d)) {
+                faces[die as usize] += 1;
+            }
+        }
+        let expected = 4_000_000 / 6;
+        for count in &faces[1..] {
+            assert!(count.abs_diff(expected) < expected / 200, "faces {:?}", faces);
+        }
+    }
+
+    #[test]
+    fn test_expected_marks() {
+        let numbers = deal_numbers(42);
+        let marked = expected_marks(&numbers, &[numbers[0]], &[24]);
+        assert!(marked[0] && marked[12] && marked[24]);
+        let repeats = numbers.iter().filter(|n| **n == numbers[0]).count();
+        let extra = usize::from(numbers[24] != numbers[0]);
+        assert_eq!(marked.iter().filter(|m| **m).count(), repeats + 1 + extra);
+    }
+
+    #[test]
+    fn test_pattern_lines() {
+        // Middle row and column cross at the FREE center
+        let mut marked = [false; 25];
+        for i in 0..5 {
+            marked[10 + i] = true;
+            marked[i * 5 + 2] = true;
+        }
+        let any = pattern_lines(WinPattern::AnyLine, &marked);
+        assert_eq!(any, vec![BingoType::Row2, BingoType::Col2]);
+        assert_eq!(pattern_lines(WinPattern::Rows, &marked), vec![BingoType::Row2]);
+        assert_eq!(pattern_lines(WinPattern::Columns, &marked), vec![BingoType::Col2]);
+        assert!(pattern_lines(WinPattern::Diagonals, &marked).is_empty());
+        assert!(pattern_lines(WinPattern::Blackout, &marked).is_empty());
+        assert_eq!(pattern_lines(WinPattern::Blackout, &[true; 25]).len(), 1);
+    }
+
+    #[test]
+    fn test_completing_sums() {
+        // 4-24 row-major, then 4-7 again; the top row waits on its 8 and the main
+        // diagonal on the 7 in its last cell
+        let mut numbers = [0; 25];
+        for (i, number) in numbers.iter_mut().enumerate() {
+            *number = 4 + (i % 21) as u8;
+        }
+        numbers[12] = 0;
+        let mut marked = [false; 25];
+        for i in [0, 1, 2, 3, 6, 12, 18] {
+            marked[i] = true;
+        }
+        let any = completing_sums(WinPattern::AnyLine, &numbers, &marked, 4);
+        let seven = CompletingSum { sum: 7, chance_bps: 154 };
+        let eight = CompletingSum { sum: 8, chance_bps: 270 };
+        assert_eq!(any, vec![seven, eight]);
+        let diagonals = completing_sums(WinPattern::Diagonals, &numbers, &marked, 4);
+        assert_eq!(diagonals, vec![seven]);
+        assert!(completing_sums(WinPattern::Columns, &numbers, &marked, 4).is_empty());
+        assert_eq!(pattern_cells_to_bingo(WinPattern::AnyLine, &marked), 1);
+        assert_eq!(pattern_cells_to_bingo(WinPattern::Columns, &marked), 3);
+    }
+
+    #[test]
+    fn test_difficulty_bonus() {
+        assert_eq!(difficulty_bonus_bps(MEDIAN_CARD_DIFFICULTY, 1_000), 0);
+        let halfway = difficulty_bonus_bps(MEDIAN_CARD_DIFFICULTY * 3 / 2, 1_000);
+        assert!((499..=500).contains(&halfway));
+        assert_eq!(difficulty_bonus_bps(MEDIAN_CARD_DIFFICULTY * 2, 1_000), 1_000);
+        assert_eq!(difficulty_bonus_bps(u32::MAX, 1_000), 1_000);
+        assert_eq!(difficulty_bonus_bps(u32::MAX, 0), 0);
+    }
+
+    #[test]
+    fn test_payout_math() {
+        let curve = PayoutTier::default_curve();
+        assert_eq!(tier_payout(&curve, 100, 5), Some(1_000));
+        assert_eq!(tier_payout(&curve, 100, 30), Some(120));
+        assert_eq!(tier_payout(&curve, 100, 1_000), Some(20));
+        assert_eq!(tier_payout(&curve, u128::MAX, 5), None);
+        assert_eq!(tier_payout(&[], 100, 5), Some(0));
+        assert_eq!(stacked_bonus(10_000, 5_000, 2), Some(10_000));
+        assert_eq!(stacked_bonus(u128::MAX, 2, 1), None);
+    }
+
+    #[test]
+    fn test_escrow_interest() {
+        let day = 24 * 60 * 60 * 1_000_000;
+        assert_eq!(escrow_interest(10_000, 10, day), Some(10));
+        assert_eq!(escrow_interest(10_000, 10, 3 * day / 2), Some(15));
+        assert_eq!(escrow_interest(10_000, 0, day), Some(0));
+        assert_eq!(escrow_interest(u128::MAX, 10, day), None);
+    }
+
+    #[test]
+    fn test_insurance_table_is_flatter() {
+        let curve = PayoutTier::default_curve();
+        let payout = |mode, rolls| mode_tier_payout(&curve, mode, 100, rolls);
+        assert_eq!(payout(GameMode::Insurance, 5), Some(300));
+        assert_eq!(payout(GameMode::Insurance, 60), Some(80));
+        assert_eq!(payout(GameMode::Classic, 60), Some(20));
+        let payouts: Vec<_> = [5, 12, 17, 22, 30, 40, 60]
+            .iter()
+            .map(|rolls| payout(GameMode::Insurance, *rolls).unwrap())
+            .collect();
+        assert!(payouts.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+
+    #[test]
+    fn test_points_scoring() {
+        assert_eq!((mark_points(4), mark_points(24)), (10, 10));
+        assert_eq!((mark_points(14), mark_points(0), mark_points(25)), (1, 0, 0));
+        let numbers = deal_numbers(42);
+        // Numbers 4-24 once each: every distance from 14 twice, except 14 itself
+        let singles = MARK_POINTS_BY_DISTANCE.iter().sum::<u32>() * 2 - 1;
+        let repeats: u32 = (0..25)
+            .filter(|i| numbers[..*i].contains(&numbers[*i]))
+            .map(|i| mark_points(numbers[i]))
+            .sum();
+        assert_eq!(cells_points(&numbers, (1 << 25) - 1), singles + repeats);
+        assert_eq!(cells_points(&numbers, 1 << 12), 0);
+
+        assert_eq!(points_payout(100, 45), Some(800));
+        assert_eq!(points_payout(100, 32), Some(150));
+        assert_eq!(points_payout(100, 22), Some(50));
+        assert_eq!(points_payout(100, 21), Some(0));
+        assert_eq!(points_payout(u128::MAX, 60), None);
+        assert_eq!(points_tier(0), POINTS_TIER_MIN_POINTS.len());
+    }
+
+    #[test]
+    fn test_mark_purchase_fee() {
+        let mut marked = [false; 25];
+        marked[12] = true;
+        assert_eq!(cells_to_bingo(&marked), 4);
+        assert_eq!(mark_purchase_fee(1_000, 4), Some(500));
+        marked[6] = true;
+        marked[18] = true;
+        marked[24] = true;
+        assert_eq!(cells_to_bingo(&marked), 1);
+        assert_eq!(mark_purchase_fee(1_000, 1), Some(1_250));
+        marked[0] = true;
+        assert_eq!(cells_to_bingo(&marked), 0);
+        assert_eq!(mark_purchase_fee(1_000, 5), Some(250));
+    }
+}
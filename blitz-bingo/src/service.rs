@@ -3,17 +3,34 @@
 
 #![cfg_attr(target_arch = "wasm32", no_main)]
 
+mod messages;
 mod state;
 
 use std::sync::Arc;
 
-use async_graphql::{EmptySubscription, Object, Schema};
+use async_graphql::{EmptySubscription, Interface, Object, Schema, ID};
 use blitz_bingo::{
-    BingoCard, FlashportAbi, GameSession, Operation, PlayerBalance, 
-    ENTRY_FEE, ROLL_COST,
+    amounts, archive_bet_bucket, multiplier_tier, parse_node_id, probability, rules,
+    ActiveGameSummary, AdminAction, AdminQueryGrant, AirdropBatch, AirdropCredit, ApiUsage,
+    ArchiveFilter, ArchiveSort, BatchOutcome, BingoCard, BucketStats, BudgetAlert, ConsumedNonce,
+    Currency, DataPurgeRequest, DeltaField, DiagnosticEvent, DiagnosticKind, Dispute,
+    DisputeOutcome, Duel, DuelStatus, EntropyRecord, EpochPool, EpochVolume, ErrorCode, Experiment,
+    ExperimentBucket, FastState, FlashportAbi, FlashportParameters, GameAttestation, GameConfig,
+    GameMode, GamePhase, GameSession, GameTemplate, GovernanceProposal, HappyHour, HealthReport,
+    HighRollerTable, HistorySummary, HoldReason, LeaderboardEntry, OnboardingProgress, Operation,
+    OperationMetrics, OutboxEntry, PayoutRoute, PayoutTier, PendingAutoRoll, PlayerAccount,
+    PlayerBalance, PlayerPreferences, ProposalStatus, PruneReport, RaffleDraw, Receipt,
+    RegisteredInstance, RollRecord, RollResultsHash, RuntimeContext, ScheduledWithdrawal,
+    SeasonMode, SessionEndPolicy, SkinInventory, StatsSnapshot, Syndicate, Tournament,
+    VestingSchedule, WinRecord, WithdrawalHold, ABI_VERSION, ARCHIVE_DAY_MICROS,
+    DEFAULT_MAX_QUERY_COMPLEXITY, DEFAULT_MAX_QUERY_DEPTH, DICE_SKINS, DUEL_RAKE_BPS, ENTRY_FEE,
+    INSURANCE_ROLL_COST, MARK_PURCHASE_FEE_BPS, MAX_ADMIN_GRANT_TTL_SECS, MAX_AIRDROP_RECIPIENTS,
+    MAX_AUTO_ROLLS, MAX_BET, MAX_CLAIM_EXTENSION_PAGE, MAX_SYNDICATE_NAME_LEN, MIN_BET,
+    MULTIPLIER_LOCK_FEE_BPS, MULTIPLIER_TIER_NAMES, POINTS_GAME_ROLLS, POINTS_TIER_MIN_POINTS,
+    ROLL_COST,
 };
 use linera_sdk::{
-    linera_base_types::{Amount, ChainId, WithServiceAbi},
+    linera_base_types::{AccountOwner, Amount, ApplicationId, ChainId, WithServiceAbi},
     views::View,
     Service, ServiceRuntime,
 };
@@ -33,7 +50,7 @@ impl WithServiceAbi for FlashportService {
 }
 
 impl Service for FlashportService {
-    type Parameters = ();
+    type Parameters = FlashportParameters;
 
     async fn new(runtime: ServiceRuntime<Self>) -> Self {
         let state = FlashportState::load(runtime.root_view_storage_context())
@@ -46,18 +63,39 @@ impl Service for FlashportService {
     }
 
     async fn handle_query(&self, query: Self::Query) -> Self::QueryResponse {
-        Schema::build(
+        // Guard public endpoints against deep or expensive queries
+        let limits = ServiceLimits::from_config(self.state.config.get());
+        let mut builder = Schema::build(
             QueryRoot {
                 state: self.state.clone(),
+                runtime: self.runtime.clone(),
             },
             MutationRoot {
+                state: self.state.clone(),
                 runtime: self.runtime.clone(),
             },
             EmptySubscription,
         )
-        .finish()
-        .execute(query)
-        .await
+        .limit_depth(limits.max_depth)
+        .limit_complexity(limits.max_complexity);
+        if !limits.introspection_enabled {
+            builder = builder.disable_introspection();
+        }
+        let response = builder.finish().execute(query).await;
+        response.extension("readState", self.read_state())
+    }
+}
+
+impl FlashportService {
+    /// State version and height the query was answered from, and whether an operation
+    /// (a paused AutoRoll batch) is still pending, so clients can detect stale reads
+    fn read_state(&self) -> async_graphql::Value {
+        async_graphql::Value::from_json(serde_json::json!({
+            "stateVersion": *self.state.state_version.get(),
+            "blockHeight": *self.state.state_height.get(),
+            "operationPending": *self.state.paused_batches.get() > 0,
+        }))
+        .expect("Read state serializes to JSON")
     }
 }
 
@@ -67,28 +105,135 @@ impl Service for FlashportService {
 
 struct QueryRoot {
     state: Arc<FlashportState>,
+    runtime: Arc<ServiceRuntime<FlashportService>>,
 }
 
 #[Object]
 impl QueryRoot {
     /// Get current session status
-    async fn session(&self) -> Option<GameSession> {
-        self.state.active_session.get().clone()
+    async fn session(&self, owner: Option<AccountOwner>) -> Option<GameSession> {
+        let account = self.account(owner).await;
+        account.active_session.clone()
     }
 
     /// Check if a session exists
-    async fn has_session(&self) -> bool {
-        self.state.active_session.get().is_some()
+    async fn has_session(&self, owner: Option<AccountOwner>) -> bool {
+        let account = self.account(owner).await;
+        account.active_session.is_some()
+    }
+
+    /// Resolve a global node id. Only the current card and session, the recent roll
+    /// history and the win archive are kept, so ids of older objects resolve to null.
+    async fn node(&self, id: ID, owner: Option<AccountOwner>) -> Option<Node> {
+        let account = self.account(owner).await;
+        let (type_name, key) = parse_node_id(&id)?;
+        match type_name.as_str() {
+            "BingoCard" => account
+                .current_card
+                .clone()
+                .filter(|card| card.id == key)
+                .map(|card| Node::BingoCard(Box::new(card))),
+            "GameSession" => account
+                .active_session
+                .clone()
+                .filter(|session| session.session_id == key)
+                .map(Node::GameSession),
+            "RollRecord" => account
+                .roll_history
+                .iter()
+                .find(|record| record.roll_id == key)
+                .cloned()
+                .map(Node::RollRecord),
+            "WinRecord" => {
+                for index in (0..self.state.win_archive.count()).rev() {
+                    let win = self.state.win_archive.get(index).await.ok()??;
+                    if win.game_id == key {
+                        return Some(Node::WinRecord(Box::new(win)));
+                    }
+                }
+                None
+            }
+            _ => None,
+        }
     }
 
     /// Get the current active bingo card
-    async fn current_card(&self) -> Option<BingoCard> {
-        self.state.current_card.get().clone()
+    async fn current_card(&self, owner: Option<AccountOwner>) -> Option<BingoCard> {
+        let account = self.account(owner).await;
+        account.current_card.clone()
+    }
+
+    /// Get the second card of a hedged game
+    async fn hedge_card(&self, owner: Option<AccountOwner>) -> Option<BingoCard> {
+        let account = self.account(owner).await;
+        account.hedge_card.clone()
     }
 
     /// Get all numbers drawn in the current game
-    async fn drawn_numbers(&self) -> Vec<u8> {
-        self.state.drawn_numbers.get().clone()
+    async fn drawn_numbers(&self, owner: Option<AccountOwner>) -> Vec<u8> {
+        let account = self.account(owner).await;
+        account.drawn_numbers.clone()
+    }
+
+    /// Exact chance of every sum four dice can roll, 4 through 24
+    async fn sum_probabilities(&self) -> Vec<probability::SumProbability> {
+        probability::sum_distribution()
+    }
+
+    /// Odds of each sum that has not been drawn yet this game. A roll landing on one of
+    /// these earns the fresh-sum bonus if it marks the card.
+    async fn undrawn_sum_odds(&self, owner: Option<AccountOwner>) -> UndrawnSumOdds {
+        let account = self.account(owner).await;
+        let drawn = &account.drawn_numbers;
+        let card = account.current_card.as_ref();
+        let sums: Vec<SumOdds> = (4..=24u8)
+            .filter(|sum| !drawn.contains(sum))
+            .map(|sum| SumOdds {
+                sum,
+                chance: probability::sum_ways(sum) as f64 / probability::ROLL_OUTCOMES as f64,
+                marks_card: card.is_some_and(|card| {
+                    card.numbers
+                        .iter()
+                        .zip(card.marked.iter())
+                        .any(|(number, marked)| *number == sum && !marked)
+                }),
+            })
+            .collect();
+        UndrawnSumOdds {
+            fresh_chance: sums.iter().map(|odds| odds.chance).sum(),
+            fresh_mark_chance: sums
+                .iter()
+                .filter(|odds| odds.marks_card)
+                .map(|odds| odds.chance)
+                .sum(),
+            sums,
+        }
+    }
+
+    /// Scoring tables of PointsPerMark games: the points each number scores once
+    /// marked, and the payout tier each points total reaches
+    async fn points_scoring(&self, owner: Option<AccountOwner>) -> PointsScoring {
+        let account = self.account(owner).await;
+        let card = account.current_card.as_ref();
+        PointsScoring {
+            game_rolls: POINTS_GAME_ROLLS,
+            numbers: (4..=24u8)
+                .map(|number| NumberPoints {
+                    number,
+                    points: rules::mark_points(number),
+                })
+                .collect(),
+            tiers: POINTS_TIER_MIN_POINTS
+                .iter()
+                .map(|min_points| PointsTier {
+                    min_points: *min_points,
+                    multiplier_display: rules::points_multiplier(*min_points).2,
+                })
+                .collect(),
+            current_points: card
+                .filter(|card| card.mode == GameMode::PointsPerMark)
+                .map(|card| rules::cells_points(&card.numbers, card.marked_bitmask())),
+        }
     }
 
     /// Get total games played
@@ -101,33 +246,72 @@ impl QueryRoot {
         *self.state.total_wins.get()
     }
 
-    /// Get the number of rolls in history
-    async fn roll_history_count(&self) -> usize {
-        self.state.roll_history.count()
+    /// Get the number of rolls in an owner's history
+    async fn roll_history_count(&self, owner: Option<AccountOwner>) -> usize {
+        self.account(owner).await.roll_history.len()
     }
 
-    /// Get the most recent roll (last roll made)
-    async fn last_roll(&self) -> Option<LastRollResult> {
-        let count = self.state.roll_history.count();
-        if count == 0 {
-            return None;
+    /// Get the rolling hash over every roll result so far. A light client folds a
+    /// roll history dump with `chain_roll_results` and compares it with this.
+    async fn roll_results_hash(&self) -> RollResultsHash {
+        RollResultsHash {
+            last_roll_id: *self.state.roll_counter.get(),
+            hash: *self.state.roll_results_hash.get(),
         }
-        
-        // Get the last item in the queue (most recent roll)
-        if let Some(record) = self.state.roll_history.back().await.ok().flatten() {
+    }
+
+    /// Get the most recent roll (last roll made)
+    async fn last_roll(&self, owner: Option<AccountOwner>) -> Option<LastRollResult> {
+        let account = self.account(owner).await;
+        // The last item in the history is the most recent roll
+        if let Some(record) = account.roll_history.last() {
             Some(LastRollResult {
-                dice: record.dice.to_vec(),
+                dice: record.dice.iter().chain(&record.extra_dice).copied().collect(),
                 sum: record.sum,
                 matched: record.matched,
                 timestamp_micros: record.timestamp_micros,
-                game_over: *self.state.has_unclaimed_prize.get(),
+                game_over: account.game_phase == GamePhase::BingoPending,
                 is_lucky: record.is_lucky,
+                skin_id: record.skin_id,
             })
         } else {
             None
         }
     }
 
+    /// Export an owner's rolls with ids after `after_id` as parallel column arrays, oldest
+    /// first (default 50 per page), for analytics pipelines that ingest columnar data
+    async fn bulk_roll_export(
+        &self,
+        after_id: Option<u64>,
+        limit: Option<usize>,
+        owner: Option<AccountOwner>,
+    ) -> RollColumns {
+        let after_id = after_id.unwrap_or(0);
+        let limit = limit.unwrap_or(50);
+        let mut rolls: Vec<RollRecord> = self
+            .account(owner)
+            .await
+            .roll_history
+            .into_iter()
+            .filter(|record| record.roll_id > after_id)
+            .take(limit + 1)
+            .collect();
+        let has_more = rolls.len() > limit;
+        rolls.truncate(limit);
+        RollColumns {
+            next_after_id: rolls.last().map_or(after_id, |record| record.roll_id),
+            has_more,
+            roll_ids: rolls.iter().map(|record| record.roll_id).collect(),
+            dice: rolls.iter().flat_map(|record| record.dice).collect(),
+            sums: rolls.iter().map(|record| record.sum).collect(),
+            matched: rolls.iter().map(|record| record.matched).collect(),
+            is_lucky: rolls.iter().map(|record| record.is_lucky).collect(),
+            timestamps_micros: rolls.iter().map(|record| record.timestamp_micros).collect(),
+            fees_atto: rolls.into_iter().map(|record| record.fee_paid_atto).collect(),
+        }
+    }
+
     /// Get win rate as percentage (0-100)
     async fn win_rate(&self) -> f64 {
         let total = *self.state.total_games.get();
@@ -142,23 +326,104 @@ impl QueryRoot {
     // === Token Economics Queries ===
     
     /// Get player's current balance info
-    async fn player_balance(&self) -> PlayerBalance {
+    async fn player_balance(&self, owner: Option<AccountOwner>) -> PlayerBalance {
+        let account = self.account(owner).await;
         PlayerBalance {
-            available_atto: format!("{}", u128::from(*self.state.player_balance.get())),
-            total_deposited_atto: format!("{}", u128::from(*self.state.total_deposited.get())),
-            total_won_atto: format!("{}", u128::from(*self.state.total_won.get())),
-            total_spent_atto: format!("{}", u128::from(*self.state.total_spent.get())),
+            available_atto: format!("{}", u128::from(account.player_balance)),
+            total_deposited_atto: format!("{}", u128::from(account.total_deposited)),
+            total_won_atto: format!("{}", u128::from(account.total_won)),
+            total_spent_atto: format!("{}", u128::from(account.total_spent)),
+            points_atto: format!("{}", u128::from(account.points_balance)),
+            frozen_atto: format!("{}", u128::from(account.frozen_balance)),
+            pending_withdrawals_atto: format!("{}", u128::from(account.pending_withdrawals)),
+            vesting_atto: format!("{}", u128::from(account.vesting_balance)),
         }
     }
+
+    /// Get the free-to-play points balance (in atto-scaled points)
+    async fn points_balance(&self, owner: Option<AccountOwner>) -> String {
+        let account = self.account(owner).await;
+        format!("{}", u128::from(account.points_balance))
+    }
     
+    /// Look up a deposit/withdrawal ledger entry by receipt id
+    async fn receipt(&self, id: u64) -> Option<Receipt> {
+        self.state.receipts.get(&id).await.ok().flatten()
+    }
+
     /// Get current prize pool amount (in atto)
-    async fn current_prize_pool(&self) -> String {
-        format!("{}", u128::from(*self.state.current_prize_pool.get()))
+    async fn current_prize_pool(&self, owner: Option<AccountOwner>) -> String {
+        let account = self.account(owner).await;
+        format!("{}", u128::from(account.current_prize_pool))
     }
     
+    /// Get the prize pool epoch in progress (rolls over on the first operation after it ends)
+    async fn current_epoch(&self) -> EpochPool {
+        self.state.epoch.get().clone()
+    }
+
+    /// Get closed prize pool epochs, most recent first (default 10)
+    async fn epoch_history(&self, limit: Option<usize>) -> Vec<EpochPool> {
+        let count = self.state.epoch_history.count();
+        let start = count.saturating_sub(limit.unwrap_or(10));
+        let mut epochs = self
+            .state
+            .epoch_history
+            .read(start..count)
+            .await
+            .unwrap_or_default();
+        epochs.reverse();
+        epochs
+    }
+
+    /// Get real-money return to player over the current epoch and the last
+    /// ROLLING_RTP_EPOCHS closed ones, from sums the contract keeps up to date
+    async fn rolling_rtp(&self) -> RollingRtpReport {
+        let window = self.state.rolling_rtp.get();
+        let current = self.state.epoch.get();
+        let wagered = u128::from(window.wagered) + u128::from(current.wagered);
+        let paid_out = u128::from(window.paid_out) + u128::from(current.paid_out);
+        let rtp_percent = if wagered == 0 {
+            0.0
+        } else {
+            paid_out as f64 / wagered as f64 * 100.0
+        };
+        RollingRtpReport {
+            closed_epochs: window.epochs.clone(),
+            current_epoch_id: current.epoch_id,
+            wagered: Amount::from_attos(wagered),
+            paid_out: Amount::from_attos(paid_out),
+            rtp_percent,
+        }
+    }
+
+    /// Get the owners with the most real-money winnings, best first (at most
+    /// LEADERBOARD_SIZE)
+    async fn leaderboard(&self) -> Vec<LeaderboardEntry> {
+        self.state.leaderboard.get().clone()
+    }
+
+    /// Get an owner's lifetime real-money winnings (None if they never won)
+    async fn owner_winnings(&self, owner: AccountOwner) -> Option<LeaderboardEntry> {
+        self.state.owner_winnings.get(&owner).await.ok().flatten()
+    }
+
+    /// Get the open game at a glance (None between games)
+    async fn active_game(&self, owner: Option<AccountOwner>) -> Option<ActiveGameSummary> {
+        let account = self.account(owner).await;
+        account.active_game.clone()
+    }
+
     /// Check if there's an unclaimed prize
-    async fn has_unclaimed_prize(&self) -> bool {
-        *self.state.has_unclaimed_prize.get()
+    async fn has_unclaimed_prize(&self, owner: Option<AccountOwner>) -> bool {
+        let account = self.account(owner).await;
+        account.game_phase == GamePhase::BingoPending
+    }
+
+    /// Lifecycle phase of the current game
+    async fn game_phase(&self, owner: Option<AccountOwner>) -> GamePhase {
+        let account = self.account(owner).await;
+        account.game_phase
     }
     
     /// Get the entry fee in atto LINERA
@@ -180,234 +445,2754 @@ impl QueryRoot {
     async fn roll_cost_linera(&self) -> f64 {
         ROLL_COST as f64 / 1e18
     }
-    
-    /// Get the current potential payout if player wins now
-    async fn potential_payout(&self) -> Option<PotentialPayout> {
-        self.calculate_potential_payout()
-    }
 
-    /// Get statistics summary
-    async fn stats(&self) -> GameStats {
-        let total_games = *self.state.total_games.get();
-        let total_wins = *self.state.total_wins.get();
-        let current_rolls = self
+    /// Every charge the contract can levy, read from the live configuration so a
+    /// changed fee reaches clients without a release
+    async fn fee_schedule(&self) -> FeeSchedule {
+        let now = self.runtime.system_time().micros();
+        let happy_hour_discount_bps = self
             .state
-            .current_card
+            .happy_hours
             .get()
-            .as_ref()
-            .map(|c| c.rolls_count)
+            .iter()
+            .filter(|happy_hour| happy_hour.is_active(now))
+            .map(|happy_hour| happy_hour.discount_bps)
+            .max()
             .unwrap_or(0);
-        let balance = *self.state.player_balance.get();
+        let flat = |key: &str, operation: &str, amount: Amount, basis: &str| FeeCharge {
+            key: key.to_string(),
+            operation: operation.to_string(),
+            amount: Some(amount),
+            bps: None,
+            basis: basis.to_string(),
+        };
+        let share = |key: &str, operation: &str, bps: u32, basis: &str| FeeCharge {
+            key: key.to_string(),
+            operation: operation.to_string(),
+            amount: None,
+            bps: Some(bps),
+            basis: basis.to_string(),
+        };
 
-        GameStats {
-            total_games,
-            total_wins,
-            current_game_rolls: current_rolls,
-            win_rate: if total_games == 0 {
-                0.0
-            } else {
-                (total_wins as f64 / total_games as f64) * 100.0
-            },
-            balance_atto: format!("{}", u128::from(balance)),
-            balance_linera: u128::from(balance) as f64 / 1e18,
+        let mut charges = vec![
+            flat(
+                "roll_fee",
+                "RollAndMatch",
+                Amount::from_attos(ROLL_COST),
+                "Per roll of a standard game; game templates may set their own",
+            ),
+            flat(
+                "insurance_roll_fee",
+                "RollAndMatch",
+                Amount::from_attos(INSURANCE_ROLL_COST),
+                "Per roll of an Insurance-mode game",
+            ),
+        ];
+        if let Some(experiment) = self.state.experiment.get() {
+            let arms = [("A", &experiment.arm_a), ("B", &experiment.arm_b)];
+            for (bucket, arm) in arms {
+                charges.push(flat(
+                    &format!("experiment_{}_roll_fee", bucket.to_lowercase()),
+                    "RollAndMatch",
+                    arm.roll_cost,
+                    &format!("Per roll of a game in experiment bucket {}", bucket),
+                ));
+            }
+        }
+        if let Some(table) = self.state.high_roller_table.get() {
+            charges.push(flat(
+                "high_roller_roll_fee",
+                "RollAndMatch",
+                table.roll_cost,
+                "Per roll of a high-roller game",
+            ));
+        }
+        charges.extend([
+            share(
+                "mark_purchase_fee",
+                "BuyMark",
+                MARK_PURCHASE_FEE_BPS,
+                "Of the tier payout, once at five cells from bingo and once more per cell closer",
+            ),
+            share(
+                "multiplier_lock_fee",
+                "LockMultiplier",
+                MULTIPLIER_LOCK_FEE_BPS,
+                "Of the card's current tier payout",
+            ),
+        ]);
+        // Ending a session with a game unfinished is the only way to cancel one
+        let (forfeit_bps, forfeit_basis) = match self.state.config.get().session_end_policy {
+            SessionEndPolicy::RefundUnplayed => {
+                (10_000, "Of the bet of an unfinished game already rolled")
+            }
+            SessionEndPolicy::Refund => (0, "Unfinished games are refunded in full"),
+            SessionEndPolicy::Forfeit => (10_000, "Of the bet of any unfinished game"),
+        };
+        charges.extend([
+            share(
+                "session_end_forfeit",
+                "EndSession",
+                forfeit_bps,
+                forfeit_basis,
+            ),
+            flat(
+                "withdrawal_fee",
+                "Withdraw",
+                Amount::ZERO,
+                "Withdrawals are free",
+            ),
+            share(
+                "duel_rake",
+                "DuelRoll",
+                DUEL_RAKE_BPS,
+                "Of a settled duel pot, kept by the house",
+            ),
+        ]);
+
+        FeeSchedule {
+            min_bet: Amount::from_attos(MIN_BET),
+            max_bet: Amount::from_attos(MAX_BET),
+            high_roller_max_bet: self
+                .state
+                .high_roller_table
+                .get()
+                .as_ref()
+                .map(|table| table.max_bet),
+            happy_hour_discount_bps,
+            charges,
         }
     }
+    
+    /// Get the player's self-imposed preferences
+    async fn preferences(&self, owner: Option<AccountOwner>) -> PlayerPreferences {
+        let account = self.account(owner).await;
+        account.preferences.clone()
+    }
 
+    /// Get the admin-tunable game configuration
+    async fn config(&self) -> GameConfig {
+        self.state.config.get().clone()
+    }
 
+    /// Multipliers Classic wins pay by roll count, best first: the configured payout
+    /// curve, or the default one while none is set
+    async fn payout_curve(&self) -> Vec<PayoutTier> {
+        self.state.config.get().payout_tiers()
     }
 
+    /// Whether new games and rolls are paused by the admin
+    async fn paused(&self) -> bool {
+        *self.state.paused.get()
+    }
 
-/// Game statistics summary with balance
-#[derive(async_graphql::SimpleObject)]
-struct GameStats {
-    total_games: u64,
-    total_wins: u64,
-    current_game_rolls: u32,
-    win_rate: f64,
-    balance_atto: String,
-    balance_linera: f64,
-}
+    /// Get the running A/B configuration experiment, if any
+    async fn experiment(&self) -> Option<Experiment> {
+        self.state.experiment.get().clone()
+    }
 
-/// Last roll result for display
-#[derive(async_graphql::SimpleObject)]
-struct LastRollResult {
-    dice: Vec<u8>,
-    sum: u8,
-    matched: bool,
-    timestamp_micros: u64,
-    game_over: bool,
-    is_lucky: bool,
-}
+    /// Get the high-roller table, if open
+    async fn high_roller_table(&self) -> Option<HighRollerTable> {
+        self.state.high_roller_table.get().clone()
+    }
 
-/// Potential payout info for current game
-#[derive(async_graphql::SimpleObject)]
-struct PotentialPayout {
-    bet_amount_atto: String,
-    bet_amount_linera: f64,
-    rolls_count: u32,
-    multiplier: String,
-    potential_payout_atto: String,
-    potential_payout_linera: f64,
-    tier_name: String,
-}
+    /// Get every registered season mode and whether it is running now
+    async fn season_modes(&self) -> Vec<SeasonModeStatus> {
+        let now = self.runtime.system_time().micros();
+        self.state
+            .season_modes
+            .index_values()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, mode)| SeasonModeStatus {
+                live: mode.is_live(now),
+                mode,
+            })
+            .collect()
+    }
 
-impl QueryRoot {
-    /// Helper: Get multiplier based on roll count (mirrors contract logic)
-    fn get_multiplier(rolls: u32) -> (u32, u32, String, String) {
-        // (numerator, denominator, display, tier_name)
-        match rolls {
-            0..=9 => (10, 1, "10x".to_string(), "LEGENDARY".to_string()),
-            10..=14 => (5, 1, "5x".to_string(), "EPIC".to_string()),
-            15..=19 => (3, 1, "3x".to_string(), "RARE".to_string()),
-            20..=24 => (2, 1, "2x".to_string(), "GOOD".to_string()),
-            25..=34 => (12, 10, "1.2x".to_string(), "NORMAL".to_string()),
-            35..=44 => (8, 10, "0.8x".to_string(), "REDUCED".to_string()),
-            _ => (2, 10, "0.2x".to_string(), "MINIMAL".to_string()),
+    /// Get a published game template
+    async fn game_template(&self, template_id: u64) -> Option<GameTemplate> {
+        self.state.game_templates.get(&template_id).await.ok().flatten()
+    }
+
+    /// Get published game templates, newest first (`limit` defaults to 20)
+    async fn game_templates(&self, limit: Option<u32>) -> Vec<GameTemplate> {
+        let mut templates = Vec::new();
+        let mut template_id = *self.state.game_template_counter.get();
+        while template_id > 0 && templates.len() < limit.unwrap_or(20) as usize {
+            let template = self.state.game_templates.get(&template_id).await;
+            if let Some(template) = template.ok().flatten() {
+                templates.push(template);
+            }
+            template_id -= 1;
         }
+        templates
     }
-    
-    /// Get the current potential payout if player wins now
-    fn calculate_potential_payout(&self) -> Option<PotentialPayout> {
-        let card = self.state.current_card.get().as_ref()?;
-        
-        let bet_amount_atto: u128 = card.bet_amount_atto.parse().unwrap_or(0);
-        if bet_amount_atto == 0 {
-            return None;
+
+    /// Get aggregated outcomes of high-roller games
+    async fn high_roller_stats(&self) -> BucketStats {
+        self.state.high_roller_stats.get().clone()
+    }
+
+    /// Get aggregated outcomes of Insurance-mode games
+    async fn insurance_stats(&self) -> BucketStats {
+        self.state.insurance_stats.get().clone()
+    }
+
+    /// Get aggregated RTP and engagement per experiment bucket
+    async fn experiment_stats(&self) -> Vec<BucketReport> {
+        let mut reports = Vec::new();
+        for bucket in [ExperimentBucket::A, ExperimentBucket::B] {
+            let stats = self
+                .state
+                .experiment_stats
+                .get(&bucket)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            let staked = u128::from(stats.wagered) + u128::from(stats.fees);
+            let rtp = if staked == 0 {
+                0.0
+            } else {
+                u128::from(stats.paid_out) as f64 / staked as f64 * 100.0
+            };
+            reports.push(BucketReport {
+                bucket,
+                players: stats.players,
+                games: stats.games,
+                rolls: stats.rolls,
+                wagered: stats.wagered,
+                fees: stats.fees,
+                paid_out: stats.paid_out,
+                rtp_percent: rtp,
+            });
         }
-        
-        let (num, denom, multiplier, tier_name) = Self::get_multiplier(card.rolls_count);
-        let payout_atto = bet_amount_atto.saturating_mul(num as u128) / (denom as u128);
-        
-        Some(PotentialPayout {
-            bet_amount_atto: bet_amount_atto.to_string(),
-            bet_amount_linera: bet_amount_atto as f64 / 1e18,
-            rolls_count: card.rolls_count,
-            multiplier,
-            potential_payout_atto: payout_atto.to_string(),
-            potential_payout_linera: payout_atto as f64 / 1e18,
-            tier_name,
+        reports
+    }
+
+    /// Admin-only queries, unlocked by the admin's signature over an `AdminQueryGrant`
+    /// for this chain and application expiring at `expires_at_micros` (see
+    /// `AdminQueryGrant::encode_signature` for the encoding)
+    async fn admin(
+        &self,
+        expires_at_micros: u64,
+        signature: String,
+    ) -> async_graphql::Result<AdminQuery> {
+        self.check_admin_grant(expires_at_micros, &signature)?;
+        Ok(AdminQuery {
+            state: self.state.clone(),
         })
     }
-}
 
-// =============================================================================
-// MUTATION ROOT - Schedule operations
-// =============================================================================
+    /// Get claimed prizes with their tier statistics, most recent first (default 10)
+    async fn win_archive(&self, limit: Option<usize>) -> Vec<WinRecord> {
+        let count = self.state.win_archive.count();
+        let start = count.saturating_sub(limit.unwrap_or(10));
+        let mut wins = self
+            .state
+            .win_archive
+            .read(start..count)
+            .await
+            .unwrap_or_default();
+        wins.reverse();
+        wins
+    }
 
-struct MutationRoot {
-    runtime: Arc<ServiceRuntime<FlashportService>>,
-}
+    /// Search claimed prizes by outcome, bet, bingo type, mode and claim time (default
+    /// newest first, 10 results). Filters narrow the search through the archive's
+    /// indexes, so only wins they all share are read.
+    async fn search_archive(
+        &self,
+        filter: Option<ArchiveFilter>,
+        sort: Option<ArchiveSort>,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<Vec<WinRecord>> {
+        let filter = filter.unwrap_or_default();
+        let sort = sort.unwrap_or_default();
+        let limit = limit.unwrap_or(10);
+        if filter.bet_range().is_none() {
+            return Err("Bet bounds must be amounts in atto".into());
+        }
+        let mut candidates = match self.archive_candidates(&filter).await {
+            Some(indexes) => indexes,
+            None => (0..self.state.win_archive.count() as u32).collect(),
+        };
+        if sort == ArchiveSort::Newest {
+            candidates.reverse();
+        }
 
-#[Object]
-impl MutationRoot {
-    /// Start a new session
-    async fn start_session(&self, expires_in_secs: u64) -> bool {
-        let op = Operation::StartSession { expires_in_secs };
-        self.runtime.schedule_operation(&op);
-        true
+        let mut wins = Vec::new();
+        for index in candidates {
+            let win = self.state.win_archive.get(index as usize).await;
+            let Some(win) = win.ok().flatten() else {
+                continue;
+            };
+            if !filter.matches(&win) {
+                continue;
+            }
+            wins.push(win);
+            // Archive order already is the requested order: stop once the page is full
+            if matches!(sort, ArchiveSort::Newest | ArchiveSort::Oldest) && wins.len() == limit {
+                break;
+            }
+        }
+        let atto = |amount: &str| amount.parse::<u128>().unwrap_or(0);
+        match sort {
+            ArchiveSort::Newest | ArchiveSort::Oldest => {}
+            ArchiveSort::LargestPayout => {
+                wins.sort_by_key(|win| std::cmp::Reverse(atto(&win.payout_atto)))
+            }
+            ArchiveSort::LargestBet => {
+                wins.sort_by_key(|win| std::cmp::Reverse(atto(&win.bet_amount_atto)))
+            }
+            ArchiveSort::FewestRolls => wins.sort_by_key(|win| win.rolls_count),
+        }
+        wins.truncate(limit);
+        Ok(wins)
     }
 
-    /// End the current session
-    async fn end_session(&self) -> bool {
-        self.runtime.schedule_operation(&Operation::EndSession);
-        true
+    /// Get recorded bonus airdrop batches, most recent first (default 10)
+    async fn airdrops(&self, limit: Option<usize>) -> Vec<AirdropBatch> {
+        let count = self.state.airdrops.count();
+        let start = count.saturating_sub(limit.unwrap_or(10));
+        let mut batches = self
+            .state
+            .airdrops
+            .read(start..count)
+            .await
+            .unwrap_or_default();
+        batches.reverse();
+        batches
     }
 
-    /// Deposit funds (specify amount in LINERA)
-    async fn deposit(&self, amount_linera: f64) -> bool {
-        // Convert LINERA to atto (1 LINERA = 10^18 atto)
-        let amount_atto = (amount_linera * 1e18) as u128;
-        self.runtime.schedule_operation(&Operation::Deposit { amount_atto });
-        true
+    /// Get the promotional bonus credited to an owner
+    async fn bonus_balance(&self, owner: AccountOwner) -> Amount {
+        self.state
+            .bonus_balances
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default()
     }
-    
-    /// Withdraw funds
-    async fn withdraw(&self, amount_atto: String) -> bool {
-        let amount = amount_atto.parse::<u128>().unwrap_or(0);
-        let op = Operation::Withdraw {
-            amount: Amount::from_attos(amount),
-        };
-        self.runtime.schedule_operation(&op);
-        true
+
+    /// Get the public log of admin interventions, most recent first (default 10)
+    async fn admin_log(&self, limit: Option<usize>) -> Vec<AdminAction> {
+        let count = self.state.admin_log.count();
+        let start = count.saturating_sub(limit.unwrap_or(10));
+        let mut actions = self
+            .state
+            .admin_log
+            .read(start..count)
+            .await
+            .unwrap_or_default();
+        actions.reverse();
+        actions
     }
 
-    /// Start a new game with bet amount (1-100 LINERA)
-    async fn new_game(&self, bet_amount_linera: f64) -> bool {
-        // Convert LINERA to atto (1 LINERA = 10^18 atto)
-        let bet_amount_atto = (bet_amount_linera * 1e18) as u128;
-        let op = Operation::NewGame { bet_amount_atto };
-        self.runtime.schedule_operation(&op);
-        true
+    /// Get the relay nonces an owner has used in envelopes that are still valid; a
+    /// relayer picks any other value for the next one
+    async fn relay_nonces(&self, owner: AccountOwner) -> Vec<ConsumedNonce> {
+        let now = self.runtime.system_time().micros();
+        let nonces = self
+            .state
+            .relay_nonces
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        nonces
+            .into_iter()
+            .filter(|consumed| consumed.expires_at_micros > now)
+            .collect()
     }
 
-    /// Roll 4 dice and match on the current card (costs 0.1 LINERA)
-    async fn roll_and_match(&self) -> bool {
-        self.runtime.schedule_operation(&Operation::RollAndMatch);
-        true
+    /// Get the compliance hold on an owner's withdrawals (None if they are not held)
+    async fn withdrawal_hold(&self, owner: AccountOwner) -> Option<WithdrawalHold> {
+        let now = self.runtime.system_time().micros();
+        let hold = self.state.withdrawal_holds.get(&owner).await.ok().flatten();
+        hold.filter(|hold| hold.is_active(now))
     }
 
-    /// Claim prize after winning
-    async fn claim_prize(&self) -> bool {
-        self.runtime.schedule_operation(&Operation::ClaimPrize);
-        true
+    /// Get a governance proposal by id (None if there is no such proposal)
+    async fn proposal(&self, proposal_id: u64) -> Option<GovernanceProposal> {
+        self.state.proposals.get(&proposal_id).await.ok().flatten()
     }
 
-    /// Auto-roll multiple times (schedules N roll operations)
-    async fn auto_roll(&self, count: u32) -> u32 {
-        let count = count.min(100); // Cap at 100 rolls
-        for _ in 0..count {
-            self.runtime.schedule_operation(&Operation::RollAndMatch);
+    /// Get the governed changes waiting out the governance timelock, oldest first,
+    /// so players can see them coming
+    async fn pending_changes(&self) -> Vec<GovernanceProposal> {
+        let mut pending = Vec::new();
+        for proposal_id in 1..=*self.state.proposal_counter.get() {
+            if let Ok(Some(proposal)) = self.state.proposals.get(&proposal_id).await {
+                if proposal.status == ProposalStatus::Pending {
+                    pending.push(proposal);
+                }
+            }
         }
-        count
+        pending
     }
-}
-    
 
+    /// Get an owner's first-run checklist; an owner who has done nothing yet gets an
+    /// empty one pointing at the first step
+    async fn onboarding_progress(&self, owner: AccountOwner) -> OnboardingProgress {
+        self.account(Some(owner)).await.onboarding
+    }
 
-#[cfg(test)]
-mod tests {
-    use std::sync::Arc;
+    /// Get the operations an owner has signed (zero for an owner who has signed none)
+    async fn api_usage(&self, owner: AccountOwner) -> ApiUsage {
+        self.account(Some(owner)).await.api_usage
+    }
 
-    use async_graphql::{Request, Response, Value};
-    use futures::FutureExt as _;
-    use linera_sdk::{util::BlockingWait, views::View, Service, ServiceRuntime};
-    use serde_json::json;
+    /// Get the deployment this service answers for, at the height of the state it reads
+    async fn runtime_context(&self) -> RuntimeContext {
+        RuntimeContext {
+            chain_id: self.runtime.chain_id(),
+            application_id: self.runtime.application_id().forget_abi(),
+            block_height: *self.state.state_height.get(),
+        }
+    }
 
-    use super::{FlashportService, FlashportState};
+    /// Get the chain keeping the deployment registry, if configured
+    async fn lobby_chain(&self) -> Option<ChainId> {
+        *self.state.lobby_chain.get()
+    }
 
-    #[test]
-    fn test_query_stats() {
-        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
-        let state = FlashportState::load(runtime.root_view_storage_context())
-            .blocking_wait()
-            .expect("Failed to load state");
+    /// Get every FlashPort deployment registered with this lobby chain
+    async fn instances(&self) -> Vec<RegisteredInstance> {
+        self.state
+            .instances
+            .index_values()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, instance)| instance)
+            .collect()
+    }
 
-        let service = FlashportService {
-            state: Arc::new(state),
-            runtime,
-        };
+    /// Get the snapshot most recently emitted on the stats stream
+    async fn last_published_stats(&self) -> Option<StatsSnapshot> {
+        self.state.last_published_stats.get().clone()
+    }
 
-        let request = Request::new("{ totalGames totalWins }");
+    /// Get unacknowledged operator notifications with ids after `after_id`, oldest
+    /// first (default 20 per page)
+    async fn outbox(&self, after_id: Option<u64>, limit: Option<usize>) -> OutboxPage {
+        let after_id = after_id.unwrap_or(0);
+        let limit = limit.unwrap_or(20);
+        let entries = self.state.outbox.elements().await.unwrap_or_default();
+        let pending = entries.len();
+        let mut page: Vec<OutboxEntry> = entries
+            .into_iter()
+            .filter(|entry| entry.id > after_id)
+            .take(limit + 1)
+            .collect();
+        let has_more = page.len() > limit;
+        page.truncate(limit);
+        OutboxPage {
+            next_after_id: page.last().map_or(after_id, |entry| entry.id),
+            entries: page,
+            has_more,
+            pending,
+        }
+    }
 
-        let response = service
-            .handle_query(request)
-            .now_or_never()
-            .expect("Query should not await");
+    /// Warn when the open game has gone `staleGameWarningSecs` without a roll, with the
+    /// time left before its bet or prize can be lost (None when fresh or turned off)
+    async fn stale_game_warning(&self, owner: Option<AccountOwner>) -> Option<StaleGameWarning> {
+        let account = self.account(owner).await;
+        let threshold_micros = self.state.config.get().stale_game_warning_secs as u64 * 1_000_000;
+        let phase = account.game_phase;
+        let card = account.current_card.as_ref()?;
+        if threshold_micros == 0 || !phase.is_open() {
+            return None;
+        }
+        let now = self.runtime.system_time().micros();
+        let idle_micros = now.saturating_sub(card.last_active_micros);
+        if idle_micros <= threshold_micros {
+            return None;
+        }
 
-        let expected = Response::new(
-            Value::from_json(json!({
-                "totalGames": 0,
-                "totalWins": 0
-            }))
-            .unwrap(),
-        );
+        // An active bet is forfeited once the session lapses (the next EndSession or
+        // NewGame takes it); a pending prize expires at its claim deadline
+        let forfeits_at_micros = match phase {
+            GamePhase::BingoPending => {
+                Some(card.claim_deadline_micros).filter(|deadline| *deadline > 0)
+            }
+            _ => Some(
+                account
+                    .active_session
+                    .as_ref()
+                    .map_or(now, |session| session.expires_at_micros),
+            ),
+        };
+        Some(StaleGameWarning {
+            game_id: card.id,
+            phase,
+            idle_secs: idle_micros / 1_000_000,
+            forfeits_at_micros,
+            secs_until_forfeit: forfeits_at_micros
+                .map(|deadline| deadline.saturating_sub(now) / 1_000_000),
+        })
+    }
 
-        assert_eq!(response, expected);
+    /// Get the running happy hour (largest discount if several overlap) and the
+    /// ones still to come, soonest first
+    async fn happy_hours(&self) -> HappyHourSchedule {
+        let now = self.runtime.system_time().micros();
+        let schedule = self.state.happy_hours.get();
+        let current = schedule
+            .iter()
+            .filter(|happy_hour| happy_hour.is_active(now))
+            .max_by_key(|happy_hour| happy_hour.discount_bps)
+            .cloned();
+        let upcoming = schedule
+            .iter()
+            .filter(|happy_hour| happy_hour.starts_at_micros > now)
+            .cloned()
+            .collect();
+        HappyHourSchedule { current, upcoming }
     }
 
-    #[test]
-    fn test_query_fees() {
-        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+    /// Get the current raffle round, including `owner`'s tickets when given
+    async fn raffle_status(&self, owner: Option<AccountOwner>) -> RaffleStatus {
+        let owner_tickets = match owner {
+            Some(owner) => self
+                .state
+                .raffle_tickets
+                .get(&owner)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(0),
+            None => 0,
+        };
+        RaffleStatus {
+            pool: *self.state.raffle_pool.get(),
+            owner_tickets,
+            total_tickets: *self.state.raffle_total_tickets.get(),
+            draw_at_micros: *self.state.raffle_draw_at_micros.get(),
+            draws_completed: self.state.raffle_history.count(),
+        }
+    }
+
+    /// Get past raffle draws, most recent first (default 10)
+    async fn raffle_history(&self, limit: Option<usize>) -> Vec<RaffleDraw> {
+        let count = self.state.raffle_history.count();
+        let start = count.saturating_sub(limit.unwrap_or(10));
+        let mut draws = self
+            .state
+            .raffle_history
+            .read(start..count)
+            .await
+            .unwrap_or_default();
+        draws.reverse();
+        draws
+    }
+
+    /// Get a house-card tournament with every entrant's progress
+    async fn tournament(&self, tournament_id: u64) -> Option<Tournament> {
+        self.state.tournaments.get(&tournament_id).await.ok().flatten()
+    }
+
+    /// Get the interest `owner`'s tournament entry fee has earned in escrow so far and
+    /// will have earned when the tournament closes
+    async fn tournament_accrual(
+        &self,
+        tournament_id: u64,
+        owner: AccountOwner,
+    ) -> Option<TournamentAccrual> {
+        let tournament = self.state.tournaments.get(&tournament_id).await.ok().flatten()?;
+        let entry = tournament.entries.iter().find(|entry| entry.owner == owner)?;
+        let now = self.runtime.system_time().micros();
+        let accrued = tournament.accrued_interest(entry, now)?;
+        let at_close = tournament.accrued_interest(entry, tournament.closes_at_micros)?;
+        Some(TournamentAccrual {
+            interest_bps_per_day: tournament.interest_bps_per_day,
+            entered_at_micros: entry.entered_at_micros,
+            accrued: Amount::from_attos(accrued),
+            at_close: Amount::from_attos(at_close),
+            settled: tournament.settled,
+        })
+    }
+
+    /// Get tournaments, most recent first (default 10)
+    async fn tournaments(&self, limit: Option<usize>) -> Vec<Tournament> {
+        let latest = *self.state.tournament_counter.get();
+        let mut tournaments = Vec::new();
+        for tournament_id in (1..=latest).rev().take(limit.unwrap_or(10)) {
+            if let Ok(Some(tournament)) = self.state.tournaments.get(&tournament_id).await {
+                tournaments.push(tournament);
+            }
+        }
+        tournaments
+    }
+
+    /// Get a duel with both players' cards
+    async fn duel(&self, duel_id: u64) -> Option<Duel> {
+        self.state.duels.get(&duel_id).await.ok().flatten()
+    }
+
+    /// Get duels, most recent first (default 10); `status` keeps only duels in that
+    /// state, e.g. WAITING for ones open to join
+    async fn duels(&self, status: Option<DuelStatus>, limit: Option<usize>) -> Vec<Duel> {
+        let latest = *self.state.duel_counter.get();
+        let mut duels = Vec::new();
+        for duel_id in (1..=latest).rev() {
+            if duels.len() >= limit.unwrap_or(10) {
+                break;
+            }
+            if let Ok(Some(duel)) = self.state.duels.get(&duel_id).await {
+                if status.is_none_or(|status| duel.status == status) {
+                    duels.push(duel);
+                }
+            }
+        }
+        duels
+    }
+
+    /// Get a syndicate with its members' stakes and bet limits
+    async fn syndicate(&self, syndicate_id: u64) -> Option<Syndicate> {
+        self.state.syndicates.get(&syndicate_id).await.ok().flatten()
+    }
+
+    /// Get open syndicates, most recent first (default 10); `member` keeps only the
+    /// syndicates that owner belongs to
+    async fn syndicates(
+        &self,
+        member: Option<AccountOwner>,
+        limit: Option<usize>,
+    ) -> Vec<Syndicate> {
+        let latest = *self.state.syndicate_counter.get();
+        let mut syndicates = Vec::new();
+        for syndicate_id in (1..=latest).rev() {
+            if syndicates.len() >= limit.unwrap_or(10) {
+                break;
+            }
+            if let Ok(Some(syndicate)) = self.state.syndicates.get(&syndicate_id).await {
+                if member.is_none_or(|owner| syndicate.member_index(&owner).is_some()) {
+                    syndicates.push(syndicate);
+                }
+            }
+        }
+        syndicates
+    }
+
+    /// Get scheduled withdrawals, released or not, most recent first (default 10)
+    async fn scheduled_withdrawals(&self, limit: Option<usize>) -> Vec<ScheduledWithdrawal> {
+        let latest = *self.state.scheduled_withdrawal_counter.get();
+        let mut withdrawals = Vec::new();
+        for withdrawal_id in (1..=latest).rev().take(limit.unwrap_or(10)) {
+            if let Ok(Some(withdrawal)) =
+                self.state.scheduled_withdrawals.get(&withdrawal_id).await
+            {
+                withdrawals.push(withdrawal);
+            }
+        }
+        withdrawals
+    }
+
+    /// Get an owner's vesting payouts that still have installments to come
+    async fn vesting_schedules(&self, owner: AccountOwner) -> Vec<VestingSchedule> {
+        self.state
+            .vesting_schedules
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    /// Get filed disputes and their rulings, most recent first (default 10)
+    async fn disputes(&self, limit: Option<usize>) -> Vec<Dispute> {
+        let latest = *self.state.dispute_counter.get();
+        let mut disputes = Vec::new();
+        for dispute_id in (1..=latest).rev().take(limit.unwrap_or(10)) {
+            if let Ok(Some(dispute)) = self.state.disputes.get(&dispute_id).await {
+                disputes.push(dispute);
+            }
+        }
+        disputes
+    }
+
+    /// Get the attestation of a claimed game, if one was produced with AttestGame
+    async fn attestation(&self, game_id: u64) -> Option<GameAttestation> {
+        self.state.attestations.get(&game_id).await.ok().flatten()
+    }
+
+    /// Get the result of the most recent HealthCheck operation
+    async fn last_health_check(&self) -> Option<HealthReport> {
+        self.state.last_health_check.get().clone()
+    }
+
+    /// Get lifetime totals of the history PruneHistory has deleted
+    async fn history_summary(&self) -> HistorySummary {
+        self.state.history_summary.get().clone()
+    }
+
+    /// Get what the most recent PruneHistory run deleted
+    async fn last_prune(&self) -> Option<PruneReport> {
+        self.state.last_prune.get().clone()
+    }
+
+    /// Get how often each card cell has been marked, globally or for `owner`
+    async fn cell_heatmap(&self, owner: Option<AccountOwner>) -> CellHeatmap {
+        let hits = match owner {
+            Some(owner) => self.account(Some(owner)).await.cell_hits,
+            None => *self.state.cell_hits.get(),
+        };
+        CellHeatmap::new(hits)
+    }
+
+    /// Get how many rolls claimed wins took, in buckets of `bucket_size` rolls
+    /// (default 5), with the roll counts that common shares of wins landed within
+    async fn rolls_to_bingo_distribution(
+        &self,
+        bucket_size: Option<u32>,
+    ) -> RollsToBingoDistribution {
+        let counts = self.state.rolls_to_bingo_counts.get();
+        RollsToBingoDistribution::new(counts, bucket_size.unwrap_or(5).max(1))
+    }
+
+    /// Get the randomness inputs of the most recent blocks that drew dice or dealt
+    /// cards, newest first (`limit` defaults to 20)
+    async fn entropy_history(&self, limit: Option<u32>) -> Vec<EntropyRecord> {
+        let limit = limit.unwrap_or(20) as usize;
+        // The latest block's record is only archived once another block draws
+        let latest = self.state.entropy_block.get().clone();
+        let earlier = limit.saturating_sub(latest.iter().len());
+        let history = self.state.entropy_history.read_back(earlier).await;
+        let mut records: Vec<EntropyRecord> = latest.into_iter().collect();
+        records.extend(history.unwrap_or_default().into_iter().rev());
+        records.truncate(limit);
+        records
+    }
+
+    /// Get the count, failures and fees charged for every operation type executed so far
+    async fn operation_metrics(&self) -> Vec<OperationMetrics> {
+        self.state.operation_metrics.get().clone()
+    }
+
+    /// Get the display text of every error code in `locale` (default "en"; locales
+    /// without a translation get English). Responses carry the code, not the text.
+    async fn messages(&self, locale: Option<String>) -> Vec<LocalizedMessage> {
+        let locale = locale.unwrap_or_else(|| "en".to_string());
+        messages::catalog(&locale)
+            .into_iter()
+            .map(|(code, text)| LocalizedMessage {
+                code,
+                text: text.to_string(),
+            })
+            .collect()
+    }
+
+    /// Get the locales `messages` has translations for
+    async fn message_locales(&self) -> Vec<String> {
+        messages::LOCALES.iter().map(|locale| locale.to_string()).collect()
+    }
+
+    /// Get an owner's recent AutoRoll batch outcomes, most recent first, or just the one
+    /// with `handle`
+    async fn last_operation_results(
+        &self,
+        handle: Option<u64>,
+        owner: Option<AccountOwner>,
+    ) -> Vec<BatchOutcome> {
+        let mut outcomes = self.account(owner).await.batch_outcomes;
+        outcomes.retain(|outcome| handle.is_none_or(|handle| outcome.handle == handle));
+        outcomes.reverse();
+        outcomes
+    }
+
+    /// Get the AutoRoll batch paused by the per-block roll cap, if any
+    async fn pending_auto_roll(&self, owner: Option<AccountOwner>) -> Option<PendingAutoRoll> {
+        let account = self.account(owner).await;
+        account.pending_auto_roll.clone()
+    }
+
+    /// Get the ABI version this service and its contract speak; clients tagging
+    /// operations with `Operation::Versioned` should compare against it
+    async fn abi_version(&self) -> u32 {
+        ABI_VERSION
+    }
+
+    /// Get the dice skin catalog
+    async fn dice_skins(&self) -> Vec<DiceSkinInfo> {
+        DICE_SKINS
+            .iter()
+            .map(|skin| DiceSkinInfo {
+                skin_id: skin.skin_id,
+                name: skin.name.to_string(),
+                price: Amount::from_attos(skin.price_atto),
+                for_sale: skin.price_atto > 0,
+            })
+            .collect()
+    }
+
+    /// Get an owner's collected and equipped dice skins
+    async fn skin_inventory(&self, owner: AccountOwner) -> SkinInventory {
+        self.account(Some(owner)).await.skins
+    }
+
+    /// Get the asset an owner's winnings are bridged into (None = internal balance)
+    async fn payout_route(&self, owner: AccountOwner) -> Option<PayoutRoute> {
+        self.state.payout_routes.get(&owner).await.ok().flatten()
+    }
+
+    /// Get the bridge application that routed payouts go through, if configured
+    async fn payout_bridge(&self) -> Option<ApplicationId> {
+        *self.state.payout_bridge.get()
+    }
+
+    /// Get the pending data purge request, if any
+    async fn data_purge(&self, owner: Option<AccountOwner>) -> Option<DataPurgeRequest> {
+        let account = self.account(owner).await;
+        account.data_purge.clone()
+    }
+
+    /// Get the query guards applied by this service
+    async fn service_limits(&self) -> ServiceLimits {
+        ServiceLimits::from_config(self.state.config.get())
+    }
+
+    /// Get the current game at a glance, with the budget alerts to show the player
+    async fn game_snapshot(&self, owner: Option<AccountOwner>) -> GameSnapshot {
+        let account = self.account(owner).await;
+        GameSnapshot {
+            game_phase: account.game_phase,
+            current_card: account.current_card.clone(),
+            available_atto: u128::from(account.player_balance).to_string(),
+            budget_alerts: account.budget_alerts.clone(),
+        }
+    }
+
+    /// Get only the parts of the player-facing state changed by operations after
+    /// `since_version` (a `stateVersion` from an earlier response), for clients on slow
+    /// connections. `changed` lists them, so a null card there means it was cleared.
+    async fn state_delta(&self, since_version: u64, owner: Option<AccountOwner>) -> StateDelta {
+        let account = self.account(owner).await;
+        let changed: Vec<DeltaField> = self
+            .state
+            .delta_versions
+            .index_values()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, version)| *version > since_version)
+            .map(|(field, _)| field)
+            .collect();
+        let includes = |field| changed.contains(&field);
+        StateDelta {
+            state_version: *self.state.state_version.get(),
+            available_atto: includes(DeltaField::Balance)
+                .then(|| u128::from(account.player_balance).to_string()),
+            points_atto: includes(DeltaField::Points)
+                .then(|| u128::from(account.points_balance).to_string()),
+            game_phase: includes(DeltaField::GamePhase).then_some(account.game_phase),
+            current_card: includes(DeltaField::CurrentCard)
+                .then(|| account.current_card.clone())
+                .flatten(),
+            hedge_card: includes(DeltaField::HedgeCard)
+                .then(|| account.hedge_card.clone())
+                .flatten(),
+            session: includes(DeltaField::Session)
+                .then(|| account.active_session.clone())
+                .flatten(),
+            budget_alerts: includes(DeltaField::BudgetAlerts)
+                .then(|| account.budget_alerts.clone()),
+            changed,
+        }
+    }
+
+    /// Get the minimal bot snapshot as compact JSON (see `FastState`), skipping
+    /// GraphQL object decoding for high-frequency clients
+    async fn fast_state(&self, owner: Option<AccountOwner>) -> String {
+        let account = self.account(owner).await;
+        let card = account.current_card.as_ref();
+        let rolls = card.map(|c| c.rolls_count).unwrap_or(0);
+        let (num, denom, _, _) = card.map_or(self.get_multiplier(GameMode::Classic, 0), |c| {
+            self.get_multiplier(c.mode, c.payout_rolls())
+        });
+        let snapshot = FastState {
+            balance_atto: u128::from(account.player_balance).to_string(),
+            points_atto: u128::from(account.points_balance).to_string(),
+            game_id: card.map(|c| c.id).unwrap_or(0),
+            marked: card.map(|c| c.marked_bitmask()).unwrap_or(0),
+            rolls,
+            tier_bps: num * 10_000 / denom,
+            unclaimed_win: account.game_phase == GamePhase::BingoPending,
+        };
+        serde_json::to_string(&snapshot).expect("FastState serializes to JSON")
+    }
+
+    /// Chance of going broke before a bingo when every game bets `bet_linera`
+    /// and gives up after `rolls` rolls (None for a bet outside 1-100 LINERA or no rolls)
+    async fn risk_of_ruin(
+        &self,
+        bet_linera: f64,
+        rolls: u32,
+        bankroll_linera: f64,
+    ) -> Option<RiskOfRuin> {
+        let bet_atto = (bet_linera * 1e18) as u128;
+        if !(MIN_BET..=MAX_BET).contains(&bet_atto) || rolls == 0 {
+            return None;
+        }
+        let rolls = rolls.min(probability::MAX_MODELLED_ROLLS);
+        let cost_per_game = bet_atto + rolls as u128 * ROLL_COST;
+        let games_affordable = ((bankroll_linera.max(0.0) * 1e18) as u128 / cost_per_game) as u64;
+        let win_chance = probability::bingo_chance_within(rolls);
+        Some(RiskOfRuin {
+            win_chance,
+            rolls,
+            cost_per_game_atto: cost_per_game.to_string(),
+            games_affordable,
+            risk_of_ruin: probability::ruin_chance(win_chance, games_affordable),
+        })
+    }
+
+    /// Get the current potential payout if player wins now
+    async fn potential_payout(&self, owner: Option<AccountOwner>) -> Option<PotentialPayout> {
+        let account = self.account(owner).await;
+        self.calculate_potential_payout(&account)
+    }
+
+    /// Get an owner's statistics summary
+    async fn stats(&self, owner: Option<AccountOwner>) -> GameStats {
+        let account = self.account(owner).await;
+        let total_games = account.games_played;
+        let total_wins = account.games_won;
+        let current_rolls = account
+            .current_card
+            .as_ref()
+            .map(|c| c.rolls_count)
+            .unwrap_or(0);
+        let balance = account.player_balance;
+
+        GameStats {
+            total_games,
+            total_wins,
+            current_game_rolls: current_rolls,
+            win_rate: if total_games == 0 {
+                0.0
+            } else {
+                (total_wins as f64 / total_games as f64) * 100.0
+            },
+            balance_atto: format!("{}", u128::from(balance)),
+            balance_linera: u128::from(balance) as f64 / 1e18,
+        }
+    }
+
+
+    }
+
+
+/// Any object addressable by a global node id
+#[derive(Interface)]
+#[graphql(field(name = "node_id", ty = "ID"))]
+enum Node {
+    BingoCard(Box<BingoCard>),
+    GameSession(GameSession),
+    RollRecord(RollRecord),
+    WinRecord(Box<WinRecord>),
+}
+
+/// Game statistics summary with balance
+#[derive(async_graphql::SimpleObject)]
+struct GameStats {
+    total_games: u64,
+    total_wins: u64,
+    current_game_rolls: u32,
+    win_rate: f64,
+    balance_atto: String,
+    balance_linera: f64,
+}
+
+/// Sums still undrawn this game and the chance the next roll hits one
+#[derive(async_graphql::SimpleObject)]
+struct UndrawnSumOdds {
+    sums: Vec<SumOdds>,
+    /// Chance the next roll is a sum not drawn before
+    fresh_chance: f64,
+    /// Chance the next roll is a fresh sum that also marks the card
+    fresh_mark_chance: f64,
+}
+
+/// Chance of a single undrawn sum on the next roll
+#[derive(async_graphql::SimpleObject)]
+struct SumOdds {
+    sum: u8,
+    chance: f64,
+    /// Whether the sum would mark an unmarked cell on the current card
+    marks_card: bool,
+}
+
+/// Scoring tables of PointsPerMark games
+#[derive(async_graphql::SimpleObject)]
+struct PointsScoring {
+    /// Rolls every PointsPerMark game plays before it settles
+    game_rolls: u32,
+    /// Points each number scores once marked
+    numbers: Vec<NumberPoints>,
+    /// Payout tiers, best first; fewer points than the last one pay nothing
+    tiers: Vec<PointsTier>,
+    /// Points of the current card, if it is a PointsPerMark game
+    current_points: Option<u32>,
+}
+
+/// Points a marked number scores
+#[derive(async_graphql::SimpleObject)]
+struct NumberPoints {
+    number: u8,
+    points: u32,
+}
+
+/// Fewest points reaching a payout tier, and what the tier pays
+#[derive(async_graphql::SimpleObject)]
+struct PointsTier {
+    min_points: u32,
+    multiplier_display: String,
+}
+
+/// GraphQL execution guards applied to every query.
+/// There is no per-field timeout: the service runs as a Wasm module without timers,
+/// so the node's own query timeout is the backstop.
+#[derive(async_graphql::SimpleObject)]
+struct ServiceLimits {
+    max_depth: usize,
+    max_complexity: usize,
+    introspection_enabled: bool,
+}
+
+impl ServiceLimits {
+    fn from_config(config: &GameConfig) -> Self {
+        let or_default = |value: u32, default: usize| match value {
+            0 => default,
+            value => value as usize,
+        };
+        ServiceLimits {
+            max_depth: or_default(config.max_query_depth, DEFAULT_MAX_QUERY_DEPTH),
+            max_complexity: or_default(config.max_query_complexity, DEFAULT_MAX_QUERY_COMPLEXITY),
+            introspection_enabled: !config.disable_introspection,
+        }
+    }
+}
+
+/// Roll-fee happy hours for the UI banner
+#[derive(async_graphql::SimpleObject)]
+struct HappyHourSchedule {
+    /// Happy hour discounting rolls right now, if any
+    current: Option<HappyHour>,
+    /// Happy hours that have not started yet, soonest first
+    upcoming: Vec<HappyHour>,
+}
+
+/// Bet limits and every charge the contract can levy
+#[derive(async_graphql::SimpleObject)]
+struct FeeSchedule {
+    min_bet: Amount,
+    max_bet: Amount,
+    /// Largest bet at the high-roller table, if it is open
+    high_roller_max_bet: Option<Amount>,
+    /// Discount off every roll fee from the happy hour running now (0 = none)
+    happy_hour_discount_bps: u32,
+    charges: Vec<FeeCharge>,
+}
+
+/// One charge: a flat `amount` or a `bps` share of what `basis` describes
+#[derive(async_graphql::SimpleObject)]
+struct FeeCharge {
+    /// Stable key clients look the charge up by, e.g. "roll_fee"
+    key: String,
+    /// Operation that levies it (see `Operation::name`)
+    operation: String,
+    amount: Option<Amount>,
+    bps: Option<u32>,
+    basis: String,
+}
+
+/// Nudge for a game left idle past the configured threshold
+#[derive(async_graphql::SimpleObject)]
+struct StaleGameWarning {
+    game_id: u64,
+    phase: GamePhase,
+    /// Time since the card was dealt or last rolled
+    idle_secs: u64,
+    /// When the bet or pending prize can be lost (microseconds since epoch, None = never)
+    forfeits_at_micros: Option<u64>,
+    secs_until_forfeit: Option<u64>,
+}
+
+/// Current raffle round
+#[derive(async_graphql::SimpleObject)]
+struct RaffleStatus {
+    pool: Amount,
+    /// Tickets held by the queried owner (0 when no owner is given)
+    owner_tickets: u64,
+    total_tickets: u64,
+    /// Earliest time the round can be drawn (microseconds since epoch)
+    draw_at_micros: u64,
+    draws_completed: usize,
+}
+
+/// Cell hit counts with a positional-bias check
+#[derive(async_graphql::SimpleObject)]
+struct CellHeatmap {
+    /// Hits per cell, row-major (the FREE center is never hit)
+    hits: Vec<u64>,
+    total_hits: u64,
+    /// Chi-squared statistic against an even spread over the 24 numbered cells
+    /// (23 degrees of freedom; values far above ~35 suggest positional bias)
+    chi_squared: f64,
+}
+
+impl CellHeatmap {
+    fn new(hits: [u64; 25]) -> Self {
+        let total_hits: u64 = hits.iter().sum();
+        let expected = total_hits as f64 / 24.0;
+        let chi_squared = if total_hits == 0 {
+            0.0
+        } else {
+            hits.iter()
+                .enumerate()
+                .filter(|(i, _)| *i != 12)
+                .map(|(_, h)| (*h as f64 - expected).powi(2) / expected)
+                .sum()
+        };
+        CellHeatmap {
+            hits: hits.to_vec(),
+            total_hits,
+            chi_squared,
+        }
+    }
+}
+
+/// Claimed wins by rolls-to-bingo
+#[derive(async_graphql::SimpleObject)]
+struct RollsToBingoDistribution {
+    total_wins: u64,
+    mean_rolls: f64,
+    /// Consecutive roll ranges from 0 up to the slowest win
+    buckets: Vec<RollsBucket>,
+    /// Rolls within which each of ROLLS_PERCENTILES percent of wins landed (empty
+    /// before the first win)
+    percentiles: Vec<RollsPercentile>,
+}
+
+/// Wins that took between `min_rolls` and `max_rolls` rolls
+#[derive(async_graphql::SimpleObject)]
+struct RollsBucket {
+    min_rolls: u32,
+    max_rolls: u32,
+    wins: u64,
+    /// Share of all wins that took at most `max_rolls` rolls
+    cumulative_share: f64,
+}
+
+/// Rolls within which `percentile` percent of wins landed
+#[derive(async_graphql::SimpleObject)]
+struct RollsPercentile {
+    percentile: u32,
+    rolls: u32,
+}
+
+/// Percentiles reported by `rollsToBingoDistribution`
+const ROLLS_PERCENTILES: [u32; 5] = [25, 50, 75, 90, 99];
+
+impl RollsToBingoDistribution {
+    fn new(counts: &[u64], bucket_size: u32) -> Self {
+        let total_wins: u64 = counts.iter().sum();
+        let total_rolls: u64 = counts
+            .iter()
+            .enumerate()
+            .map(|(rolls, wins)| rolls as u64 * wins)
+            .sum();
+        let share = |wins: u64| wins as f64 / total_wins.max(1) as f64;
+
+        let mut buckets = Vec::new();
+        let mut cumulative = 0;
+        for (index, chunk) in counts.chunks(bucket_size as usize).enumerate() {
+            let wins: u64 = chunk.iter().sum();
+            cumulative += wins;
+            let min_rolls = index as u32 * bucket_size;
+            buckets.push(RollsBucket {
+                min_rolls,
+                max_rolls: min_rolls + bucket_size - 1,
+                wins,
+                cumulative_share: share(cumulative),
+            });
+        }
+
+        // Each percentile is the fewest rolls that covers at least that share of wins
+        let mut percentiles = Vec::new();
+        if total_wins > 0 {
+            let mut cumulative = 0;
+            let mut targets = ROLLS_PERCENTILES.iter().peekable();
+            for (rolls, wins) in counts.iter().enumerate() {
+                cumulative += wins;
+                while let Some(&&percentile) = targets.peek() {
+                    let needed = (percentile as u64 * total_wins).div_ceil(100);
+                    if cumulative < needed {
+                        break;
+                    }
+                    percentiles.push(RollsPercentile {
+                        percentile,
+                        rolls: rolls as u32,
+                    });
+                    targets.next();
+                }
+            }
+        }
+
+        RollsToBingoDistribution {
+            total_wins,
+            mean_rolls: total_rolls as f64 / total_wins.max(1) as f64,
+            buckets,
+            percentiles,
+        }
+    }
+}
+
+/// Player-facing state changed since a client's last `stateVersion`; fields not in
+/// `changed` are omitted
+#[derive(async_graphql::SimpleObject)]
+struct StateDelta {
+    state_version: u64,
+    changed: Vec<DeltaField>,
+    available_atto: Option<String>,
+    points_atto: Option<String>,
+    game_phase: Option<GamePhase>,
+    current_card: Option<BingoCard>,
+    hedge_card: Option<BingoCard>,
+    session: Option<GameSession>,
+    budget_alerts: Option<Vec<BudgetAlert>>,
+}
+
+/// The current game with the player's balance and active budget alerts
+#[derive(async_graphql::SimpleObject)]
+struct GameSnapshot {
+    game_phase: GamePhase,
+    current_card: Option<BingoCard>,
+    available_atto: String,
+    budget_alerts: Vec<BudgetAlert>,
+}
+
+/// Escrow interest on one tournament entry fee
+#[derive(async_graphql::SimpleObject)]
+struct TournamentAccrual {
+    interest_bps_per_day: u32,
+    entered_at_micros: u64,
+    /// Interest earned up to now
+    accrued: Amount,
+    /// Interest earned by the time the tournament closes
+    at_close: Amount,
+    /// Whether the interest has been credited
+    settled: bool,
+}
+
+/// Display text for one error code in the requested locale
+#[derive(async_graphql::SimpleObject)]
+struct LocalizedMessage {
+    code: ErrorCode,
+    text: String,
+}
+
+/// A season mode and whether new games can start under it now
+#[derive(async_graphql::SimpleObject)]
+struct SeasonModeStatus {
+    mode: SeasonMode,
+    live: bool,
+}
+
+/// One page of the operator outbox
+#[derive(async_graphql::SimpleObject)]
+struct OutboxPage {
+    entries: Vec<OutboxEntry>,
+    /// Pass as `afterId` to fetch the next page
+    next_after_id: u64,
+    has_more: bool,
+    /// Entries awaiting acknowledgement in total
+    pending: usize,
+}
+
+/// One page of roll history laid out column by column: entry `i` of every array
+/// belongs to the same roll
+#[derive(async_graphql::SimpleObject)]
+struct RollColumns {
+    roll_ids: Vec<u64>,
+    /// Four dice per roll, flattened (roll `i` is `dice[4i..4i + 4]`)
+    dice: Vec<u8>,
+    sums: Vec<u8>,
+    matched: Vec<bool>,
+    is_lucky: Vec<bool>,
+    timestamps_micros: Vec<u64>,
+    /// Roll fee paid (in atto LINERA)
+    fees_atto: Vec<String>,
+    /// Pass as `afterId` to fetch the next page
+    next_after_id: u64,
+    has_more: bool,
+}
+
+/// Session flagged for implausibly fast or regular rolling
+#[derive(async_graphql::SimpleObject)]
+struct CadenceFlag {
+    owner: AccountOwner,
+    session_id: u64,
+    requests: u64,
+    mean_interval_ms: f64,
+    /// Interval std-dev as a share of the mean (basis points)
+    jitter_bps: u32,
+    /// Mean interval is under `cadenceMinIntervalMs`
+    too_fast: bool,
+    /// Jitter is under `cadenceMinJitterBps`
+    too_regular: bool,
+}
+
+/// Per-bucket experiment outcome with return-to-player
+#[derive(async_graphql::SimpleObject)]
+struct BucketReport {
+    bucket: ExperimentBucket,
+    players: u64,
+    games: u64,
+    rolls: u64,
+    wagered: Amount,
+    fees: Amount,
+    paid_out: Amount,
+    /// Payouts as a percentage of bets plus roll fees
+    rtp_percent: f64,
+}
+
+/// Real-money return to player over the rolling epoch window
+#[derive(async_graphql::SimpleObject)]
+struct RollingRtpReport {
+    /// Closed epochs in the window, oldest first
+    closed_epochs: Vec<EpochVolume>,
+    /// Epoch in progress, also counted in the totals
+    current_epoch_id: u64,
+    wagered: Amount,
+    paid_out: Amount,
+    /// Payouts as a percentage of bets
+    rtp_percent: f64,
+}
+
+/// Chance of exhausting a bankroll before hitting bingo under a fixed betting plan
+#[derive(async_graphql::SimpleObject)]
+struct RiskOfRuin {
+    /// Chance a single game reaches bingo within the roll budget (0-1)
+    win_chance: f64,
+    /// Roll budget per game (capped at the longest game the model covers)
+    rolls: u32,
+    /// Bet plus every roll fee of a game that never hits bingo (in atto)
+    cost_per_game_atto: String,
+    /// Losing games the bankroll covers
+    games_affordable: u64,
+    /// Chance of losing every one of them (0-1)
+    risk_of_ruin: f64,
+}
+
+/// A dice skin in the catalog
+#[derive(async_graphql::SimpleObject)]
+struct DiceSkinInfo {
+    skin_id: u32,
+    name: String,
+    price: Amount,
+    /// False for skins earned through an achievement only
+    for_sale: bool,
+}
+
+/// Last roll result for display
+#[derive(async_graphql::SimpleObject)]
+struct LastRollResult {
+    dice: Vec<u8>,
+    sum: u8,
+    matched: bool,
+    timestamp_micros: u64,
+    game_over: bool,
+    is_lucky: bool,
+    skin_id: Option<u32>,
+}
+
+/// Potential payout info for current game
+#[derive(async_graphql::SimpleObject)]
+struct PotentialPayout {
+    bet_amount_atto: String,
+    bet_amount_linera: f64,
+    rolls_count: u32,
+    multiplier: String,
+    potential_payout_atto: String,
+    potential_payout_linera: f64,
+    tier_name: String,
+}
+
+impl QueryRoot {
+    /// Helper: Get multiplier and tier name based on mode and roll count (same rules and
+    /// payout curve as the contract)
+    fn get_multiplier(&self, mode: GameMode, rolls: u32) -> (u32, u32, String, String) {
+        // (numerator, denominator, display, tier_name)
+        let curve = self.state.config.get().payout_tiers();
+        let (num, denom, display) = rules::mode_multiplier(&curve, mode, rolls);
+        (num, denom, display, MULTIPLIER_TIER_NAMES[multiplier_tier(rolls)].to_string())
+    }
+
+    /// Helper: Refuse unless `signature` is the admin's over a grant for this chain and
+    /// application that has not expired
+    fn check_admin_grant(
+        &self,
+        expires_at_micros: u64,
+        signature: &str,
+    ) -> async_graphql::Result<()> {
+        let now = self.runtime.system_time().micros();
+        if now >= expires_at_micros {
+            return Err(async_graphql::Error::new("Admin grant has expired"));
+        }
+        if expires_at_micros - now > MAX_ADMIN_GRANT_TTL_SECS * 1_000_000 {
+            return Err(async_graphql::Error::new(format!(
+                "Admin grants may be valid for at most {}s",
+                MAX_ADMIN_GRANT_TTL_SECS
+            )));
+        }
+        let grant = AdminQueryGrant {
+            chain_id: self.runtime.chain_id(),
+            application_id: self.runtime.application_id().forget_abi(),
+            expires_at_micros,
+        };
+        let admin = *self.state.admin.get();
+        let signed_by_admin = AdminQueryGrant::decode_signature(signature).is_some_and(|signature| {
+            Some(signature.owner()) == admin && signature.verify(&grant).is_ok()
+        });
+        if !signed_by_admin {
+            return Err(async_graphql::Error::new("Not signed by the admin"));
+        }
+        Ok(())
+    }
+    
+    /// Archive indexes, ascending, of the wins every indexed filter in `filter` lets
+    /// through; `None` if no filter narrows the search. Callers still check each win
+    /// against `filter`, since bet and date buckets are wider than the bounds.
+    async fn archive_candidates(&self, filter: &ArchiveFilter) -> Option<Vec<u32>> {
+        let mut narrowed: Vec<Vec<u32>> = Vec::new();
+        if filter.net_wins_only {
+            let count = self.state.archive_net_wins.count();
+            let net_wins = self.state.archive_net_wins.read(0..count).await;
+            narrowed.push(net_wins.unwrap_or_default());
+        }
+        if let Some(bingo_type) = filter.bingo_type {
+            let by_type = self.state.archive_by_bingo_type.get(&bingo_type).await;
+            narrowed.push(by_type.ok().flatten().unwrap_or_default());
+        }
+        if let Some(mode) = filter.mode {
+            let by_mode = self.state.archive_by_mode.get(&mode).await;
+            narrowed.push(by_mode.ok().flatten().unwrap_or_default());
+        }
+        if filter.min_bet_atto.is_some() || filter.max_bet_atto.is_some() {
+            let (min_bet, max_bet) = filter.bet_range().unwrap_or((0, u128::MAX));
+            let mut by_bet = Vec::new();
+            for bucket in archive_bet_bucket(min_bet)..=archive_bet_bucket(max_bet) {
+                let indexes = self.state.archive_by_bet_bucket.get(&bucket).await;
+                by_bet.extend(indexes.ok().flatten().unwrap_or_default());
+            }
+            by_bet.sort_unstable();
+            narrowed.push(by_bet);
+        }
+        if filter.from_micros.is_some() || filter.to_micros.is_some() {
+            let from_day = filter.from_micros.unwrap_or(0) / ARCHIVE_DAY_MICROS;
+            let to_day = filter.to_micros.unwrap_or(u64::MAX) / ARCHIVE_DAY_MICROS;
+            let days = self.state.archive_by_day.indices().await;
+            let mut by_day = Vec::new();
+            for day in days.unwrap_or_default() {
+                if !(from_day..=to_day).contains(&day) {
+                    continue;
+                }
+                let indexes = self.state.archive_by_day.get(&day).await;
+                by_day.extend(indexes.ok().flatten().unwrap_or_default());
+            }
+            by_day.sort_unstable();
+            narrowed.push(by_day);
+        }
+
+        // Intersect, starting from the smallest index list
+        narrowed.sort_by_key(Vec::len);
+        let mut lists = narrowed.into_iter();
+        let mut candidates = lists.next()?;
+        for list in lists {
+            candidates.retain(|index| list.binary_search(index).is_ok());
+        }
+        Some(candidates)
+    }
+
+    /// Account of `owner`; player queries read the admin's account when no owner is
+    /// given (an empty one before instantiation), and an owner who never played reads
+    /// as a new account
+    async fn account(&self, owner: Option<AccountOwner>) -> PlayerAccount {
+        let Some(owner) = owner.or(*self.state.admin.get()) else {
+            return PlayerAccount::default();
+        };
+        self.state
+            .players
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(PlayerAccount::open)
+    }
+
+    /// Get the current potential payout if player wins now
+    fn calculate_potential_payout(&self, account: &PlayerAccount) -> Option<PotentialPayout> {
+        let card = account.current_card.as_ref()?;
+        
+        let bet_amount_atto: u128 = card.bet_amount_atto.parse().unwrap_or(0);
+        if bet_amount_atto == 0 {
+            return None;
+        }
+        
+        let (num, denom, multiplier, tier_name) =
+            self.get_multiplier(card.mode, card.payout_rolls());
+        let payout_atto = bet_amount_atto.saturating_mul(num as u128) / (denom as u128);
+        
+        Some(PotentialPayout {
+            bet_amount_atto: bet_amount_atto.to_string(),
+            bet_amount_linera: bet_amount_atto as f64 / 1e18,
+            rolls_count: card.rolls_count,
+            multiplier,
+            potential_payout_atto: payout_atto.to_string(),
+            potential_payout_linera: payout_atto as f64 / 1e18,
+            tier_name,
+        })
+    }
+}
+
+// =============================================================================
+// MUTATION ROOT - Schedule operations
+// =============================================================================
+
+struct MutationRoot {
+    state: Arc<FlashportState>,
+    runtime: Arc<ServiceRuntime<FlashportService>>,
+}
+
+#[Object]
+impl MutationRoot {
+    /// Start a new session limited to the `SESSION_CAN_*` bits in `permissions`
+    /// (defaults to all of them)
+    async fn start_session(&self, expires_in_secs: u64, permissions: Option<u32>) -> bool {
+        let op = Operation::StartSession {
+            expires_in_secs,
+            permissions,
+        };
+        self.runtime.schedule_operation(&op);
+        true
+    }
+
+    /// End the current session
+    async fn end_session(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::EndSession);
+        true
+    }
+
+    /// Deposit funds (`amount` in LINERA as a decimal string, e.g. "2.5"); custodians
+    /// depositing for a player name them in `creditTo`
+    async fn deposit(
+        &self,
+        amount: Option<String>,
+        #[graphql(deprecation = "Floats lose precision; use `amount`")] amount_linera: Option<f64>,
+        credit_to: Option<AccountOwner>,
+    ) -> async_graphql::Result<bool> {
+        let amount_atto = Self::linera_input("amount", amount, amount_linera)?;
+        self.runtime.schedule_operation(&Operation::Deposit {
+            amount_atto,
+            credit_to,
+        });
+        Ok(true)
+    }
+    
+    /// Withdraw funds
+    async fn withdraw(&self, amount_atto: String) -> bool {
+        let amount = amount_atto.parse::<u128>().unwrap_or(0);
+        let op = Operation::Withdraw {
+            amount: Amount::from_attos(amount),
+        };
+        self.runtime.schedule_operation(&op);
+        true
+    }
+
+    /// Start a new game with bet amount (1-100 LINERA or points, defaults to real LINERA;
+    /// `betAmount` is a decimal string, e.g. "2.5")
+    async fn new_game(
+        &self,
+        bet_amount: Option<String>,
+        #[graphql(deprecation = "Floats lose precision; use `betAmount`")]
+        bet_amount_linera: Option<f64>,
+        currency: Option<Currency>,
+        mode: Option<GameMode>,
+        hedge: Option<bool>,
+    ) -> async_graphql::Result<bool> {
+        let bet_amount_atto = Self::linera_input("betAmount", bet_amount, bet_amount_linera)?;
+        let op = Operation::NewGame {
+            bet_amount_atto,
+            currency: currency.unwrap_or_default(),
+            mode: mode.unwrap_or_default(),
+            hedge: hedge.unwrap_or_default(),
+            template_id: None,
+        };
+        self.runtime.schedule_operation(&op);
+        Ok(true)
+    }
+
+    /// Start a new game under a published game template, which sets the mode
+    /// (`betAmount` is a decimal string and must match any entry fee the template fixes)
+    async fn new_template_game(
+        &self,
+        template_id: u64,
+        bet_amount: String,
+        currency: Option<Currency>,
+        hedge: Option<bool>,
+    ) -> async_graphql::Result<bool> {
+        let bet_amount_atto = Self::linera_input("betAmount", Some(bet_amount), None)?;
+        let op = Operation::NewGame {
+            bet_amount_atto,
+            currency: currency.unwrap_or_default(),
+            mode: GameMode::default(),
+            hedge: hedge.unwrap_or_default(),
+            template_id: Some(template_id),
+        };
+        self.runtime.schedule_operation(&op);
+        Ok(true)
+    }
+
+    /// Start a game under a running season mode (`betAmount` is a decimal string)
+    async fn new_season_game(
+        &self,
+        mode_id: u64,
+        bet_amount: String,
+    ) -> async_graphql::Result<bool> {
+        let bet_amount_atto = Self::linera_input("betAmount", Some(bet_amount), None)?;
+        self.runtime.schedule_operation(&Operation::NewSeasonGame {
+            mode_id,
+            bet_amount_atto,
+        });
+        Ok(true)
+    }
+
+    /// Buy a Classic real-money game for another owner, who alone may play it and
+    /// receives its payout (`betAmount` is a decimal string)
+    async fn sponsor_game(
+        &self,
+        for_owner: AccountOwner,
+        bet_amount: String,
+    ) -> async_graphql::Result<bool> {
+        let bet_amount_atto = Self::linera_input("betAmount", Some(bet_amount), None)?;
+        self.runtime.schedule_operation(&Operation::SponsorGame {
+            for_owner,
+            bet_amount_atto,
+        });
+        Ok(true)
+    }
+
+    /// Roll 4 dice and match on the current card (costs 0.05 LINERA)
+    async fn roll_and_match(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::RollAndMatch);
+        true
+    }
+
+    /// Buy a mark on one unmarked cell; the fee grows as the card nears bingo
+    async fn buy_mark(&self, row: u8, col: u8) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::BuyMark { row, col });
+        true
+    }
+
+    /// Keep the current multiplier tier for the next 5 rolls (once per game)
+    async fn lock_multiplier(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::LockMultiplier);
+        true
+    }
+
+    /// Claim prize after winning
+    async fn claim_prize(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::ClaimPrize);
+        true
+    }
+
+    /// Claim every vesting installment that has unlocked
+    async fn claim_vested_tranche(&self) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::ClaimVestedTranche);
+        true
+    }
+
+    /// Withdraw everything except locked bets and an open game's roll reserve
+    async fn withdraw_all(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::WithdrawAll);
+        true
+    }
+
+    /// Lock `amount` (LINERA, e.g. "2.5") away until `releaseAtMicros`, then pay it out
+    /// with releaseWithdrawal
+    async fn schedule_withdrawal(
+        &self,
+        amount: String,
+        release_at_micros: u64,
+    ) -> async_graphql::Result<bool> {
+        let amount_atto = Self::linera_input("amount", Some(amount), None)?;
+        self.runtime.schedule_operation(&Operation::ScheduleWithdrawal {
+            amount: Amount::from_attos(amount_atto),
+            release_at_micros,
+        });
+        Ok(true)
+    }
+
+    /// Pay out a scheduled withdrawal whose time lock has passed
+    async fn release_withdrawal(&self, withdrawal_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::ReleaseWithdrawal { withdrawal_id });
+        true
+    }
+
+    /// Refill play points once they run below the minimum bet
+    async fn refill_points(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::RefillPoints);
+        true
+    }
+
+    /// Replace the player's self-imposed preferences
+    async fn set_preferences(&self, preferences: PlayerPreferences) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::SetPreferences { preferences });
+        true
+    }
+
+    /// Bridge the signer's future winnings into `target_asset` (None = keep them here)
+    async fn set_payout_route(&self, target_asset: Option<String>) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::SetPayoutRoute { target_asset });
+        true
+    }
+
+    /// Request a purge of the signer's personal data (confirmable after a grace period)
+    async fn purge_my_data(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::PurgeMyData);
+        true
+    }
+
+    /// Carry out a requested data purge once its grace period has passed
+    async fn confirm_data_purge(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::ConfirmDataPurge);
+        true
+    }
+
+    /// Withdraw a pending data purge request
+    async fn cancel_data_purge(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::CancelDataPurge);
+        true
+    }
+
+    /// Buy a dice skin from the catalog
+    async fn buy_skin(&self, skin_id: u32) -> bool {
+        self.runtime.schedule_operation(&Operation::BuySkin { skin_id });
+        true
+    }
+
+    /// Equip an owned dice skin, or plain dice with null
+    async fn equip_skin(&self, skin_id: Option<u32>) -> bool {
+        self.runtime.schedule_operation(&Operation::EquipSkin { skin_id });
+        true
+    }
+
+    /// Admin operations, checked against the current state before they are scheduled
+    /// so operator tooling gets a readable error instead of a failed block
+    async fn admin(&self) -> AdminMutation {
+        AdminMutation {
+            state: self.state.clone(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Propose replacing the game configuration (admin only; waits out the timelock)
+    async fn update_config(&self, config: GameConfig) -> bool {
+        self.runtime.schedule_operation(&Operation::UpdateConfig { config });
+        true
+    }
+
+    /// Propose starting, replacing or stopping the A/B configuration experiment
+    /// (admin only; waits out the timelock)
+    async fn set_experiment(&self, experiment: Option<Experiment>) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::SetExperiment { experiment });
+        true
+    }
+
+    /// Propose opening, replacing or closing the achievement-gated high-roller table
+    /// (admin only; waits out the timelock)
+    async fn set_high_roller_table(&self, table: Option<HighRollerTable>) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::SetHighRollerTable { table });
+        true
+    }
+
+    /// Propose adding a season mode (`modeId` 0) or replacing one, e.g. to enable or
+    /// disable it (admin only; waits out the timelock)
+    async fn upsert_season_mode(&self, mode: SeasonMode) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::UpsertSeasonMode { mode });
+        true
+    }
+
+    /// Publish a game template (`templateId` 0) or replace one the signer published
+    async fn upsert_game_template(&self, template: GameTemplate) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::UpsertGameTemplate { template });
+        true
+    }
+
+    /// Extend pending claim deadlines by `window_secs` for one page of accounts after
+    /// `after` (default `MAX_CLAIM_EXTENSION_PAGE` per page; admin only, logged publicly)
+    async fn extend_claim_deadlines(
+        &self,
+        window_secs: u64,
+        reason: String,
+        after: Option<AccountOwner>,
+        limit: Option<u32>,
+    ) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::ExtendClaimDeadlines {
+                window_secs,
+                reason,
+                after,
+                limit: limit.unwrap_or(MAX_CLAIM_EXTENSION_PAGE),
+            });
+        true
+    }
+
+    /// Credit promotional bonuses out of the epoch bonus pool (admin only)
+    async fn airdrop_bonus(&self, credits: Vec<AirdropCredit>) -> bool {
+        let recipients = credits
+            .into_iter()
+            .map(|credit| (credit.owner, u128::from(credit.amount)))
+            .collect();
+        self.runtime
+            .schedule_operation(&Operation::AirdropBonus { recipients });
+        true
+    }
+
+    /// Propose a roll-fee discount window (admin only; waits out the timelock)
+    async fn schedule_happy_hour(
+        &self,
+        starts_at_micros: u64,
+        ends_at_micros: u64,
+        discount_bps: u32,
+    ) -> bool {
+        self.runtime.schedule_operation(&Operation::ScheduleHappyHour {
+            starts_at_micros,
+            ends_at_micros,
+            discount_bps,
+        });
+        true
+    }
+
+    /// Propose cancelling a happy hour (admin only; waits out the timelock)
+    async fn cancel_happy_hour(&self, happy_hour_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::CancelHappyHour { happy_hour_id });
+        true
+    }
+
+    /// Announce this deployment to the lobby chain (admin only; fails on-chain otherwise)
+    async fn register_with_lobby(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::RegisterWithLobby);
+        true
+    }
+
+    /// Emit a statistics snapshot (fails on-chain within the publish interval)
+    async fn publish_stats(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::PublishStats);
+        true
+    }
+
+    /// Acknowledge delivered outbox entries up to and including `up_to_id` (admin only)
+    async fn ack_outbox(&self, up_to_id: u64) -> bool {
+        self.runtime.schedule_operation(&Operation::AckOutbox { up_to_id });
+        true
+    }
+
+    /// Call the partner application of a queued partner notification outbox entry
+    async fn deliver_partner_notification(&self, entry_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::DeliverPartnerNotification { entry_id });
+        true
+    }
+
+    /// Dispute a recently settled game, freezing its winnings until the admin rules
+    async fn dispute_game(&self, game_id: u64, reason: String) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::DisputeGame { game_id, reason });
+        true
+    }
+
+    /// Rule on a dispute; `award` (LINERA, e.g. "2.5") is only allowed when upholding
+    /// it (admin only; fails on-chain otherwise)
+    async fn resolve_dispute(
+        &self,
+        dispute_id: u64,
+        outcome: DisputeOutcome,
+        award: Option<String>,
+        note: String,
+    ) -> async_graphql::Result<bool> {
+        let award_atto = match award {
+            Some(award) => Self::linera_input("award", Some(award), None)?,
+            None => 0,
+        };
+        self.runtime.schedule_operation(&Operation::ResolveDispute {
+            dispute_id,
+            outcome,
+            award_atto,
+            note,
+        });
+        Ok(true)
+    }
+
+    /// Attest a claimed game's outcome for bridges and oracles on other chains
+    async fn attest_game(&self, game_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::AttestGame { game_id });
+        true
+    }
+
+    /// Validate the chain's internal invariants and record the result
+    async fn health_check(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::HealthCheck);
+        true
+    }
+
+    /// Play a free demo game on a throwaway card; no deposit or session needed
+    async fn demo_game(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::DemoGame);
+        true
+    }
+
+    /// Delete all but the newest `keep_last` entries of each history (admin only;
+    /// fails on-chain otherwise)
+    async fn prune_history(&self, keep_last: u32) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::PruneHistory { keep_last });
+        true
+    }
+
+    /// Draw the weekly raffle (fails on-chain before the draw time)
+    async fn draw_raffle(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::DrawRaffle);
+        true
+    }
+
+    /// Open a house-card tournament (admin only; fails on-chain otherwise)
+    async fn create_tournament(&self, entry_fee_atto: String, duration_secs: u64) -> bool {
+        let entry_fee_atto = entry_fee_atto.parse::<u128>().unwrap_or(0);
+        self.runtime.schedule_operation(&Operation::CreateTournament {
+            entry_fee_atto,
+            duration_secs,
+        });
+        true
+    }
+
+    /// Pay the entry fee and join a tournament
+    async fn enter_tournament(&self, tournament_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::EnterTournament { tournament_id });
+        true
+    }
+
+    /// Take the next roll of a tournament's shared dice stream
+    async fn tournament_roll(&self, tournament_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::TournamentRoll { tournament_id });
+        true
+    }
+
+    /// Rank and pay out a closed tournament
+    async fn settle_tournament(&self, tournament_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::SettleTournament { tournament_id });
+        true
+    }
+
+    /// Open a duel and take its first seat; `roll_fee` (LINERA, e.g. "0.5") is what
+    /// each roll pays into the pot
+    async fn open_duel(&self, roll_fee: String) -> async_graphql::Result<bool> {
+        let roll_fee_atto = Self::linera_input("rollFee", Some(roll_fee), None)?;
+        self.runtime
+            .schedule_operation(&Operation::OpenDuel { roll_fee_atto });
+        Ok(true)
+    }
+
+    /// Take the second seat of a waiting duel
+    async fn join_duel(&self, duel_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::JoinDuel { duel_id });
+        true
+    }
+
+    /// Roll on your turn of a duel (fails on-chain when it is the other player's)
+    async fn duel_roll(&self, duel_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::DuelRoll { duel_id });
+        true
+    }
+
+    /// Take the pot of a duel whose other player let their turn run out
+    async fn claim_duel_pot(&self, duel_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::ClaimDuelPot { duel_id });
+        true
+    }
+
+    /// Start a syndicate you manage and join it as its first member
+    async fn create_syndicate(&self, name: String) -> async_graphql::Result<bool> {
+        let name = name.trim().to_string();
+        if name.is_empty() || name.len() > MAX_SYNDICATE_NAME_LEN {
+            return Err(format!(
+                "Syndicate name must be 1 to {} bytes",
+                MAX_SYNDICATE_NAME_LEN
+            )
+            .into());
+        }
+        self.runtime
+            .schedule_operation(&Operation::CreateSyndicate { name });
+        Ok(true)
+    }
+
+    /// Add a member to your syndicate or change their bet limit (LINERA, e.g. "5";
+    /// "0" makes them a backer who cannot play)
+    async fn set_syndicate_member(
+        &self,
+        syndicate_id: u64,
+        member: AccountOwner,
+        max_bet: String,
+    ) -> async_graphql::Result<bool> {
+        let max_bet_atto = Self::linera_input("maxBet", Some(max_bet), None)?;
+        self.runtime.schedule_operation(&Operation::SetSyndicateMember {
+            syndicate_id,
+            member,
+            max_bet_atto,
+        });
+        Ok(true)
+    }
+
+    /// Pay `amount` (LINERA, e.g. "10") into a syndicate's bankroll
+    async fn contribute_to_syndicate(
+        &self,
+        syndicate_id: u64,
+        amount: String,
+    ) -> async_graphql::Result<bool> {
+        let amount_atto = Self::linera_input("amount", Some(amount), None)?;
+        self.runtime.schedule_operation(&Operation::ContributeToSyndicate {
+            syndicate_id,
+            amount_atto,
+        });
+        Ok(true)
+    }
+
+    /// Leave a syndicate and take out your stake
+    async fn leave_syndicate(&self, syndicate_id: u64) -> bool {
+        self.runtime
+            .schedule_operation(&Operation::LeaveSyndicate { syndicate_id });
+        true
+    }
+
+    /// Play a game for a syndicate from its bankroll; `bet` (LINERA) must be within
+    /// your member bet limit
+    async fn syndicate_game(&self, syndicate_id: u64, bet: String) -> async_graphql::Result<bool> {
+        let bet_amount_atto = Self::linera_input("bet", Some(bet), None)?;
+        self.runtime.schedule_operation(&Operation::SyndicateGame {
+            syndicate_id,
+            bet_amount_atto,
+        });
+        Ok(true)
+    }
+
+    /// Auto-roll in a single operation, optionally returning a compact summary
+    async fn auto_roll_batch(&self, count: u32, stop_on_bingo: bool, aggregate: bool) -> u32 {
+        let count = count.min(MAX_AUTO_ROLLS);
+        self.runtime.schedule_operation(&Operation::AutoRoll {
+            count,
+            stop_on_bingo,
+            aggregate,
+        });
+        count
+    }
+
+    /// Resume the AutoRoll batch paused by the per-block roll cap
+    async fn continue_auto_roll(&self) -> bool {
+        self.runtime.schedule_operation(&Operation::ContinueAutoRoll);
+        true
+    }
+
+    /// Roll up to `count` times in one AutoRoll operation and return the handle its
+    /// outcome will probably be recorded under (see `lastOperationResults`). This is
+    /// only a hint: it is read before the operation runs, so it is wrong if another
+    /// batch executes first. The `AggregatedRolls` response carries the real handle.
+    async fn roll_burst(&self, count: u32, stop_on_bingo: bool) -> u64 {
+        self.runtime.schedule_operation(&Operation::AutoRoll {
+            count: count.min(MAX_AUTO_ROLLS),
+            stop_on_bingo,
+            aggregate: true,
+        });
+        *self.state.batch_counter.get() + 1
+    }
+
+    /// Auto-roll multiple times (schedules N roll operations)
+    async fn auto_roll(&self, count: u32) -> u32 {
+        let count = count.min(100); // Cap at 100 rolls
+        for _ in 0..count {
+            self.runtime.schedule_operation(&Operation::RollAndMatch);
+        }
+        count
+    }
+}
+
+impl MutationRoot {
+    /// Helper: Resolve a LINERA amount given either as a decimal string or through its
+    /// deprecated float alias, into atto
+    fn linera_input(
+        name: &str,
+        decimal: Option<String>,
+        legacy_float: Option<f64>,
+    ) -> async_graphql::Result<u128> {
+        let parsed = match (decimal, legacy_float) {
+            (Some(decimal), None) => amounts::parse_linera(&decimal),
+            (None, Some(value)) => amounts::linera_from_f64(value),
+            (Some(_), Some(_)) => Err(format!("Pass `{}` or its deprecated alias, not both", name)),
+            (None, None) => Err(format!("Missing `{}`", name)),
+        };
+        parsed.map_err(async_graphql::Error::new)
+    }
+}
+
+/// Admin-only queries under `admin(expiresAtMicros, signature) { ... }`
+struct AdminQuery {
+    state: Arc<FlashportState>,
+}
+
+#[Object]
+impl AdminQuery {
+    /// Get sessions whose roll cadence looks automated under the configured thresholds
+    /// (admin review of promotional terms; sessions need CADENCE_MIN_SAMPLES intervals)
+    async fn suspicious_sessions(&self) -> Vec<CadenceFlag> {
+        let config = self.state.config.get();
+        let cadences = self
+            .state
+            .session_cadence
+            .index_values()
+            .await
+            .unwrap_or_default();
+        cadences
+            .into_iter()
+            .filter_map(|(owner, cadence)| {
+                let (too_fast, too_regular) = cadence.flags(config);
+                (too_fast || too_regular).then_some(CadenceFlag {
+                    owner,
+                    session_id: cadence.session_id,
+                    requests: cadence.requests,
+                    mean_interval_ms: cadence.mean_interval_micros() as f64 / 1_000.0,
+                    jitter_bps: cadence.jitter_bps(),
+                    too_fast,
+                    too_regular,
+                })
+            })
+            .collect()
+    }
+
+    /// Get the diagnostics events recorded while the `diagnostics` parameter is on,
+    /// newest first, optionally of one kind (`limit` defaults to 50): fees charged,
+    /// payout tiers chosen and the entropy behind each roll
+    async fn diagnostics(
+        &self,
+        kind: Option<DiagnosticKind>,
+        limit: Option<usize>,
+    ) -> Vec<DiagnosticEvent> {
+        let events = self.state.diagnostics.elements().await.unwrap_or_default();
+        events
+            .into_iter()
+            .rev()
+            .filter(|event| kind.is_none_or(|kind| event.kind == kind))
+            .take(limit.unwrap_or(50))
+            .collect()
+    }
+}
+
+/// Admin mutations under `admin { ... }`. Each one mirrors an admin operation and
+/// rejects input the contract would refuse; the signer is still checked on-chain.
+struct AdminMutation {
+    state: Arc<FlashportState>,
+    runtime: Arc<ServiceRuntime<FlashportService>>,
+}
+
+#[Object]
+impl AdminMutation {
+    /// Stop (`paused` true) or resume new games and rolls
+    async fn set_paused(&self, paused: bool, reason: String) -> async_graphql::Result<bool> {
+        let reason = Self::require_reason(reason)?;
+        if *self.state.paused.get() == paused {
+            return Err(async_graphql::Error::new(if paused {
+                "The deployment is already paused"
+            } else {
+                "The deployment is already running"
+            }));
+        }
+        self.runtime
+            .schedule_operation(&Operation::SetPaused { paused, reason });
+        Ok(true)
+    }
+
+    /// Propose replacing the game configuration once the timelock has passed
+    async fn update_config(&self, config: GameConfig) -> async_graphql::Result<bool> {
+        let bps_fields = [
+            ("difficultyBonusBps", config.difficulty_bonus_bps),
+            ("freshSumBonusBps", config.fresh_sum_bonus_bps),
+            ("cadenceMinJitterBps", config.cadence_min_jitter_bps),
+        ];
+        if let Some((name, _)) = bps_fields.iter().find(|(_, bps)| *bps > 10_000) {
+            return Err(async_graphql::Error::new(format!(
+                "`{}` must be between 0 and 10000 bps",
+                name
+            )));
+        }
+        if config.vesting_installments == 1 {
+            return Err(async_graphql::Error::new(
+                "`vestingInstallments` must be 0 (default) or at least 2",
+            ));
+        }
+        config
+            .card_constraints
+            .validate()
+            .map_err(async_graphql::Error::new)?;
+        self.runtime
+            .schedule_operation(&Operation::UpdateConfig { config });
+        Ok(true)
+    }
+
+    /// Propose paying `amount` (LINERA, e.g. "2.5") out of the epoch bonus pool to the
+    /// operator once the timelock has passed
+    async fn withdraw_treasury(
+        &self,
+        amount: String,
+        reason: String,
+    ) -> async_graphql::Result<bool> {
+        let reason = Self::require_reason(reason)?;
+        let amount = Amount::from_attos(MutationRoot::linera_input("amount", Some(amount), None)?);
+        if amount == Amount::ZERO {
+            return Err(async_graphql::Error::new("`amount` must be greater than 0"));
+        }
+        let bonus_pool = self.state.epoch.get().bonus_pool;
+        if amount > bonus_pool {
+            return Err(async_graphql::Error::new(format!(
+                "The bonus pool holds only {} atto",
+                u128::from(bonus_pool)
+            )));
+        }
+        self.runtime
+            .schedule_operation(&Operation::WithdrawTreasury { amount, reason });
+        Ok(true)
+    }
+
+    /// Rule on an open dispute; `award` (LINERA) is only allowed when upholding it
+    async fn resolve_dispute(
+        &self,
+        dispute_id: u64,
+        outcome: DisputeOutcome,
+        award: Option<String>,
+        note: String,
+    ) -> async_graphql::Result<bool> {
+        let note = Self::require_reason(note)?;
+        let dispute = self.state.disputes.get(&dispute_id).await.ok().flatten();
+        match dispute {
+            None => {
+                return Err(async_graphql::Error::new(format!(
+                    "Dispute {} not found",
+                    dispute_id
+                )))
+            }
+            Some(dispute) if dispute.resolution.is_some() => {
+                return Err(async_graphql::Error::new(format!(
+                    "Dispute {} is already resolved",
+                    dispute_id
+                )))
+            }
+            Some(_) => {}
+        }
+        let award_atto = match award {
+            Some(award) => MutationRoot::linera_input("award", Some(award), None)?,
+            None => 0,
+        };
+        if award_atto > 0 && outcome != DisputeOutcome::Upheld {
+            return Err(async_graphql::Error::new(
+                "An award is only allowed when upholding a dispute",
+            ));
+        }
+        self.runtime.schedule_operation(&Operation::ResolveDispute {
+            dispute_id,
+            outcome,
+            award_atto,
+            note,
+        });
+        Ok(true)
+    }
+
+    /// Delete all but the newest `keep_last` entries of each history, keeping totals
+    /// in `historySummary`
+    async fn prune_history(&self, keep_last: u32) -> async_graphql::Result<bool> {
+        if keep_last == 0 {
+            return Err(async_graphql::Error::new("`keepLast` must be at least 1"));
+        }
+        self.runtime
+            .schedule_operation(&Operation::PruneHistory { keep_last });
+        Ok(true)
+    }
+
+    /// Credit promotional bonuses out of the epoch bonus pool, all or none
+    async fn airdrop_bonus(&self, credits: Vec<AirdropCredit>) -> async_graphql::Result<bool> {
+        if credits.is_empty() || credits.len() > MAX_AIRDROP_RECIPIENTS {
+            return Err(async_graphql::Error::new(format!(
+                "An airdrop credits between 1 and {} recipients",
+                MAX_AIRDROP_RECIPIENTS
+            )));
+        }
+        let mut total = Amount::ZERO;
+        for (index, credit) in credits.iter().enumerate() {
+            if credit.amount == Amount::ZERO {
+                return Err(async_graphql::Error::new(format!(
+                    "Airdrop credit for {} must be greater than 0",
+                    credit.owner
+                )));
+            }
+            if credits[..index].iter().any(|other| other.owner == credit.owner) {
+                return Err(async_graphql::Error::new(format!(
+                    "{} is listed more than once",
+                    credit.owner
+                )));
+            }
+            total = total.saturating_add(credit.amount);
+        }
+        let bonus_pool = self.state.epoch.get().bonus_pool;
+        if total > bonus_pool {
+            return Err(async_graphql::Error::new(format!(
+                "Airdrop needs {} atto, the bonus pool holds {} atto",
+                u128::from(total),
+                u128::from(bonus_pool)
+            )));
+        }
+        let recipients = credits
+            .into_iter()
+            .map(|credit| (credit.owner, u128::from(credit.amount)))
+            .collect();
+        self.runtime
+            .schedule_operation(&Operation::AirdropBonus { recipients });
+        Ok(true)
+    }
+
+    /// Block `owner`'s withdrawals for a compliance review; the hold lapses after
+    /// `expiresInSecs` (0 = until released)
+    async fn place_withdrawal_hold(
+        &self,
+        owner: AccountOwner,
+        reason: HoldReason,
+        expires_in_secs: u64,
+        note: String,
+    ) -> async_graphql::Result<bool> {
+        let note = Self::require_reason(note)?;
+        self.runtime.schedule_operation(&Operation::PlaceWithdrawalHold {
+            owner,
+            reason,
+            expires_in_secs,
+            note,
+        });
+        Ok(true)
+    }
+
+    /// Lift the compliance hold on `owner`'s withdrawals
+    async fn release_withdrawal_hold(
+        &self,
+        owner: AccountOwner,
+        note: String,
+    ) -> async_graphql::Result<bool> {
+        let note = Self::require_reason(note)?;
+        let now = self.runtime.system_time().micros();
+        let hold = self.state.withdrawal_holds.get(&owner).await.ok().flatten();
+        if !hold.is_some_and(|hold| hold.is_active(now)) {
+            return Err(async_graphql::Error::new(format!(
+                "Withdrawals of {} are not on hold",
+                owner
+            )));
+        }
+        self.runtime
+            .schedule_operation(&Operation::ReleaseWithdrawalHold { owner, note });
+        Ok(true)
+    }
+
+    /// Apply a pending governed change once its timelock has passed
+    async fn execute_proposal(&self, proposal_id: u64) -> async_graphql::Result<bool> {
+        let proposal = self.pending_proposal(proposal_id).await?;
+        let now = self.runtime.system_time().micros();
+        if now < proposal.executable_at_micros {
+            return Err(async_graphql::Error::new(format!(
+                "Proposal {} is timelocked until {} micros",
+                proposal_id, proposal.executable_at_micros
+            )));
+        }
+        self.runtime
+            .schedule_operation(&Operation::ExecuteProposal { proposal_id });
+        Ok(true)
+    }
+
+    /// Drop a pending proposal without applying it
+    async fn cancel_proposal(
+        &self,
+        proposal_id: u64,
+        reason: String,
+    ) -> async_graphql::Result<bool> {
+        let reason = Self::require_reason(reason)?;
+        self.pending_proposal(proposal_id).await?;
+        self.runtime.schedule_operation(&Operation::CancelProposal {
+            proposal_id,
+            reason,
+        });
+        Ok(true)
+    }
+}
+
+impl AdminMutation {
+    /// Helper: Admin actions are logged publicly, so each one needs a reason
+    fn require_reason(reason: String) -> async_graphql::Result<String> {
+        let reason = reason.trim().to_string();
+        if reason.is_empty() {
+            return Err(async_graphql::Error::new("A reason is required"));
+        }
+        Ok(reason)
+    }
+
+    /// Helper: Load a proposal that is still pending, or explain why not
+    async fn pending_proposal(
+        &self,
+        proposal_id: u64,
+    ) -> async_graphql::Result<GovernanceProposal> {
+        match self.state.proposals.get(&proposal_id).await.ok().flatten() {
+            Some(proposal) if proposal.status == ProposalStatus::Pending => Ok(proposal),
+            Some(proposal) => Err(async_graphql::Error::new(format!(
+                "Proposal {} is already {:?}",
+                proposal_id, proposal.status
+            ))),
+            None => Err(async_graphql::Error::new(format!(
+                "Proposal {} not found",
+                proposal_id
+            ))),
+        }
+    }
+}
+    
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_graphql::{Request, Response, Value};
+    use futures::FutureExt as _;
+    use linera_sdk::{
+        linera_base_types::{
+            AccountOwner, AccountSecretKey, Amount, ApplicationId, ChainId, CryptoHash, Timestamp,
+        },
+        util::BlockingWait,
+        views::View,
+        Service, ServiceRuntime,
+    };
+    use serde_json::json;
+
+    use blitz_bingo::{
+        archive_bet_bucket, node_id, probability, AdminQueryGrant, BingoCard, BingoType, Currency,
+        DeltaField, DiagnosticEvent, DiagnosticKind, GameConfig, GameMode, GamePhase, GameSession,
+        Operation, PayoutTier, PlayerAccount, RollRecord, RuntimeContext, SessionCadence,
+        WinRecord, ARCHIVE_DAY_MICROS, CADENCE_MIN_SAMPLES, MAX_ADMIN_GRANT_TTL_SECS, MIN_BET,
+    };
+
+    use super::{FlashportService, FlashportState};
+
+    /// `readState` extension of a response answered from untouched state
+    fn fresh_read_state() -> Value {
+        Value::from_json(json!({
+            "stateVersion": 0,
+            "blockHeight": 0,
+            "operationPending": false
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_query_stats() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request = Request::new("{ totalGames totalWins }");
+
+        let response = service
+            .handle_query(request)
+            .now_or_never()
+            .expect("Query should not await");
+
+        let expected = Response::new(
+            Value::from_json(json!({
+                "totalGames": 0,
+                "totalWins": 0
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_read_state_extension() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        state.state_version.set(7);
+        state.state_height.set(42);
+        state.paused_batches.set(1);
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let response = service
+            .handle_query(Request::new("{ totalGames }"))
+            .now_or_never()
+            .expect("Query should not await");
+
+        let expected = Value::from_json(json!({
+            "stateVersion": 7,
+            "blockHeight": 42,
+            "operationPending": true
+        }))
+        .unwrap();
+        assert_eq!(response.extensions.get("readState"), Some(&expected));
+    }
+
+    #[test]
+    fn test_query_runtime_context() {
+        let chain_id = ChainId(CryptoHash::from([3u64; 4]));
+        let application_id = ApplicationId::new(CryptoHash::from([4u64; 4]));
+        let runtime = Arc::new(
+            ServiceRuntime::<FlashportService>::new()
+                .with_chain_id(chain_id)
+                .with_application_id(application_id.with_abi()),
+        );
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        state.state_height.set(42);
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let response = service
+            .handle_query(Request::new("{ runtimeContext { chainId blockHeight } }"))
+            .now_or_never()
+            .expect("Query should not await");
+
+        let expected = Value::from_json(json!({
+            "runtimeContext": {
+                "chainId": chain_id.to_string(),
+                "blockHeight": 42
+            }
+        }))
+        .unwrap();
+        assert_eq!(response.data, expected);
+    }
+
+    #[test]
+    fn test_query_state_delta() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        state.state_version.set(5);
+        let account = PlayerAccount {
+            player_balance: Amount::from_tokens(3),
+            game_phase: GamePhase::Claimed,
+            ..PlayerAccount::default()
+        };
+        let admin = AccountOwner::Address20([1; 20]);
+        state.admin.set(Some(admin));
+        state.players.insert(&admin, account).unwrap();
+        for (field, version) in [
+            (DeltaField::Balance, 5),
+            (DeltaField::GamePhase, 2),
+            (DeltaField::CurrentCard, 4),
+        ] {
+            state.delta_versions.insert(&field, version).unwrap();
+        }
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        // The card changed after version 3 by being cleared; the phase change is older
+        let request = Request::new(
+            "{ stateDelta(sinceVersion: 3) { stateVersion changed availableAtto gamePhase \
+             currentCard { id } } }",
+        );
+        // Listing the stamped fields reads the map's entries from storage
+        let response = service.handle_query(request).blocking_wait();
+
+        let expected = Value::from_json(json!({
+            "stateDelta": {
+                "stateVersion": 5,
+                "changed": ["BALANCE", "CURRENT_CARD"],
+                "availableAtto": "3000000000000000000",
+                "gamePhase": null,
+                "currentCard": null
+            }
+        }))
+        .unwrap();
+        assert_eq!(response.data, expected);
+    }
+
+    #[test]
+    fn test_query_service_limits() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request =
+            Request::new("{ serviceLimits { maxDepth maxComplexity introspectionEnabled } }");
+
+        let response = service
+            .handle_query(request)
+            .now_or_never()
+            .expect("Query should not await");
+
+        let expected = Response::new(
+            Value::from_json(json!({
+                "serviceLimits": {
+                    "maxDepth": 16,
+                    "maxComplexity": 2000,
+                    "introspectionEnabled": true
+                }
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_fees() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request = Request::new("{ entryFeeLinera rollCostLinera }");
+
+        let response = service
+            .handle_query(request)
+            .now_or_never()
+            .expect("Query should not await");
+
+        // Entry fee should be 5.0 LINERA, roll cost 0.05 LINERA
+        let expected = Response::new(
+            Value::from_json(json!({
+                "entryFeeLinera": 5.0,
+                "rollCostLinera": 0.05
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_fee_schedule() {
+        let runtime = Arc::new(
+            ServiceRuntime::<FlashportService>::new()
+                .with_system_time(Timestamp::from(1_000_000_000)),
+        );
+        let state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request = Request::new(
+            "{ feeSchedule { highRollerMaxBet happyHourDiscountBps \
+             charges { key operation bps } } }",
+        );
+
+        let response = service
+            .handle_query(request)
+            .now_or_never()
+            .expect("Query should not await");
+
+        // No experiment or high-roller table, and the default session end policy
+        let expected = Response::new(
+            Value::from_json(json!({
+                "feeSchedule": {
+                    "highRollerMaxBet": null,
+                    "happyHourDiscountBps": 0,
+                    "charges": [
+                        { "key": "roll_fee", "operation": "RollAndMatch", "bps": null },
+                        { "key": "insurance_roll_fee", "operation": "RollAndMatch", "bps": null },
+                        { "key": "mark_purchase_fee", "operation": "BuyMark", "bps": 2500 },
+                        {
+                            "key": "multiplier_lock_fee",
+                            "operation": "LockMultiplier",
+                            "bps": 1000
+                        },
+                        { "key": "session_end_forfeit", "operation": "EndSession", "bps": 10000 },
+                        { "key": "withdrawal_fee", "operation": "Withdraw", "bps": null },
+                        { "key": "duel_rake", "operation": "DuelRoll", "bps": 500 }
+                    ]
+                }
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_sum_probabilities() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
         let state = FlashportState::load(runtime.root_view_storage_context())
             .blocking_wait()
             .expect("Failed to load state");
@@ -417,22 +3202,612 @@ mod tests {
             runtime,
         };
 
-        let request = Request::new("{ entryFeeLinera rollCostLinera }");
+        let request = Request::new("{ sumProbabilities { sum ways outcomes } }");
+
+        let response = service
+            .handle_query(request)
+            .now_or_never()
+            .expect("Query should not await");
+
+        // One entry per sum 4-24, each counted out of the 1296 outcomes of four dice
+        let sums: Vec<_> = (4..=24u8)
+            .map(|sum| json!({ "sum": sum, "ways": probability::sum_ways(sum), "outcomes": 1296 }))
+            .collect();
+        let data = json!({ "sumProbabilities": sums });
+        let expected = Response::new(Value::from_json(data).unwrap())
+            .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_fast_state() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request = Request::new("{ fastState }");
 
         let response = service
             .handle_query(request)
             .now_or_never()
             .expect("Query should not await");
 
-        // Entry fee should be 5.0 LINERA, roll cost 0.1 LINERA
         let expected = Response::new(
             Value::from_json(json!({
-                "entryFeeLinera": 5.0,
-                "rollCostLinera": 0.1
+                "fastState": r#"{"b":"0","p":"0","g":0,"m":0,"r":0,"t":100000,"w":false}"#
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_risk_of_ruin() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        // 1 LINERA bet + 10 rolls costs 1.5 LINERA, more than the bankroll
+        let request = Request::new(
+            "{ riskOfRuin(betLinera: 1.0, rolls: 10, bankrollLinera: 1.0) { gamesAffordable riskOfRuin } }",
+        );
+
+        let response = service
+            .handle_query(request)
+            .now_or_never()
+            .expect("Query should not await");
+
+        let expected = Response::new(
+            Value::from_json(json!({
+                "riskOfRuin": { "gamesAffordable": 0, "riskOfRuin": 1.0 }
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_rolls_to_bingo_distribution() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        // Wins after 3, 7, 7 and 12 rolls
+        let mut counts = vec![0; 13];
+        counts[3] = 1;
+        counts[7] = 2;
+        counts[12] = 1;
+        state.rolls_to_bingo_counts.set(counts);
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request = Request::new(
+            "{ rollsToBingoDistribution { totalWins meanRolls \
+             buckets { minRolls maxRolls wins cumulativeShare } \
+             percentiles { percentile rolls } } }",
+        );
+
+        let response = service
+            .handle_query(request)
+            .now_or_never()
+            .expect("Query should not await");
+
+        let expected = Response::new(
+            Value::from_json(json!({
+                "rollsToBingoDistribution": {
+                    "totalWins": 4,
+                    "meanRolls": 7.25,
+                    "buckets": [
+                        { "minRolls": 0, "maxRolls": 4, "wins": 1, "cumulativeShare": 0.25 },
+                        { "minRolls": 5, "maxRolls": 9, "wins": 2, "cumulativeShare": 0.75 },
+                        { "minRolls": 10, "maxRolls": 14, "wins": 1, "cumulativeShare": 1.0 }
+                    ],
+                    "percentiles": [
+                        { "percentile": 25, "rolls": 3 },
+                        { "percentile": 50, "rolls": 7 },
+                        { "percentile": 75, "rolls": 7 },
+                        { "percentile": 90, "rolls": 12 },
+                        { "percentile": 99, "rolls": 12 }
+                    ]
+                }
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_search_archive() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        // A Classic win, an Insurance win paying less than its bet, and a big Classic
+        // win the next day, indexed as the contract files them
+        let wins = [
+            (GameMode::Classic, MIN_BET, 2 * MIN_BET, 10),
+            (GameMode::Insurance, 2 * MIN_BET, MIN_BET, 20),
+            (GameMode::Classic, 4 * MIN_BET, 8 * MIN_BET, ARCHIVE_DAY_MICROS + 10),
+        ];
+        for (index, (mode, bet, payout, claimed_at_micros)) in wins.into_iter().enumerate() {
+            let win = WinRecord {
+                game_id: index as u64,
+                owner: None,
+                sponsor: None,
+                currency: Currency::Real,
+                mode,
+                bet_amount_atto: bet.to_string(),
+                payout_atto: payout.to_string(),
+                rolls_count: 10,
+                bingo_type: Some(BingoType::Row0),
+                purchased_marks: Vec::new(),
+                mark_fees_atto: "0".to_string(),
+                multiplier_lock: None,
+                tier: "EPIC".to_string(),
+                tier_win_chance_bps: 0,
+                rolls_percentile_bps: None,
+                claimed_at_micros,
+                context: RuntimeContext {
+                    chain_id: ChainId(CryptoHash::from([3u64; 4])),
+                    application_id: ApplicationId::new(CryptoHash::from([4u64; 4])),
+                    block_height: 100,
+                },
+            };
+            let index = index as u32;
+            if win.net_win() {
+                state.archive_net_wins.push(index);
+            }
+            let by_mode = state.archive_by_mode.get_mut_or_default(&mode).blocking_wait();
+            by_mode.unwrap().push(index);
+            let bucket = archive_bet_bucket(bet);
+            let by_bet = state.archive_by_bet_bucket.get_mut_or_default(&bucket).blocking_wait();
+            by_bet.unwrap().push(index);
+            let day = win.archive_day();
+            let by_day = state.archive_by_day.get_mut_or_default(&day).blocking_wait();
+            by_day.unwrap().push(index);
+            state.win_archive.push(win);
+        }
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request = Request::new(format!(
+            "{{ newest: searchArchive(limit: 2) {{ gameId }} \
+             netWins: searchArchive(filter: {{ netWinsOnly: true }}, sort: LARGEST_BET) \
+             {{ gameId }} \
+             narrowed: searchArchive(filter: {{ mode: CLASSIC, maxBetAtto: \"{}\", \
+             fromMicros: 5, toMicros: {} }}) {{ gameId }} }}",
+            2 * MIN_BET,
+            ARCHIVE_DAY_MICROS
+        ));
+
+        // Listing the archived days reads the index's keys from storage
+        let response = service.handle_query(request).blocking_wait();
+
+        let expected = Response::new(
+            Value::from_json(json!({
+                "newest": [{ "gameId": 2 }, { "gameId": 1 }],
+                "netWins": [{ "gameId": 2 }, { "gameId": 0 }],
+                "narrowed": [{ "gameId": 0 }]
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_node() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        let account = PlayerAccount {
+            active_session: Some(GameSession {
+                session_id: 3,
+                ..GameSession::default()
+            }),
+            current_card: Some(BingoCard {
+                id: 9,
+                ..BingoCard::default()
+            }),
+            ..PlayerAccount::default()
+        };
+        let admin = AccountOwner::Address20([1; 20]);
+        state.admin.set(Some(admin));
+        state.players.insert(&admin, account).unwrap();
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let id = node_id("GameSession", 3).0;
+        let card_id = node_id("BingoCard", 9).0;
+        let request = Request::new(format!(
+            r#"{{ node(id: "{id}") {{ nodeId ... on GameSession {{ sessionId }} }}
+               card: node(id: "{card_id}") {{ nodeId ... on BingoCard {{ id gameId }} }} }}"#
+        ));
+
+        let response = service
+            .handle_query(request)
+            .now_or_never()
+            .expect("Query should not await");
+
+        let expected = Response::new(
+            Value::from_json(json!({
+                "node": { "nodeId": id, "sessionId": 3 },
+                "card": { "nodeId": card_id, "id": 9, "gameId": 9 }
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_bulk_roll_export() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        let mut account = PlayerAccount::default();
+        for roll_id in 1..=3 {
+            account.roll_history.push(RollRecord {
+                roll_id,
+                dice: [1, 2, 3, roll_id as u8],
+                sum: 6 + roll_id as u8,
+                matched: roll_id == 2,
+                timestamp_micros: roll_id * 1_000,
+                fee_paid_atto: "5".to_string(),
+                is_lucky: false,
+                extra_dice: Vec::new(),
+                skin_id: None,
+            });
+        }
+        let admin = AccountOwner::Address20([1; 20]);
+        state.admin.set(Some(admin));
+        state.players.insert(&admin, account).unwrap();
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request = Request::new(
+            "{ bulkRollExport(afterId: 1, limit: 1) { rollIds dice sums matched \
+             timestampsMicros feesAtto nextAfterId hasMore } }",
+        );
+
+        let response = service.handle_query(request).blocking_wait();
+
+        let expected = Response::new(
+            Value::from_json(json!({
+                "bulkRollExport": {
+                    "rollIds": [2],
+                    "dice": [1, 2, 3, 2],
+                    "sums": [8],
+                    "matched": [true],
+                    "timestampsMicros": [2000],
+                    "feesAtto": ["5"],
+                    "nextAfterId": 2,
+                    "hasMore": true
+                }
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_stale_game_warning() {
+        let runtime = Arc::new(
+            ServiceRuntime::<FlashportService>::new()
+                .with_system_time(Timestamp::from(100_000_000)),
+        );
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        state.config.set(GameConfig {
+            stale_game_warning_secs: 60,
+            ..GameConfig::default()
+        });
+        let account = PlayerAccount {
+            active_session: Some(GameSession {
+                session_id: 1,
+                expires_at_micros: 400_000_000,
+                ..GameSession::default()
+            }),
+            current_card: Some(BingoCard {
+                id: 7,
+                last_active_micros: 10_000_000,
+                ..BingoCard::default()
+            }),
+            game_phase: GamePhase::Active,
+            ..PlayerAccount::default()
+        };
+        let admin = AccountOwner::Address20([1; 20]);
+        state.admin.set(Some(admin));
+        state.players.insert(&admin, account).unwrap();
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request = Request::new(
+            "{ staleGameWarning { gameId idleSecs forfeitsAtMicros secsUntilForfeit } }",
+        );
+
+        let response = service.handle_query(request).blocking_wait();
+
+        let expected = Response::new(
+            Value::from_json(json!({
+                "staleGameWarning": {
+                    "gameId": 7,
+                    "idleSecs": 90,
+                    "forfeitsAtMicros": 400_000_000,
+                    "secsUntilForfeit": 300
+                }
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_payout_curve() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        state.config.set(GameConfig {
+            payout_curve: vec![
+                PayoutTier {
+                    max_rolls: 20,
+                    numerator: 2,
+                    denominator: 1,
+                    label: "2x".to_string(),
+                },
+                PayoutTier {
+                    max_rolls: u32::MAX,
+                    numerator: 1,
+                    denominator: 2,
+                    label: "0.5x".to_string(),
+                },
+            ],
+            ..GameConfig::default()
+        });
+        let account = PlayerAccount {
+            current_card: Some(BingoCard {
+                bet_amount_atto: MIN_BET.to_string(),
+                rolls_count: 30,
+                ..BingoCard::default()
+            }),
+            ..PlayerAccount::default()
+        };
+        let admin = AccountOwner::Address20([1; 20]);
+        state.admin.set(Some(admin));
+        state.players.insert(&admin, account).unwrap();
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request = Request::new(
+            "{ payoutCurve { maxRolls label } \
+             potentialPayout { multiplier potentialPayoutAtto } }",
+        );
+
+        let response = service.handle_query(request).blocking_wait();
+
+        let expected = Response::new(
+            Value::from_json(json!({
+                "payoutCurve": [
+                    { "maxRolls": 20, "label": "2x" },
+                    { "maxRolls": u32::MAX, "label": "0.5x" }
+                ],
+                "potentialPayout": {
+                    "multiplier": "0.5x",
+                    "potentialPayoutAtto": (MIN_BET / 2).to_string()
+                }
             }))
             .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_query_raffle_status() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+
+        let request = Request::new(
+            "{ raffleStatus { ownerTickets totalTickets drawsCompleted } raffleHistory { drawId } }",
         );
 
+        let response = service.handle_query(request).blocking_wait();
+
+        let expected = Response::new(
+            Value::from_json(json!({
+                "raffleStatus": {
+                    "ownerTickets": 0,
+                    "totalTickets": 0,
+                    "drawsCompleted": 0
+                },
+                "raffleHistory": []
+            }))
+            .unwrap(),
+        )
+        .extension("readState", fresh_read_state());
+
         assert_eq!(response, expected);
     }
+
+    #[test]
+    fn test_admin_mutations_validate_input() {
+        let runtime = Arc::new(ServiceRuntime::<FlashportService>::new());
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        state.epoch.get_mut().bonus_pool = Amount::from_attos(MIN_BET);
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime: runtime.clone(),
+        };
+        let run = |mutation: &str| {
+            service
+                .handle_query(Request::new(format!("mutation {{ admin {{ {} }} }}", mutation)))
+                .blocking_wait()
+        };
+
+        // Refused before anything is scheduled
+        for mutation in [
+            r#"withdrawTreasury(amount: "2", reason: "ops")"#,
+            r#"withdrawTreasury(amount: "1", reason: "  ")"#,
+            r#"setPaused(paused: false, reason: "upgrade")"#,
+            r#"resolveDispute(disputeId: 1, outcome: DISMISSED, note: "checked")"#,
+            "airdropBonus(credits: [])",
+        ] {
+            assert!(!run(mutation).errors.is_empty(), "{} was accepted", mutation);
+        }
+        assert!(runtime.scheduled_operations::<Operation>().is_empty());
+
+        for mutation in [
+            r#"withdrawTreasury(amount: "1", reason: "ops")"#,
+            r#"setPaused(paused: true, reason: "upgrade")"#,
+        ] {
+            let response = run(mutation);
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+        }
+        let scheduled = runtime.scheduled_operations::<Operation>();
+        assert_eq!(scheduled.len(), 2);
+        assert!(matches!(
+            &scheduled[0],
+            Operation::WithdrawTreasury { amount, reason }
+                if *amount == Amount::from_attos(MIN_BET) && reason == "ops"
+        ));
+        assert!(matches!(&scheduled[1], Operation::SetPaused { paused: true, .. }));
+    }
+
+    #[test]
+    fn test_admin_queries_require_admin_grant() {
+        let chain_id = ChainId(CryptoHash::from([3u64; 4]));
+        let application_id = ApplicationId::new(CryptoHash::from([4u64; 4]));
+        let runtime = Arc::new(
+            ServiceRuntime::<FlashportService>::new()
+                .with_chain_id(chain_id)
+                .with_application_id(application_id.with_abi())
+                .with_system_time(Timestamp::from(1_000_000_000)),
+        );
+        let mut state = FlashportState::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        let admin = AccountSecretKey::generate();
+        state.admin.set(Some(AccountOwner::from(admin.public())));
+        state.config.get_mut().cadence_min_interval_ms = 500;
+        let cadence = SessionCadence {
+            session_id: 7,
+            requests: CADENCE_MIN_SAMPLES + 1,
+            intervals: CADENCE_MIN_SAMPLES,
+            interval_sum_micros: CADENCE_MIN_SAMPLES * 100_000,
+            ..SessionCadence::default()
+        };
+        state
+            .session_cadence
+            .insert(&AccountOwner::Address20([7; 20]), cadence)
+            .unwrap();
+        state.diagnostics.push_back(DiagnosticEvent {
+            event_id: 1,
+            kind: DiagnosticKind::FeeCharged,
+            game_id: Some(1),
+            block_height: 0,
+            at_micros: 0,
+            detail: "fee=roll".to_string(),
+        });
+
+        let service = FlashportService {
+            state: Arc::new(state),
+            runtime,
+        };
+        let query = |secret: &AccountSecretKey, expires_at_micros: u64| {
+            let grant = AdminQueryGrant {
+                chain_id,
+                application_id,
+                expires_at_micros,
+            };
+            let signature = AdminQueryGrant::encode_signature(&secret.sign(&grant));
+            let request = format!(
+                r#"{{ admin(expiresAtMicros: {}, signature: "{}") {{ {} }} }}"#,
+                expires_at_micros,
+                signature,
+                "suspiciousSessions { sessionId tooFast } diagnostics { eventId kind }"
+            );
+            service.handle_query(Request::new(request)).blocking_wait()
+        };
+
+        // Other signers, expired grants and grants valid for too long are refused
+        let expires_at_micros = 1_000_000_000 + 60_000_000;
+        let stranger = AccountSecretKey::generate();
+        assert!(!query(&stranger, expires_at_micros).errors.is_empty());
+        assert!(!query(&admin, 1_000_000_000).errors.is_empty());
+        let too_late = 1_000_000_000 + (MAX_ADMIN_GRANT_TTL_SECS + 1) * 1_000_000;
+        assert!(!query(&admin, too_late).errors.is_empty());
+
+        let response = query(&admin, expires_at_micros);
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let expected = json!({
+            "admin": {
+                "suspiciousSessions": [{ "sessionId": 7, "tooFast": true }],
+                "diagnostics": [{ "eventId": 1, "kind": "FEE_CHARGED" }]
+            }
+        });
+        assert_eq!(response.data, Value::from_json(expected).unwrap());
+
+        // Diagnostics are not part of the public schema
+        let request = Request::new("{ diagnostics { eventId } }");
+        let response = service.handle_query(request).blocking_wait();
+        assert!(!response.errors.is_empty());
+    }
 }
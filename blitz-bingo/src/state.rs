@@ -1,49 +1,254 @@
 // FlashPort Phase 1+2: Application State
 // Uses linera-views for persistent storage with token tracking
 
-use linera_sdk::linera_base_types::Amount;
-use linera_sdk::views::{linera_views, MapView, QueueView, RegisterView, RootView, ViewStorageContext};
+use linera_sdk::linera_base_types::{AccountOwner, Amount, ApplicationId, ChainId, CryptoHash};
+use linera_sdk::views::{
+    linera_views, LogView, MapView, QueueView, RegisterView, RootView, ViewStorageContext,
+};
 
-use blitz_bingo::{BingoCard, GameSession, PlayerBalance, RollRecord};
+use blitz_bingo::{
+    AdminAction, AirdropBatch, BingoType, BucketStats, ConsumedNonce, DeltaField, DiagnosticEvent,
+    Dispute, Duel, EntropyRecord, EpochPool, Experiment, ExperimentBucket, GameAttestation,
+    GameConfig, GameMode, GameTemplate, GovernanceProposal, HappyHour, HealthReport,
+    HighRollerTable, HistorySummary, LeaderboardEntry, OperationMetrics, OutboxEntry,
+    PartnerNotice, PayoutRoute, PlayerAccount, PruneReport, RaffleDraw, Receipt,
+    RegisteredInstance, RollingRtp, ScheduledWithdrawal, SeasonMode, SessionCadence, SettledGame,
+    StatsSnapshot, Syndicate, Tournament, VestingSchedule, WinRecord, WithdrawalHold,
+};
 
 /// The complete FlashPort application state
 #[derive(RootView, async_graphql::SimpleObject)]
 #[view(context = ViewStorageContext)]
 pub struct FlashportState {
+    // === Administration ===
+    /// Owner allowed to run admin operations (the instantiating signer)
+    pub admin: RegisterView<Option<AccountOwner>>,
+    /// New games and rolls are stopped until the admin resumes the deployment
+    pub paused: RegisterView<bool>,
+    /// Admin-tunable game configuration
+    pub config: RegisterView<GameConfig>,
+    /// Running A/B configuration experiment, if any
+    pub experiment: RegisterView<Option<Experiment>>,
+    /// Sticky experiment bucket per owner
+    pub experiment_buckets: MapView<AccountOwner, ExperimentBucket>,
+    /// Aggregated outcomes per experiment bucket
+    pub experiment_stats: MapView<ExperimentBucket, BucketStats>,
+    /// VIP table for bets above MAX_BET, if open
+    pub high_roller_table: RegisterView<Option<HighRollerTable>>,
+    /// Aggregated outcomes of high-roller games, kept apart from the main bankroll
+    pub high_roller_stats: RegisterView<BucketStats>,
+    /// Aggregated outcomes of Insurance-mode games, so their RTP can be compared
+    pub insurance_stats: RegisterView<BucketStats>,
+    /// Partner applications notified of wins at or above the threshold
+    pub reward_partners: RegisterView<Vec<ApplicationId>>,
+    /// Minimum real-money payout that triggers partner notifications
+    pub reward_notify_threshold: RegisterView<Amount>,
+    /// Partner notifications not yet delivered, by their outbox entry id (the outbox
+    /// lists them for clients)
+    #[graphql(skip)]
+    pub partner_notices: MapView<u64, PartnerNotice>,
+    /// Bridge application that converts routed payouts, if configured
+    pub payout_bridge: RegisterView<Option<ApplicationId>>,
+    /// Public log of admin interventions, oldest first
+    pub admin_log: LogView<AdminAction>,
+    /// Compliance holds on withdrawals, by owner; lapsed holds are cleared when next
+    /// checked
+    pub withdrawal_holds: MapView<AccountOwner, WithdrawalHold>,
+    /// Config changes and treasury withdrawals proposed under the governance timelock,
+    /// by id; executed and cancelled ones are kept for the record
+    pub proposals: MapView<u64, GovernanceProposal>,
+    /// Last issued proposal id
+    pub proposal_counter: RegisterView<u64>,
+    /// Chain keeping the deployment registry, if any
+    pub lobby_chain: RegisterView<Option<ChainId>>,
+    /// Deployments registered with this chain (only filled on the lobby chain)
+    pub instances: MapView<ChainId, RegisteredInstance>,
+    /// Scheduled and running roll-fee happy hours, by start time
+    pub happy_hours: RegisterView<Vec<HappyHour>>,
+    /// Last issued happy hour id
+    pub happy_hour_counter: RegisterView<u64>,
+    /// Registered season modes, by id
+    pub season_modes: MapView<u64, SeasonMode>,
+    /// Last issued season mode id
+    pub season_mode_counter: RegisterView<u64>,
+    /// Published game templates, by id
+    pub game_templates: MapView<u64, GameTemplate>,
+    /// Last issued game template id
+    pub game_template_counter: RegisterView<u64>,
+
+    // === Read Consistency ===
+    /// Operations and messages executed so far; echoed by every query response so
+    /// clients can tell a stale read from a fresh one
+    pub state_version: RegisterView<u64>,
+    /// Block height of the last executed operation or message
+    pub state_height: RegisterView<u64>,
+    /// State version of the last operation that changed each `DeltaField`
+    pub delta_versions: MapView<DeltaField, u64>,
+
+    // === Player Accounts ===
+    /// Balances, game, session and settings of each owner (see `PlayerAccount`)
+    pub players: MapView<AccountOwner, PlayerAccount>,
+    /// Real money every account has spent, together (the sum of `total_spent`)
+    pub total_spent: RegisterView<Amount>,
+    /// Real money every account has won, together (the sum of `total_won`)
+    pub total_won: RegisterView<Amount>,
+    /// Asset each owner wants their real-money winnings delivered in
+    pub payout_routes: MapView<AccountOwner, PayoutRoute>,
+
     // === Session Management ===
-    /// Current active session (None if not started)
-    pub active_session: RegisterView<Option<GameSession>>,
     /// Counter for generating unique session IDs
     pub session_counter: RegisterView<u64>,
+    /// Roll request timing of each owner's latest session
+    pub session_cadence: MapView<AccountOwner, SessionCadence>,
+    /// Nonces of relayed operations per owner, until their envelopes expire
+    pub relay_nonces: MapView<AccountOwner, Vec<ConsumedNonce>>,
 
     // === Dice-Bingo Game State ===
-    /// The user's current active bingo card
-    pub current_card: RegisterView<Option<BingoCard>>,
     /// Counter for generating unique game IDs
     pub game_counter: RegisterView<u64>,
-    /// All numbers drawn in the current game
-    pub drawn_numbers: RegisterView<Vec<u8>>,
-    /// Whether current game has unclaimed prize
-    pub has_unclaimed_prize: RegisterView<bool>,
 
     // === Token Economics ===
-    /// Player's available balance (deposited - spent + won)
-    pub player_balance: RegisterView<Amount>,
-    /// Total deposited by player
-    pub total_deposited: RegisterView<Amount>,
-    /// Total won by player
-    pub total_won: RegisterView<Amount>,
-    /// Total spent on fees by player
-    pub total_spent: RegisterView<Amount>,
-    /// Current prize pool for active bingo game
-    pub current_prize_pool: RegisterView<Amount>,
+    /// Vesting payouts with installments still to come, per winner
+    pub vesting_schedules: MapView<AccountOwner, Vec<VestingSchedule>>,
+    /// Every scheduled withdrawal, keyed by id
+    pub scheduled_withdrawals: MapView<u64, ScheduledWithdrawal>,
+    /// Last issued scheduled withdrawal id
+    pub scheduled_withdrawal_counter: RegisterView<u64>,
+    /// Ledger of every deposit and withdrawal, keyed by receipt id
+    pub receipts: MapView<u64, Receipt>,
+    /// Last issued receipt id
+    pub receipt_counter: RegisterView<u64>,
+
+    // === Prize Pool Epochs ===
+    /// Accounting for the epoch in progress
+    pub epoch: RegisterView<EpochPool>,
+    /// Every closed epoch, oldest first
+    pub epoch_history: LogView<EpochPool>,
+
+    // === Bonus Airdrops ===
+    /// Promotional bonus credited to each owner by AirdropBonus
+    pub bonus_balances: MapView<AccountOwner, Amount>,
+    /// Every airdrop batch, oldest first
+    pub airdrops: LogView<AirdropBatch>,
+
+    // === Raffle ===
+    /// Pool paid out at the next draw
+    pub raffle_pool: RegisterView<Amount>,
+    /// Tickets held per owner in the current round (one per whole LINERA wagered)
+    pub raffle_tickets: MapView<AccountOwner, u64>,
+    /// Tickets issued in the current round
+    pub raffle_total_tickets: RegisterView<u64>,
+    /// When the current round may be drawn (microseconds since epoch)
+    pub raffle_draw_at_micros: RegisterView<u64>,
+    /// Every completed draw, oldest first
+    pub raffle_history: LogView<RaffleDraw>,
+
+    // === Disputes ===
+    /// Every filed dispute, keyed by id
+    pub disputes: MapView<u64, Dispute>,
+    /// Dispute filed against each game, by game id
+    pub disputed_games: MapView<u64, u64>,
+    /// How every game ended, by game id, so any settlement can be disputed
+    pub settled_games: MapView<u64, SettledGame>,
+    /// Last issued dispute id
+    pub dispute_counter: RegisterView<u64>,
+    /// Attestations of claimed games, by game id
+    pub attestations: MapView<u64, GameAttestation>,
+
+    // === Health Checks ===
+    /// Result of the most recent HealthCheck operation
+    pub last_health_check: RegisterView<Option<HealthReport>>,
+
+    // === History Pruning ===
+    /// Aggregates of everything PruneHistory has deleted
+    pub history_summary: RegisterView<HistorySummary>,
+    /// Most recent PruneHistory run, admin or automatic
+    pub last_prune: RegisterView<Option<PruneReport>>,
+
+    // === Tournaments ===
+    /// Every house-card tournament, keyed by id
+    pub tournaments: MapView<u64, Tournament>,
+    /// Last issued tournament id
+    pub tournament_counter: RegisterView<u64>,
+
+    // === Duels ===
+    /// Every two-player duel, keyed by id
+    pub duels: MapView<u64, Duel>,
+    /// Last issued duel id
+    pub duel_counter: RegisterView<u64>,
+
+    // === Syndicates ===
+    /// Open syndicates by id; a syndicate is removed when its last member leaves
+    pub syndicates: MapView<u64, Syndicate>,
+    /// Last issued syndicate id
+    pub syndicate_counter: RegisterView<u64>,
+
+    // === Demo Games ===
+    /// DemoGame operations played, which also keeps two demos in one block apart
+    pub demo_games: RegisterView<u64>,
 
     // === Dice-Bingo Statistics ===
+    /// Rolls that marked each card cell across all games (row-major)
+    pub cell_hits: RegisterView<[u64; 25]>,
     /// Total games played
     pub total_games: RegisterView<u64>,
     /// Total games won (bingo achieved)
     pub total_wins: RegisterView<u64>,
-    /// History of recent roll results (keeps last 50)
-    pub roll_history: QueueView<RollRecord>,
+    /// Claimed wins by rolls-to-bingo (index = rolls taken)
+    pub rolls_to_bingo_counts: RegisterView<Vec<u64>>,
+    /// Every claimed prize, oldest first
+    pub win_archive: LogView<WinRecord>,
+    /// `win_archive` indexes of wins whose payout came to at least the bet
+    pub archive_net_wins: LogView<u32>,
+    /// `win_archive` indexes by the first line each win completed
+    pub archive_by_bingo_type: MapView<BingoType, Vec<u32>>,
+    /// `win_archive` indexes by game mode
+    pub archive_by_mode: MapView<GameMode, Vec<u32>>,
+    /// `win_archive` indexes by bet bucket (see `archive_bet_bucket`)
+    pub archive_by_bet_bucket: MapView<u8, Vec<u32>>,
+    /// `win_archive` indexes by claim day (see `ARCHIVE_DAY_MICROS`)
+    pub archive_by_day: MapView<u64, Vec<u32>>,
+    /// Last issued roll id
+    pub roll_counter: RegisterView<u64>,
+    /// Rolling hash over every roll record, up to the latest (see `chain_roll_results`)
+    pub roll_results_hash: RegisterView<Option<CryptoHash>>,
+    /// Randomness inputs of the latest block that drew any
+    pub entropy_block: RegisterView<Option<EntropyRecord>>,
+    /// Randomness inputs of earlier blocks, oldest first (keeps last ENTROPY_HISTORY_LEN)
+    pub entropy_history: QueueView<EntropyRecord>,
+    /// Decisions recorded while `FlashportParameters::diagnostics` is on, oldest first
+    /// (keeps last DIAGNOSTICS_CAPACITY)
+    pub diagnostics: QueueView<DiagnosticEvent>,
+    /// Last issued diagnostics event id
+    pub diagnostic_counter: RegisterView<u64>,
+    /// Most recent snapshot emitted by PublishStats
+    pub last_published_stats: RegisterView<Option<StatsSnapshot>>,
+
+    // === Read Models ===
+    /// Owners with the most real-money winnings, best first (at most LEADERBOARD_SIZE)
+    pub leaderboard: RegisterView<Vec<LeaderboardEntry>>,
+    /// Lifetime real-money winnings per owner, feeding `leaderboard`
+    pub owner_winnings: MapView<AccountOwner, LeaderboardEntry>,
+    /// Wagers and payouts of the most recent closed epochs
+    pub rolling_rtp: RegisterView<RollingRtp>,
+
+    // === Operation Metrics ===
+    /// Count, failures and fees per operation type, sorted by `Operation::name`; kept
+    /// in one register so recording them never waits on a storage read
+    pub operation_metrics: RegisterView<Vec<OperationMetrics>>,
+
+    // === Operator Outbox ===
+    /// Notifications not yet acknowledged by the operator, oldest first
+    pub outbox: QueueView<OutboxEntry>,
+    /// Last issued outbox entry id
+    pub outbox_counter: RegisterView<u64>,
+    /// Last issued batch handle
+    pub batch_counter: RegisterView<u64>,
+    /// Accounts with an AutoRoll batch paused by the per-block roll cap
+    pub paused_batches: RegisterView<u64>,
+    /// Block that `block_rolls` counts for
+    pub block_rolls_height: RegisterView<u64>,
+    /// AutoRoll rolls executed in that block
+    pub block_rolls: RegisterView<u32>,
 }
 
@@ -0,0 +1,185 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reusable `TestValidator` scenarios for FlashPort integration tests.
+//!
+//! Each builder returns a chain already in a known state, so a test only has to
+//! describe the edge case it exercises:
+//!
+//! ```ignore
+//! mod common;
+//!
+//! let scenario = common::Scenario::bingo_pending(None).await;
+//! scenario.execute(Operation::ClaimPrize).await;
+//! ```
+
+#![cfg(not(target_arch = "wasm32"))]
+// Every test binary compiles this module but uses only some of the fixtures
+#![allow(dead_code)]
+
+use blitz_bingo::{
+    Currency, FlashportAbi, FlashportParameters, GameConfig, GameMode, InstantiationArgument,
+    Operation, MIN_BET, MIN_GOVERNANCE_TIMELOCK_SECS,
+};
+use linera_sdk::{
+    linera_base_types::{ApplicationId, TimeDelta},
+    test::{ActiveChain, QueryOutcome, TestValidator, WorkerError},
+};
+
+/// Real balance deposited by `Scenario::funded_player` (100 LINERA)
+pub const FUNDED_DEPOSIT: u128 = 100 * MIN_BET;
+
+/// Rolls `Scenario::bingo_pending` allows before giving up on a bingo
+pub const MAX_ROLLS_TO_BINGO: usize = 500;
+
+/// A chain running one FlashPort instance, with helpers that keep block timestamps
+/// in step with the validator clock
+pub struct Scenario {
+    pub validator: TestValidator,
+    pub chain: ActiveChain,
+    pub application_id: ApplicationId<FlashportAbi>,
+}
+
+impl Scenario {
+    /// A fresh instance. A `config` replaces the default one: the chain owner proposes
+    /// it as the instance admin and executes it once the timelock has passed.
+    pub async fn new(config: Option<GameConfig>) -> Self {
+        let (validator, module_id) = TestValidator::with_current_module::<
+            FlashportAbi,
+            FlashportParameters,
+            InstantiationArgument,
+        >()
+        .await;
+        let mut chain = validator.new_chain().await;
+        let application_id = chain
+            .create_application(
+                module_id,
+                FlashportParameters::default(),
+                InstantiationArgument::default(),
+                vec![],
+            )
+            .await;
+        let scenario = Scenario {
+            validator,
+            chain,
+            application_id,
+        };
+        if let Some(config) = config {
+            scenario.execute(Operation::UpdateConfig { config }).await;
+            scenario.advance_time(u64::from(MIN_GOVERNANCE_TIMELOCK_SECS));
+            scenario
+                .execute(Operation::ExecuteProposal { proposal_id: 1 })
+                .await;
+        }
+        scenario
+    }
+
+    /// A player with `FUNDED_DEPOSIT` of real balance and an active session
+    pub async fn funded_player(config: Option<GameConfig>) -> Self {
+        let scenario = Self::new(config).await;
+        scenario
+            .execute(Operation::Deposit {
+                amount_atto: FUNDED_DEPOSIT,
+                credit_to: None,
+            })
+            .await;
+        scenario
+            .execute(Operation::StartSession {
+                expires_in_secs: 3600,
+                permissions: None,
+            })
+            .await;
+        scenario
+    }
+
+    /// A funded player with a Classic `MIN_BET` game dealt and one roll made
+    pub async fn mid_game(config: Option<GameConfig>) -> Self {
+        let scenario = Self::funded_player(config).await;
+        scenario
+            .execute(Operation::NewGame {
+                bet_amount_atto: MIN_BET,
+                currency: Currency::Real,
+                mode: GameMode::Classic,
+                hedge: false,
+                template_id: None,
+            })
+            .await;
+        scenario.execute(Operation::RollAndMatch).await;
+        scenario
+    }
+
+    /// A mid-game player who has rolled until the prize became claimable
+    pub async fn bingo_pending(config: Option<GameConfig>) -> Self {
+        let scenario = Self::mid_game(config).await;
+        for _ in 0..MAX_ROLLS_TO_BINGO {
+            match scenario.game_phase().await.as_str() {
+                "ACTIVE" => scenario.execute(Operation::RollAndMatch).await,
+                "BINGO_PENDING" => return scenario,
+                phase => panic!("Game left play in phase {} before a bingo", phase),
+            }
+        }
+        panic!("No bingo within {} rolls", MAX_ROLLS_TO_BINGO);
+    }
+
+    /// Move the validator clock forward; later blocks are timestamped from it
+    pub fn advance_time(&self, secs: u64) {
+        self.validator.clock().add(TimeDelta::from_secs(secs));
+    }
+
+    /// Run `operation` in its own block, panicking if the block is rejected
+    pub async fn execute(&self, operation: Operation) {
+        self.try_execute(vec![operation])
+            .await
+            .expect("Failed to execute block");
+    }
+
+    /// Run `operations` together in one block. A reverted operation rejects the whole
+    /// block, so none of them leaves a trace.
+    pub async fn try_execute(&self, operations: Vec<Operation>) -> Result<(), WorkerError> {
+        let now = self.validator.clock().current_time();
+        self.chain
+            .try_add_block(|block| {
+                block.with_timestamp(now);
+                for operation in operations {
+                    block.with_operation(self.application_id, operation);
+                }
+            })
+            .await
+            .map(|_| ())
+    }
+
+    /// Run a GraphQL query against the instance's service
+    pub async fn query(&self, query: &str) -> serde_json::Value {
+        let QueryOutcome { response, .. } =
+            self.chain.graphql_query(self.application_id, query).await;
+        response
+    }
+
+    /// The game phase as GraphQL names it, e.g. "BINGO_PENDING"
+    pub async fn game_phase(&self) -> String {
+        let response = self.query("query { gamePhase }").await;
+        response["gamePhase"].as_str().unwrap().to_string()
+    }
+
+    /// Available real balance in atto
+    pub async fn available_atto(&self) -> u128 {
+        let response = self.query("query { playerBalance { availableAtto } }").await;
+        response["playerBalance"]["availableAtto"]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    /// Current prize pool in atto
+    pub async fn prize_pool_atto(&self) -> u128 {
+        let response = self.query("query { currentPrizePool }").await;
+        response["currentPrizePool"].as_str().unwrap().parse().unwrap()
+    }
+
+    /// Wins counted so far
+    pub async fn total_wins(&self) -> u64 {
+        let response = self.query("query { totalWins }").await;
+        response["totalWins"].as_u64().unwrap()
+    }
+}
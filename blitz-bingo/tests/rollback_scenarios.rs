@@ -0,0 +1,112 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Edge cases around claiming and withdrawing, run on a `TestValidator` chain from
+//! the shared scenarios in `common`.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use blitz_bingo::{GameConfig, Operation, MIN_BET};
+use common::{Scenario, FUNDED_DEPOSIT};
+use linera_sdk::linera_base_types::Amount;
+
+/// A claim after the deadline expires the game and pays nothing
+#[tokio::test(flavor = "multi_thread")]
+async fn claim_after_expiry() {
+    let scenario = Scenario::bingo_pending(Some(GameConfig {
+        claim_window_secs: 60,
+        ..GameConfig::default()
+    }))
+    .await;
+    let balance = scenario.available_atto().await;
+
+    scenario.advance_time(61);
+    scenario.execute(Operation::ClaimPrize).await;
+    assert_eq!(scenario.game_phase().await, "EXPIRED");
+    assert_eq!(scenario.available_atto().await, balance);
+
+    // The expiry is final: a second attempt is refused the same way
+    scenario.execute(Operation::ClaimPrize).await;
+    assert_eq!(scenario.game_phase().await, "EXPIRED");
+    assert_eq!(scenario.available_atto().await, balance);
+}
+
+/// A second claim of the same bingo pays nothing more
+#[tokio::test(flavor = "multi_thread")]
+async fn double_claim() {
+    let scenario = Scenario::bingo_pending(None).await;
+    let balance = scenario.available_atto().await;
+
+    scenario.execute(Operation::ClaimPrize).await;
+    assert_eq!(scenario.game_phase().await, "CLAIMED");
+    let paid = scenario.available_atto().await;
+    assert!(paid > balance);
+
+    scenario.execute(Operation::ClaimPrize).await;
+    assert_eq!(scenario.game_phase().await, "CLAIMED");
+    assert_eq!(scenario.available_atto().await, paid);
+    assert_eq!(scenario.total_wins().await, 1);
+}
+
+/// Withdrawing mid-game never touches the escrowed bet
+#[tokio::test(flavor = "multi_thread")]
+async fn withdraw_during_game() {
+    let scenario = Scenario::mid_game(None).await;
+    let available = scenario.available_atto().await;
+    assert!(available < FUNDED_DEPOSIT - MIN_BET);
+    assert_eq!(scenario.prize_pool_atto().await, MIN_BET);
+
+    // More than the available balance is refused outright
+    scenario
+        .execute(Operation::Withdraw {
+            amount: Amount::from_attos(available + 1),
+        })
+        .await;
+    assert_eq!(scenario.available_atto().await, available);
+
+    // WithdrawAll keeps one roll fee back for the open game
+    scenario.execute(Operation::WithdrawAll).await;
+    let reserve = scenario.available_atto().await;
+    assert!(reserve > 0 && reserve < MIN_BET);
+    assert_eq!(scenario.prize_pool_atto().await, MIN_BET);
+
+    // Withdrawing the reserve too leaves the game open but unable to pay for rolls
+    scenario
+        .execute(Operation::Withdraw {
+            amount: Amount::from_attos(reserve),
+        })
+        .await;
+    assert_eq!(scenario.available_atto().await, 0);
+    scenario.execute(Operation::RollAndMatch).await;
+    assert_eq!(scenario.game_phase().await, "ACTIVE");
+    assert_eq!(scenario.prize_pool_atto().await, MIN_BET);
+}
+
+/// A reverted operation rejects its whole block, undoing the operations before it
+#[tokio::test(flavor = "multi_thread")]
+async fn reverted_operation_rolls_back_its_block() {
+    let scenario = Scenario::bingo_pending(None).await;
+    let balance = scenario.available_atto().await;
+
+    let result = scenario
+        .try_execute(vec![
+            Operation::ClaimPrize,
+            Operation::Withdraw {
+                amount: Amount::from_attos(MIN_BET),
+            },
+            Operation::Sequenced {
+                requires_session_id: u64::MAX,
+                operation: Box::new(Operation::RollAndMatch),
+            },
+        ])
+        .await;
+    assert!(result.is_err());
+    assert_eq!(scenario.game_phase().await, "BINGO_PENDING");
+    assert_eq!(scenario.available_atto().await, balance);
+
+    // The chain is unaffected: the same claim goes through in the next block
+    scenario.execute(Operation::ClaimPrize).await;
+    assert_eq!(scenario.game_phase().await, "CLAIMED");
+}
@@ -5,25 +5,43 @@
 
 #![cfg(not(target_arch = "wasm32"))]
 
-use flashport::Operation;
+use blitz_bingo::{
+    Currency, FlashportAbi, FlashportParameters, GameMode, InstantiationArgument, Operation,
+    MIN_BET,
+};
 use linera_sdk::test::{QueryOutcome, TestValidator};
 
 /// Tests the complete game flow: deposit -> session -> new game -> roll
 #[tokio::test(flavor = "multi_thread")]
 async fn single_chain_game_flow() {
-    let (validator, module_id) =
-        TestValidator::with_current_module::<flashport::FlashportAbi, (), ()>().await;
+    let (validator, module_id) = TestValidator::with_current_module::<
+        FlashportAbi,
+        FlashportParameters,
+        InstantiationArgument,
+    >()
+    .await;
     let mut chain = validator.new_chain().await;
 
-    // Create the application with no initialization argument
+    // Create the application with default instantiation arguments
     let application_id = chain
-        .create_application(module_id, (), (), vec![])
+        .create_application(
+            module_id,
+            FlashportParameters::default(),
+            InstantiationArgument::default(),
+            vec![],
+        )
         .await;
 
     // Deposit funds first (required for new game)
     chain
         .add_block(|block| {
-            block.with_operation(application_id, Operation::Deposit);
+            block.with_operation(
+                application_id,
+                Operation::Deposit {
+                    amount_atto: 10_000_000_000_000_000_000,
+                    credit_to: None,
+                },
+            );
         })
         .await;
 
@@ -34,6 +52,7 @@ async fn single_chain_game_flow() {
                 application_id,
                 Operation::StartSession {
                     expires_in_secs: 3600,
+                    permissions: None,
                 },
             );
         })
@@ -48,7 +67,16 @@ async fn single_chain_game_flow() {
     // Start a new game (costs 5 LINERA)
     chain
         .add_block(|block| {
-            block.with_operation(application_id, Operation::NewGame);
+            block.with_operation(
+                application_id,
+                Operation::NewGame {
+                    bet_amount_atto: MIN_BET,
+                    currency: Currency::Real,
+                    mode: GameMode::Classic,
+                    hedge: false,
+                    template_id: None,
+                },
+            );
         })
         .await;
 
@@ -58,7 +86,7 @@ async fn single_chain_game_flow() {
         .await;
     assert_eq!(response["totalGames"].as_u64(), Some(1));
 
-    // Roll the dice (costs 0.1 LINERA)
+    // Roll the dice (costs 0.05 LINERA)
     chain
         .add_block(|block| {
             block.with_operation(application_id, Operation::RollAndMatch);
@@ -75,12 +103,21 @@ async fn single_chain_game_flow() {
 /// Tests that game operations fail without deposits
 #[tokio::test(flavor = "multi_thread")]
 async fn operations_require_balance() {
-    let (validator, module_id) =
-        TestValidator::with_current_module::<flashport::FlashportAbi, (), ()>().await;
+    let (validator, module_id) = TestValidator::with_current_module::<
+        FlashportAbi,
+        FlashportParameters,
+        InstantiationArgument,
+    >()
+    .await;
     let mut chain = validator.new_chain().await;
 
     let application_id = chain
-        .create_application(module_id, (), (), vec![])
+        .create_application(
+            module_id,
+            FlashportParameters::default(),
+            InstantiationArgument::default(),
+            vec![],
+        )
         .await;
 
     // Check initial state - no session, no games, no balance
@@ -94,12 +131,21 @@ async fn operations_require_balance() {
 /// Tests fee structure queries
 #[tokio::test(flavor = "multi_thread")]
 async fn fee_structure() {
-    let (validator, module_id) =
-        TestValidator::with_current_module::<flashport::FlashportAbi, (), ()>().await;
+    let (validator, module_id) = TestValidator::with_current_module::<
+        FlashportAbi,
+        FlashportParameters,
+        InstantiationArgument,
+    >()
+    .await;
     let mut chain = validator.new_chain().await;
 
     let application_id = chain
-        .create_application(module_id, (), (), vec![])
+        .create_application(
+            module_id,
+            FlashportParameters::default(),
+            InstantiationArgument::default(),
+            vec![],
+        )
         .await;
 
     // Query fee structure
@@ -111,26 +157,41 @@ async fn fee_structure() {
     let entry_fee = response["entryFeeLinera"].as_f64().unwrap();
     assert!((entry_fee - 5.0).abs() < 0.01);
     
-    // Roll cost should be 0.1 LINERA
+    // Roll cost should be 0.05 LINERA
     let roll_cost = response["rollCostLinera"].as_f64().unwrap();
-    assert!((roll_cost - 0.1).abs() < 0.01);
+    assert!((roll_cost - 0.05).abs() < 0.01);
 }
 
 /// Tests multiple rolls with sufficient balance
 #[tokio::test(flavor = "multi_thread")]
 async fn multiple_rolls() {
-    let (validator, module_id) =
-        TestValidator::with_current_module::<flashport::FlashportAbi, (), ()>().await;
+    let (validator, module_id) = TestValidator::with_current_module::<
+        FlashportAbi,
+        FlashportParameters,
+        InstantiationArgument,
+    >()
+    .await;
     let mut chain = validator.new_chain().await;
 
     let application_id = chain
-        .create_application(module_id, (), (), vec![])
+        .create_application(
+            module_id,
+            FlashportParameters::default(),
+            InstantiationArgument::default(),
+            vec![],
+        )
         .await;
 
     // Deposit funds
     chain
         .add_block(|block| {
-            block.with_operation(application_id, Operation::Deposit);
+            block.with_operation(
+                application_id,
+                Operation::Deposit {
+                    amount_atto: 10_000_000_000_000_000_000,
+                    credit_to: None,
+                },
+            );
         })
         .await;
 
@@ -141,6 +202,7 @@ async fn multiple_rolls() {
                 application_id,
                 Operation::StartSession {
                     expires_in_secs: 3600,
+                    permissions: None,
                 },
             );
         })
@@ -149,7 +211,16 @@ async fn multiple_rolls() {
     // Start new game
     chain
         .add_block(|block| {
-            block.with_operation(application_id, Operation::NewGame);
+            block.with_operation(
+                application_id,
+                Operation::NewGame {
+                    bet_amount_atto: MIN_BET,
+                    currency: Currency::Real,
+                    mode: GameMode::Classic,
+                    hedge: false,
+                    template_id: None,
+                },
+            );
         })
         .await;
 